@@ -4,7 +4,7 @@ use mathilde_binary_transport::codec::mathldbt_v1::{
     MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1_with_workspace,
     encode_mathldbt_v1_into_with_workspace,
 };
-use mathilde_binary_transport::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+use mathilde_binary_transport::schema::{ColumnarField, ColumnarSchema, ColumnarType, TimeUnit};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Serialize)]
@@ -44,7 +44,10 @@ fn make_bars_like_batch(rows: usize) -> ColumnarBatch {
         },
         ColumnarField {
             name: Some("e_ms".to_string()),
-            ty: ColumnarType::I64,
+            ty: ColumnarType::Timestamp {
+                unit: TimeUnit::Millisecond,
+                tz: None,
+            },
         },
         ColumnarField {
             name: Some("open".to_string()),
@@ -126,7 +129,9 @@ fn make_bars_like_batch(rows: usize) -> ColumnarBatch {
                 offsets: tf_offsets,
                 data: tf_data,
             },
-            ColumnData::FixedI64 {
+            ColumnData::FixedTimestamp {
+                unit: TimeUnit::Millisecond,
+                tz: None,
                 validity: validity_all.clone(),
                 values: e_vals,
             },
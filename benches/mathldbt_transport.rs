@@ -1,6 +1,6 @@
 use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
 use mathilde_binary_transport::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
-use mathilde_binary_transport::batch_view::{ColumnDataView, ColumnarBatchView, VarDataView};
+use mathilde_binary_transport::batch_view::{ColumnDataView, ColumnarBatchView};
 use mathilde_binary_transport::codec::mathldbt_v1::{
     MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1_into_with_workspace,
     decode_mathldbt_v1_with_workspace, encode_mathldbt_v1_into_with_workspace,
@@ -12,7 +12,7 @@ use mathilde_binary_transport::codec::mathldbt_v1_compressed::{
     decode_mathldbt_v1_compressed_with_workspace,
     encode_mathldbt_v1_compressed_into_with_workspace,
 };
-use mathilde_binary_transport::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+use mathilde_binary_transport::schema::{ColumnarField, ColumnarSchema, ColumnarType, TimeUnit};
 
 fn make_bars_like_batch(rows: usize) -> ColumnarBatch {
     let schema = ColumnarSchema::new(vec![
@@ -26,7 +26,10 @@ fn make_bars_like_batch(rows: usize) -> ColumnarBatch {
         },
         ColumnarField {
             name: Some("e_ms".to_string()),
-            ty: ColumnarType::I64,
+            ty: ColumnarType::Timestamp {
+                unit: TimeUnit::Millisecond,
+                tz: None,
+            },
         },
         ColumnarField {
             name: Some("open".to_string()),
@@ -108,7 +111,9 @@ fn make_bars_like_batch(rows: usize) -> ColumnarBatch {
                 offsets: tf_offsets,
                 data: tf_data,
             },
-            ColumnData::FixedI64 {
+            ColumnData::FixedTimestamp {
+                unit: TimeUnit::Millisecond,
+                tz: None,
                 validity: validity_all.clone(),
                 values: e_vals,
             },
@@ -142,60 +147,8 @@ fn bench_transport(c: &mut Criterion) {
     for &rows in &[2_000usize, 100_000usize] {
         let batch = make_bars_like_batch(rows);
 
-        let mut view_cols: Vec<ColumnDataView<'_>> = Vec::with_capacity(batch.columns.len());
-        for col in batch.columns.iter() {
-            match col {
-                ColumnData::Var {
-                    ty,
-                    validity,
-                    offsets,
-                    data,
-                } => view_cols.push(ColumnDataView::Var {
-                    ty: *ty,
-                    validity: validity.as_bytes(),
-                    offsets: offsets.as_slice(),
-                    data: VarDataView::Contiguous(data.as_slice()),
-                }),
-                ColumnData::FixedI64 { validity, values } => view_cols.push(ColumnDataView::FixedI64 {
-                    validity: validity.as_bytes(),
-                    values: values.as_slice(),
-                }),
-                ColumnData::FixedF64Bits { validity, values } => {
-                    view_cols.push(ColumnDataView::FixedF64Bits {
-                        validity: validity.as_bytes(),
-                        values: values.as_slice(),
-                    })
-                }
-                ColumnData::FixedBool { validity, values } => view_cols.push(ColumnDataView::FixedBool {
-                    validity: validity.as_bytes(),
-                    values: values.as_slice(),
-                }),
-                ColumnData::FixedI16 { validity, values } => view_cols.push(ColumnDataView::FixedI16 {
-                    validity: validity.as_bytes(),
-                    values: values.as_slice(),
-                }),
-                ColumnData::FixedI32 { validity, values } => view_cols.push(ColumnDataView::FixedI32 {
-                    validity: validity.as_bytes(),
-                    values: values.as_slice(),
-                }),
-                ColumnData::FixedF32Bits { validity, values } => {
-                    view_cols.push(ColumnDataView::FixedF32Bits {
-                        validity: validity.as_bytes(),
-                        values: values.as_slice(),
-                    })
-                }
-                ColumnData::FixedUuid { validity, values } => view_cols.push(ColumnDataView::FixedUuid {
-                    validity: validity.as_bytes(),
-                    values: values.as_slice(),
-                }),
-                ColumnData::FixedTimestampMicros { validity, values } => {
-                    view_cols.push(ColumnDataView::FixedTimestampMicros {
-                        validity: validity.as_bytes(),
-                        values: values.as_slice(),
-                    })
-                }
-            }
-        }
+        let view_cols: Vec<ColumnDataView<'_>> =
+            batch.columns.iter().map(ColumnData::as_view).collect();
         let view = ColumnarBatchView {
             schema: &batch.schema,
             row_count: batch.row_count,
@@ -290,7 +243,7 @@ fn bench_transport(c: &mut Criterion) {
                     .schema
                     .fields()
                     .iter()
-                    .map(|f| ColumnData::new_all_invalid(f.ty, 0).unwrap())
+                    .map(|f| ColumnData::new_all_invalid(f.ty.clone(), 0).unwrap())
                     .collect(),
             )
             .unwrap();
@@ -333,7 +286,7 @@ fn bench_transport(c: &mut Criterion) {
                         .schema
                         .fields()
                         .iter()
-                        .map(|f| ColumnData::new_all_invalid(f.ty, 0).unwrap())
+                        .map(|f| ColumnData::new_all_invalid(f.ty.clone(), 0).unwrap())
                         .collect(),
                 )
                 .unwrap();
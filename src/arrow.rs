@@ -0,0 +1,765 @@
+//! Conversions between [`ColumnarBatch`] and Arrow `RecordBatch` (feature `arrow`).
+//!
+//! | `ColumnarType`           | Arrow `DataType`                                           |
+//! |--------------------------|------------------------------------------------------------|
+//! | `Bool`                   | `Boolean` (non-zero bytes map to `true`)                   |
+//! | `I16`/`I32`/`I64`        | `Int16`/`Int32`/`Int64`                                    |
+//! | `I8`                     | `Int8`                                                     |
+//! | `U8`/`U16`/`U32`/`U64`   | `UInt8`/`UInt16`/`UInt32`/`UInt64`                         |
+//! | `F32`/`F64`              | `Float32`/`Float64` (bit patterns preserved)               |
+//! | `Uuid`                   | `FixedSizeBinary(16)` with the `arrow.uuid` extension name |
+//! | `FixedSizeBinary(n)`     | `FixedSizeBinary(n)`                                       |
+//! | `FixedSizeList(T, n)`    | `FixedSizeList` of `n` non-null `item` values of type T    |
+//! | `TimestampTzMicros`      | `Timestamp(Microsecond, "UTC")`                            |
+//! | `Timestamp { unit, tz }` | `Timestamp(unit, tz)`                                      |
+//! | `Date`                   | `Date32`                                                   |
+//! | `TimeMicros`             | `Time64(Microsecond)`                                      |
+//! | `IntervalMonthDayMicros` | `Interval(MonthDayNano)` (whole microseconds only)         |
+//! | `Utf8`                   | `Utf8`                                                     |
+//! | `Bytes`                  | `Binary`                                                   |
+//! | `JsonbText`              | `Utf8` with the `arrow.json` extension name                |
+//! | `Decimal { p, s }`       | `Decimal128(p, s)`                                         |
+//! | `List(T)`                | `List` of a nullable field named `item` of type T          |
+//! | `Struct(fields)`         | `Struct` with the same field names and types               |
+//!
+//! `Timestamp(Microsecond, "UTC")` always converts back to `TimestampTzMicros` (a
+//! `Timestamp { unit: Microsecond, tz: Some("UTC") }` schema is rejected), and only
+//! `FixedSizeBinary(16)` fields tagged `arrow.uuid` convert back to `Uuid`. Unnamed fields
+//! map to Arrow fields named `""` and back. [`into_record_batch`] moves value,
+//! offset and validity buffers into Arrow without copying (except byte-per-row `Bool`, `Uuid`
+//! and intervals); [`from_record_batch`] always copies into owned buffers.
+
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::schema::{
+    ColumnarField, ColumnarSchema, ColumnarType, IntervalMonthDayMicros,
+    TimeUnit as ColumnarTimeUnit,
+};
+use crate::{Error, LimitKind, Result};
+use arrow_array::types::{
+    ArrowTimestampType, Date32Type, Decimal128Type, Float32Type, Float64Type, Int8Type, Int16Type,
+    Int32Type, Int64Type, IntervalMonthDayNanoType, Time64MicrosecondType,
+    TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType, UInt8Type, UInt16Type, UInt32Type, UInt64Type,
+};
+use arrow_array::{
+    Array, ArrayRef, BinaryArray, BooleanArray, FixedSizeBinaryArray, FixedSizeListArray,
+    ListArray, PrimitiveArray, RecordBatch, RecordBatchOptions, StringArray, StructArray,
+};
+use arrow_buffer::{
+    ArrowNativeType, BooleanBuffer, Buffer, IntervalMonthDayNano, NullBuffer, OffsetBuffer,
+    ScalarBuffer,
+};
+use arrow_schema::{DataType, Field, Fields, IntervalUnit, Schema, TimeUnit};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+const JSON_EXTENSION_NAME: &str = "arrow.json";
+const UUID_EXTENSION_NAME: &str = "arrow.uuid";
+const UTC: &str = "UTC";
+const LIST_ITEM: &str = "item";
+
+fn arrow_time_unit(unit: ColumnarTimeUnit) -> TimeUnit {
+    match unit {
+        ColumnarTimeUnit::Second => TimeUnit::Second,
+        ColumnarTimeUnit::Millisecond => TimeUnit::Millisecond,
+        ColumnarTimeUnit::Microsecond => TimeUnit::Microsecond,
+        ColumnarTimeUnit::Nanosecond => TimeUnit::Nanosecond,
+    }
+}
+
+fn arrow_field(field: &ColumnarField) -> Field {
+    arrow_field_of(field.name.clone().unwrap_or_default(), &field.ty)
+}
+
+fn arrow_field_of(name: String, ty: &ColumnarType) -> Field {
+    let data_type = match ty {
+        ColumnarType::Bool => DataType::Boolean,
+        ColumnarType::I16 => DataType::Int16,
+        ColumnarType::I32 => DataType::Int32,
+        ColumnarType::I64 => DataType::Int64,
+        ColumnarType::I8 => DataType::Int8,
+        ColumnarType::U8 => DataType::UInt8,
+        ColumnarType::U16 => DataType::UInt16,
+        ColumnarType::U32 => DataType::UInt32,
+        ColumnarType::U64 => DataType::UInt64,
+        ColumnarType::F32 => DataType::Float32,
+        ColumnarType::F64 => DataType::Float64,
+        ColumnarType::Uuid => DataType::FixedSizeBinary(16),
+        ColumnarType::TimestampTzMicros => {
+            DataType::Timestamp(TimeUnit::Microsecond, Some(UTC.into()))
+        }
+        ColumnarType::Timestamp { unit, tz } => {
+            DataType::Timestamp(arrow_time_unit(*unit), tz.clone())
+        }
+        ColumnarType::Date => DataType::Date32,
+        ColumnarType::TimeMicros => DataType::Time64(TimeUnit::Microsecond),
+        ColumnarType::IntervalMonthDayMicros => DataType::Interval(IntervalUnit::MonthDayNano),
+        ColumnarType::Utf8 | ColumnarType::JsonbText => DataType::Utf8,
+        ColumnarType::Bytes => DataType::Binary,
+        &ColumnarType::Decimal { precision, scale } => DataType::Decimal128(precision, scale),
+        ColumnarType::List(item) => {
+            DataType::List(Arc::new(arrow_field_of(LIST_ITEM.to_string(), item)))
+        }
+        ColumnarType::Struct(fields) => DataType::Struct(fields.iter().map(arrow_field).collect()),
+        // `ColumnarSchema::new` bounds fixed sizes to `i32`.
+        ColumnarType::FixedSizeBinary(size) => DataType::FixedSizeBinary(*size as i32),
+        ColumnarType::FixedSizeList(item, size) => DataType::FixedSizeList(
+            Arc::new(arrow_field_of(LIST_ITEM.to_string(), item)),
+            *size as i32,
+        ),
+    };
+    let arrow = Field::new(name, data_type, true);
+    let extension = match ty {
+        ColumnarType::JsonbText => JSON_EXTENSION_NAME,
+        ColumnarType::Uuid => UUID_EXTENSION_NAME,
+        _ => return arrow,
+    };
+    arrow.with_metadata(HashMap::from([(
+        EXTENSION_NAME_KEY.to_string(),
+        extension.to_string(),
+    )]))
+}
+
+fn columnar_type(f: &Field) -> Option<ColumnarType> {
+    let extension = f.metadata().get(EXTENSION_NAME_KEY).map(String::as_str);
+    let is_json = extension == Some(JSON_EXTENSION_NAME);
+    let ty = match f.data_type() {
+        DataType::Boolean => ColumnarType::Bool,
+        DataType::Int16 => ColumnarType::I16,
+        DataType::Int32 => ColumnarType::I32,
+        DataType::Int64 => ColumnarType::I64,
+        DataType::Int8 => ColumnarType::I8,
+        DataType::UInt8 => ColumnarType::U8,
+        DataType::UInt16 => ColumnarType::U16,
+        DataType::UInt32 => ColumnarType::U32,
+        DataType::UInt64 => ColumnarType::U64,
+        DataType::Float32 => ColumnarType::F32,
+        DataType::Float64 => ColumnarType::F64,
+        DataType::FixedSizeBinary(16) if extension == Some(UUID_EXTENSION_NAME) => {
+            ColumnarType::Uuid
+        }
+        &DataType::FixedSizeBinary(size) => ColumnarType::FixedSizeBinary(size.try_into().ok()?),
+        DataType::Timestamp(TimeUnit::Microsecond, Some(tz)) if tz.as_ref() == UTC => {
+            ColumnarType::TimestampTzMicros
+        }
+        DataType::Timestamp(unit, tz) => ColumnarType::Timestamp {
+            unit: match unit {
+                TimeUnit::Second => ColumnarTimeUnit::Second,
+                TimeUnit::Millisecond => ColumnarTimeUnit::Millisecond,
+                TimeUnit::Microsecond => ColumnarTimeUnit::Microsecond,
+                TimeUnit::Nanosecond => ColumnarTimeUnit::Nanosecond,
+            },
+            tz: tz.clone(),
+        },
+        DataType::Date32 => ColumnarType::Date,
+        DataType::Time64(TimeUnit::Microsecond) => ColumnarType::TimeMicros,
+        DataType::Interval(IntervalUnit::MonthDayNano) => ColumnarType::IntervalMonthDayMicros,
+        DataType::Utf8 if is_json => ColumnarType::JsonbText,
+        DataType::Utf8 => ColumnarType::Utf8,
+        DataType::Binary => ColumnarType::Bytes,
+        &DataType::Decimal128(precision, scale) => ColumnarType::Decimal { precision, scale },
+        DataType::List(item) => ColumnarType::List(Box::new(columnar_type(item)?)),
+        DataType::FixedSizeList(item, size) => match item.data_type() {
+            DataType::Float32 | DataType::Float64 => ColumnarType::FixedSizeList(
+                Box::new(columnar_type(item)?),
+                (*size).try_into().ok()?,
+            ),
+            _ => return None,
+        },
+        DataType::Struct(fields) => ColumnarType::Struct(
+            fields
+                .iter()
+                .map(|f| columnar_field(f))
+                .collect::<Option<_>>()?,
+        ),
+        _ => return None,
+    };
+    Some(ty)
+}
+
+fn columnar_field(f: &Field) -> Option<ColumnarField> {
+    Some(ColumnarField {
+        name: (!f.name().is_empty()).then(|| f.name().clone()),
+        ty: columnar_type(f)?,
+    })
+}
+
+/// Converts an Arrow schema, rejecting types without a lossless `ColumnarType` mapping.
+pub fn columnar_schema_from_arrow(schema: &Schema) -> Result<ColumnarSchema> {
+    let mut fields = Vec::with_capacity(schema.fields().len());
+    for (i, f) in schema.fields().iter().enumerate() {
+        fields.push(columnar_field(f).ok_or(Error::Schema {
+            column: Some(i),
+            reason: "unsupported Arrow data type",
+        })?);
+    }
+    ColumnarSchema::new(fields)
+}
+
+pub fn arrow_schema(schema: &ColumnarSchema) -> Schema {
+    Schema::new(schema.fields().iter().map(arrow_field).collect::<Vec<_>>())
+}
+
+fn null_buffer(validity: ValidityBitmap, row_count: usize) -> Option<NullBuffer> {
+    let bits = BooleanBuffer::new(Buffer::from_vec(validity.bytes), 0, row_count);
+    Some(NullBuffer::new(bits)).filter(|n| n.null_count() > 0)
+}
+
+/// Reinterprets an owned buffer of `S` as Arrow values of the same width (e.g. `u64` bits as
+/// `f64`) without copying.
+fn scalar_buffer<S: ArrowNativeType, T: ArrowNativeType>(values: Vec<S>) -> ScalarBuffer<T> {
+    let len = values.len();
+    ScalarBuffer::new(Buffer::from_vec(values), 0, len)
+}
+
+fn offset_buffer(offsets: Vec<u32>) -> Result<OffsetBuffer<i32>> {
+    if offsets.last().is_some_and(|&o| o > i32::MAX as u32) {
+        return Err(Error::LimitExceeded {
+            kind: LimitKind::PayloadLength,
+            column: None,
+            offset: None,
+        });
+    }
+    // Monotonic and starting at 0: checked by `ColumnarBatch::validate`.
+    Ok(OffsetBuffer::new(scalar_buffer(offsets)))
+}
+
+fn timestamp_array<T: ArrowTimestampType>(
+    values: Vec<i64>,
+    nulls: Option<NullBuffer>,
+    tz: Option<Arc<str>>,
+) -> ArrayRef {
+    Arc::new(PrimitiveArray::<T>::new(values.into(), nulls).with_timezone_opt(tz))
+}
+
+fn fixed_size_list_array(
+    size: u32,
+    values: ArrayRef,
+    nulls: Option<NullBuffer>,
+) -> Result<ArrayRef> {
+    let item = Arc::new(Field::new(LIST_ITEM, values.data_type().clone(), true));
+    Ok(Arc::new(
+        FixedSizeListArray::try_new(item, size as i32, values, nulls)
+            .map_err(|_| Error::Internal("arrow fixed-size list construction failed"))?,
+    ))
+}
+
+fn into_array(col: ColumnData, row_count: usize) -> Result<ArrayRef> {
+    let array: ArrayRef =
+        match col {
+            ColumnData::FixedBool { validity, values } => Arc::new(BooleanArray::new(
+                values.iter().map(|&b| b != 0).collect(),
+                null_buffer(validity, row_count),
+            )),
+            ColumnData::BitPackedBool { validity, values } => Arc::new(BooleanArray::new(
+                BooleanBuffer::new(Buffer::from_vec(values), 0, row_count),
+                null_buffer(validity, row_count),
+            )),
+            ColumnData::FixedI16 { validity, values } => Arc::new(
+                PrimitiveArray::<Int16Type>::new(values.into(), null_buffer(validity, row_count)),
+            ),
+            ColumnData::FixedI32 { validity, values } => Arc::new(
+                PrimitiveArray::<Int32Type>::new(values.into(), null_buffer(validity, row_count)),
+            ),
+            ColumnData::FixedI64 { validity, values } => Arc::new(
+                PrimitiveArray::<Int64Type>::new(values.into(), null_buffer(validity, row_count)),
+            ),
+            ColumnData::FixedI8 { validity, values } => Arc::new(PrimitiveArray::<Int8Type>::new(
+                values.into(),
+                null_buffer(validity, row_count),
+            )),
+            ColumnData::FixedU8 { validity, values } => Arc::new(PrimitiveArray::<UInt8Type>::new(
+                values.into(),
+                null_buffer(validity, row_count),
+            )),
+            ColumnData::FixedU16 { validity, values } => Arc::new(
+                PrimitiveArray::<UInt16Type>::new(values.into(), null_buffer(validity, row_count)),
+            ),
+            ColumnData::FixedU32 { validity, values } => Arc::new(
+                PrimitiveArray::<UInt32Type>::new(values.into(), null_buffer(validity, row_count)),
+            ),
+            ColumnData::FixedU64 { validity, values } => Arc::new(
+                PrimitiveArray::<UInt64Type>::new(values.into(), null_buffer(validity, row_count)),
+            ),
+            ColumnData::FixedF32Bits { validity, values } => {
+                Arc::new(PrimitiveArray::<Float32Type>::new(
+                    scalar_buffer(values),
+                    null_buffer(validity, row_count),
+                ))
+            }
+            ColumnData::FixedF64Bits { validity, values } => {
+                Arc::new(PrimitiveArray::<Float64Type>::new(
+                    scalar_buffer(values),
+                    null_buffer(validity, row_count),
+                ))
+            }
+            ColumnData::FixedUuid { validity, values } => Arc::new(FixedSizeBinaryArray::new(
+                16,
+                Buffer::from(values.as_flattened()),
+                null_buffer(validity, row_count),
+            )),
+            ColumnData::FixedSizeBinary {
+                size,
+                validity,
+                values,
+            } => Arc::new(
+                FixedSizeBinaryArray::try_new(
+                    size as i32,
+                    Buffer::from_vec(values),
+                    null_buffer(validity, row_count),
+                )
+                .map_err(|_| Error::Internal("arrow fixed-size binary construction failed"))?,
+            ),
+            ColumnData::FixedSizeListF32Bits {
+                size,
+                validity,
+                values,
+            } => fixed_size_list_array(
+                size,
+                Arc::new(PrimitiveArray::<Float32Type>::new(
+                    scalar_buffer(values),
+                    None,
+                )),
+                null_buffer(validity, row_count),
+            )?,
+            ColumnData::FixedSizeListF64Bits {
+                size,
+                validity,
+                values,
+            } => fixed_size_list_array(
+                size,
+                Arc::new(PrimitiveArray::<Float64Type>::new(
+                    scalar_buffer(values),
+                    None,
+                )),
+                null_buffer(validity, row_count),
+            )?,
+            ColumnData::FixedTimestampMicros { validity, values } => Arc::new(
+                PrimitiveArray::<TimestampMicrosecondType>::new(
+                    values.into(),
+                    null_buffer(validity, row_count),
+                )
+                .with_timezone(UTC),
+            ),
+            ColumnData::FixedTimestamp {
+                unit,
+                tz,
+                validity,
+                values,
+            } => {
+                let nulls = null_buffer(validity, row_count);
+                match unit {
+                    ColumnarTimeUnit::Second => {
+                        timestamp_array::<TimestampSecondType>(values, nulls, tz)
+                    }
+                    ColumnarTimeUnit::Millisecond => {
+                        timestamp_array::<TimestampMillisecondType>(values, nulls, tz)
+                    }
+                    ColumnarTimeUnit::Microsecond => {
+                        timestamp_array::<TimestampMicrosecondType>(values, nulls, tz)
+                    }
+                    ColumnarTimeUnit::Nanosecond => {
+                        timestamp_array::<TimestampNanosecondType>(values, nulls, tz)
+                    }
+                }
+            }
+            ColumnData::FixedDate32 { validity, values } => Arc::new(
+                PrimitiveArray::<Date32Type>::new(values.into(), null_buffer(validity, row_count)),
+            ),
+            ColumnData::FixedTimeMicros { validity, values } => {
+                Arc::new(PrimitiveArray::<Time64MicrosecondType>::new(
+                    values.into(),
+                    null_buffer(validity, row_count),
+                ))
+            }
+            ColumnData::FixedInterval { validity, values } => {
+                let mut nanos = Vec::with_capacity(values.len());
+                for (i, v) in values.into_iter().enumerate() {
+                    let nanoseconds = match v.micros.checked_mul(1000) {
+                        Some(n) => n,
+                        // Null slots carry no value; only valid ones must fit.
+                        None if validity.bytes[i / 8] & (1 << (i % 8)) == 0 => 0,
+                        None => {
+                            return Err(Error::InvalidBatch {
+                                column: None,
+                                reason: "interval out of Arrow range",
+                            });
+                        }
+                    };
+                    nanos.push(IntervalMonthDayNano::new(v.months, v.days, nanoseconds));
+                }
+                Arc::new(PrimitiveArray::<IntervalMonthDayNanoType>::new(
+                    nanos.into(),
+                    null_buffer(validity, row_count),
+                ))
+            }
+            ColumnData::FixedDecimal128 {
+                precision,
+                scale,
+                validity,
+                values,
+            } => Arc::new(
+                PrimitiveArray::<Decimal128Type>::new(
+                    values.into(),
+                    null_buffer(validity, row_count),
+                )
+                .with_precision_and_scale(precision, scale)
+                .map_err(|_| Error::Schema {
+                    column: None,
+                    reason: "invalid decimal precision/scale",
+                })?,
+            ),
+            ColumnData::Var {
+                ty,
+                validity,
+                offsets,
+                data,
+            } => {
+                let offsets = offset_buffer(offsets)?;
+                let nulls = null_buffer(validity, row_count);
+                let data = Buffer::from_vec(data);
+                if ty == ColumnarType::Bytes {
+                    Arc::new(BinaryArray::new(offsets, data, nulls))
+                } else {
+                    Arc::new(StringArray::try_new(offsets, data, nulls).map_err(|_| {
+                        Error::InvalidBatch {
+                            column: None,
+                            reason: "invalid UTF-8 in string column",
+                        }
+                    })?)
+                }
+            }
+            ColumnData::List {
+                validity,
+                offsets,
+                child,
+            } => {
+                let item = Arc::new(arrow_field_of(LIST_ITEM.to_string(), &child.ty()));
+                let child_rows = offsets.last().copied().unwrap_or(0) as usize;
+                let values = into_array(*child, child_rows)?;
+                let offsets = offset_buffer(offsets)?;
+                Arc::new(
+                    ListArray::try_new(item, offsets, values, null_buffer(validity, row_count))
+                        .map_err(|_| Error::Internal("arrow list construction failed"))?,
+                )
+            }
+            ColumnData::Struct {
+                names,
+                validity,
+                children,
+            } => {
+                let fields: Fields = names
+                    .into_iter()
+                    .zip(&children)
+                    .map(|(name, child)| arrow_field_of(name.unwrap_or_default(), &child.ty()))
+                    .collect();
+                let arrays = children
+                    .into_iter()
+                    .map(|child| into_array(child, row_count))
+                    .collect::<Result<Vec<_>>>()?;
+                Arc::new(
+                    StructArray::try_new(fields, arrays, null_buffer(validity, row_count))
+                        .map_err(|_| Error::Internal("arrow struct construction failed"))?,
+                )
+            }
+        };
+    Ok(array)
+}
+
+/// Converts a batch into an Arrow `RecordBatch`, moving its buffers where layouts match.
+pub fn into_record_batch(batch: ColumnarBatch) -> Result<RecordBatch> {
+    batch.validate()?;
+    let schema = Arc::new(arrow_schema(&batch.schema));
+    let row_count = batch.row_count;
+    let mut arrays = Vec::with_capacity(batch.columns.len());
+    for (i, col) in batch.columns.into_iter().enumerate() {
+        arrays.push(into_array(col, row_count).map_err(|e| e.at_column(i))?);
+    }
+    let options = RecordBatchOptions::new().with_row_count(Some(row_count));
+    RecordBatch::try_new_with_options(schema, arrays, &options)
+        .map_err(|_| Error::Internal("arrow record batch construction failed"))
+}
+
+pub fn to_record_batch(batch: &ColumnarBatch) -> Result<RecordBatch> {
+    into_record_batch(batch.clone())
+}
+
+fn validity_from(array: &dyn Array) -> Result<ValidityBitmap> {
+    let len = array.len();
+    let Some(nulls) = array.nulls() else {
+        return ValidityBitmap::new_all_valid(len);
+    };
+    let mut validity = ValidityBitmap::new_all_invalid(len)?;
+    let bits = nulls.inner().sliced();
+    let n = validity.bytes.len();
+    validity.bytes.copy_from_slice(&bits.as_slice()[..n]);
+    if len % 8 != 0 {
+        validity.bytes[n - 1] &= (1u8 << (len % 8)) - 1;
+    }
+    Ok(validity)
+}
+
+fn downcast<T: 'static>(array: &dyn Array) -> Result<&T> {
+    array
+        .as_any()
+        .downcast_ref::<T>()
+        .ok_or(Error::Internal("arrow array does not match its data type"))
+}
+
+/// Sliced arrays may not start at offset 0: returns the offsets rebased to 0 and the range of
+/// child values they index.
+fn rebase_offsets(offsets: &OffsetBuffer<i32>) -> (Vec<u32>, Range<usize>) {
+    let first = offsets.first().copied().unwrap_or(0);
+    let last = offsets.last().copied().unwrap_or(0);
+    let rebased = offsets.iter().map(|&o| (o - first) as u32).collect();
+    (rebased, first as usize..last as usize)
+}
+
+fn var_parts(offsets: &OffsetBuffer<i32>, values: &[u8]) -> (Vec<u32>, Vec<u8>) {
+    let (rebased, range) = rebase_offsets(offsets);
+    (rebased, values[range].to_vec())
+}
+
+fn timestamp_values<T: ArrowTimestampType>(array: &dyn Array) -> Result<Vec<i64>> {
+    Ok(downcast::<PrimitiveArray<T>>(array)?.values().to_vec())
+}
+
+fn column_from(array: &dyn Array, ty: ColumnarType) -> Result<ColumnData> {
+    let validity = validity_from(array)?;
+    let col = match ty {
+        ColumnarType::Bool => ColumnData::FixedBool {
+            validity,
+            values: downcast::<BooleanArray>(array)?
+                .values()
+                .iter()
+                .map(u8::from)
+                .collect(),
+        },
+        ColumnarType::I16 => ColumnData::FixedI16 {
+            validity,
+            values: downcast::<PrimitiveArray<Int16Type>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::I32 => ColumnData::FixedI32 {
+            validity,
+            values: downcast::<PrimitiveArray<Int32Type>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::I64 => ColumnData::FixedI64 {
+            validity,
+            values: downcast::<PrimitiveArray<Int64Type>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::I8 => ColumnData::FixedI8 {
+            validity,
+            values: downcast::<PrimitiveArray<Int8Type>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::U8 => ColumnData::FixedU8 {
+            validity,
+            values: downcast::<PrimitiveArray<UInt8Type>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::U16 => ColumnData::FixedU16 {
+            validity,
+            values: downcast::<PrimitiveArray<UInt16Type>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::U32 => ColumnData::FixedU32 {
+            validity,
+            values: downcast::<PrimitiveArray<UInt32Type>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::U64 => ColumnData::FixedU64 {
+            validity,
+            values: downcast::<PrimitiveArray<UInt64Type>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::F32 => ColumnData::FixedF32Bits {
+            validity,
+            values: downcast::<PrimitiveArray<Float32Type>>(array)?
+                .values()
+                .iter()
+                .map(|v| v.to_bits())
+                .collect(),
+        },
+        ColumnarType::F64 => ColumnData::FixedF64Bits {
+            validity,
+            values: downcast::<PrimitiveArray<Float64Type>>(array)?
+                .values()
+                .iter()
+                .map(|v| v.to_bits())
+                .collect(),
+        },
+        ColumnarType::Uuid => {
+            let a = downcast::<FixedSizeBinaryArray>(array)?;
+            let mut values = Vec::with_capacity(a.len());
+            for i in 0..a.len() {
+                let v: [u8; 16] = a
+                    .value(i)
+                    .try_into()
+                    .map_err(|_| Error::Internal("FixedSizeBinary(16) value has wrong width"))?;
+                values.push(v);
+            }
+            ColumnData::FixedUuid { validity, values }
+        }
+        ColumnarType::FixedSizeBinary(size) => ColumnData::FixedSizeBinary {
+            size,
+            validity,
+            values: downcast::<FixedSizeBinaryArray>(array)?
+                .value_data()
+                .to_vec(),
+        },
+        ColumnarType::FixedSizeList(item, size) => {
+            let a = downcast::<FixedSizeListArray>(array)?;
+            let values = a.values();
+            // Items have no validity of their own; only nulls under null rows are dropped.
+            if let Some(nulls) = values.nulls() {
+                let row_valid = |i: usize| a.is_valid(i / size as usize);
+                if nulls.iter().enumerate().any(|(i, v)| !v && row_valid(i)) {
+                    return Err(Error::InvalidBatch {
+                        column: None,
+                        reason: "fixed-size list items must not be null",
+                    });
+                }
+            }
+            match column_from(values.as_ref(), *item)? {
+                ColumnData::FixedF32Bits { values, .. } => ColumnData::FixedSizeListF32Bits {
+                    size,
+                    validity,
+                    values,
+                },
+                ColumnData::FixedF64Bits { values, .. } => ColumnData::FixedSizeListF64Bits {
+                    size,
+                    validity,
+                    values,
+                },
+                _ => return Err(Error::Internal("fixed-size list item is not a float")),
+            }
+        }
+        ColumnarType::TimestampTzMicros => ColumnData::FixedTimestampMicros {
+            validity,
+            values: downcast::<PrimitiveArray<TimestampMicrosecondType>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::Timestamp { unit, tz } => ColumnData::FixedTimestamp {
+            unit,
+            tz,
+            validity,
+            values: match unit {
+                ColumnarTimeUnit::Second => timestamp_values::<TimestampSecondType>(array)?,
+                ColumnarTimeUnit::Millisecond => {
+                    timestamp_values::<TimestampMillisecondType>(array)?
+                }
+                ColumnarTimeUnit::Microsecond => {
+                    timestamp_values::<TimestampMicrosecondType>(array)?
+                }
+                ColumnarTimeUnit::Nanosecond => timestamp_values::<TimestampNanosecondType>(array)?,
+            },
+        },
+        ColumnarType::Date => ColumnData::FixedDate32 {
+            validity,
+            values: downcast::<PrimitiveArray<Date32Type>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::TimeMicros => ColumnData::FixedTimeMicros {
+            validity,
+            values: downcast::<PrimitiveArray<Time64MicrosecondType>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::IntervalMonthDayMicros => {
+            let a = downcast::<PrimitiveArray<IntervalMonthDayNanoType>>(array)?;
+            let mut values = Vec::with_capacity(a.len());
+            for (i, v) in a.values().iter().enumerate() {
+                if v.nanoseconds % 1000 != 0 && a.is_valid(i) {
+                    return Err(Error::InvalidBatch {
+                        column: None,
+                        reason: "interval has sub-microsecond precision",
+                    });
+                }
+                values.push(IntervalMonthDayMicros {
+                    months: v.months,
+                    days: v.days,
+                    micros: v.nanoseconds / 1000,
+                });
+            }
+            ColumnData::FixedInterval { validity, values }
+        }
+        ColumnarType::Decimal { precision, scale } => ColumnData::FixedDecimal128 {
+            precision,
+            scale,
+            validity,
+            values: downcast::<PrimitiveArray<Decimal128Type>>(array)?
+                .values()
+                .to_vec(),
+        },
+        ColumnarType::Utf8 | ColumnarType::JsonbText => {
+            let a = downcast::<StringArray>(array)?;
+            let (offsets, data) = var_parts(a.offsets(), a.values());
+            ColumnData::Var {
+                ty,
+                validity,
+                offsets,
+                data,
+            }
+        }
+        ColumnarType::Bytes => {
+            let a = downcast::<BinaryArray>(array)?;
+            let (offsets, data) = var_parts(a.offsets(), a.values());
+            ColumnData::Var {
+                ty,
+                validity,
+                offsets,
+                data,
+            }
+        }
+        ColumnarType::List(item) => {
+            let a = downcast::<ListArray>(array)?;
+            let (offsets, range) = rebase_offsets(a.offsets());
+            let values = a.values().slice(range.start, range.len());
+            ColumnData::List {
+                validity,
+                offsets,
+                child: Box::new(column_from(values.as_ref(), *item)?),
+            }
+        }
+        ColumnarType::Struct(fields) => {
+            let a = downcast::<StructArray>(array)?;
+            let mut names = Vec::with_capacity(fields.len());
+            let mut children = Vec::with_capacity(fields.len());
+            for (field, values) in fields.into_iter().zip(a.columns()) {
+                names.push(field.name);
+                children.push(column_from(values.as_ref(), field.ty)?);
+            }
+            ColumnData::Struct {
+                names,
+                validity,
+                children,
+            }
+        }
+    };
+    Ok(col)
+}
+
+/// Converts an Arrow `RecordBatch` into an owned batch (see the module docs for the mapping).
+pub fn from_record_batch(batch: &RecordBatch) -> Result<ColumnarBatch> {
+    let schema = columnar_schema_from_arrow(batch.schema_ref())?;
+    let mut columns = Vec::with_capacity(schema.len());
+    for (i, (field, array)) in schema.fields().iter().zip(batch.columns()).enumerate() {
+        columns.push(column_from(array.as_ref(), field.ty.clone()).map_err(|e| e.at_column(i))?);
+    }
+    ColumnarBatch::new(schema, batch.num_rows(), columns)
+}
@@ -1,12 +1,72 @@
-use crate::schema::{ColumnarSchema, ColumnarType};
-use crate::{Error, Result};
+use crate::batch_view::{ColumnDataView, VarDataView};
+use crate::schema::{
+    ColumnarField, ColumnarSchema, ColumnarType, IntervalMonthDayMicros, TimeUnit,
+};
+use crate::{Error, LimitKind, Result};
+use std::sync::Arc;
+
+pub(crate) fn invalid(reason: &'static str) -> Error {
+    Error::InvalidBatch {
+        column: None,
+        reason,
+    }
+}
 
 fn ceil_div_8(n: usize) -> Result<usize> {
     n.checked_add(7)
-        .ok_or_else(|| Error::Other("size overflow".to_string()))
+        .ok_or(Error::LimitExceeded {
+            kind: LimitKind::RowCount,
+            column: None,
+            offset: None,
+        })
         .map(|v| v / 8)
 }
 
+/// Rejects valid decimal slots whose mantissa has more than `precision` digits.
+pub(crate) fn check_decimal_values(validity: &[u8], values: &[i128], precision: u8) -> Result<()> {
+    let limit = 10u128.pow(u32::from(precision));
+    for (i, v) in values.iter().enumerate() {
+        if (validity[i / 8] >> (i % 8)) & 1 == 1 && v.unsigned_abs() >= limit {
+            return Err(invalid("decimal value exceeds precision"));
+        }
+    }
+    Ok(())
+}
+
+/// Number of values held by `row_count` rows of a fixed-size column.
+pub(crate) fn fixed_size_len(row_count: usize, size: u32) -> Result<usize> {
+    row_count
+        .checked_mul(size as usize)
+        .ok_or(Error::LimitExceeded {
+            kind: LimitKind::RowCount,
+            column: None,
+            offset: None,
+        })
+}
+
+/// Checks `row_count + 1` non-decreasing offsets starting at 0 and returns the final offset.
+pub(crate) fn check_offsets(offsets: &[u32], row_count: usize) -> Result<usize> {
+    let expected_offsets_len = row_count.checked_add(1).ok_or(Error::LimitExceeded {
+        kind: LimitKind::RowCount,
+        column: None,
+        offset: None,
+    })?;
+    if offsets.len() != expected_offsets_len {
+        return Err(invalid("offsets length mismatch"));
+    }
+    if offsets[0] != 0 {
+        return Err(invalid("offsets[0] must be 0"));
+    }
+    let mut prev = 0u32;
+    for &o in offsets.iter() {
+        if o < prev {
+            return Err(invalid("offsets must be non-decreasing"));
+        }
+        prev = o;
+    }
+    Ok(prev as usize)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidityBitmap {
     pub bytes: Vec<u8>,
@@ -31,7 +91,7 @@ impl ValidityBitmap {
             let mask = (1u8 << rem) - 1;
             let last = bytes
                 .last_mut()
-                .ok_or_else(|| Error::Other("validity bitmap out of bounds".to_string()))?;
+                .ok_or_else(|| invalid("validity bitmap out of bounds"))?;
             *last = mask;
         }
         Ok(Self { bytes })
@@ -45,7 +105,7 @@ impl ValidityBitmap {
         let byte_idx = row_idx / 8;
         let bit_idx = row_idx % 8;
         if byte_idx >= self.bytes.len() {
-            return Err(Error::Other("validity bitmap out of bounds".to_string()));
+            return Err(invalid("validity bitmap out of bounds"));
         }
         let mask = 1u8 << bit_idx;
         if is_valid {
@@ -60,7 +120,7 @@ impl ValidityBitmap {
         let byte_idx = row_idx / 8;
         let bit_idx = row_idx % 8;
         if byte_idx >= self.bytes.len() {
-            return Err(Error::Other("validity bitmap out of bounds".to_string()));
+            return Err(invalid("validity bitmap out of bounds"));
         }
         Ok((self.bytes[byte_idx] & (1u8 << bit_idx)) != 0)
     }
@@ -76,6 +136,11 @@ pub enum ColumnData {
         validity: ValidityBitmap,
         values: Vec<u8>,
     },
+    /// `Bool` with one bit per row, LSB-first like [`ValidityBitmap`].
+    BitPackedBool {
+        validity: ValidityBitmap,
+        values: Vec<u8>,
+    },
     FixedI16 {
         validity: ValidityBitmap,
         values: Vec<i16>,
@@ -88,6 +153,26 @@ pub enum ColumnData {
         validity: ValidityBitmap,
         values: Vec<i64>,
     },
+    FixedI8 {
+        validity: ValidityBitmap,
+        values: Vec<i8>,
+    },
+    FixedU8 {
+        validity: ValidityBitmap,
+        values: Vec<u8>,
+    },
+    FixedU16 {
+        validity: ValidityBitmap,
+        values: Vec<u16>,
+    },
+    FixedU32 {
+        validity: ValidityBitmap,
+        values: Vec<u32>,
+    },
+    FixedU64 {
+        validity: ValidityBitmap,
+        values: Vec<u64>,
+    },
     FixedF32Bits {
         validity: ValidityBitmap,
         values: Vec<u32>,
@@ -104,26 +189,270 @@ pub enum ColumnData {
         validity: ValidityBitmap,
         values: Vec<i64>,
     },
+    FixedTimestamp {
+        unit: TimeUnit,
+        tz: Option<Arc<str>>,
+        validity: ValidityBitmap,
+        values: Vec<i64>,
+    },
+    FixedDate32 {
+        validity: ValidityBitmap,
+        values: Vec<i32>,
+    },
+    FixedTimeMicros {
+        validity: ValidityBitmap,
+        values: Vec<i64>,
+    },
+    FixedInterval {
+        validity: ValidityBitmap,
+        values: Vec<IntervalMonthDayMicros>,
+    },
+    FixedDecimal128 {
+        precision: u8,
+        scale: i8,
+        validity: ValidityBitmap,
+        values: Vec<i128>,
+    },
+    /// Row `i` holds `values[i * size..(i + 1) * size]`.
+    FixedSizeBinary {
+        size: u32,
+        validity: ValidityBitmap,
+        values: Vec<u8>,
+    },
+    FixedSizeListF32Bits {
+        size: u32,
+        validity: ValidityBitmap,
+        values: Vec<u32>,
+    },
+    FixedSizeListF64Bits {
+        size: u32,
+        validity: ValidityBitmap,
+        values: Vec<u64>,
+    },
     Var {
         ty: ColumnarType,
         validity: ValidityBitmap,
         offsets: Vec<u32>,
         data: Vec<u8>,
     },
+    /// Row `i` holds the `child` items `offsets[i]..offsets[i + 1]`.
+    List {
+        validity: ValidityBitmap,
+        offsets: Vec<u32>,
+        child: Box<ColumnData>,
+    },
+    /// One child column per struct field, each with `row_count` rows; `names[i]` names
+    /// `children[i]`.
+    Struct {
+        names: Vec<Option<String>>,
+        validity: ValidityBitmap,
+        children: Vec<ColumnData>,
+    },
 }
 
 impl ColumnData {
     pub fn ty(&self) -> ColumnarType {
         match self {
-            ColumnData::FixedBool { .. } => ColumnarType::Bool,
+            ColumnData::FixedBool { .. } | ColumnData::BitPackedBool { .. } => ColumnarType::Bool,
             ColumnData::FixedI16 { .. } => ColumnarType::I16,
             ColumnData::FixedI32 { .. } => ColumnarType::I32,
             ColumnData::FixedI64 { .. } => ColumnarType::I64,
+            ColumnData::FixedI8 { .. } => ColumnarType::I8,
+            ColumnData::FixedU8 { .. } => ColumnarType::U8,
+            ColumnData::FixedU16 { .. } => ColumnarType::U16,
+            ColumnData::FixedU32 { .. } => ColumnarType::U32,
+            ColumnData::FixedU64 { .. } => ColumnarType::U64,
             ColumnData::FixedF32Bits { .. } => ColumnarType::F32,
             ColumnData::FixedF64Bits { .. } => ColumnarType::F64,
             ColumnData::FixedUuid { .. } => ColumnarType::Uuid,
             ColumnData::FixedTimestampMicros { .. } => ColumnarType::TimestampTzMicros,
-            ColumnData::Var { ty, .. } => *ty,
+            ColumnData::FixedTimestamp { unit, tz, .. } => ColumnarType::Timestamp {
+                unit: *unit,
+                tz: tz.clone(),
+            },
+            ColumnData::FixedDate32 { .. } => ColumnarType::Date,
+            ColumnData::FixedTimeMicros { .. } => ColumnarType::TimeMicros,
+            ColumnData::FixedInterval { .. } => ColumnarType::IntervalMonthDayMicros,
+            ColumnData::FixedDecimal128 {
+                precision, scale, ..
+            } => ColumnarType::Decimal {
+                precision: *precision,
+                scale: *scale,
+            },
+            ColumnData::FixedSizeBinary { size, .. } => ColumnarType::FixedSizeBinary(*size),
+            ColumnData::FixedSizeListF32Bits { size, .. } => {
+                ColumnarType::FixedSizeList(Box::new(ColumnarType::F32), *size)
+            }
+            ColumnData::FixedSizeListF64Bits { size, .. } => {
+                ColumnarType::FixedSizeList(Box::new(ColumnarType::F64), *size)
+            }
+            ColumnData::Var { ty, .. } => ty.clone(),
+            ColumnData::List { child, .. } => ColumnarType::List(Box::new(child.ty())),
+            ColumnData::Struct {
+                names, children, ..
+            } => ColumnarType::Struct(
+                names
+                    .iter()
+                    .zip(children)
+                    .map(|(name, child)| ColumnarField {
+                        name: name.clone(),
+                        ty: child.ty(),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn as_view(&self) -> ColumnDataView<'_> {
+        match self {
+            ColumnData::FixedBool { validity, values } => ColumnDataView::FixedBool {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::BitPackedBool { validity, values } => ColumnDataView::BitPackedBool {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedI16 { validity, values } => ColumnDataView::FixedI16 {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedI32 { validity, values } => ColumnDataView::FixedI32 {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedI64 { validity, values } => ColumnDataView::FixedI64 {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedI8 { validity, values } => ColumnDataView::FixedI8 {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedU8 { validity, values } => ColumnDataView::FixedU8 {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedU16 { validity, values } => ColumnDataView::FixedU16 {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedU32 { validity, values } => ColumnDataView::FixedU32 {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedU64 { validity, values } => ColumnDataView::FixedU64 {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedF32Bits { validity, values } => ColumnDataView::FixedF32Bits {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedF64Bits { validity, values } => ColumnDataView::FixedF64Bits {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedUuid { validity, values } => ColumnDataView::FixedUuid {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedTimestampMicros { validity, values } => {
+                ColumnDataView::FixedTimestampMicros {
+                    validity: validity.as_bytes(),
+                    values: values.as_slice(),
+                }
+            }
+            ColumnData::FixedTimestamp {
+                unit,
+                tz,
+                validity,
+                values,
+            } => ColumnDataView::FixedTimestamp {
+                unit: *unit,
+                tz: tz.as_deref(),
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedDate32 { validity, values } => ColumnDataView::FixedDate32 {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedTimeMicros { validity, values } => ColumnDataView::FixedTimeMicros {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedInterval { validity, values } => ColumnDataView::FixedInterval {
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedDecimal128 {
+                precision,
+                scale,
+                validity,
+                values,
+            } => ColumnDataView::FixedDecimal128 {
+                precision: *precision,
+                scale: *scale,
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedSizeBinary {
+                size,
+                validity,
+                values,
+            } => ColumnDataView::FixedSizeBinary {
+                size: *size,
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedSizeListF32Bits {
+                size,
+                validity,
+                values,
+            } => ColumnDataView::FixedSizeListF32Bits {
+                size: *size,
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::FixedSizeListF64Bits {
+                size,
+                validity,
+                values,
+            } => ColumnDataView::FixedSizeListF64Bits {
+                size: *size,
+                validity: validity.as_bytes(),
+                values: values.as_slice(),
+            },
+            ColumnData::Var {
+                ty,
+                validity,
+                offsets,
+                data,
+            } => ColumnDataView::Var {
+                ty: ty.clone(),
+                validity: validity.as_bytes(),
+                offsets: offsets.as_slice(),
+                data: VarDataView::Contiguous(data.as_slice()),
+            },
+            ColumnData::List {
+                validity,
+                offsets,
+                child,
+            } => ColumnDataView::List {
+                validity: validity.as_bytes(),
+                offsets: offsets.as_slice(),
+                child: Box::new(child.as_view()),
+            },
+            ColumnData::Struct {
+                names,
+                validity,
+                children,
+            } => ColumnDataView::Struct {
+                names: names.iter().map(Option::as_deref).collect(),
+                validity: validity.as_bytes(),
+                children: children.iter().map(ColumnData::as_view).collect(),
+            },
         }
     }
 
@@ -131,9 +460,11 @@ impl ColumnData {
         let validity = ValidityBitmap::new_all_invalid(row_count)?;
         match ty {
             ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText => {
-                let offsets_len = row_count
-                    .checked_add(1)
-                    .ok_or_else(|| Error::Other("row_count too large".to_string()))?;
+                let offsets_len = row_count.checked_add(1).ok_or(Error::LimitExceeded {
+                    kind: LimitKind::RowCount,
+                    column: None,
+                    offset: None,
+                })?;
                 Ok(ColumnData::Var {
                     ty,
                     validity,
@@ -157,6 +488,26 @@ impl ColumnData {
                 validity,
                 values: vec![0i64; row_count],
             }),
+            ColumnarType::I8 => Ok(ColumnData::FixedI8 {
+                validity,
+                values: vec![0i8; row_count],
+            }),
+            ColumnarType::U8 => Ok(ColumnData::FixedU8 {
+                validity,
+                values: vec![0u8; row_count],
+            }),
+            ColumnarType::U16 => Ok(ColumnData::FixedU16 {
+                validity,
+                values: vec![0u16; row_count],
+            }),
+            ColumnarType::U32 => Ok(ColumnData::FixedU32 {
+                validity,
+                values: vec![0u32; row_count],
+            }),
+            ColumnarType::U64 => Ok(ColumnData::FixedU64 {
+                validity,
+                values: vec![0u64; row_count],
+            }),
             ColumnarType::F32 => Ok(ColumnData::FixedF32Bits {
                 validity,
                 values: vec![0u32; row_count],
@@ -173,78 +524,248 @@ impl ColumnData {
                 validity,
                 values: vec![0i64; row_count],
             }),
+            ColumnarType::Timestamp { unit, tz } => Ok(ColumnData::FixedTimestamp {
+                unit,
+                tz,
+                validity,
+                values: vec![0i64; row_count],
+            }),
+            ColumnarType::Date => Ok(ColumnData::FixedDate32 {
+                validity,
+                values: vec![0i32; row_count],
+            }),
+            ColumnarType::TimeMicros => Ok(ColumnData::FixedTimeMicros {
+                validity,
+                values: vec![0i64; row_count],
+            }),
+            ColumnarType::IntervalMonthDayMicros => Ok(ColumnData::FixedInterval {
+                validity,
+                values: vec![IntervalMonthDayMicros::default(); row_count],
+            }),
+            ColumnarType::Decimal { precision, scale } => Ok(ColumnData::FixedDecimal128 {
+                precision,
+                scale,
+                validity,
+                values: vec![0i128; row_count],
+            }),
+            ColumnarType::FixedSizeBinary(size) => Ok(ColumnData::FixedSizeBinary {
+                size,
+                validity,
+                values: vec![0u8; fixed_size_len(row_count, size)?],
+            }),
+            ColumnarType::FixedSizeList(item, size) => match *item {
+                ColumnarType::F32 => Ok(ColumnData::FixedSizeListF32Bits {
+                    size,
+                    validity,
+                    values: vec![0u32; fixed_size_len(row_count, size)?],
+                }),
+                ColumnarType::F64 => Ok(ColumnData::FixedSizeListF64Bits {
+                    size,
+                    validity,
+                    values: vec![0u64; fixed_size_len(row_count, size)?],
+                }),
+                _ => Err(Error::Schema {
+                    column: None,
+                    reason: "fixed-size list items must be F32 or F64",
+                }),
+            },
+            ColumnarType::List(item) => {
+                let offsets_len = row_count.checked_add(1).ok_or(Error::LimitExceeded {
+                    kind: LimitKind::RowCount,
+                    column: None,
+                    offset: None,
+                })?;
+                Ok(ColumnData::List {
+                    validity,
+                    offsets: vec![0u32; offsets_len],
+                    child: Box::new(ColumnData::new_all_invalid(*item, 0)?),
+                })
+            }
+            ColumnarType::Struct(fields) => {
+                let mut names = Vec::with_capacity(fields.len());
+                let mut children = Vec::with_capacity(fields.len());
+                for field in fields {
+                    names.push(field.name);
+                    children.push(ColumnData::new_all_invalid(field.ty, row_count)?);
+                }
+                Ok(ColumnData::Struct {
+                    names,
+                    validity,
+                    children,
+                })
+            }
         }
     }
 
-    pub fn validate_for_row_count(&self, ty: ColumnarType, row_count: usize) -> Result<()> {
-        if self.ty() != ty {
-            return Err(Error::Other("column type mismatch".to_string()));
+    pub fn validate_for_row_count(&self, ty: &ColumnarType, row_count: usize) -> Result<()> {
+        if self.ty() != *ty {
+            return Err(Error::Schema {
+                column: None,
+                reason: "column type mismatch",
+            });
         }
 
         let expected_validity = ValidityBitmap::len_for_row_count(row_count)?;
         match self {
-            ColumnData::FixedBool { validity, values } => {
+            ColumnData::FixedBool { validity, values }
+            | ColumnData::FixedU8 { validity, values } => {
                 if validity.bytes.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnData::BitPackedBool { validity, values } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != expected_validity {
+                    return Err(invalid("values length mismatch"));
                 }
             }
             ColumnData::FixedI16 { validity, values } => {
                 if validity.bytes.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != row_count {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnData::FixedI8 { validity, values } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnData::FixedU16 { validity, values } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != row_count {
+                    return Err(invalid("values length mismatch"));
                 }
             }
             ColumnData::FixedI32 { validity, values } => {
                 if validity.bytes.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
             ColumnData::FixedI64 { validity, values } => {
                 if validity.bytes.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
-            ColumnData::FixedF32Bits { validity, values } => {
+            ColumnData::FixedF32Bits { validity, values }
+            | ColumnData::FixedU32 { validity, values } => {
                 if validity.bytes.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
-            ColumnData::FixedF64Bits { validity, values } => {
+            ColumnData::FixedF64Bits { validity, values }
+            | ColumnData::FixedU64 { validity, values } => {
                 if validity.bytes.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
             ColumnData::FixedUuid { validity, values } => {
                 if validity.bytes.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
-            ColumnData::FixedTimestampMicros { validity, values } => {
+            ColumnData::FixedTimestampMicros { validity, values }
+            | ColumnData::FixedTimestamp {
+                validity, values, ..
+            }
+            | ColumnData::FixedTimeMicros { validity, values } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != row_count {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnData::FixedDate32 { validity, values } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != row_count {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnData::FixedInterval { validity, values } => {
                 if validity.bytes.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnData::FixedDecimal128 {
+                precision,
+                validity,
+                values,
+                ..
+            } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != row_count {
+                    return Err(invalid("values length mismatch"));
+                }
+                check_decimal_values(validity.as_bytes(), values, *precision)?;
+            }
+            ColumnData::FixedSizeBinary {
+                size,
+                validity,
+                values,
+            } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != fixed_size_len(row_count, *size)? {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnData::FixedSizeListF32Bits {
+                size,
+                validity,
+                values,
+            } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != fixed_size_len(row_count, *size)? {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnData::FixedSizeListF64Bits {
+                size,
+                validity,
+                values,
+            } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != fixed_size_len(row_count, *size)? {
+                    return Err(invalid("values length mismatch"));
                 }
             }
             ColumnData::Var {
@@ -254,33 +775,49 @@ impl ColumnData {
                 ..
             } => {
                 if validity.bytes.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if data.len() > (u32::MAX as usize) {
-                    return Err(Error::Other("data too large".to_string()));
-                }
-                let expected_offsets_len = row_count
-                    .checked_add(1)
-                    .ok_or_else(|| Error::Other("row_count too large".to_string()))?;
-                if offsets.len() != expected_offsets_len {
-                    return Err(Error::Other("offsets length mismatch".to_string()));
-                }
-                if offsets.first().copied().unwrap_or(1) != 0 {
-                    return Err(Error::Other("offsets[0] must be 0".to_string()));
-                }
-                let mut prev = 0u32;
-                for &o in offsets.iter() {
-                    if o < prev {
-                        return Err(Error::Other("offsets must be non-decreasing".to_string()));
-                    }
-                    prev = o;
-                }
-                let final_off = offsets
-                    .last()
-                    .copied()
-                    .ok_or_else(|| Error::Other("offsets length mismatch".to_string()))?;
-                if final_off != (data.len() as u32) {
-                    return Err(Error::Other("final offset mismatch".to_string()));
+                    return Err(Error::LimitExceeded {
+                        kind: LimitKind::PayloadLength,
+                        column: None,
+                        offset: None,
+                    });
+                }
+                if check_offsets(offsets, row_count)? != data.len() {
+                    return Err(invalid("final offset mismatch"));
+                }
+            }
+            ColumnData::List {
+                validity,
+                offsets,
+                child,
+            } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                let ColumnarType::List(item) = ty else {
+                    return Err(Error::Internal("list column with non-list type"));
+                };
+                let child_rows = check_offsets(offsets, row_count)?;
+                child.validate_for_row_count(item, child_rows)?;
+            }
+            ColumnData::Struct {
+                names,
+                validity,
+                children,
+            } => {
+                if validity.bytes.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                let ColumnarType::Struct(fields) = ty else {
+                    return Err(Error::Internal("struct column with non-struct type"));
+                };
+                if names.len() != children.len() {
+                    return Err(invalid("struct field count mismatch"));
+                }
+                for (field, child) in fields.iter().zip(children) {
+                    child.validate_for_row_count(&field.ty, row_count)?;
                 }
             }
         }
@@ -308,15 +845,26 @@ impl ColumnarBatch {
 
     pub fn validate(&self) -> Result<()> {
         if self.schema.is_empty() {
-            return Err(Error::Other(
-                "columnar schema must have at least one field".to_string(),
-            ));
+            return Err(Error::Schema {
+                column: None,
+                reason: "columnar schema must have at least one field",
+            });
         }
         if self.schema.len() != self.columns.len() {
-            return Err(Error::Other("schema/columns length mismatch".to_string()));
+            return Err(Error::Schema {
+                column: None,
+                reason: "schema/columns length mismatch",
+            });
         }
-        for (field, col) in self.schema.fields().iter().zip(self.columns.iter()) {
-            col.validate_for_row_count(field.ty, self.row_count)?;
+        for (i, (field, col)) in self
+            .schema
+            .fields()
+            .iter()
+            .zip(self.columns.iter())
+            .enumerate()
+        {
+            col.validate_for_row_count(&field.ty, self.row_count)
+                .map_err(|e| e.at_column(i))?;
         }
         Ok(())
     }
@@ -1,6 +1,11 @@
-use crate::batch::ValidityBitmap;
-use crate::schema::{ColumnarSchema, ColumnarType};
-use crate::{Error, Result};
+use crate::batch::{
+    ColumnData, ValidityBitmap, check_decimal_values, check_offsets, fixed_size_len, invalid,
+};
+use crate::schema::{
+    ColumnarField, ColumnarSchema, ColumnarType, IntervalMonthDayMicros, TimeUnit,
+};
+use crate::{Error, LimitKind, Result};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ColumnarBatchView<'a> {
@@ -12,26 +17,42 @@ pub struct ColumnarBatchView<'a> {
 impl<'a> ColumnarBatchView<'a> {
     pub fn validate(&self) -> Result<()> {
         if self.schema.is_empty() {
-            return Err(Error::Other(
-                "columnar schema must have at least one field".to_string(),
-            ));
+            return Err(Error::Schema {
+                column: None,
+                reason: "columnar schema must have at least one field",
+            });
         }
         if self.schema.len() != self.columns.len() {
-            return Err(Error::Other("schema/columns length mismatch".to_string()));
+            return Err(Error::Schema {
+                column: None,
+                reason: "schema/columns length mismatch",
+            });
         }
-        for (field, col) in self.schema.fields().iter().zip(self.columns.iter()) {
-            col.validate_for_row_count(field.ty, self.row_count)?;
+        for (i, (field, col)) in self
+            .schema
+            .fields()
+            .iter()
+            .zip(self.columns.iter())
+            .enumerate()
+        {
+            col.validate_for_row_count(&field.ty, self.row_count)
+                .map_err(|e| e.at_column(i))?;
         }
         Ok(())
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ColumnDataView<'a> {
     FixedBool {
         validity: &'a [u8],
         values: &'a [u8],
     },
+    /// `Bool` with one bit per row, LSB-first like the validity bitmap.
+    BitPackedBool {
+        validity: &'a [u8],
+        values: &'a [u8],
+    },
     FixedI16 {
         validity: &'a [u8],
         values: &'a [i16],
@@ -44,6 +65,26 @@ pub enum ColumnDataView<'a> {
         validity: &'a [u8],
         values: &'a [i64],
     },
+    FixedI8 {
+        validity: &'a [u8],
+        values: &'a [i8],
+    },
+    FixedU8 {
+        validity: &'a [u8],
+        values: &'a [u8],
+    },
+    FixedU16 {
+        validity: &'a [u8],
+        values: &'a [u16],
+    },
+    FixedU32 {
+        validity: &'a [u8],
+        values: &'a [u32],
+    },
+    FixedU64 {
+        validity: &'a [u8],
+        values: &'a [u64],
+    },
     FixedF32Bits {
         validity: &'a [u8],
         values: &'a [u32],
@@ -60,98 +101,533 @@ pub enum ColumnDataView<'a> {
         validity: &'a [u8],
         values: &'a [i64],
     },
+    FixedTimestamp {
+        unit: TimeUnit,
+        tz: Option<&'a str>,
+        validity: &'a [u8],
+        values: &'a [i64],
+    },
+    FixedDate32 {
+        validity: &'a [u8],
+        values: &'a [i32],
+    },
+    FixedTimeMicros {
+        validity: &'a [u8],
+        values: &'a [i64],
+    },
+    FixedInterval {
+        validity: &'a [u8],
+        values: &'a [IntervalMonthDayMicros],
+    },
+    FixedDecimal128 {
+        precision: u8,
+        scale: i8,
+        validity: &'a [u8],
+        values: &'a [i128],
+    },
+    /// Row `i` holds `values[i * size..(i + 1) * size]`.
+    FixedSizeBinary {
+        size: u32,
+        validity: &'a [u8],
+        values: &'a [u8],
+    },
+    FixedSizeListF32Bits {
+        size: u32,
+        validity: &'a [u8],
+        values: &'a [u32],
+    },
+    FixedSizeListF64Bits {
+        size: u32,
+        validity: &'a [u8],
+        values: &'a [u64],
+    },
     Var {
         ty: ColumnarType,
         validity: &'a [u8],
         offsets: &'a [u32],
         data: VarDataView<'a>,
     },
+    List {
+        validity: &'a [u8],
+        offsets: &'a [u32],
+        child: Box<ColumnDataView<'a>>,
+    },
+    Struct {
+        names: Vec<Option<&'a str>>,
+        validity: &'a [u8],
+        children: Vec<ColumnDataView<'a>>,
+    },
 }
 
 impl<'a> ColumnDataView<'a> {
     pub fn ty(&self) -> ColumnarType {
         match self {
-            ColumnDataView::FixedBool { .. } => ColumnarType::Bool,
+            ColumnDataView::FixedBool { .. } | ColumnDataView::BitPackedBool { .. } => {
+                ColumnarType::Bool
+            }
             ColumnDataView::FixedI16 { .. } => ColumnarType::I16,
             ColumnDataView::FixedI32 { .. } => ColumnarType::I32,
             ColumnDataView::FixedI64 { .. } => ColumnarType::I64,
+            ColumnDataView::FixedI8 { .. } => ColumnarType::I8,
+            ColumnDataView::FixedU8 { .. } => ColumnarType::U8,
+            ColumnDataView::FixedU16 { .. } => ColumnarType::U16,
+            ColumnDataView::FixedU32 { .. } => ColumnarType::U32,
+            ColumnDataView::FixedU64 { .. } => ColumnarType::U64,
             ColumnDataView::FixedF32Bits { .. } => ColumnarType::F32,
             ColumnDataView::FixedF64Bits { .. } => ColumnarType::F64,
             ColumnDataView::FixedUuid { .. } => ColumnarType::Uuid,
             ColumnDataView::FixedTimestampMicros { .. } => ColumnarType::TimestampTzMicros,
-            ColumnDataView::Var { ty, .. } => *ty,
+            ColumnDataView::FixedTimestamp { unit, tz, .. } => ColumnarType::Timestamp {
+                unit: *unit,
+                tz: tz.map(Arc::from),
+            },
+            ColumnDataView::FixedDate32 { .. } => ColumnarType::Date,
+            ColumnDataView::FixedTimeMicros { .. } => ColumnarType::TimeMicros,
+            ColumnDataView::FixedInterval { .. } => ColumnarType::IntervalMonthDayMicros,
+            ColumnDataView::FixedDecimal128 {
+                precision, scale, ..
+            } => ColumnarType::Decimal {
+                precision: *precision,
+                scale: *scale,
+            },
+            ColumnDataView::FixedSizeBinary { size, .. } => ColumnarType::FixedSizeBinary(*size),
+            ColumnDataView::FixedSizeListF32Bits { size, .. } => {
+                ColumnarType::FixedSizeList(Box::new(ColumnarType::F32), *size)
+            }
+            ColumnDataView::FixedSizeListF64Bits { size, .. } => {
+                ColumnarType::FixedSizeList(Box::new(ColumnarType::F64), *size)
+            }
+            ColumnDataView::Var { ty, .. } => ty.clone(),
+            ColumnDataView::List { child, .. } => ColumnarType::List(Box::new(child.ty())),
+            ColumnDataView::Struct {
+                names, children, ..
+            } => ColumnarType::Struct(
+                names
+                    .iter()
+                    .zip(children)
+                    .map(|(name, child)| ColumnarField {
+                        name: name.map(str::to_string),
+                        ty: child.ty(),
+                    })
+                    .collect(),
+            ),
         }
     }
 
-    pub fn validate_for_row_count(&self, ty: ColumnarType, row_count: usize) -> Result<()> {
-        if self.ty() != ty {
-            return Err(Error::Other("column type mismatch".to_string()));
+    pub fn to_column_data(&self) -> Result<ColumnData> {
+        let col = match self {
+            ColumnDataView::FixedBool { validity, values } => ColumnData::FixedBool {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::BitPackedBool { validity, values } => ColumnData::BitPackedBool {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedI16 { validity, values } => ColumnData::FixedI16 {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedI32 { validity, values } => ColumnData::FixedI32 {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedI64 { validity, values } => ColumnData::FixedI64 {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedI8 { validity, values } => ColumnData::FixedI8 {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedU8 { validity, values } => ColumnData::FixedU8 {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedU16 { validity, values } => ColumnData::FixedU16 {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedU32 { validity, values } => ColumnData::FixedU32 {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedU64 { validity, values } => ColumnData::FixedU64 {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedF32Bits { validity, values } => ColumnData::FixedF32Bits {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedF64Bits { validity, values } => ColumnData::FixedF64Bits {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedUuid { validity, values } => ColumnData::FixedUuid {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedTimestampMicros { validity, values } => {
+                ColumnData::FixedTimestampMicros {
+                    validity: ValidityBitmap {
+                        bytes: validity.to_vec(),
+                    },
+                    values: values.to_vec(),
+                }
+            }
+            ColumnDataView::FixedTimestamp {
+                unit,
+                tz,
+                validity,
+                values,
+            } => ColumnData::FixedTimestamp {
+                unit: *unit,
+                tz: tz.map(Arc::from),
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedDate32 { validity, values } => ColumnData::FixedDate32 {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedTimeMicros { validity, values } => ColumnData::FixedTimeMicros {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedInterval { validity, values } => ColumnData::FixedInterval {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedDecimal128 {
+                precision,
+                scale,
+                validity,
+                values,
+            } => ColumnData::FixedDecimal128 {
+                precision: *precision,
+                scale: *scale,
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedSizeBinary {
+                size,
+                validity,
+                values,
+            } => ColumnData::FixedSizeBinary {
+                size: *size,
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedSizeListF32Bits {
+                size,
+                validity,
+                values,
+            } => ColumnData::FixedSizeListF32Bits {
+                size: *size,
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::FixedSizeListF64Bits {
+                size,
+                validity,
+                values,
+            } => ColumnData::FixedSizeListF64Bits {
+                size: *size,
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                values: values.to_vec(),
+            },
+            ColumnDataView::Var {
+                ty,
+                validity,
+                offsets,
+                data,
+            } => ColumnData::Var {
+                ty: ty.clone(),
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                offsets: offsets.to_vec(),
+                data: data.to_vec()?,
+            },
+            ColumnDataView::List {
+                validity,
+                offsets,
+                child,
+            } => ColumnData::List {
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                offsets: offsets.to_vec(),
+                child: Box::new(child.to_column_data()?),
+            },
+            ColumnDataView::Struct {
+                names,
+                validity,
+                children,
+            } => ColumnData::Struct {
+                names: names.iter().map(|n| n.map(str::to_string)).collect(),
+                validity: ValidityBitmap {
+                    bytes: validity.to_vec(),
+                },
+                children: children
+                    .iter()
+                    .map(ColumnDataView::to_column_data)
+                    .collect::<Result<_>>()?,
+            },
+        };
+        Ok(col)
+    }
+
+    /// Like `self.ty() == *ty`, without allocating the time zone or nested types.
+    fn has_type(&self, ty: &ColumnarType) -> bool {
+        match (self, ty) {
+            (
+                ColumnDataView::FixedTimestamp { unit, tz, .. },
+                ColumnarType::Timestamp {
+                    unit: ty_unit,
+                    tz: ty_tz,
+                },
+            ) => unit == ty_unit && *tz == ty_tz.as_deref(),
+            (
+                ColumnDataView::FixedSizeListF32Bits { size, .. },
+                ColumnarType::FixedSizeList(item, ty_size),
+            ) => size == ty_size && **item == ColumnarType::F32,
+            (
+                ColumnDataView::FixedSizeListF64Bits { size, .. },
+                ColumnarType::FixedSizeList(item, ty_size),
+            ) => size == ty_size && **item == ColumnarType::F64,
+            (ColumnDataView::List { child, .. }, ColumnarType::List(item)) => child.has_type(item),
+            (
+                ColumnDataView::Struct {
+                    names, children, ..
+                },
+                ColumnarType::Struct(fields),
+            ) => {
+                names.len() == fields.len()
+                    && children.len() == fields.len()
+                    && fields
+                        .iter()
+                        .zip(names.iter().zip(children))
+                        .all(|(f, (name, child))| {
+                            f.name.as_deref() == *name && child.has_type(&f.ty)
+                        })
+            }
+            _ => self.ty() == *ty,
+        }
+    }
+
+    pub fn validate_for_row_count(&self, ty: &ColumnarType, row_count: usize) -> Result<()> {
+        if !self.has_type(ty) {
+            return Err(Error::Schema {
+                column: None,
+                reason: "column type mismatch",
+            });
         }
 
         let expected_validity = ValidityBitmap::len_for_row_count(row_count)?;
         match self {
-            ColumnDataView::FixedBool { validity, values } => {
+            ColumnDataView::FixedBool { validity, values }
+            | ColumnDataView::FixedU8 { validity, values } => {
                 if validity.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnDataView::BitPackedBool { validity, values } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != expected_validity {
+                    return Err(invalid("values length mismatch"));
                 }
             }
             ColumnDataView::FixedI16 { validity, values } => {
                 if validity.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != row_count {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnDataView::FixedI8 { validity, values } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != row_count {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnDataView::FixedU16 { validity, values } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
             ColumnDataView::FixedI32 { validity, values } => {
                 if validity.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
             ColumnDataView::FixedI64 { validity, values } => {
                 if validity.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
-            ColumnDataView::FixedF32Bits { validity, values } => {
+            ColumnDataView::FixedF32Bits { validity, values }
+            | ColumnDataView::FixedU32 { validity, values } => {
                 if validity.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
-            ColumnDataView::FixedF64Bits { validity, values } => {
+            ColumnDataView::FixedF64Bits { validity, values }
+            | ColumnDataView::FixedU64 { validity, values } => {
                 if validity.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
             ColumnDataView::FixedUuid { validity, values } => {
                 if validity.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
                 }
             }
-            ColumnDataView::FixedTimestampMicros { validity, values } => {
+            ColumnDataView::FixedTimestampMicros { validity, values }
+            | ColumnDataView::FixedTimestamp {
+                validity, values, ..
+            }
+            | ColumnDataView::FixedTimeMicros { validity, values } => {
                 if validity.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 if values.len() != row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnDataView::FixedDate32 { validity, values } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != row_count {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnDataView::FixedInterval { validity, values } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != row_count {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnDataView::FixedDecimal128 {
+                precision,
+                validity,
+                values,
+                ..
+            } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != row_count {
+                    return Err(invalid("values length mismatch"));
+                }
+                check_decimal_values(validity, values, *precision)?;
+            }
+            ColumnDataView::FixedSizeBinary {
+                size,
+                validity,
+                values,
+            } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != fixed_size_len(row_count, *size)? {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnDataView::FixedSizeListF32Bits {
+                size,
+                validity,
+                values,
+            } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != fixed_size_len(row_count, *size)? {
+                    return Err(invalid("values length mismatch"));
+                }
+            }
+            ColumnDataView::FixedSizeListF64Bits {
+                size,
+                validity,
+                values,
+            } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                if values.len() != fixed_size_len(row_count, *size)? {
+                    return Err(invalid("values length mismatch"));
                 }
             }
             ColumnDataView::Var {
@@ -161,34 +637,50 @@ impl<'a> ColumnDataView<'a> {
                 ..
             } => {
                 if validity.len() != expected_validity {
-                    return Err(Error::Other("validity length mismatch".to_string()));
+                    return Err(invalid("validity length mismatch"));
                 }
                 let data_len = data.len()?;
                 if data_len > (u32::MAX as usize) {
-                    return Err(Error::Other("data too large".to_string()));
+                    return Err(Error::LimitExceeded {
+                        kind: LimitKind::PayloadLength,
+                        column: None,
+                        offset: None,
+                    });
                 }
-                let expected_offsets_len = row_count
-                    .checked_add(1)
-                    .ok_or_else(|| Error::Other("row_count too large".to_string()))?;
-                if offsets.len() != expected_offsets_len {
-                    return Err(Error::Other("offsets length mismatch".to_string()));
+                if check_offsets(offsets, row_count)? != data_len {
+                    return Err(invalid("final offset mismatch"));
                 }
-                if offsets.first().copied().unwrap_or(1) != 0 {
-                    return Err(Error::Other("offsets[0] must be 0".to_string()));
+            }
+            ColumnDataView::List {
+                validity,
+                offsets,
+                child,
+            } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
                 }
-                let mut prev = 0u32;
-                for &o in offsets.iter() {
-                    if o < prev {
-                        return Err(Error::Other("offsets must be non-decreasing".to_string()));
-                    }
-                    prev = o;
+                let ColumnarType::List(item) = ty else {
+                    return Err(Error::Internal("list column with non-list type"));
+                };
+                let child_rows = check_offsets(offsets, row_count)?;
+                child.validate_for_row_count(item, child_rows)?;
+            }
+            ColumnDataView::Struct {
+                names,
+                validity,
+                children,
+            } => {
+                if validity.len() != expected_validity {
+                    return Err(invalid("validity length mismatch"));
+                }
+                let ColumnarType::Struct(fields) = ty else {
+                    return Err(Error::Internal("struct column with non-struct type"));
+                };
+                if names.len() != children.len() {
+                    return Err(invalid("struct field count mismatch"));
                 }
-                let final_off = offsets
-                    .last()
-                    .copied()
-                    .ok_or_else(|| Error::Other("offsets length mismatch".to_string()))?;
-                if final_off != (data_len as u32) {
-                    return Err(Error::Other("final offset mismatch".to_string()));
+                for (field, child) in fields.iter().zip(children) {
+                    child.validate_for_row_count(&field.ty, row_count)?;
                 }
             }
         }
@@ -212,13 +704,33 @@ impl<'a> VarDataView<'a> {
             VarDataView::Chunks { inline, chunks } => {
                 let mut total = inline.len();
                 for c in *chunks {
-                    total = total
-                        .checked_add(c.len())
-                        .ok_or_else(|| Error::Other("data too large".to_string()))?;
+                    total = total.checked_add(c.len()).ok_or(Error::LimitExceeded {
+                        kind: LimitKind::PayloadLength,
+                        column: None,
+                        offset: None,
+                    })?;
                 }
                 Ok(total)
             }
         }
     }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(self.len()?);
+        match self {
+            VarDataView::Contiguous(bytes) => out.extend_from_slice(bytes),
+            VarDataView::Chunks { inline, chunks } => {
+                out.extend_from_slice(inline);
+                for c in *chunks {
+                    out.extend_from_slice(c);
+                }
+            }
+        }
+        Ok(out)
+    }
 }
 
@@ -2,7 +2,9 @@ use crate::Result;
 use crate::batch::ColumnarBatch;
 use crate::batch_view::ColumnarBatchView;
 use crate::codec::mathldbt_v1::{
-    decode_mathldbt_v1, decode_mathldbt_v1_into_with_workspace, decode_mathldbt_v1_with_workspace,
+    decode_mathldbt_v1, decode_mathldbt_v1_into_with_workspace, decode_mathldbt_v1_view_with_workspace,
+    decode_mathldbt_v1_with_workspace,
+    decode_mathldbt_v1_projected, decode_mathldbt_v1_projected_with_workspace, inspect_mathldbt_v1,
     encode_mathldbt_v1_fast_path_into, encode_mathldbt_v1_fast_path_into_opt_with_workspace,
     encode_mathldbt_v1_fast_path_into_with_workspace,
     encode_mathldbt_v1_into, encode_mathldbt_v1_into_with_workspace,
@@ -16,10 +18,15 @@ use crate::codec::mathldbt_v1_compressed::{
     encode_mathldbt_v1_compressed_fast_path_into_with_workspace,
 };
 
-pub use crate::codec::mathldbt_v1::{MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace};
+pub use crate::codec::mathldbt_v1::{
+    ColumnInfo, ColumnSelector, EnvelopeInfo, MathldbtV1DecodeWorkspace, MathldbtV1DecodedView,
+    MathldbtV1Dictionary, MathldbtV1EncodeWorkspace,
+};
 pub use crate::codec::mathldbt_v1_compressed::{
     Compression, MathldbtV1CompressedDecodeWorkspace, MathldbtV1CompressedEncodeWorkspace,
 };
+pub use crate::codec::mathldbt_v1_file::{FileIndexEntry, FileReader, FileWriter};
+pub use crate::codec::mathldbt_v1_stream::{StreamReader, StreamWriter};
 
 fn enable_opt_encodings(ws: &mut MathldbtV1EncodeWorkspace) {
     ws.set_enable_dict_utf8(true)
@@ -108,6 +115,35 @@ pub fn decode_into_with_workspace(
     decode_mathldbt_v1_into_with_workspace(bytes, out, ws)
 }
 
+// Inspection (MATHLDBT v1; header and descriptors only)
+
+pub fn inspect(bytes: &[u8]) -> Result<EnvelopeInfo> {
+    inspect_mathldbt_v1(bytes)
+}
+
+// Projected decode (MATHLDBT v1; selected columns only)
+
+pub fn decode_projected(bytes: &[u8], projection: &[ColumnSelector<'_>]) -> Result<ColumnarBatch> {
+    decode_mathldbt_v1_projected(bytes, projection)
+}
+
+pub fn decode_projected_with_workspace(
+    bytes: &[u8],
+    projection: &[ColumnSelector<'_>],
+    ws: &mut MathldbtV1DecodeWorkspace,
+) -> Result<ColumnarBatch> {
+    decode_mathldbt_v1_projected_with_workspace(bytes, projection, ws)
+}
+
+// Borrowed decode (MATHLDBT v1; zero-copy where the payload allows it)
+
+pub fn decode_view_with_workspace<'a>(
+    bytes: &'a [u8],
+    ws: &'a mut MathldbtV1DecodeWorkspace,
+) -> Result<MathldbtV1DecodedView<'a>> {
+    decode_mathldbt_v1_view_with_workspace(bytes, ws)
+}
+
 // Compressed (compress(encode_v1(...)))
 
 pub fn encode_compressed_into(
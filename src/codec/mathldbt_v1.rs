@@ -1,8 +1,12 @@
-use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap, fixed_size_len, invalid};
 use crate::batch_view::{ColumnarBatchView, ColumnDataView, VarDataView};
-use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
-use crate::{Error, Result};
+use crate::schema::{
+    ColumnarField, ColumnarSchema, ColumnarType, IntervalMonthDayMicros, MAX_NESTING_DEPTH,
+    TimeUnit, decimal_params_valid, fixed_size_valid, timestamp_is_tz_micros,
+};
+use crate::{Error, LimitKind, Result};
 use std::collections::HashMap;
+use std::ops::Range;
 
 const MAGIC: &[u8; 8] = b"MATHLDBT";
 const VERSION: u16 = 1;
@@ -24,15 +28,38 @@ impl FixedEncodingId {
 }
 
 const ENC_PLAIN: u16 = 0;
-const ENC_DICT_UTF8: u16 = 2;
+const ENC_DICT: u16 = 2;
 const ENC_DELTA_VARINT_I64: u16 = 3;
+const ENC_BIT_PACKED_BOOL: u16 = 4;
+const ENC_DICT_DELTA: u16 = 5;
+const ENC_FOR_BIT_PACKED: u16 = 6;
+const ENC_NULLABLE_DELTA_VARINT_I64: u16 = 7;
+const ENC_XOR_FLOAT: u16 = 8;
+pub(crate) const ENC_BYTE_STREAM_SPLIT: u16 = 9;
+const ENC_RLE: u16 = 10;
+const ENC_CONSTANT: u16 = 11;
 
-fn type_id(ty: ColumnarType) -> u16 {
+/// Values per frame-of-reference block.
+const FOR_BLOCK_LEN: usize = 128;
+
+const TYPE_ID_DECIMAL: u16 = 12;
+const TYPE_ID_TIMESTAMP: u16 = 13;
+const TYPE_ID_LIST: u16 = 22;
+const TYPE_ID_STRUCT: u16 = 23;
+const TYPE_ID_FIXED_SIZE_BINARY: u16 = 24;
+const TYPE_ID_FIXED_SIZE_LIST: u16 = 25;
+
+pub(crate) fn type_id(ty: &ColumnarType) -> u16 {
     match ty {
         ColumnarType::Bool => 1,
         ColumnarType::I16 => 2,
         ColumnarType::I32 => 3,
         ColumnarType::I64 => 4,
+        ColumnarType::I8 => 17,
+        ColumnarType::U8 => 18,
+        ColumnarType::U16 => 19,
+        ColumnarType::U32 => 20,
+        ColumnarType::U64 => 21,
         ColumnarType::F32 => 5,
         ColumnarType::F64 => 6,
         ColumnarType::Uuid => 7,
@@ -40,29 +67,248 @@ fn type_id(ty: ColumnarType) -> u16 {
         ColumnarType::Utf8 => 9,
         ColumnarType::Bytes => 10,
         ColumnarType::JsonbText => 11,
+        ColumnarType::Decimal { .. } => TYPE_ID_DECIMAL,
+        ColumnarType::Timestamp { .. } => TYPE_ID_TIMESTAMP,
+        ColumnarType::Date => 14,
+        ColumnarType::TimeMicros => 15,
+        ColumnarType::IntervalMonthDayMicros => 16,
+        ColumnarType::List(_) => TYPE_ID_LIST,
+        ColumnarType::Struct(_) => TYPE_ID_STRUCT,
+        ColumnarType::FixedSizeBinary(_) => TYPE_ID_FIXED_SIZE_BINARY,
+        ColumnarType::FixedSizeList(..) => TYPE_ID_FIXED_SIZE_LIST,
+    }
+}
+
+/// Types without parameters; parameterized ids are handled by `read_type`.
+fn type_from_id(id: u16) -> Option<ColumnarType> {
+    match id {
+        1 => Some(ColumnarType::Bool),
+        2 => Some(ColumnarType::I16),
+        3 => Some(ColumnarType::I32),
+        4 => Some(ColumnarType::I64),
+        5 => Some(ColumnarType::F32),
+        6 => Some(ColumnarType::F64),
+        7 => Some(ColumnarType::Uuid),
+        8 => Some(ColumnarType::TimestampTzMicros),
+        9 => Some(ColumnarType::Utf8),
+        10 => Some(ColumnarType::Bytes),
+        11 => Some(ColumnarType::JsonbText),
+        14 => Some(ColumnarType::Date),
+        15 => Some(ColumnarType::TimeMicros),
+        16 => Some(ColumnarType::IntervalMonthDayMicros),
+        17 => Some(ColumnarType::I8),
+        18 => Some(ColumnarType::U8),
+        19 => Some(ColumnarType::U16),
+        20 => Some(ColumnarType::U32),
+        21 => Some(ColumnarType::U64),
+        _ => None,
+    }
+}
+
+fn time_unit_id(unit: TimeUnit) -> u8 {
+    match unit {
+        TimeUnit::Second => 0,
+        TimeUnit::Millisecond => 1,
+        TimeUnit::Microsecond => 2,
+        TimeUnit::Nanosecond => 3,
     }
 }
 
-fn type_from_id(id: u16) -> Result<ColumnarType> {
+fn time_unit_from_id(id: u8) -> Option<TimeUnit> {
     match id {
-        1 => Ok(ColumnarType::Bool),
-        2 => Ok(ColumnarType::I16),
-        3 => Ok(ColumnarType::I32),
-        4 => Ok(ColumnarType::I64),
-        5 => Ok(ColumnarType::F32),
-        6 => Ok(ColumnarType::F64),
-        7 => Ok(ColumnarType::Uuid),
-        8 => Ok(ColumnarType::TimestampTzMicros),
-        9 => Ok(ColumnarType::Utf8),
-        10 => Ok(ColumnarType::Bytes),
-        11 => Ok(ColumnarType::JsonbText),
-        _ => Err(Error::Other(format!("unknown column type id: {id}"))),
+        0 => Some(TimeUnit::Second),
+        1 => Some(TimeUnit::Millisecond),
+        2 => Some(TimeUnit::Microsecond),
+        3 => Some(TimeUnit::Nanosecond),
+        _ => None,
+    }
+}
+
+/// Writes a descriptor type: the type id, then its parameters (`Decimal`: precision u8 |
+/// scale i8; `Timestamp`: unit u8 | tz_len u16 | tz bytes, with `tz_len = 0` for no zone;
+/// `List`: the item type; `Struct`: field_count u16, then per field name_len u16 | name bytes |
+/// field type, with `name_len = 0` for an unnamed field; `FixedSizeBinary`: size u32;
+/// `FixedSizeList`: size u32 | item type).
+pub(crate) fn write_type(out: &mut Vec<u8>, ty: &ColumnarType) {
+    write_u16_le(out, type_id(ty));
+    match ty {
+        ColumnarType::Decimal { precision, scale } => {
+            out.push(*precision);
+            out.push(*scale as u8);
+        }
+        ColumnarType::Timestamp { unit, tz } => {
+            out.push(time_unit_id(*unit));
+            let tz = tz.as_deref().unwrap_or("");
+            // `ColumnarSchema::new` bounds the zone name to u16.
+            write_u16_le(out, tz.len() as u16);
+            out.extend_from_slice(tz.as_bytes());
+        }
+        ColumnarType::List(item) => write_type(out, item),
+        ColumnarType::Struct(fields) => {
+            // `ColumnarSchema::new` bounds the field count and names to u16.
+            write_u16_le(out, fields.len() as u16);
+            for field in fields {
+                let name = field.name.as_deref().unwrap_or("");
+                write_u16_le(out, name.len() as u16);
+                out.extend_from_slice(name.as_bytes());
+                write_type(out, &field.ty);
+            }
+        }
+        ColumnarType::FixedSizeBinary(size) => write_u32_le(out, *size),
+        ColumnarType::FixedSizeList(item, size) => {
+            write_u32_le(out, *size);
+            write_type(out, item);
+        }
+        _ => {}
+    }
+}
+
+/// Reads a descriptor type written by `write_type`. `read` fills the buffer from the input and
+/// returns the offset it was read from.
+pub(crate) fn read_type(
+    column: usize,
+    read: &mut dyn FnMut(&mut [u8]) -> Result<usize>,
+) -> Result<ColumnarType> {
+    read_nested_type(column, read, 0)
+}
+
+/// `depth` is the number of enclosing lists and structs.
+fn read_nested_type(
+    column: usize,
+    read: &mut dyn FnMut(&mut [u8]) -> Result<usize>,
+    depth: usize,
+) -> Result<ColumnarType> {
+    let mut id = [0u8; 2];
+    let type_offset = read(&mut id)?;
+    let type_id = u16::from_le_bytes(id);
+    if (type_id == TYPE_ID_LIST || type_id == TYPE_ID_STRUCT) && depth == MAX_NESTING_DEPTH {
+        return Err(Error::Malformed {
+            column: Some(column),
+            offset: type_offset,
+            reason: "type nesting too deep",
+        });
+    }
+    if type_id == TYPE_ID_LIST {
+        let item = read_nested_type(column, read, depth + 1)?;
+        return Ok(ColumnarType::List(Box::new(item)));
+    }
+    if type_id == TYPE_ID_STRUCT {
+        let mut count = [0u8; 2];
+        let count_offset = read(&mut count)?;
+        let field_count = u16::from_le_bytes(count);
+        if field_count == 0 {
+            return Err(Error::Malformed {
+                column: Some(column),
+                offset: count_offset,
+                reason: "struct must have at least one field",
+            });
+        }
+        let mut fields = Vec::with_capacity(field_count.into());
+        for _ in 0..field_count {
+            let mut len = [0u8; 2];
+            read(&mut len)?;
+            let mut name = vec![0u8; u16::from_le_bytes(len).into()];
+            let name_offset = read(&mut name)?;
+            let name = if name.is_empty() {
+                None
+            } else {
+                Some(String::from_utf8(name).map_err(|_| Error::Malformed {
+                    column: Some(column),
+                    offset: name_offset,
+                    reason: "invalid UTF-8 column name",
+                })?)
+            };
+            let ty = read_nested_type(column, read, depth + 1)?;
+            fields.push(ColumnarField { name, ty });
+        }
+        return Ok(ColumnarType::Struct(fields));
+    }
+    if type_id == TYPE_ID_FIXED_SIZE_BINARY || type_id == TYPE_ID_FIXED_SIZE_LIST {
+        let mut params = [0u8; 4];
+        let size_offset = read(&mut params)?;
+        let size = u32::from_le_bytes(params);
+        if !fixed_size_valid(size) {
+            return Err(Error::Malformed {
+                column: Some(column),
+                offset: size_offset,
+                reason: "invalid fixed size",
+            });
+        }
+        if type_id == TYPE_ID_FIXED_SIZE_BINARY {
+            return Ok(ColumnarType::FixedSizeBinary(size));
+        }
+        let mut item = [0u8; 2];
+        let item_offset = read(&mut item)?;
+        let item = match type_from_id(u16::from_le_bytes(item)) {
+            Some(item @ (ColumnarType::F32 | ColumnarType::F64)) => item,
+            _ => {
+                return Err(Error::Malformed {
+                    column: Some(column),
+                    offset: item_offset,
+                    reason: "fixed-size list items must be F32 or F64",
+                });
+            }
+        };
+        return Ok(ColumnarType::FixedSizeList(Box::new(item), size));
+    }
+    if type_id == TYPE_ID_DECIMAL {
+        let mut params = [0u8; 2];
+        let params_offset = read(&mut params)?;
+        let (precision, scale) = (params[0], params[1] as i8);
+        if !decimal_params_valid(precision, scale) {
+            return Err(Error::Malformed {
+                column: Some(column),
+                offset: params_offset,
+                reason: "invalid decimal precision/scale",
+            });
+        }
+        return Ok(ColumnarType::Decimal { precision, scale });
+    }
+    if type_id == TYPE_ID_TIMESTAMP {
+        let mut params = [0u8; 3];
+        let params_offset = read(&mut params)?;
+        let unit = time_unit_from_id(params[0]).ok_or(Error::Malformed {
+            column: Some(column),
+            offset: params_offset,
+            reason: "invalid timestamp unit",
+        })?;
+        let tz_len = u16::from_le_bytes([params[1], params[2]]) as usize;
+        if tz_len == 0 {
+            return Ok(ColumnarType::Timestamp { unit, tz: None });
+        }
+        let mut tz = vec![0u8; tz_len];
+        let tz_offset = read(&mut tz)?;
+        let tz = String::from_utf8(tz).map_err(|_| Error::Malformed {
+            column: Some(column),
+            offset: tz_offset,
+            reason: "invalid timestamp time zone",
+        })?;
+        if timestamp_is_tz_micros(unit, Some(&tz)) {
+            return Err(Error::Malformed {
+                column: Some(column),
+                offset: params_offset,
+                reason: "UTC microsecond timestamps must be TimestampTzMicros",
+            });
+        }
+        return Ok(ColumnarType::Timestamp {
+            unit,
+            tz: Some(tz.into()),
+        });
     }
+    type_from_id(type_id).ok_or(Error::UnknownTypeId {
+        column,
+        offset: type_offset,
+        type_id,
+    })
 }
 
 fn ceil_div_8(n: usize) -> Result<usize> {
     n.checked_add(7)
-        .ok_or_else(|| Error::Other("size overflow".to_string()))
+        .ok_or(Error::LimitExceeded {
+            kind: LimitKind::RowCount,
+            column: None,
+            offset: None,
+        })
         .map(|v| v / 8)
 }
 
@@ -75,37 +321,56 @@ fn write_u32_le(out: &mut Vec<u8>, v: u32) {
 }
 
 fn write_u16_len_bytes(out: &mut Vec<u8>, bytes: &[u8]) -> Result<()> {
-    let len: u16 = bytes
-        .len()
-        .try_into()
-        .map_err(|_| Error::Other("name too long".to_string()))?;
+    let len: u16 = bytes.len().try_into().map_err(|_| Error::LimitExceeded {
+        kind: LimitKind::NameLength,
+        column: None,
+        offset: None,
+    })?;
     write_u16_le(out, len);
     out.extend_from_slice(bytes);
     Ok(())
 }
 
+fn invalid_column(column: usize, reason: &'static str) -> Error {
+    invalid(reason).at_column(column)
+}
+
+fn payload_too_large() -> Error {
+    Error::LimitExceeded {
+        kind: LimitKind::PayloadLength,
+        column: None,
+        offset: None,
+    }
+}
+
 fn write_u32_len_bytes(out: &mut Vec<u8>, bytes: &[u8]) -> Result<()> {
-    let len: u32 = bytes
-        .len()
-        .try_into()
-        .map_err(|_| Error::Other("payload too large".to_string()))?;
+    let len: u32 = bytes.len().try_into().map_err(|_| payload_too_large())?;
     write_u32_le(out, len);
     out.extend_from_slice(bytes);
     Ok(())
 }
 
 #[inline]
-fn checked_byte_len(count: usize, elem_size: usize, err: &'static str) -> Result<usize> {
-    count
-        .checked_mul(elem_size)
-        .ok_or_else(|| Error::Other(err.to_string()))
+fn checked_byte_len(count: usize, elem_size: usize) -> Result<usize> {
+    count.checked_mul(elem_size).ok_or_else(payload_too_large)
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct MathldbtV1EncodeWorkspace {
     enable_dict_utf8: bool,
+    enable_dict_bytes: bool,
     enable_delta_varint_i64: bool,
+    enable_pg_be_fixed: bool,
+    enable_bit_packed_bool: bool,
+    enable_dict_delta: bool,
+    enable_for_bit_packed: bool,
+    enable_xor_float: bool,
+    enable_byte_stream_split: bool,
+    enable_rle: bool,
 
+    column_dictionaries: Vec<Option<MathldbtV1Dictionary>>,
+    column_byte_stream_split: Vec<Option<bool>>,
+    delta_dicts: Vec<DeltaDictEncodeState>,
     dict_values: Vec<Vec<u8>>,
     dict_map: HashMap<Vec<u8>, u32>,
     dict_indices: Vec<u32>,
@@ -116,11 +381,18 @@ pub struct MathldbtV1EncodeWorkspace {
     view_var_coalesce: Vec<u8>,
 
     delta_buf: Vec<u8>,
+    for_buf: Vec<u8>,
+    xor_buf: Vec<u8>,
+    split_buf: Vec<u8>,
+    rle_buf: Vec<u8>,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct MathldbtV1DecodeWorkspace {
-    dict_offsets: Vec<u32>,
+    dicts: DictDecodeState,
+    keep_bit_packed_bool: bool,
+
+    view_arena: Vec<ColumnData>,
 }
 
 impl MathldbtV1EncodeWorkspace {
@@ -129,15 +401,383 @@ impl MathldbtV1EncodeWorkspace {
         self
     }
 
+    /// Dictionary-encodes `Bytes` columns, with the same layout and rules as `Utf8`.
+    pub fn set_enable_dict_bytes(&mut self, enabled: bool) -> &mut Self {
+        self.enable_dict_bytes = enabled;
+        self
+    }
+
+    /// Always dictionary-encodes top-level varlen column `column` with `dictionary`, whatever
+    /// the other options; `None` removes it. Encoding fails on values missing from it.
+    pub fn set_column_dictionary(
+        &mut self,
+        column: usize,
+        dictionary: Option<MathldbtV1Dictionary>,
+    ) -> &mut Self {
+        if self.column_dictionaries.len() <= column {
+            self.column_dictionaries.resize(column + 1, None);
+        }
+        self.column_dictionaries[column] = dictionary;
+        self
+    }
+
+    /// Makes dictionary-encoded top-level columns `DictDelta`: each column's dictionary persists
+    /// across batches, and a batch only carries the entries the previous ones did not. Meant
+    /// for streams, whose batches are decoded in order by one workspace.
+    pub fn set_enable_dict_delta(&mut self, enabled: bool) -> &mut Self {
+        self.enable_dict_delta = enabled;
+        self
+    }
+
+    pub(crate) fn dict_delta_enabled(&self) -> bool {
+        self.enable_dict_delta
+    }
+
+    /// Forgets the delta dictionaries sent so far; the next batch starts each one afresh.
+    pub fn reset_dict_deltas(&mut self) -> &mut Self {
+        self.delta_dicts.clear();
+        self
+    }
+
+    /// Drops entries added by an encode that failed before its batch was emitted.
+    fn discard_pending_dict_entries(&mut self) {
+        for state in &mut self.delta_dicts {
+            for key in state.pending.drain(..) {
+                state.map.remove(&key);
+            }
+        }
+    }
+
+    /// Keeps the entries added by the last encode; call once its batch has been emitted.
+    pub(crate) fn commit_pending_dict_entries(&mut self) {
+        for state in &mut self.delta_dicts {
+            state.pending.clear();
+        }
+    }
+
+    /// Emits `I64`, `U64` and timestamp columns as `DeltaVarintI64` when smaller than plain;
+    /// columns with nulls use `NullableDeltaVarintI64`, which skips the null slots.
     pub fn set_enable_delta_varint_i64(&mut self, enabled: bool) -> &mut Self {
         self.enable_delta_varint_i64 = enabled;
         self
     }
+
+    /// Emits `I16`, `I32` and `I64` columns as `ForBitPacked` (frame of reference plus bit
+    /// packing) when that is smaller than the plain layout. For `I64` columns with delta
+    /// encoding also enabled, the smaller of the two is used.
+    pub fn set_enable_for_bit_packed(&mut self, enabled: bool) -> &mut Self {
+        self.enable_for_bit_packed = enabled;
+        self
+    }
+
+    /// Emits `F32` and `F64` columns as `XorFloat` (each value's bits XORed with the previous
+    /// value's) when that is smaller than the plain layout.
+    pub fn set_enable_xor_float(&mut self, enabled: bool) -> &mut Self {
+        self.enable_xor_float = enabled;
+        self
+    }
+
+    /// Emits `F32`, `F64` and `I64` columns as `ByteStreamSplit` (byte `k` of every value in
+    /// plane `k`) when no smaller encoding applies. The payload is no smaller than plain, but
+    /// often compresses better; the compressed encoders try it on their own for top-level
+    /// columns this leaves unset.
+    pub fn set_enable_byte_stream_split(&mut self, enabled: bool) -> &mut Self {
+        self.enable_byte_stream_split = enabled;
+        self
+    }
+
+    /// Overrides `ByteStreamSplit` for top-level column `column`: `Some(false)` keeps it out
+    /// even when enabled for all columns; `None` follows [`Self::set_enable_byte_stream_split`].
+    pub fn set_column_byte_stream_split(
+        &mut self,
+        column: usize,
+        enabled: Option<bool>,
+    ) -> &mut Self {
+        if self.column_byte_stream_split.len() <= column {
+            self.column_byte_stream_split.resize(column + 1, None);
+        }
+        self.column_byte_stream_split[column] = enabled;
+        self
+    }
+
+    /// Whether `ByteStreamSplit` is neither enabled nor disabled for top-level column `column`.
+    pub(crate) fn byte_stream_split_unset(&self, column: usize) -> bool {
+        !self.enable_byte_stream_split
+            && self
+                .column_byte_stream_split
+                .get(column)
+                .copied()
+                .flatten()
+                .is_none()
+    }
+
+    /// Runs `f` with `ByteStreamSplit` also enabled for those of the top-level `columns` that
+    /// have no setting of their own, then restores the previous per-column settings.
+    pub(crate) fn with_byte_stream_split<R>(
+        &mut self,
+        columns: &[usize],
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let prev_len = self.column_byte_stream_split.len();
+        let mut set = Vec::with_capacity(columns.len());
+        for &column in columns {
+            if self.column_byte_stream_split.len() <= column {
+                self.column_byte_stream_split.resize(column + 1, None);
+            }
+            if self.column_byte_stream_split[column].is_none() {
+                self.column_byte_stream_split[column] = Some(true);
+                set.push(column);
+            }
+        }
+        let r = f(self);
+        for column in set {
+            self.column_byte_stream_split[column] = None;
+        }
+        self.column_byte_stream_split.truncate(prev_len);
+        r
+    }
+
+    /// Emits scalar fixed-width columns (`Bool` included) as `Rle` (runs of equal values), or
+    /// as `Constant` (the value once) when all values are equal, whenever that is smaller than
+    /// the encoding otherwise chosen. `Dict` columns get the same treatment for their indices;
+    /// `DictDelta` columns are left as they are.
+    pub fn set_enable_rle(&mut self, enabled: bool) -> &mut Self {
+        self.enable_rle = enabled;
+        self
+    }
+
+    /// Emits fixed-width columns as `PgBeFixed` (big-endian values) instead of `PlainLe`.
+    ///
+    /// The delta encodings, `ForBitPacked`, `XorFloat`, `ByteStreamSplit` and `Rle` take
+    /// precedence when enabled and chosen; columns they pass over still use `PgBeFixed`.
+    pub fn set_enable_pg_be_fixed(&mut self, enabled: bool) -> &mut Self {
+        self.enable_pg_be_fixed = enabled;
+        self
+    }
+
+    /// Emits `Bool` columns as `BitPackedBool` (one bit per row), taking precedence over
+    /// `PgBeFixed`.
+    pub fn set_enable_bit_packed_bool(&mut self, enabled: bool) -> &mut Self {
+        self.enable_bit_packed_bool = enabled;
+        self
+    }
+}
+
+impl MathldbtV1DecodeWorkspace {
+    /// Decodes `Bool` columns as [`ColumnData::BitPackedBool`] instead of one byte per row.
+    /// `decode_*_into` follows the destination column's shape instead.
+    pub fn set_keep_bit_packed_bool(&mut self, keep: bool) -> &mut Self {
+        self.keep_bit_packed_bool = keep;
+        self
+    }
+
+    /// Forgets the `DictDelta` dictionaries received so far.
+    pub fn reset_dict_deltas(&mut self) -> &mut Self {
+        self.dicts.reset_deltas();
+        self
+    }
+
+    /// Caps the entries kept across all `DictDelta` dictionaries; a batch whose deltas would
+    /// exceed it fails with [`LimitKind::DictEntries`]. `None` (the default) removes the cap.
+    pub fn set_max_dict_delta_entries(&mut self, max: Option<usize>) -> &mut Self {
+        self.dicts.max_delta_entries = max;
+        self
+    }
+
+    /// Like [`Self::set_max_dict_delta_entries`], for the total bytes of the entries.
+    pub fn set_max_dict_delta_bytes(&mut self, max: Option<usize>) -> &mut Self {
+        self.dicts.max_delta_bytes = max;
+        self
+    }
+}
+
+fn write_be_values<T: Copy, const N: usize>(
+    out: &mut Vec<u8>,
+    values: &[T],
+    row_count: usize,
+    to_be: impl Fn(T) -> [u8; N],
+) -> Result<()> {
+    if values.len() != row_count {
+        return Err(invalid("values length mismatch"));
+    }
+    let byte_len = checked_byte_len(row_count, N)?;
+    write_u32_le(out, byte_len.try_into().map_err(|_| payload_too_large())?);
+    out.reserve(byte_len);
+    for &v in values {
+        out.extend_from_slice(&to_be(v));
+    }
+    write_u32_le(out, 0);
+    Ok(())
+}
+
+/// Postgres `interval` order: micros i64 | days i32 | months i32.
+pub(crate) fn interval_to_be_bytes(v: IntervalMonthDayMicros) -> [u8; 16] {
+    let mut b = [0u8; 16];
+    b[..8].copy_from_slice(&v.micros.to_be_bytes());
+    b[8..12].copy_from_slice(&v.days.to_be_bytes());
+    b[12..].copy_from_slice(&v.months.to_be_bytes());
+    b
+}
+
+/// `PlainLe` order: months i32 | days i32 | micros i64.
+fn interval_to_le_bytes(v: IntervalMonthDayMicros) -> [u8; 16] {
+    let mut b = [0u8; 16];
+    b[..4].copy_from_slice(&v.months.to_le_bytes());
+    b[4..8].copy_from_slice(&v.days.to_le_bytes());
+    b[8..].copy_from_slice(&v.micros.to_le_bytes());
+    b
+}
+
+pub(crate) fn interval_from_be_bytes(b: &[u8]) -> IntervalMonthDayMicros {
+    IntervalMonthDayMicros {
+        micros: i64::from_be_slice(&b[..8]),
+        days: i32::from_be_slice(&b[8..12]),
+        months: i32::from_be_slice(&b[12..]),
+    }
+}
+
+/// Writes payload_1/payload_2 for a `PgBeFixed` column. Shared by the owned and fast-path
+/// encoders so both produce identical bytes.
+fn write_pg_be_fixed_payloads(
+    out: &mut Vec<u8>,
+    col: &ColumnDataView<'_>,
+    row_count: usize,
+) -> Result<()> {
+    match col {
+        ColumnDataView::FixedBool { values, .. } | ColumnDataView::FixedU8 { values, .. } => {
+            write_be_values(out, values, row_count, |v| [v])
+        }
+        ColumnDataView::BitPackedBool { values, .. } => {
+            write_unpacked_bool_payloads(out, values, row_count)
+        }
+        ColumnDataView::FixedI8 { values, .. } => {
+            write_be_values(out, values, row_count, i8::to_be_bytes)
+        }
+        ColumnDataView::FixedI16 { values, .. } => {
+            write_be_values(out, values, row_count, i16::to_be_bytes)
+        }
+        ColumnDataView::FixedU16 { values, .. } => {
+            write_be_values(out, values, row_count, u16::to_be_bytes)
+        }
+        ColumnDataView::FixedI32 { values, .. } | ColumnDataView::FixedDate32 { values, .. } => {
+            write_be_values(out, values, row_count, i32::to_be_bytes)
+        }
+        ColumnDataView::FixedI64 { values, .. }
+        | ColumnDataView::FixedTimestampMicros { values, .. }
+        | ColumnDataView::FixedTimestamp { values, .. }
+        | ColumnDataView::FixedTimeMicros { values, .. } => {
+            write_be_values(out, values, row_count, i64::to_be_bytes)
+        }
+        ColumnDataView::FixedF32Bits { values, .. } | ColumnDataView::FixedU32 { values, .. } => {
+            write_be_values(out, values, row_count, u32::to_be_bytes)
+        }
+        ColumnDataView::FixedF64Bits { values, .. } | ColumnDataView::FixedU64 { values, .. } => {
+            write_be_values(out, values, row_count, u64::to_be_bytes)
+        }
+        ColumnDataView::FixedUuid { values, .. } => write_be_values(out, values, row_count, |v| v),
+        ColumnDataView::FixedSizeBinary { size, values, .. } => {
+            write_be_values(out, values, fixed_size_len(row_count, *size)?, |v| [v])
+        }
+        ColumnDataView::FixedSizeListF32Bits { size, values, .. } => write_be_values(
+            out,
+            values,
+            fixed_size_len(row_count, *size)?,
+            u32::to_be_bytes,
+        ),
+        ColumnDataView::FixedSizeListF64Bits { size, values, .. } => write_be_values(
+            out,
+            values,
+            fixed_size_len(row_count, *size)?,
+            u64::to_be_bytes,
+        ),
+        ColumnDataView::FixedDecimal128 { values, .. } => {
+            write_be_values(out, values, row_count, i128::to_be_bytes)
+        }
+        ColumnDataView::FixedInterval { values, .. } => {
+            write_be_values(out, values, row_count, interval_to_be_bytes)
+        }
+        ColumnDataView::Var { .. }
+        | ColumnDataView::List { .. }
+        | ColumnDataView::Struct { .. } => {
+            Err(Error::Internal("invalid encoding for varlen column"))
+        }
+    }
+}
+
+/// Zeroes the bits past `row_count` in the last byte of a bit-packed payload.
+fn mask_bit_padding(bytes: &mut [u8], row_count: usize) {
+    let rem = row_count % 8;
+    match bytes.last_mut() {
+        Some(last) if rem != 0 => *last &= (1u8 << rem) - 1,
+        _ => {}
+    }
+}
+
+/// Packs one-byte-per-row `Bool` values into bits, LSB-first; any non-zero byte is `true`.
+fn pack_bool_bytes(values: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    values.chunks(8).map(|chunk| {
+        chunk
+            .iter()
+            .enumerate()
+            .fold(0u8, |b, (i, &v)| b | (u8::from(v != 0) << i))
+    })
+}
+
+/// Writes payload_1/payload_2 for a `BitPackedBool` column: one bit per row, LSB-first, with
+/// the padding bits of the last byte zeroed.
+fn write_bit_packed_bool_payloads(
+    out: &mut Vec<u8>,
+    col: &ColumnDataView<'_>,
+    row_count: usize,
+) -> Result<()> {
+    let byte_len = ceil_div_8(row_count)?;
+    let len_ok = match col {
+        ColumnDataView::FixedBool { values, .. } => values.len() == row_count,
+        ColumnDataView::BitPackedBool { values, .. } => values.len() == byte_len,
+        _ => return Err(Error::Internal("invalid encoding for non-bool column")),
+    };
+    if !len_ok {
+        return Err(invalid("values length mismatch"));
+    }
+    write_u32_le(out, byte_len.try_into().map_err(|_| payload_too_large())?);
+    let start = out.len();
+    match col {
+        ColumnDataView::FixedBool { values, .. } => out.extend(pack_bool_bytes(values)),
+        ColumnDataView::BitPackedBool { values, .. } => out.extend_from_slice(values),
+        _ => {}
+    }
+    mask_bit_padding(&mut out[start..], row_count);
+    write_u32_le(out, 0);
+    Ok(())
+}
+
+/// Writes a `BitPackedBool` column with one `0`/`1` byte per row, for `PlainLe` and
+/// `PgBeFixed`.
+fn write_unpacked_bool_payloads(out: &mut Vec<u8>, values: &[u8], row_count: usize) -> Result<()> {
+    if values.len() != ceil_div_8(row_count)? {
+        return Err(invalid("values length mismatch"));
+    }
+    write_u32_le(out, row_count.try_into().map_err(|_| payload_too_large())?);
+    out.extend((0..row_count).map(|i| (values[i / 8] >> (i % 8)) & 1));
+    write_u32_le(out, 0);
+    Ok(())
+}
+
+/// `U64` columns reuse the `i64` delta coding on the same bits; wrapping deltas round-trip.
+fn u64_as_i64(values: &[u64]) -> &[i64] {
+    // SAFETY: `u64` and `i64` have the same size and alignment and every bit pattern is valid
+    // for both, so the slice can be reinterpreted in place with the same length and lifetime.
+    unsafe { std::slice::from_raw_parts(values.as_ptr() as *const i64, values.len()) }
+}
+
+fn u64_as_i64_mut(values: &mut [u64]) -> &mut [i64] {
+    // SAFETY: as in `u64_as_i64`; the exclusive borrow is moved into the returned slice.
+    unsafe { std::slice::from_raw_parts_mut(values.as_mut_ptr() as *mut i64, values.len()) }
 }
 
 #[inline]
 fn zigzag_i64_to_u64(x: i64) -> u64 {
-    ((x as u64) << 1) ^ (((x >> 63) as u64) & 1)
+    ((x as u64) << 1) ^ ((x >> 63) as u64)
 }
 
 #[inline]
@@ -156,26 +796,25 @@ fn write_u64_varint(out: &mut Vec<u8>, mut x: u64) {
     out.push(x as u8);
 }
 
-fn read_u64_varint(bytes: &[u8], pos: &mut usize) -> Result<u64> {
+/// Reads a LEB128 varint; on failure returns the reason, leaving location to the caller.
+fn read_u64_varint(bytes: &[u8], pos: &mut usize) -> core::result::Result<u64, &'static str> {
     let mut x: u64 = 0;
     let mut shift: u32 = 0;
     loop {
         if *pos >= bytes.len() {
-            return Err(Error::Other("truncated varint".to_string()));
+            return Err("truncated varint");
         }
         let b = bytes[*pos];
         *pos += 1;
         let lo = (b & 0x7F) as u64;
         if shift >= 64 {
-            return Err(Error::Other("varint overflow".to_string()));
+            return Err("varint overflow");
         }
         x |= lo << shift;
         if (b & 0x80) == 0 {
             return Ok(x);
         }
-        shift = shift
-            .checked_add(7)
-            .ok_or_else(|| Error::Other("varint overflow".to_string()))?;
+        shift = shift.checked_add(7).ok_or("varint overflow")?;
     }
 }
 
@@ -208,217 +847,911 @@ fn validity_all_valid(validity: &[u8], row_count: usize) -> bool {
     (validity[needed - 1] & mask) == mask
 }
 
+/// Builds a delta payload: the first value (i64 LE) then each following value's zigzag
+/// varint delta from the previous one. Columns with nulls get `NullableDeltaVarintI64`, which
+/// covers only the valid slots (an all-null column has an empty payload).
+///
+/// Returns `None` when the payload is not smaller than the plain layout.
 fn build_delta_varint_i64_payload<'a>(
     ws: &'a mut MathldbtV1EncodeWorkspace,
     values: &[i64],
-) -> Result<Option<&'a [u8]>> {
+    validity: &[u8],
+) -> Result<Option<(u16, &'a [u8])>> {
     if values.is_empty() {
         return Ok(None);
     }
+    let all_valid = validity_all_valid(validity, values.len());
+    let mut valid = values
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| all_valid || (validity[i / 8] & (1u8 << (i % 8))) != 0)
+        .map(|(_, &v)| v);
     ws.delta_buf.clear();
     ws.delta_buf.reserve(8 + values.len().saturating_mul(2));
-    ws.delta_buf.extend_from_slice(&values[0].to_le_bytes());
-    let mut prev = values[0];
-    for &v in &values[1..] {
-        let delta = v.wrapping_sub(prev);
-        write_u64_varint(&mut ws.delta_buf, zigzag_i64_to_u64(delta));
-        prev = v;
+    if let Some(first) = valid.next() {
+        ws.delta_buf.extend_from_slice(&first.to_le_bytes());
+        let mut prev = first;
+        for v in valid {
+            let delta = v.wrapping_sub(prev);
+            write_u64_varint(&mut ws.delta_buf, zigzag_i64_to_u64(delta));
+            prev = v;
+        }
     }
     if ws.delta_buf.len() >= values.len() * 8 {
         return Ok(None);
     }
-    Ok(Some(ws.delta_buf.as_slice()))
+    let encoding_id = if all_valid {
+        ENC_DELTA_VARINT_I64
+    } else {
+        ENC_NULLABLE_DELTA_VARINT_I64
+    };
+    Ok(Some((encoding_id, ws.delta_buf.as_slice())))
 }
 
-fn build_dict_utf8_payload<'a>(
-    ws: &'a mut MathldbtV1EncodeWorkspace,
-    validity: &[u8],
-    row_count: usize,
-    offsets: &[u32],
-    data: &[u8],
-) -> Result<Option<(&'a [u8], &'a [u8])>> {
-    if row_count == 0 {
-        return Ok(None);
-    }
-    if offsets.len() != row_count + 1 {
-        return Ok(None);
-    }
-    if data.len() > (u32::MAX as usize) {
+/// Builds a `ForBitPacked` payload: block count u32, then for each block of up to
+/// [`FOR_BLOCK_LEN`] values its minimum (little-endian, column width) | bit width u8 | each
+/// value minus the minimum in `bit width` bits, LSB-first, the last byte zero-padded.
+///
+/// Null slots are packed like valid ones, so their values round-trip. Returns `None` when the
+/// payload is not smaller than the plain layout.
+fn build_for_bit_packed_payload<'a, T: ForInt>(
+    buf: &'a mut Vec<u8>,
+    values: &[T],
+) -> Result<Option<&'a [u8]>> {
+    if values.is_empty() {
         return Ok(None);
     }
+    let block_count: u32 = values
+        .len()
+        .div_ceil(FOR_BLOCK_LEN)
+        .try_into()
+        .map_err(|_| payload_too_large())?;
+    buf.clear();
+    write_u32_le(buf, block_count);
+    for block in values.chunks(FOR_BLOCK_LEN) {
+        let min = block.iter().map(|&v| v.into()).min().unwrap_or(0);
+        let diff = |v: T| (v.into() as u64).wrapping_sub(min as u64);
+        let max_diff = block.iter().map(|&v| diff(v)).max().unwrap_or(0);
+        let width = 64 - max_diff.leading_zeros();
+        buf.extend_from_slice(&min.to_le_bytes()[..T::SIZE]);
+        buf.push(width as u8);
 
-    ws.dict_values.clear();
-    ws.dict_map.clear();
-    ws.dict_indices.clear();
-    ws.dict_indices.reserve(row_count);
-
-    for row in 0..row_count {
-        let is_valid = (validity[row / 8] & (1u8 << (row % 8))) != 0;
-        if !is_valid {
-            ws.dict_indices.push(0);
-            continue;
-        }
-        let start = offsets[row] as usize;
-        let end = offsets[row + 1] as usize;
-        if end < start || end > data.len() {
-            return Err(Error::Other("offset out of bounds".to_string()));
+        let mut acc: u128 = 0;
+        let mut acc_bits = 0u32;
+        for &v in block {
+            acc |= u128::from(diff(v)) << acc_bits;
+            acc_bits += width;
+            while acc_bits >= 8 {
+                buf.push(acc as u8);
+                acc >>= 8;
+                acc_bits -= 8;
+            }
         }
-        let bytes = &data[start..end];
-        if let Some(&idx) = ws.dict_map.get(bytes) {
-            ws.dict_indices.push(idx);
-            continue;
+        if acc_bits > 0 {
+            buf.push(acc as u8);
         }
-        let idx: u32 = ws
-            .dict_values
-            .len()
-            .try_into()
-            .map_err(|_| Error::Other("dict too large".to_string()))?;
-        let key = bytes.to_vec();
-        ws.dict_values.push(key.clone());
-        ws.dict_map.insert(key, idx);
-        ws.dict_indices.push(idx);
     }
-
-    let dict_count = ws.dict_values.len();
-    if dict_count == 0 {
+    if buf.len() >= values.len() * T::SIZE {
         return Ok(None);
     }
+    Ok(Some(buf.as_slice()))
+}
 
-    let index_width: usize = if dict_count <= 0x100 {
-        1
-    } else if dict_count <= 0x1_0000 {
-        2
+/// Picks the encoding of an `I64` column among the enabled options: the smaller of the delta
+/// encodings and `ForBitPacked`, preferring the delta encoding on ties, then `ByteStreamSplit`
+/// when `split`. `None` keeps the plain layout.
+fn build_i64_payload<'a>(
+    ws: &'a mut MathldbtV1EncodeWorkspace,
+    values: &[i64],
+    validity: &[u8],
+    split: bool,
+) -> Result<Option<(u16, &'a [u8])>> {
+    let delta = if ws.enable_delta_varint_i64 {
+        build_delta_varint_i64_payload(ws, values, validity)?.map(|(id, p)| (id, p.len()))
     } else {
-        4
+        None
+    };
+    let for_len = if ws.enable_for_bit_packed {
+        build_for_bit_packed_payload(&mut ws.for_buf, values)?.map(<[u8]>::len)
+    } else {
+        None
     };
+    Ok(match (delta, for_len) {
+        (Some((_, d)), Some(f)) if f < d => Some((ENC_FOR_BIT_PACKED, ws.for_buf.as_slice())),
+        (Some((encoding_id, _)), _) => Some((encoding_id, ws.delta_buf.as_slice())),
+        (None, Some(_)) => Some((ENC_FOR_BIT_PACKED, ws.for_buf.as_slice())),
+        (None, None) if split => Some((
+            ENC_BYTE_STREAM_SPLIT,
+            build_byte_stream_split_payload(&mut ws.split_buf, values)?,
+        )),
+        (None, None) => None,
+    })
+}
+
+/// Picks the encoding of an `F32` or `F64` column: `XorFloat` when enabled and smaller, then
+/// `ByteStreamSplit` when `split`. `None` keeps the plain layout.
+fn build_float_payload<'a, T: XorFloat + ByteStreamSplit>(
+    ws: &'a mut MathldbtV1EncodeWorkspace,
+    values: &[T],
+    split: bool,
+) -> Result<Option<(u16, &'a [u8])>> {
+    if ws.enable_xor_float && build_xor_float_payload(&mut ws.xor_buf, values)?.is_some() {
+        return Ok(Some((ENC_XOR_FLOAT, ws.xor_buf.as_slice())));
+    }
+    if split {
+        let payload = build_byte_stream_split_payload(&mut ws.split_buf, values)?;
+        return Ok(Some((ENC_BYTE_STREAM_SPLIT, payload)));
+    }
+    Ok(None)
+}
 
-    let indices_len = row_count
-        .checked_mul(index_width)
-        .ok_or_else(|| Error::Other("indices overflow".to_string()))?;
+fn byte_stream_split_requested(ws: &MathldbtV1EncodeWorkspace, column: Option<usize>) -> bool {
+    match column.and_then(|c| ws.column_byte_stream_split.get(c).copied().flatten()) {
+        Some(enabled) => enabled,
+        None => ws.enable_byte_stream_split,
+    }
+}
 
-    ws.dict_indices_bytes.clear();
-    ws.dict_indices_bytes.reserve(indices_len);
-    match index_width {
-        1 => {
-            for &idx in &ws.dict_indices {
-                ws.dict_indices_bytes.push(idx as u8);
-            }
+/// Builds a `ByteStreamSplit` payload: `T::SIZE` planes of `values.len()` bytes, plane `k`
+/// holding byte `k` (little-endian) of each value in row order.
+fn build_byte_stream_split_payload<'a, T: ByteStreamSplit>(
+    buf: &'a mut Vec<u8>,
+    values: &[T],
+) -> Result<&'a [u8]> {
+    let rows = values.len();
+    buf.clear();
+    buf.resize(checked_byte_len(rows, T::SIZE)?, 0);
+    for (i, &v) in values.iter().enumerate() {
+        let bits = v.to_le_u64();
+        for k in 0..T::SIZE {
+            buf[k * rows + i] = (bits >> (8 * k)) as u8;
         }
-        2 => {
-            for &idx in &ws.dict_indices {
-                let v: u16 = idx
-                    .try_into()
-                    .map_err(|_| Error::Other("dict index overflow".to_string()))?;
-                ws.dict_indices_bytes.extend_from_slice(&v.to_le_bytes());
-            }
+    }
+    Ok(buf.as_slice())
+}
+
+/// Builds an `Rle` payload into `buf`: for each run of equal values, its length (varint) then
+/// the value's bytes. A single run becomes `Constant`, the value's bytes alone. Returns the
+/// encoding id, or `None` when there are no values or the payload is not smaller than `limit`.
+fn build_run_length_payload<T: PartialEq, B: AsRef<[u8]>>(
+    buf: &mut Vec<u8>,
+    values: impl IntoIterator<Item = T>,
+    limit: usize,
+    to_bytes: impl Fn(&T) -> B,
+) -> Option<u16> {
+    buf.clear();
+    let mut values = values.into_iter();
+    let mut current = values.next()?;
+    let mut run_len: u64 = 1;
+    for v in values {
+        if v == current {
+            run_len += 1;
+            continue;
         }
-        4 => {
-            for &idx in &ws.dict_indices {
-                ws.dict_indices_bytes.extend_from_slice(&idx.to_le_bytes());
-            }
+        write_u64_varint(buf, run_len);
+        buf.extend_from_slice(to_bytes(&current).as_ref());
+        if buf.len() >= limit {
+            return None;
         }
-        _ => return Err(Error::Other("invalid index width".to_string())),
+        current = v;
+        run_len = 1;
     }
-    if ws.dict_indices_bytes.len() != indices_len {
-        return Err(Error::Other("indices length mismatch".to_string()));
+    let encoding_id = if buf.is_empty() {
+        ENC_CONSTANT
+    } else {
+        write_u64_varint(buf, run_len);
+        ENC_RLE
+    };
+    buf.extend_from_slice(to_bytes(&current).as_ref());
+    (buf.len() < limit).then_some(encoding_id)
+}
+
+/// Builds the `Rle` or `Constant` payload of a scalar fixed-width column into `buf` when it is
+/// smaller than the plain layout. Returns the encoding id and payload length.
+fn build_fixed_run_length_payload(
+    buf: &mut Vec<u8>,
+    field_ty: &ColumnarType,
+    col: &ColumnDataView<'_>,
+    row_count: usize,
+) -> Option<(u16, usize)> {
+    fn runs<T: Copy + PartialEq, const N: usize>(
+        buf: &mut Vec<u8>,
+        values: &[T],
+        row_count: usize,
+        to_le: impl Fn(T) -> [u8; N],
+    ) -> Option<u16> {
+        if values.len() != row_count {
+            return None;
+        }
+        let limit = row_count.checked_mul(N)?;
+        build_run_length_payload(buf, values.iter().copied(), limit, |&v| to_le(v))
     }
 
-    ws.dict_offsets.clear();
-    ws.dict_offsets.reserve(dict_count + 1);
-    ws.dict_offsets.push(0u32);
-    let mut total: u32 = 0;
-    for v in &ws.dict_values {
-        let len_u32: u32 = v
+    // Values are written at the width the decoder reads for the field type.
+    let width = run_length_value_width(field_ty)?;
+    let encoding_id = match col {
+        ColumnDataView::FixedBool { values, .. } | ColumnDataView::FixedU8 { values, .. }
+            if width == 1 =>
+        {
+            runs(buf, values, row_count, |v| [v])
+        }
+        ColumnDataView::BitPackedBool { values, .. } if width == 1 => {
+            if values.len() != row_count.div_ceil(8) {
+                return None;
+            }
+            let bits = (0..row_count).map(|i| (values[i / 8] >> (i % 8)) & 1);
+            build_run_length_payload(buf, bits, row_count, |&v| [v])
+        }
+        ColumnDataView::FixedI8 { values, .. } if width == 1 => {
+            runs(buf, values, row_count, i8::to_le_bytes)
+        }
+        ColumnDataView::FixedI16 { values, .. } if width == 2 => {
+            runs(buf, values, row_count, i16::to_le_bytes)
+        }
+        ColumnDataView::FixedU16 { values, .. } if width == 2 => {
+            runs(buf, values, row_count, u16::to_le_bytes)
+        }
+        ColumnDataView::FixedI32 { values, .. } | ColumnDataView::FixedDate32 { values, .. }
+            if width == 4 =>
+        {
+            runs(buf, values, row_count, i32::to_le_bytes)
+        }
+        ColumnDataView::FixedI64 { values, .. }
+        | ColumnDataView::FixedTimestampMicros { values, .. }
+        | ColumnDataView::FixedTimestamp { values, .. }
+        | ColumnDataView::FixedTimeMicros { values, .. }
+            if width == 8 =>
+        {
+            runs(buf, values, row_count, i64::to_le_bytes)
+        }
+        ColumnDataView::FixedF32Bits { values, .. } | ColumnDataView::FixedU32 { values, .. }
+            if width == 4 =>
+        {
+            runs(buf, values, row_count, u32::to_le_bytes)
+        }
+        ColumnDataView::FixedF64Bits { values, .. } | ColumnDataView::FixedU64 { values, .. }
+            if width == 8 =>
+        {
+            runs(buf, values, row_count, u64::to_le_bytes)
+        }
+        ColumnDataView::FixedUuid { values, .. } if width == 16 => {
+            runs(buf, values, row_count, |v| v)
+        }
+        ColumnDataView::FixedDecimal128 { values, .. } if width == 16 => {
+            runs(buf, values, row_count, i128::to_le_bytes)
+        }
+        ColumnDataView::FixedInterval { values, .. } if width == 16 => {
+            runs(buf, values, row_count, interval_to_le_bytes)
+        }
+        _ => None,
+    }?;
+    Some((encoding_id, buf.len()))
+}
+
+/// The run-length encoding of a fixed-width column when its payload is smaller than that of
+/// the encoding otherwise chosen (the plain layout was the limit it was built against).
+fn fixed_run_length_choice(
+    fixed_rle: Option<(u16, usize)>,
+    encoding_id: u16,
+    payload: Option<&[u8]>,
+    row_count: usize,
+) -> Option<u16> {
+    let (rle_encoding, rle_len) = fixed_rle?;
+    let chosen_len = match payload {
+        Some(payload) => payload.len(),
+        None if encoding_id == ENC_BIT_PACKED_BOOL => row_count.div_ceil(8),
+        None => usize::MAX,
+    };
+    (rle_len < chosen_len).then_some(rle_encoding)
+}
+
+/// Width of one `Rle`/`Constant` value of a scalar fixed-width type.
+fn run_length_value_width(ty: &ColumnarType) -> Option<usize> {
+    match ty {
+        ColumnarType::Bool | ColumnarType::I8 | ColumnarType::U8 => Some(1),
+        ColumnarType::I16 | ColumnarType::U16 => Some(2),
+        ColumnarType::I32 | ColumnarType::U32 | ColumnarType::F32 | ColumnarType::Date => Some(4),
+        ColumnarType::I64
+        | ColumnarType::U64
+        | ColumnarType::F64
+        | ColumnarType::TimestampTzMicros
+        | ColumnarType::Timestamp { .. }
+        | ColumnarType::TimeMicros => Some(8),
+        ColumnarType::Uuid
+        | ColumnarType::Decimal { .. }
+        | ColumnarType::IntervalMonthDayMicros => Some(16),
+        _ => None,
+    }
+}
+
+/// LSB-first bit writer; `finish` zero-pads the last byte.
+struct BitWriter<'a> {
+    out: &'a mut Vec<u8>,
+    acc: u128,
+    bits: u32,
+}
+
+impl<'a> BitWriter<'a> {
+    fn new(out: &'a mut Vec<u8>) -> Self {
+        Self {
+            out,
+            acc: 0,
+            bits: 0,
+        }
+    }
+
+    /// Appends the low `width` (at most 64) bits of `value`, whose other bits must be zero.
+    fn push(&mut self, value: u64, width: u32) {
+        self.acc |= u128::from(value) << self.bits;
+        self.bits += width;
+        while self.bits >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(self) {
+        if self.bits > 0 {
+            self.out.push(self.acc as u8);
+        }
+    }
+}
+
+/// Builds an `XorFloat` payload: the first value's bits (little-endian, column width), then
+/// for each following value an LSB-first bit stream entry for its XOR with the previous value:
+/// `0` when equal; `1 0` and the XOR's bits inside the previous window when they fit; otherwise
+/// `1 1`, the leading zero count and the window length minus one (5 bits each for `F32`, 6 for
+/// `F64`) and the window bits. The last byte is zero-padded.
+///
+/// Null slots are encoded like valid ones, so their bits round-trip. Returns `None` when the
+/// payload is not smaller than the plain layout.
+fn build_xor_float_payload<'a, T: XorFloat>(
+    buf: &'a mut Vec<u8>,
+    values: &[T],
+) -> Result<Option<&'a [u8]>> {
+    let Some((&first, rest)) = values.split_first() else {
+        return Ok(None);
+    };
+    let bits = 8 * T::SIZE as u32;
+    buf.clear();
+    let mut prev: u64 = first.into();
+    buf.extend_from_slice(&prev.to_le_bytes()[..T::SIZE]);
+    let mut w = BitWriter::new(buf);
+    let mut window: Option<(u32, u32)> = None;
+    for &v in rest {
+        let v: u64 = v.into();
+        let xor = v ^ prev;
+        prev = v;
+        if xor == 0 {
+            w.push(0, 1);
+            continue;
+        }
+        let leading = xor.leading_zeros() - (64 - bits);
+        let trailing = xor.trailing_zeros();
+        match window {
+            Some((lead, len)) if leading >= lead && trailing >= bits - lead - len => {
+                w.push(0b01, 2);
+                w.push(xor >> (bits - lead - len), len);
+            }
+            _ => {
+                let len = bits - leading - trailing;
+                w.push(0b11, 2);
+                w.push(u64::from(leading), T::FIELD_BITS);
+                w.push(u64::from(len - 1), T::FIELD_BITS);
+                w.push(xor >> trailing, len);
+                window = Some((leading, len));
+            }
+        }
+    }
+    w.finish();
+    if buf.len() >= values.len() * T::SIZE {
+        return Ok(None);
+    }
+    Ok(Some(buf.as_slice()))
+}
+
+/// Narrowest index width (1, 2 or 4 bytes) that addresses `dict_count` entries.
+fn dict_index_width(dict_count: usize) -> usize {
+    if dict_count <= 0x100 {
+        1
+    } else if dict_count <= 0x1_0000 {
+        2
+    } else {
+        4
+    }
+}
+
+/// Appends a dict blob: index width u8 | entry count u32 | offsets | entry bytes.
+fn write_dict_blob<'v>(
+    blob: &mut Vec<u8>,
+    index_width: usize,
+    offsets: &[u32],
+    values: impl IntoIterator<Item = &'v [u8]>,
+) -> Result<()> {
+    let dict_count = offsets.len() - 1;
+    let dict_count_u32: u32 = dict_count.try_into().map_err(|_| Error::LimitExceeded {
+        kind: LimitKind::DictEntries,
+        column: None,
+        offset: None,
+    })?;
+    let total = offsets.last().copied().unwrap_or(0) as usize;
+    blob.reserve(1 + 4 + offsets.len() * 4 + total);
+    blob.push(index_width as u8);
+    write_u32_le(blob, dict_count_u32);
+    for &o in offsets {
+        write_u32_le(blob, o);
+    }
+    for v in values {
+        blob.extend_from_slice(v);
+    }
+    Ok(())
+}
+
+/// Writes one little-endian index of `index_width` bytes per row (payload_1).
+fn write_dict_indices(out: &mut Vec<u8>, indices: &[u32], index_width: usize) -> Result<()> {
+    let indices_len = checked_byte_len(indices.len(), index_width)?;
+    out.clear();
+    out.reserve(indices_len);
+    match index_width {
+        1 => {
+            for &idx in indices {
+                out.push(idx as u8);
+            }
+        }
+        2 => {
+            for &idx in indices {
+                let v: u16 = idx
+                    .try_into()
+                    .map_err(|_| Error::Internal("dict index overflow"))?;
+                out.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        4 => {
+            for &idx in indices {
+                out.extend_from_slice(&idx.to_le_bytes());
+            }
+        }
+        _ => return Err(Error::Internal("invalid index width")),
+    }
+    if out.len() != indices_len {
+        return Err(Error::Internal("indices length mismatch"));
+    }
+    Ok(())
+}
+
+/// Returns the value of each valid row, or `None` for null rows.
+fn var_row<'d>(
+    validity: &[u8],
+    offsets: &[u32],
+    data: &'d [u8],
+    row: usize,
+) -> Result<Option<&'d [u8]>> {
+    if (validity[row / 8] & (1u8 << (row % 8))) == 0 {
+        return Ok(None);
+    }
+    let start = offsets[row] as usize;
+    let end = offsets[row + 1] as usize;
+    if end < start || end > data.len() {
+        return Err(invalid("offset out of bounds"));
+    }
+    Ok(Some(&data[start..end]))
+}
+
+/// A caller-built dictionary for a varlen column. Entry `i` is the `i`-th value passed to
+/// [`MathldbtV1Dictionary::new`], so indices are stable across batches, and encoding only
+/// looks values up instead of building a dictionary per batch.
+#[derive(Debug, Clone)]
+pub struct MathldbtV1Dictionary {
+    map: HashMap<Vec<u8>, u32>,
+    blob: Vec<u8>,
+}
+
+impl MathldbtV1Dictionary {
+    /// Values must be unique; any varlen column type (`Utf8`, `Bytes`, `JsonbText`) can use it.
+    pub fn new<V: AsRef<[u8]>>(values: impl IntoIterator<Item = V>) -> Result<Self> {
+        let mut map = HashMap::new();
+        let mut offsets = vec![0u32];
+        let mut bytes = Vec::new();
+        for v in values {
+            let v = v.as_ref();
+            let idx: u32 = map.len().try_into().map_err(|_| Error::LimitExceeded {
+                kind: LimitKind::DictEntries,
+                column: None,
+                offset: None,
+            })?;
+            if map.insert(v.to_vec(), idx).is_some() {
+                return Err(invalid("duplicate dictionary value"));
+            }
+            bytes.extend_from_slice(v);
+            offsets.push(bytes.len().try_into().map_err(|_| payload_too_large())?);
+        }
+        let mut blob = Vec::new();
+        write_dict_blob(
+            &mut blob,
+            dict_index_width(map.len()),
+            &offsets,
+            [bytes.as_slice()],
+        )?;
+        Ok(Self { map, blob })
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn index_of(&self, value: &[u8]) -> Option<u32> {
+        self.map.get(value).copied()
+    }
+}
+
+/// A column's `DictDelta` dictionary on the encoding side.
+#[derive(Debug, Default, Clone)]
+struct DeltaDictEncodeState {
+    map: HashMap<Vec<u8>, u32>,
+    /// Entries added by the batch being encoded, in index order.
+    pending: Vec<Vec<u8>>,
+}
+
+/// A `DictDelta` dictionary as received so far: `offsets` starts at 0 and has one more item
+/// than there are entries.
+#[derive(Debug, Default, Clone)]
+struct DeltaDict {
+    offsets: Vec<u32>,
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct DictDecodeState {
+    /// Scratch for the offsets of a `Dict` blob.
+    offsets: Vec<u32>,
+    /// `DictDelta` dictionaries by dictionary id.
+    deltas: HashMap<u32, DeltaDict>,
+    /// Ids the batch being decoded added entries to, with their entry and byte counts
+    /// beforehand (`None` for dictionaries it started).
+    pending: Vec<(u32, Option<(usize, usize)>)>,
+    /// Entries and bytes across all of `deltas`.
+    delta_entries: usize,
+    delta_bytes: usize,
+    max_delta_entries: Option<usize>,
+    max_delta_bytes: Option<usize>,
+}
+
+impl DictDecodeState {
+    fn reset_deltas(&mut self) {
+        self.deltas.clear();
+        self.pending.clear();
+        self.delta_entries = 0;
+        self.delta_bytes = 0;
+    }
+
+    /// Drops entries added by a decode that failed before its batch was returned.
+    fn discard_pending(&mut self) {
+        for (id, base) in self.pending.drain(..) {
+            let Some(dict) = self.deltas.get_mut(&id) else {
+                continue;
+            };
+            let (entries, bytes) = base.unwrap_or((0, 0));
+            self.delta_entries -= dict.offsets.len() - 1 - entries;
+            self.delta_bytes -= dict.bytes.len() - bytes;
+            if base.is_none() {
+                self.deltas.remove(&id);
+            } else {
+                dict.offsets.truncate(entries + 1);
+                dict.bytes.truncate(bytes);
+            }
+        }
+    }
+
+    /// Keeps the entries added by the last decode; call once its batch is complete.
+    fn commit_pending(&mut self) {
+        self.pending.clear();
+    }
+}
+
+/// Whether a varlen column is dictionary-encoded: a dictionary is supplied for top-level
+/// `column`, or per-batch dictionaries are enabled for its type.
+fn dict_requested(
+    ws: &MathldbtV1EncodeWorkspace,
+    ty: &ColumnarType,
+    column: Option<usize>,
+) -> bool {
+    if column.is_some_and(|c| matches!(ws.column_dictionaries.get(c), Some(Some(_)))) {
+        return true;
+    }
+    match ty {
+        ColumnarType::Utf8 | ColumnarType::JsonbText => ws.enable_dict_utf8,
+        ColumnarType::Bytes => ws.enable_dict_bytes,
+        _ => false,
+    }
+}
+
+/// Encoding id, payload_1 and payload_2 of a dictionary-encoded column.
+type DictPayload<'a> = (u16, &'a [u8], &'a [u8]);
+
+/// Builds the encoding id and payloads for a dictionary-encoded varlen column: from the
+/// dictionary supplied for top-level `column` if there is one (values missing from it are an
+/// error), from the column's delta dictionary when those are enabled, and otherwise from a
+/// per-batch dictionary, which is only used when it is smaller than the plain layout.
+fn build_dict_payload<'a>(
+    ws: &'a mut MathldbtV1EncodeWorkspace,
+    column: Option<usize>,
+    validity: &[u8],
+    row_count: usize,
+    offsets: &[u32],
+    data: &[u8],
+) -> Result<Option<DictPayload<'a>>> {
+    if row_count == 0 {
+        return Ok(None);
+    }
+    if offsets.len() != row_count + 1 {
+        return Ok(None);
+    }
+    if data.len() > (u32::MAX as usize) {
+        return Ok(None);
+    }
+
+    ws.dict_indices.clear();
+    ws.dict_indices.reserve(row_count);
+
+    let delta_column = column.filter(|&c| {
+        ws.enable_dict_delta && !matches!(ws.column_dictionaries.get(c), Some(Some(_)))
+    });
+    if let Some(column) = delta_column {
+        build_dict_delta_payload(ws, column, validity, row_count, offsets, data)?;
+        return Ok(Some((
+            ENC_DICT_DELTA,
+            ws.dict_indices_bytes.as_slice(),
+            ws.dict_blob.as_slice(),
+        )));
+    }
+
+    let supplied = column.and_then(|c| ws.column_dictionaries.get(c)?.as_ref());
+    if let Some(dict) = supplied {
+        for row in 0..row_count {
+            let idx = match var_row(validity, offsets, data, row)? {
+                Some(bytes) => dict
+                    .index_of(bytes)
+                    .ok_or_else(|| invalid("value not in dictionary"))?,
+                None => 0,
+            };
+            ws.dict_indices.push(idx);
+        }
+        let index_width = dict.blob[0] as usize;
+        write_dict_indices(&mut ws.dict_indices_bytes, &ws.dict_indices, index_width)?;
+        return Ok(Some(run_length_dict_payload(
+            ws.enable_rle.then_some(&mut ws.rle_buf),
+            &ws.dict_indices_bytes,
+            index_width,
+            &dict.blob,
+        )));
+    }
+
+    ws.dict_values.clear();
+    ws.dict_map.clear();
+
+    for row in 0..row_count {
+        let Some(bytes) = var_row(validity, offsets, data, row)? else {
+            ws.dict_indices.push(0);
+            continue;
+        };
+        if let Some(&idx) = ws.dict_map.get(bytes) {
+            ws.dict_indices.push(idx);
+            continue;
+        }
+        let idx: u32 = ws
+            .dict_values
             .len()
             .try_into()
-            .map_err(|_| Error::Other("dict entry too large".to_string()))?;
-        total = total
-            .checked_add(len_u32)
-            .ok_or_else(|| Error::Other("dict overflow".to_string()))?;
-        ws.dict_offsets.push(total);
+            .map_err(|_| Error::LimitExceeded {
+                kind: LimitKind::DictEntries,
+                column: None,
+                offset: None,
+            })?;
+        let key = bytes.to_vec();
+        ws.dict_values.push(key.clone());
+        ws.dict_map.insert(key, idx);
+        ws.dict_indices.push(idx);
     }
 
-    ws.dict_blob.clear();
-    ws.dict_blob
-        .reserve(1 + 4 + (dict_count + 1) * 4 + total as usize);
-    ws.dict_blob.push(index_width as u8);
-    let dict_count_u32: u32 = dict_count
-        .try_into()
-        .map_err(|_| Error::Other("dict too large".to_string()))?;
-    write_u32_le(&mut ws.dict_blob, dict_count_u32);
-    for &o in &ws.dict_offsets {
-        write_u32_le(&mut ws.dict_blob, o);
+    let dict_count = ws.dict_values.len();
+    if dict_count == 0 {
+        return Ok(None);
     }
+
+    write_dict_indices(
+        &mut ws.dict_indices_bytes,
+        &ws.dict_indices,
+        dict_index_width(dict_count),
+    )?;
+
+    ws.dict_offsets.clear();
+    ws.dict_offsets.reserve(dict_count + 1);
+    ws.dict_offsets.push(0u32);
+    let mut total: u32 = 0;
     for v in &ws.dict_values {
-        ws.dict_blob.extend_from_slice(v.as_slice());
+        let len_u32: u32 = v.len().try_into().map_err(|_| payload_too_large())?;
+        total = total.checked_add(len_u32).ok_or_else(payload_too_large)?;
+        ws.dict_offsets.push(total);
     }
+    ws.dict_blob.clear();
+    write_dict_blob(
+        &mut ws.dict_blob,
+        dict_index_width(dict_count),
+        &ws.dict_offsets,
+        ws.dict_values.iter().map(Vec::as_slice),
+    )?;
 
     let plain_offsets_len = (row_count + 1)
         .checked_mul(4)
-        .ok_or_else(|| Error::Other("offsets overflow".to_string()))?;
+        .ok_or_else(payload_too_large)?;
     let plain_total = plain_offsets_len
         .checked_add(data.len())
-        .ok_or_else(|| Error::Other("payload overflow".to_string()))?;
+        .ok_or_else(payload_too_large)?;
     let dict_total = ws
         .dict_indices_bytes
         .len()
         .checked_add(ws.dict_blob.len())
-        .ok_or_else(|| Error::Other("payload overflow".to_string()))?;
+        .ok_or_else(payload_too_large)?;
     if dict_total >= plain_total {
         return Ok(None);
     }
 
-    Ok(Some((
-        ws.dict_indices_bytes.as_slice(),
-        ws.dict_blob.as_slice(),
+    Ok(Some(run_length_dict_payload(
+        ws.enable_rle.then_some(&mut ws.rle_buf),
+        &ws.dict_indices_bytes,
+        dict_index_width(dict_count),
+        &ws.dict_blob,
     )))
 }
 
-fn decode_dict_utf8_to_var_col(
-    ws: &mut MathldbtV1DecodeWorkspace,
-    row_count: usize,
+/// Payloads of a `Dict` column. With an `rle_buf` (`Rle` enabled) the indices become runs
+/// (`Rle`, with the dictionary blob still in payload_2) or, when every row has the same index,
+/// that entry's bytes alone (`Constant`), whichever is smaller than the indices.
+fn run_length_dict_payload<'a>(
+    rle_buf: Option<&'a mut Vec<u8>>,
+    indices: &'a [u8],
+    index_width: usize,
+    blob: &'a [u8],
+) -> DictPayload<'a> {
+    let Some(buf) = rle_buf else {
+        return (ENC_DICT, indices, blob);
+    };
+    let chunks = indices.chunks_exact(index_width);
+    let runs = build_run_length_payload(buf, chunks, indices.len(), |c| *c);
+    match runs {
+        Some(ENC_RLE) => (ENC_RLE, buf.as_slice(), blob),
+        Some(_) => match dict_blob_entry(blob, dict_index(indices, index_width, 0)) {
+            Some(entry) => (ENC_CONSTANT, entry, &[]),
+            None => (ENC_DICT, indices, blob),
+        },
+        None => (ENC_DICT, indices, blob),
+    }
+}
+
+/// Entry `idx` of a well-formed dict blob, or `None` past its entries.
+fn dict_blob_entry(blob: &[u8], idx: usize) -> Option<&[u8]> {
+    let dict_count = u32::from_le_bytes(blob.get(1..5)?.try_into().ok()?) as usize;
+    if idx >= dict_count {
+        return None;
+    }
+    let offset_at = |i: usize| {
+        let j = 5 + i * 4;
+        u32::from_le_bytes([blob[j], blob[j + 1], blob[j + 2], blob[j + 3]]) as usize
+    };
+    let bytes = &blob[5 + (dict_count + 1) * 4..];
+    bytes.get(offset_at(idx)..offset_at(idx + 1))
+}
+
+/// Builds `DictDelta` payloads for top-level `column`, whose dictionary id is its index.
+/// payload_2 is dict id u32 | base entry count u32 | a dict blob of the new entries, with the
+/// index width of the whole dictionary.
+fn build_dict_delta_payload(
+    ws: &mut MathldbtV1EncodeWorkspace,
+    column: usize,
     validity: &[u8],
-    indices_bytes: &[u8],
-    dict_blob: &[u8],
-    out_offsets: &mut Vec<u32>,
-    out_data: &mut Vec<u8>,
+    row_count: usize,
+    offsets: &[u32],
+    data: &[u8],
 ) -> Result<()> {
-    if row_count == 0 {
-        out_offsets.clear();
-        out_offsets.push(0);
-        out_data.clear();
-        return Ok(());
+    let dict_id: u32 = column.try_into().map_err(|_| Error::LimitExceeded {
+        kind: LimitKind::ColumnCount,
+        column: Some(column),
+        offset: None,
+    })?;
+    if ws.delta_dicts.len() <= column {
+        ws.delta_dicts.resize_with(column + 1, Default::default);
+    }
+    let state = &mut ws.delta_dicts[column];
+    let base_count = state.map.len();
+
+    for row in 0..row_count {
+        let Some(bytes) = var_row(validity, offsets, data, row)? else {
+            ws.dict_indices.push(0);
+            continue;
+        };
+        if let Some(&idx) = state.map.get(bytes) {
+            ws.dict_indices.push(idx);
+            continue;
+        }
+        let idx: u32 = state
+            .map
+            .len()
+            .try_into()
+            .map_err(|_| Error::LimitExceeded {
+                kind: LimitKind::DictEntries,
+                column: Some(column),
+                offset: None,
+            })?;
+        state.map.insert(bytes.to_vec(), idx);
+        state.pending.push(bytes.to_vec());
+        ws.dict_indices.push(idx);
+    }
+
+    write_dict_indices(
+        &mut ws.dict_indices_bytes,
+        &ws.dict_indices,
+        dict_index_width(state.map.len()),
+    )?;
+
+    ws.dict_offsets.clear();
+    ws.dict_offsets.reserve(state.pending.len() + 1);
+    ws.dict_offsets.push(0u32);
+    let mut total: u32 = 0;
+    for v in &state.pending {
+        let len_u32: u32 = v.len().try_into().map_err(|_| payload_too_large())?;
+        total = total.checked_add(len_u32).ok_or_else(payload_too_large)?;
+        ws.dict_offsets.push(total);
     }
+    ws.dict_blob.clear();
+    write_u32_le(&mut ws.dict_blob, dict_id);
+    write_u32_le(&mut ws.dict_blob, base_count as u32);
+    write_dict_blob(
+        &mut ws.dict_blob,
+        dict_index_width(state.map.len()),
+        &ws.dict_offsets,
+        state.pending.iter().map(Vec::as_slice),
+    )
+}
 
+/// Validates a dict blob at `blob_offset` and reads its offsets into `dict_offsets`. Returns
+/// the index width and the entry bytes.
+fn read_dict_blob<'b>(
+    body: &ColumnBody<'_>,
+    dict_blob: &'b [u8],
+    blob_offset: usize,
+    dict_offsets: &mut Vec<u32>,
+) -> Result<(usize, &'b [u8])> {
     if dict_blob.len() < 1 + 4 {
-        return Err(Error::Other("dict blob truncated".to_string()));
+        return Err(body.malformed(blob_offset, "dict blob truncated"));
     }
     let index_width = dict_blob[0] as usize;
     if index_width != 1 && index_width != 2 && index_width != 4 {
-        return Err(Error::Other("invalid dict index width".to_string()));
+        return Err(body.malformed(blob_offset, "invalid dict index width"));
     }
     let dict_count =
         u32::from_le_bytes([dict_blob[1], dict_blob[2], dict_blob[3], dict_blob[4]]) as usize;
-    let offsets_bytes_len = (dict_count + 1)
-        .checked_mul(4)
-        .ok_or_else(|| Error::Other("dict offsets overflow".to_string()))?;
     let header_len: usize = 1 + 4;
     let offsets_start: usize = header_len;
-    let offsets_end = offsets_start
-        .checked_add(offsets_bytes_len)
-        .ok_or_else(|| Error::Other("dict offsets overflow".to_string()))?;
-    if offsets_end > dict_blob.len() {
-        return Err(Error::Other("dict offsets truncated".to_string()));
-    }
+    let offsets_end = (dict_count + 1)
+        .checked_mul(4)
+        .and_then(|n| n.checked_add(offsets_start))
+        .filter(|&end| end <= dict_blob.len())
+        .ok_or_else(|| body.malformed(blob_offset + 1, "dict offsets truncated"))?;
+    let offsets_bytes_len = offsets_end - offsets_start;
 
-    ws.dict_offsets.clear();
-    ws.dict_offsets.resize(dict_count + 1, 0u32);
+    dict_offsets.clear();
+    dict_offsets.resize(dict_count + 1, 0u32);
     #[cfg(target_endian = "little")]
     {
         let src = &dict_blob[offsets_start..offsets_end];
         let dst = unsafe {
             std::slice::from_raw_parts_mut(
-                ws.dict_offsets.as_mut_ptr() as *mut u8,
+                dict_offsets.as_mut_ptr() as *mut u8,
                 offsets_bytes_len,
             )
         };
@@ -428,7 +1761,7 @@ fn decode_dict_utf8_to_var_col(
     {
         for i in 0..(dict_count + 1) {
             let j = offsets_start + i * 4;
-            ws.dict_offsets[i] = u32::from_le_bytes([
+            dict_offsets[i] = u32::from_le_bytes([
                 dict_blob[j],
                 dict_blob[j + 1],
                 dict_blob[j + 2],
@@ -437,28 +1770,76 @@ fn decode_dict_utf8_to_var_col(
         }
     }
 
+    let dict_offset_at = |i: usize| blob_offset + offsets_start + i * 4;
     let dict_bytes = &dict_blob[offsets_end..];
-    let dict_total = ws.dict_offsets.last().copied().unwrap_or(0);
+    let dict_total = dict_offsets.last().copied().unwrap_or(0);
     if dict_total as usize != dict_bytes.len() {
-        return Err(Error::Other("dict final offset mismatch".to_string()));
+        return Err(body.invalid_offsets(dict_offset_at(dict_count), "dict final offset mismatch"));
     }
     let mut prev = 0u32;
-    for &o in ws.dict_offsets.iter() {
+    for (i, &o) in dict_offsets.iter().enumerate() {
         if o < prev {
-            return Err(Error::Other(
-                "dict offsets must be non-decreasing".to_string(),
-            ));
+            return Err(
+                body.invalid_offsets(dict_offset_at(i), "dict offsets must be non-decreasing")
+            );
         }
         prev = o;
     }
+    Ok((index_width, dict_bytes))
+}
+
+fn dict_index(indices_bytes: &[u8], index_width: usize, row: usize) -> usize {
+    match index_width {
+        1 => indices_bytes[row] as usize,
+        2 => {
+            let j = row * 2;
+            u16::from_le_bytes([indices_bytes[j], indices_bytes[j + 1]]) as usize
+        }
+        _ => {
+            let j = row * 4;
+            u32::from_le_bytes([
+                indices_bytes[j],
+                indices_bytes[j + 1],
+                indices_bytes[j + 2],
+                indices_bytes[j + 3],
+            ]) as usize
+        }
+    }
+}
 
-    let expected_indices_len = row_count
-        .checked_mul(index_width)
-        .ok_or_else(|| Error::Other("indices overflow".to_string()))?;
-    if indices_bytes.len() != expected_indices_len {
-        return Err(Error::Other("indices length mismatch".to_string()));
+/// Checks the length of payload_1 and that every valid row's index is below `dict_count`.
+fn check_dict_indices(
+    body: &ColumnBody<'_>,
+    row_count: usize,
+    index_width: usize,
+    dict_count: usize,
+) -> Result<()> {
+    let expected_indices_len = checked_byte_len(row_count, index_width)?;
+    if body.payload1.len() != expected_indices_len {
+        return Err(body.malformed(body.payload1_offset, "indices length mismatch"));
+    }
+    for row in 0..row_count {
+        let is_valid = (body.validity[row / 8] & (1u8 << (row % 8))) != 0;
+        if is_valid && dict_index(body.payload1, index_width, row) >= dict_count {
+            return Err(body.malformed(
+                body.payload1_offset + row * index_width,
+                "dict index out of bounds",
+            ));
+        }
     }
+    Ok(())
+}
 
+/// Expands checked indices (see [`check_dict_indices`]) into a varlen column.
+fn expand_dict_indices(
+    body: &ColumnBody<'_>,
+    row_count: usize,
+    index_width: usize,
+    dict_offsets: &[u32],
+    dict_bytes: &[u8],
+    out_offsets: &mut Vec<u32>,
+    out_data: &mut Vec<u8>,
+) -> Result<()> {
     out_offsets.clear();
     out_offsets.reserve(row_count + 1);
     out_offsets.push(0u32);
@@ -466,81 +1847,377 @@ fn decode_dict_utf8_to_var_col(
 
     let mut total: u32 = 0;
     for row in 0..row_count {
-        let is_valid = (validity[row / 8] & (1u8 << (row % 8))) != 0;
+        let is_valid = (body.validity[row / 8] & (1u8 << (row % 8))) != 0;
         if !is_valid {
             out_offsets.push(total);
             continue;
         }
-        let idx: usize = match index_width {
-            1 => indices_bytes[row] as usize,
-            2 => {
-                let j = row * 2;
-                u16::from_le_bytes([indices_bytes[j], indices_bytes[j + 1]]) as usize
-            }
-            4 => {
-                let j = row * 4;
-                u32::from_le_bytes([
-                    indices_bytes[j],
-                    indices_bytes[j + 1],
-                    indices_bytes[j + 2],
-                    indices_bytes[j + 3],
-                ]) as usize
-            }
-            _ => return Err(Error::Other("invalid index width".to_string())),
-        };
-        if idx >= dict_count {
-            return Err(Error::Other("dict index out of bounds".to_string()));
-        }
-        let start = ws.dict_offsets[idx] as usize;
-        let end = ws.dict_offsets[idx + 1] as usize;
+        let idx = dict_index(body.payload1, index_width, row);
+        let start = dict_offsets[idx] as usize;
+        let end = dict_offsets[idx + 1] as usize;
         out_data.extend_from_slice(&dict_bytes[start..end]);
-        let add: u32 = (end - start)
-            .try_into()
-            .map_err(|_| Error::Other("dict expansion too large".to_string()))?;
-        total = total
-            .checked_add(add)
-            .ok_or_else(|| Error::Other("dict expansion overflow".to_string()))?;
+        let add: u32 = (end - start).try_into().map_err(|_| payload_too_large())?;
+        total = total.checked_add(add).ok_or_else(payload_too_large)?;
         out_offsets.push(total);
     }
 
     Ok(())
 }
 
+fn decode_dict_to_var_col(
+    dicts: &mut DictDecodeState,
+    body: &ColumnBody<'_>,
+    row_count: usize,
+    out_offsets: &mut Vec<u32>,
+    out_data: &mut Vec<u8>,
+) -> Result<()> {
+    if row_count == 0 {
+        out_offsets.clear();
+        out_offsets.push(0);
+        out_data.clear();
+        return Ok(());
+    }
+
+    let (index_width, dict_bytes) = read_dict_blob(
+        body,
+        body.payload2,
+        body.payload2_offset,
+        &mut dicts.offsets,
+    )?;
+    check_dict_indices(body, row_count, index_width, dicts.offsets.len() - 1)?;
+    expand_dict_indices(
+        body,
+        row_count,
+        index_width,
+        &dicts.offsets,
+        dict_bytes,
+        out_offsets,
+        out_data,
+    )
+}
+
+/// Applies the dictionary delta in a `DictDelta` column's payload_2 to the dictionary state.
+/// Returns the dictionary id and index width once the delta and the indices are checked.
+///
+/// The base entry count must match the entries received so far for that id, and be 0 for an
+/// id not seen yet; a dictionary only restarts after `reset_dict_deltas`. The new entries stay
+/// pending until the whole batch has decoded.
+fn apply_dict_delta(
+    dicts: &mut DictDecodeState,
+    body: &ColumnBody<'_>,
+    row_count: usize,
+) -> Result<(u32, usize)> {
+    let payload = body.payload2;
+    let at = body.payload2_offset;
+    if payload.len() < 4 + 4 {
+        return Err(body.malformed(at, "dict delta header truncated"));
+    }
+    let dict_id = u32::from_le_bytes([payload[0], payload[1], payload[2], payload[3]]);
+    let base_count = u32::from_le_bytes([payload[4], payload[5], payload[6], payload[7]]) as usize;
+    let (index_width, new_bytes) = read_dict_blob(body, &payload[8..], at + 8, &mut dicts.offsets)?;
+
+    let base = dicts
+        .deltas
+        .get(&dict_id)
+        .map(|dict| (dict.offsets.len() - 1, dict.bytes.len()));
+    match base {
+        None if base_count != 0 => return Err(body.malformed(at, "unknown dictionary id")),
+        Some((entries, _)) if entries != base_count => {
+            return Err(body.malformed(at + 4, "dictionary delta out of sequence"));
+        }
+        _ => {}
+    }
+    let dict_entries_exceeded = || Error::LimitExceeded {
+        kind: LimitKind::DictEntries,
+        column: Some(body.column),
+        offset: Some(body.payload2_offset),
+    };
+    let new_count = dicts.offsets.len() - 1;
+    let dict_count = base_count
+        .checked_add(new_count)
+        .ok_or_else(dict_entries_exceeded)?;
+    check_dict_indices(body, row_count, index_width, dict_count)?;
+    let delta_entries = dicts.delta_entries.saturating_add(new_count);
+    let delta_bytes = dicts.delta_bytes.saturating_add(new_bytes.len());
+    if dicts
+        .max_delta_entries
+        .is_some_and(|max| delta_entries > max)
+        || dicts.max_delta_bytes.is_some_and(|max| delta_bytes > max)
+    {
+        return Err(dict_entries_exceeded());
+    }
+
+    if !dicts.pending.iter().any(|&(id, _)| id == dict_id) {
+        dicts.pending.push((dict_id, base));
+    }
+    let dict = dicts.deltas.entry(dict_id).or_insert_with(|| DeltaDict {
+        offsets: vec![0],
+        bytes: Vec::new(),
+    });
+    let base_len: u32 = dict
+        .bytes
+        .len()
+        .try_into()
+        .map_err(|_| payload_too_large())?;
+    for &o in &dicts.offsets[1..] {
+        dict.offsets
+            .push(base_len.checked_add(o).ok_or_else(payload_too_large)?);
+    }
+    dict.bytes.extend_from_slice(new_bytes);
+    dicts.delta_entries = delta_entries;
+    dicts.delta_bytes = delta_bytes;
+    Ok((dict_id, index_width))
+}
+
+fn decode_dict_delta_to_var_col(
+    dicts: &mut DictDecodeState,
+    body: &ColumnBody<'_>,
+    row_count: usize,
+    out_offsets: &mut Vec<u32>,
+    out_data: &mut Vec<u8>,
+) -> Result<()> {
+    let (dict_id, index_width) = apply_dict_delta(dicts, body, row_count)?;
+    let dict = &dicts.deltas[&dict_id];
+    expand_dict_indices(
+        body,
+        row_count,
+        index_width,
+        &dict.offsets,
+        &dict.bytes,
+        out_offsets,
+        out_data,
+    )
+}
+
+/// Decodes a `DeltaVarintI64` payload, or with `skip_nulls` a `NullableDeltaVarintI64` one,
+/// whose values cover only the valid rows; null rows decode as zero.
 fn decode_delta_varint_i64_from_payload(
-    payload: &[u8],
+    body: &ColumnBody<'_>,
     row_count: usize,
+    skip_nulls: bool,
     out: &mut [i64],
 ) -> Result<()> {
     if row_count == 0 {
         return Ok(());
     }
     if out.len() < row_count {
-        return Err(Error::Other("output length mismatch".to_string()));
-    }
-    if payload.len() < 8 {
-        return Err(Error::Other("delta payload truncated".to_string()));
-    }
-    let mut pos = 8usize;
-    let base_bytes = &payload[..8];
-    let mut prev = i64::from_le_bytes([
-        base_bytes[0],
-        base_bytes[1],
-        base_bytes[2],
-        base_bytes[3],
-        base_bytes[4],
-        base_bytes[5],
-        base_bytes[6],
-        base_bytes[7],
-    ]);
-    out[0] = prev;
-    for i in 1..row_count {
-        let zz = read_u64_varint(payload, &mut pos)?;
-        let delta = zigzag_u64_to_i64(zz);
-        prev = prev.wrapping_add(delta);
-        out[i] = prev;
+        return Err(Error::Internal("output length mismatch"));
+    }
+    let payload = body.payload1;
+    let mut pos = 0usize;
+    let mut prev: Option<i64> = None;
+    for (row, slot) in out[..row_count].iter_mut().enumerate() {
+        if skip_nulls && (body.validity[row / 8] & (1u8 << (row % 8))) == 0 {
+            *slot = 0;
+            continue;
+        }
+        let value = match prev {
+            None => {
+                let base_bytes = payload.get(..8).ok_or_else(|| {
+                    body.malformed(body.payload1_offset, "delta payload truncated")
+                })?;
+                pos = 8;
+                i64::from_le_bytes([
+                    base_bytes[0],
+                    base_bytes[1],
+                    base_bytes[2],
+                    base_bytes[3],
+                    base_bytes[4],
+                    base_bytes[5],
+                    base_bytes[6],
+                    base_bytes[7],
+                ])
+            }
+            Some(prev) => {
+                let start = pos;
+                let zz = read_u64_varint(payload, &mut pos)
+                    .map_err(|reason| body.malformed(body.payload1_offset + start, reason))?;
+                prev.wrapping_add(zigzag_u64_to_i64(zz))
+            }
+        };
+        prev = Some(value);
+        *slot = value;
+    }
+    if pos != payload.len() {
+        return Err(body.malformed(
+            body.payload1_offset + pos,
+            "trailing bytes in delta payload",
+        ));
+    }
+    Ok(())
+}
+
+fn decode_for_bit_packed_into<T: ForInt>(
+    body: &ColumnBody<'_>,
+    row_count: usize,
+    out: &mut Vec<T>,
+) -> Result<()> {
+    let payload = body.payload1;
+    let at = body.payload1_offset;
+    if payload.len() < 4 {
+        return Err(body.malformed(at, "FOR payload truncated"));
+    }
+    let block_count = u32::from_le_bytes([payload[0], payload[1], payload[2], payload[3]]) as usize;
+    if block_count != row_count.div_ceil(FOR_BLOCK_LEN) {
+        return Err(body.malformed(at, "FOR block count mismatch"));
+    }
+
+    out.clear();
+    out.reserve(row_count);
+    let mut pos = 4usize;
+    for block in 0..block_count {
+        let len = (row_count - block * FOR_BLOCK_LEN).min(FOR_BLOCK_LEN);
+        let header = payload
+            .get(pos..pos + T::SIZE + 1)
+            .ok_or_else(|| body.malformed(at + pos, "FOR payload truncated"))?;
+        let min: i64 = T::from_le_slice(&header[..T::SIZE]).into();
+        let width = u32::from(header[T::SIZE]);
+        if width > 8 * T::SIZE as u32 {
+            return Err(body.malformed(at + pos + T::SIZE, "invalid FOR bit width"));
+        }
+        pos += T::SIZE + 1;
+        let packed_len = (len * width as usize).div_ceil(8);
+        let packed = payload
+            .get(pos..pos + packed_len)
+            .ok_or_else(|| body.malformed(at + pos, "FOR payload truncated"))?;
+
+        let mask = match width {
+            0 => 0,
+            _ => u64::MAX >> (64 - width),
+        };
+        let mut acc: u128 = 0;
+        let mut acc_bits = 0u32;
+        let mut next = 0usize;
+        for _ in 0..len {
+            while acc_bits < width {
+                acc |= u128::from(packed[next]) << acc_bits;
+                next += 1;
+                acc_bits += 8;
+            }
+            let diff = (acc as u64) & mask;
+            acc >>= width;
+            acc_bits -= width;
+            out.push(T::from_i64_wrapping(min.wrapping_add(diff as i64)));
+        }
+        if acc != 0 {
+            return Err(body.malformed(at + pos + packed_len - 1, "FOR padding must be zero"));
+        }
+        pos += packed_len;
     }
     if pos != payload.len() {
-        return Err(Error::Other("trailing bytes in delta payload".to_string()));
+        return Err(body.malformed(at + pos, "trailing bytes in FOR payload"));
+    }
+    Ok(())
+}
+
+/// LSB-first bit reader over a payload slice.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    next: usize,
+    acc: u128,
+    bits: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            next: 0,
+            acc: 0,
+            bits: 0,
+        }
+    }
+
+    /// Reads `width` (1 to 64) bits, or `None` past the end of the input.
+    fn read(&mut self, width: u32) -> Option<u64> {
+        while self.bits < width {
+            let b = *self.bytes.get(self.next)?;
+            self.acc |= u128::from(b) << self.bits;
+            self.next += 1;
+            self.bits += 8;
+        }
+        let v = (self.acc as u64) & (u64::MAX >> (64 - width));
+        self.acc >>= width;
+        self.bits -= width;
+        Some(v)
+    }
+
+    /// Index of the byte holding the next unread bit.
+    fn byte_pos(&self) -> usize {
+        self.next - self.bits.div_ceil(8) as usize
+    }
+}
+
+fn decode_byte_stream_split_into<T: ByteStreamSplit>(
+    body: &ColumnBody<'_>,
+    row_count: usize,
+    out: &mut Vec<T>,
+) -> Result<()> {
+    let payload = body.payload1;
+    if payload.len() != checked_byte_len(row_count, T::SIZE)? {
+        return Err(body.malformed(body.payload1_offset, "values length mismatch"));
+    }
+    out.clear();
+    out.reserve(row_count);
+    let mut le = [0u8; 8];
+    for i in 0..row_count {
+        for (k, b) in le[..T::SIZE].iter_mut().enumerate() {
+            *b = payload[k * row_count + i];
+        }
+        out.push(T::from_le_slice(&le[..T::SIZE]));
+    }
+    Ok(())
+}
+
+fn decode_xor_float_into<T: XorFloat>(
+    body: &ColumnBody<'_>,
+    row_count: usize,
+    out: &mut Vec<T>,
+) -> Result<()> {
+    let payload = body.payload1;
+    let at = body.payload1_offset;
+    out.clear();
+    if row_count == 0 {
+        if !payload.is_empty() {
+            return Err(body.malformed(at, "trailing bytes in XOR float payload"));
+        }
+        return Ok(());
+    }
+    let first = payload
+        .get(..T::SIZE)
+        .ok_or_else(|| body.malformed(at, "XOR float payload truncated"))?;
+    let bits = 8 * T::SIZE as u32;
+    let at = at + T::SIZE;
+    let truncated = || body.malformed(at + payload.len() - T::SIZE, "XOR float stream truncated");
+
+    out.reserve(row_count);
+    let mut prev: u64 = T::from_le_slice(first).into();
+    out.push(T::from_u64_bits(prev));
+    let mut r = BitReader::new(&payload[T::SIZE..]);
+    let mut window: Option<(u32, u32)> = None;
+    for _ in 1..row_count {
+        let entry = r.byte_pos();
+        if r.read(1).ok_or_else(truncated)? == 1 {
+            let (lead, len) = if r.read(1).ok_or_else(truncated)? == 1 {
+                let lead = r.read(T::FIELD_BITS).ok_or_else(truncated)? as u32;
+                let len = r.read(T::FIELD_BITS).ok_or_else(truncated)? as u32 + 1;
+                if lead + len > bits {
+                    return Err(body.malformed(at + entry, "invalid XOR float window"));
+                }
+                window = Some((lead, len));
+                (lead, len)
+            } else {
+                window.ok_or_else(|| body.malformed(at + entry, "invalid XOR float window"))?
+            };
+            prev ^= r.read(len).ok_or_else(truncated)? << (bits - lead - len);
+        }
+        out.push(T::from_u64_bits(prev));
+    }
+    if r.acc != 0 {
+        return Err(body.malformed(at + r.next - 1, "XOR float padding must be zero"));
+    }
+    if r.next != r.bytes.len() {
+        return Err(body.malformed(at + r.next, "trailing bytes in XOR float payload"));
     }
     Ok(())
 }
@@ -555,417 +2232,538 @@ pub fn encode_mathldbt_v1_fast_path_into(view: &ColumnarBatchView<'_>, out: &mut
     encode_mathldbt_v1_fast_path_into_with_workspace(view, out, &mut ws)
 }
 
-pub fn encode_mathldbt_v1_fast_path_into_with_workspace(
-    view: &ColumnarBatchView<'_>,
+/// Writes `row_count + 1` offsets as a length-prefixed little-endian payload.
+fn write_offsets(
     out: &mut Vec<u8>,
-    ws: &mut MathldbtV1EncodeWorkspace,
+    col_idx: usize,
+    offsets: &[u32],
+    row_count: usize,
 ) -> Result<()> {
-    view.validate()?;
-    out.clear();
+    if offsets.len()
+        != row_count.checked_add(1).ok_or(Error::LimitExceeded {
+            kind: LimitKind::RowCount,
+            column: Some(col_idx),
+            offset: None,
+        })?
+    {
+        return Err(invalid_column(col_idx, "offsets length mismatch"));
+    }
+    write_le_values(out, offsets)
+}
+
+/// Writes `values` as a length-prefixed little-endian payload, the layout `decode_fixed_into`
+/// reads back for `PlainLe`.
+fn write_le_values<T: FixedScalar>(out: &mut Vec<u8>, values: &[T]) -> Result<()> {
+    let byte_len = checked_byte_len(values.len(), T::SIZE)?;
+    write_u32_le(out, byte_len.try_into().map_err(|_| payload_too_large())?);
+    out.reserve(byte_len);
+    #[cfg(target_endian = "little")]
+    {
+        // SAFETY: every `FixedScalar` is a plain value without padding (integers, byte arrays
+        // and the `#[repr(C)]` interval), so on a little-endian target its `byte_len` bytes in
+        // memory are already its wire bytes.
+        let bytes = unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len) };
+        out.extend_from_slice(bytes);
+    }
+    #[cfg(not(target_endian = "little"))]
+    {
+        for &v in values {
+            v.write_le(out);
+        }
+    }
+    Ok(())
+}
 
+/// Writes the envelope header up to and including the (empty) schema id.
+fn write_envelope_header(out: &mut Vec<u8>, row_count: usize, col_count: usize) -> Result<()> {
     out.extend_from_slice(MAGIC);
     write_u16_le(out, VERSION);
     write_u16_le(out, 0); // flags
 
-    let row_count_u32: u32 = view
-        .row_count
-        .try_into()
-        .map_err(|_| Error::Other("row_count too large".to_string()))?;
+    let row_count_u32: u32 = row_count.try_into().map_err(|_| Error::LimitExceeded {
+        kind: LimitKind::RowCount,
+        column: None,
+        offset: None,
+    })?;
     write_u32_le(out, row_count_u32);
 
-    let col_count_u16: u16 = view
-        .columns
-        .len()
-        .try_into()
-        .map_err(|_| Error::Other("col_count too large".to_string()))?;
+    let col_count_u16: u16 = col_count.try_into().map_err(|_| Error::LimitExceeded {
+        kind: LimitKind::ColumnCount,
+        column: None,
+        offset: None,
+    })?;
     if col_count_u16 == 0 {
-        return Err(Error::Other(
-            "MATHLDBT must have at least one column".to_string(),
-        ));
+        return Err(Error::Schema {
+            column: None,
+            reason: "MATHLDBT must have at least one column",
+        });
     }
     write_u16_le(out, col_count_u16);
 
     write_u16_le(out, 0); // schema_id_len (v1: none)
+    Ok(())
+}
 
-    let expected_validity = ceil_div_8(view.row_count)?;
-
-    for (field, col) in view.schema.fields().iter().zip(view.columns.iter()) {
-        write_u16_le(out, type_id(field.ty));
+/// Writes one column descriptor (type through payloads), followed by the descriptors of any
+/// nested child columns. Both the owned and the fast-path encoder go through here.
+fn encode_column_view(
+    out: &mut Vec<u8>,
+    ws: &mut MathldbtV1EncodeWorkspace,
+    col_idx: usize,
+    field_ty: &ColumnarType,
+    name: Option<&[u8]>,
+    col: &ColumnDataView<'_>,
+    row_count: usize,
+) -> Result<()> {
+    write_type(out, field_ty);
+    // Only top-level columns have a name (possibly empty) and a supplied dictionary.
+    let dict_column = name.map(|_| col_idx);
+    let expected_validity = ceil_div_8(row_count)?;
 
-        let name_bytes = field.name.as_deref().unwrap_or("").as_bytes();
+    let validity: &[u8] = match col {
+        ColumnDataView::FixedBool { validity, .. } => validity,
+        ColumnDataView::BitPackedBool { validity, .. } => validity,
+        ColumnDataView::FixedI16 { validity, .. } => validity,
+        ColumnDataView::FixedI32 { validity, .. } => validity,
+        ColumnDataView::FixedI64 { validity, .. } => validity,
+        ColumnDataView::FixedI8 { validity, .. } => validity,
+        ColumnDataView::FixedU8 { validity, .. } => validity,
+        ColumnDataView::FixedU16 { validity, .. } => validity,
+        ColumnDataView::FixedU32 { validity, .. } => validity,
+        ColumnDataView::FixedU64 { validity, .. } => validity,
+        ColumnDataView::FixedF32Bits { validity, .. } => validity,
+        ColumnDataView::FixedF64Bits { validity, .. } => validity,
+        ColumnDataView::FixedUuid { validity, .. } => validity,
+        ColumnDataView::FixedTimestampMicros { validity, .. } => validity,
+        ColumnDataView::FixedTimestamp { validity, .. } => validity,
+        ColumnDataView::FixedDate32 { validity, .. } => validity,
+        ColumnDataView::FixedTimeMicros { validity, .. } => validity,
+        ColumnDataView::FixedInterval { validity, .. } => validity,
+        ColumnDataView::FixedDecimal128 { validity, .. } => validity,
+        ColumnDataView::FixedSizeBinary { validity, .. } => validity,
+        ColumnDataView::FixedSizeListF32Bits { validity, .. } => validity,
+        ColumnDataView::FixedSizeListF64Bits { validity, .. } => validity,
+        ColumnDataView::Var { validity, .. } => validity,
+        ColumnDataView::List { validity, .. } => validity,
+        ColumnDataView::Struct { validity, .. } => validity,
+    };
+    if validity.len() != expected_validity {
+        return Err(invalid_column(col_idx, "validity length mismatch"));
+    }
 
-        let validity: &[u8] = match col {
-            ColumnDataView::FixedBool { validity, .. } => validity,
-            ColumnDataView::FixedI16 { validity, .. } => validity,
-            ColumnDataView::FixedI32 { validity, .. } => validity,
-            ColumnDataView::FixedI64 { validity, .. } => validity,
-            ColumnDataView::FixedF32Bits { validity, .. } => validity,
-            ColumnDataView::FixedF64Bits { validity, .. } => validity,
-            ColumnDataView::FixedUuid { validity, .. } => validity,
-            ColumnDataView::FixedTimestampMicros { validity, .. } => validity,
-            ColumnDataView::Var { validity, .. } => validity,
-        };
-        if validity.len() != expected_validity {
-            return Err(Error::Other("validity length mismatch".to_string()));
-        }
+    let mut dict_payload: Option<(&[u8], &[u8])> = None;
+    let mut delta_payload: Option<&[u8]> = None;
+    let mut packed_payload: Option<&[u8]> = None;
+    let mut view_var_coalesce_to_restore: Option<Vec<u8>> = None;
+    let split = byte_stream_split_requested(ws, dict_column);
+    let fixed_rle = if ws.enable_rle {
+        build_fixed_run_length_payload(&mut ws.rle_buf, field_ty, col, row_count)
+    } else {
+        None
+    };
 
-        let mut dict_payload: Option<(&[u8], &[u8])> = None;
-        let mut delta_payload: Option<&[u8]> = None;
-        let mut view_var_coalesce_to_restore: Option<Vec<u8>> = None;
+    // Fixed-width columns fall back to this when no opt-in encoding is chosen for them.
+    let fixed_fallback = if ws.enable_pg_be_fixed {
+        FixedEncodingId::PgBeFixed as u16
+    } else {
+        FixedEncodingId::PlainLe as u16
+    };
 
-        let encoding_id: u16 = match col {
-            ColumnDataView::Var {
-                offsets, data, ty, ..
-            } if ws.enable_dict_utf8 && matches!(ty, ColumnarType::Utf8 | ColumnarType::JsonbText) =>
-            {
-                let maybe = match data {
-                    VarDataView::Contiguous(bytes) => build_dict_utf8_payload(
+    let encoding_id: u16 = match col {
+        ColumnDataView::Var {
+            offsets, data, ty, ..
+        } if dict_requested(ws, ty, dict_column) => {
+            let maybe = match data {
+                VarDataView::Contiguous(bytes) => {
+                    build_dict_payload(ws, dict_column, validity, row_count, offsets, bytes)
+                        .map_err(|e| e.at_column(col_idx))?
+                }
+                VarDataView::Chunks { inline, chunks } => {
+                    let mut coalesced = std::mem::take(&mut ws.view_var_coalesce);
+                    coalesced.clear();
+                    let expected_len = offsets.last().copied().unwrap_or(0) as usize;
+                    coalesced.reserve(expected_len);
+                    coalesced.extend_from_slice(inline);
+                    for &c in *chunks {
+                        coalesced.extend_from_slice(c);
+                    }
+                    match build_dict_payload(
                         ws,
+                        dict_column,
                         validity,
-                        view.row_count,
+                        row_count,
                         offsets,
-                        bytes,
-                    )?,
-                    VarDataView::Chunks { inline, chunks } => {
-                        let mut coalesced = std::mem::take(&mut ws.view_var_coalesce);
-                        coalesced.clear();
-                        let expected_len = offsets.last().copied().unwrap_or(0) as usize;
-                        coalesced.reserve(expected_len);
-                        coalesced.extend_from_slice(inline);
-                        for &c in *chunks {
-                            coalesced.extend_from_slice(c);
+                        coalesced.as_slice(),
+                    ) {
+                        Ok(v) => {
+                            view_var_coalesce_to_restore = Some(coalesced);
+                            v
                         }
-                        match build_dict_utf8_payload(
-                            ws,
-                            validity,
-                            view.row_count,
-                            offsets,
-                            coalesced.as_slice(),
-                        ) {
-                            Ok(v) => {
-                                view_var_coalesce_to_restore = Some(coalesced);
-                                v
-                            }
-                            Err(e) => {
-                                ws.view_var_coalesce = coalesced;
-                                return Err(e);
-                            }
+                        Err(e) => {
+                            ws.view_var_coalesce = coalesced;
+                            return Err(e.at_column(col_idx));
                         }
                     }
-                };
-                if let Some((idx_bytes, dict_blob)) = maybe {
-                    dict_payload = Some((idx_bytes, dict_blob));
-                    ENC_DICT_UTF8
-                } else {
-                    ENC_PLAIN
                 }
+            };
+            if let Some((enc, idx_bytes, dict_blob)) = maybe {
+                dict_payload = Some((idx_bytes, dict_blob));
+                enc
+            } else {
+                ENC_PLAIN
             }
-            ColumnDataView::FixedI64 { values, .. }
-                if ws.enable_delta_varint_i64
-                    && field.ty == ColumnarType::I64
-                    && validity_all_valid(validity, view.row_count) =>
-            {
-                if let Some(payload) = build_delta_varint_i64_payload(ws, values)? {
+        }
+        ColumnDataView::FixedI64 { values, .. }
+            if *field_ty == ColumnarType::I64
+                && (ws.enable_for_bit_packed || ws.enable_delta_varint_i64 || split) =>
+        {
+            match build_i64_payload(ws, values, validity, split)? {
+                Some((encoding_id @ (ENC_FOR_BIT_PACKED | ENC_BYTE_STREAM_SPLIT), payload)) => {
+                    packed_payload = Some(payload);
+                    encoding_id
+                }
+                Some((encoding_id, payload)) => {
                     delta_payload = Some(payload);
-                    ENC_DELTA_VARINT_I64
-                } else {
-                    ENC_PLAIN
+                    encoding_id
                 }
+                None => fixed_fallback,
             }
-            ColumnDataView::FixedTimestampMicros { values, .. }
-                if ws.enable_delta_varint_i64
-                    && field.ty == ColumnarType::TimestampTzMicros
-                    && validity_all_valid(validity, view.row_count) =>
+        }
+        ColumnDataView::FixedI32 { values, .. }
+            if ws.enable_for_bit_packed && *field_ty == ColumnarType::I32 =>
+        {
+            packed_payload = build_for_bit_packed_payload(&mut ws.for_buf, values)?;
+            packed_payload.map_or(fixed_fallback, |_| ENC_FOR_BIT_PACKED)
+        }
+        ColumnDataView::FixedI16 { values, .. }
+            if ws.enable_for_bit_packed && *field_ty == ColumnarType::I16 =>
+        {
+            packed_payload = build_for_bit_packed_payload(&mut ws.for_buf, values)?;
+            packed_payload.map_or(fixed_fallback, |_| ENC_FOR_BIT_PACKED)
+        }
+        ColumnDataView::FixedF32Bits { values, .. }
+            if (ws.enable_xor_float || split) && *field_ty == ColumnarType::F32 =>
+        {
+            let built = build_float_payload(ws, values, split)?;
+            packed_payload = built.map(|(_, payload)| payload);
+            built.map_or(fixed_fallback, |(encoding_id, _)| encoding_id)
+        }
+        ColumnDataView::FixedF64Bits { values, .. }
+            if (ws.enable_xor_float || split) && *field_ty == ColumnarType::F64 =>
+        {
+            let built = build_float_payload(ws, values, split)?;
+            packed_payload = built.map(|(_, payload)| payload);
+            built.map_or(fixed_fallback, |(encoding_id, _)| encoding_id)
+        }
+        ColumnDataView::FixedU64 { values, .. } if ws.enable_delta_varint_i64 => {
+            if let Some((encoding_id, payload)) =
+                build_delta_varint_i64_payload(ws, u64_as_i64(values), validity)?
             {
-                if let Some(payload) = build_delta_varint_i64_payload(ws, values)? {
-                    delta_payload = Some(payload);
-                    ENC_DELTA_VARINT_I64
-                } else {
-                    ENC_PLAIN
-                }
+                delta_payload = Some(payload);
+                encoding_id
+            } else {
+                fixed_fallback
             }
-            ColumnDataView::Var { .. } => ENC_PLAIN,
-            _ => FixedEncodingId::PlainLe as u16,
-        };
+        }
+        ColumnDataView::FixedTimestampMicros { values, .. }
+        | ColumnDataView::FixedTimestamp { values, .. }
+            if ws.enable_delta_varint_i64
+                && matches!(
+                    *field_ty,
+                    ColumnarType::TimestampTzMicros | ColumnarType::Timestamp { .. }
+                ) =>
+        {
+            if let Some((encoding_id, payload)) =
+                build_delta_varint_i64_payload(ws, values, validity)?
+            {
+                delta_payload = Some(payload);
+                encoding_id
+            } else {
+                fixed_fallback
+            }
+        }
+        ColumnDataView::FixedBool { .. } | ColumnDataView::BitPackedBool { .. }
+            if ws.enable_bit_packed_bool =>
+        {
+            ENC_BIT_PACKED_BOOL
+        }
+        ColumnDataView::Var { .. }
+        | ColumnDataView::List { .. }
+        | ColumnDataView::Struct { .. } => ENC_PLAIN,
+        _ => fixed_fallback,
+    };
+    let rle_encoding = fixed_run_length_choice(
+        fixed_rle,
+        encoding_id,
+        packed_payload.or(delta_payload),
+        row_count,
+    );
+    let encoding_id = rle_encoding.unwrap_or(encoding_id);
 
-        write_u16_le(out, encoding_id);
-        write_u16_le(out, 0); // col_flags
-        write_u16_len_bytes(out, name_bytes)?;
-        write_u32_len_bytes(out, validity)?;
+    write_u16_le(out, encoding_id);
+    write_u16_le(out, 0); // col_flags
+    write_u16_len_bytes(out, name.unwrap_or_default()).map_err(|e| e.at_column(col_idx))?;
+    write_u32_len_bytes(out, validity)?;
 
-        match col {
-            ColumnDataView::FixedBool { values, .. } => {
-                if values.len() != view.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                write_u32_len_bytes(out, values)?;
+    if rle_encoding.is_some() {
+        write_u32_len_bytes(out, &ws.rle_buf)?;
+        write_u32_le(out, 0);
+        return Ok(());
+    }
+    if encoding_id == FixedEncodingId::PgBeFixed as u16 {
+        write_pg_be_fixed_payloads(out, col, row_count).map_err(|e| e.at_column(col_idx))?;
+        return Ok(());
+    }
+    if encoding_id == ENC_BIT_PACKED_BOOL {
+        write_bit_packed_bool_payloads(out, col, row_count).map_err(|e| e.at_column(col_idx))?;
+        return Ok(());
+    }
+    if let Some(payload) = packed_payload {
+        write_u32_len_bytes(out, payload)?;
+        write_u32_le(out, 0);
+        return Ok(());
+    }
+
+    match col {
+        ColumnDataView::FixedBool { values, .. } | ColumnDataView::FixedU8 { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_u32_len_bytes(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::BitPackedBool { values, .. } => {
+            write_unpacked_bool_payloads(out, values, row_count)
+                .map_err(|e| e.at_column(col_idx))?;
+        }
+        ColumnDataView::FixedI16 { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_le_values(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::FixedI8 { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_le_values(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::FixedU16 { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_le_values(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::FixedI32 { values, .. } | ColumnDataView::FixedDate32 { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_le_values(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::FixedI64 { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            if let Some(payload) = delta_payload {
+                write_u32_len_bytes(out, payload)?;
+                write_u32_le(out, 0);
+            } else {
+                write_le_values(out, values)?;
                 write_u32_le(out, 0);
             }
-            ColumnDataView::FixedI16 { values, .. } => {
-                if values.len() != view.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                let byte_len = checked_byte_len(view.row_count, 2, "values overflow")?;
-                write_u32_le(
-                    out,
-                    byte_len
-                        .try_into()
-                        .map_err(|_| Error::Other("payload too large".to_string()))?,
-                );
-                out.reserve(byte_len);
-                #[cfg(target_endian = "little")]
-                {
-                    let values_bytes =
-                        unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len) };
-                    out.extend_from_slice(values_bytes);
-                }
-                #[cfg(not(target_endian = "little"))]
-                {
-                    for &v in *values {
-                        out.extend_from_slice(&v.to_le_bytes());
-                    }
-                }
+        }
+        ColumnDataView::FixedF32Bits { values, .. } | ColumnDataView::FixedU32 { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_le_values(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::FixedF64Bits { values, .. } | ColumnDataView::FixedU64 { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            if let Some(payload) = delta_payload {
+                write_u32_len_bytes(out, payload)?;
+                write_u32_le(out, 0);
+            } else {
+                write_le_values(out, values)?;
                 write_u32_le(out, 0);
             }
-            ColumnDataView::FixedI32 { values, .. } => {
-                if values.len() != view.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                let byte_len = checked_byte_len(view.row_count, 4, "values overflow")?;
-                write_u32_le(
-                    out,
-                    byte_len
-                        .try_into()
-                        .map_err(|_| Error::Other("payload too large".to_string()))?,
-                );
-                out.reserve(byte_len);
-                #[cfg(target_endian = "little")]
-                {
-                    let values_bytes =
-                        unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len) };
-                    out.extend_from_slice(values_bytes);
-                }
-                #[cfg(not(target_endian = "little"))]
-                {
-                    for &v in *values {
-                        out.extend_from_slice(&v.to_le_bytes());
-                    }
-                }
+        }
+        ColumnDataView::FixedUuid { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_le_values(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::FixedTimestampMicros { values, .. }
+        | ColumnDataView::FixedTimestamp { values, .. }
+        | ColumnDataView::FixedTimeMicros { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            if let Some(payload) = delta_payload {
+                write_u32_len_bytes(out, payload)?;
                 write_u32_le(out, 0);
+            } else {
+                write_le_values(out, values)?;
+                write_u32_le(out, 0);
+            }
+        }
+        ColumnDataView::FixedInterval { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_le_values(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::FixedDecimal128 { values, .. } => {
+            if values.len() != row_count {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_le_values(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::FixedSizeBinary { size, values, .. } => {
+            if values.len() != fixed_size_len(row_count, *size)? {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_u32_len_bytes(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::FixedSizeListF32Bits { size, values, .. } => {
+            if values.len() != fixed_size_len(row_count, *size)? {
+                return Err(invalid_column(col_idx, "values length mismatch"));
+            }
+            write_le_values(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::FixedSizeListF64Bits { size, values, .. } => {
+            if values.len() != fixed_size_len(row_count, *size)? {
+                return Err(invalid_column(col_idx, "values length mismatch"));
             }
-            ColumnDataView::FixedI64 { values, .. } => {
-                if values.len() != view.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+            write_le_values(out, values)?;
+            write_u32_le(out, 0);
+        }
+        ColumnDataView::Var {
+            ty, offsets, data, ..
+        } => match encoding_id {
+            ENC_PLAIN => {
+                if *ty != *field_ty {
+                    return Err(Error::Internal("internal type mismatch"));
                 }
-                if encoding_id == ENC_DELTA_VARINT_I64 {
-                    let payload =
-                        delta_payload.ok_or_else(|| Error::Other("missing delta payload".to_string()))?;
-                    write_u32_len_bytes(out, payload)?;
-                    write_u32_le(out, 0);
-                } else {
-                    let byte_len = checked_byte_len(view.row_count, 8, "values overflow")?;
-                    write_u32_le(
-                        out,
-                        byte_len
-                            .try_into()
-                            .map_err(|_| Error::Other("payload too large".to_string()))?,
-                    );
-                    out.reserve(byte_len);
-                    #[cfg(target_endian = "little")]
-                    {
-                        let values_bytes = unsafe {
-                            std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len)
-                        };
-                        out.extend_from_slice(values_bytes);
+                write_offsets(out, col_idx, offsets, row_count)?;
+
+                match data {
+                    VarDataView::Contiguous(bytes) => {
+                        write_u32_len_bytes(out, bytes)?;
                     }
-                    #[cfg(not(target_endian = "little"))]
-                    {
-                        for &v in *values {
-                            out.extend_from_slice(&v.to_le_bytes());
+                    VarDataView::Chunks { inline, chunks } => {
+                        let data_len = data.len()?;
+                        let data_len_u32: u32 =
+                            data_len.try_into().map_err(|_| payload_too_large())?;
+                        write_u32_le(out, data_len_u32);
+                        out.reserve(data_len);
+                        out.extend_from_slice(inline);
+                        for &c in *chunks {
+                            out.extend_from_slice(c);
                         }
                     }
-                    write_u32_le(out, 0);
                 }
             }
-            ColumnDataView::FixedF32Bits { values, .. } => {
-                if values.len() != view.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
+            ENC_DICT | ENC_DICT_DELTA | ENC_RLE | ENC_CONSTANT => {
+                if *ty != *field_ty {
+                    return Err(Error::Internal("internal type mismatch"));
                 }
-                let byte_len = checked_byte_len(view.row_count, 4, "values overflow")?;
-                write_u32_le(
-                    out,
-                    byte_len
-                        .try_into()
-                        .map_err(|_| Error::Other("payload too large".to_string()))?,
-                );
-                out.reserve(byte_len);
-                #[cfg(target_endian = "little")]
-                {
-                    let values_bytes =
-                        unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len) };
-                    out.extend_from_slice(values_bytes);
-                }
-                #[cfg(not(target_endian = "little"))]
-                {
-                    for &v in *values {
-                        out.extend_from_slice(&v.to_le_bytes());
-                    }
-                }
-                write_u32_le(out, 0);
+                let (idx_bytes, dict_blob) =
+                    dict_payload.ok_or(Error::Internal("missing dict payload"))?;
+                write_u32_len_bytes(out, idx_bytes)?;
+                write_u32_len_bytes(out, dict_blob)?;
             }
-            ColumnDataView::FixedF64Bits { values, .. } => {
-                if values.len() != view.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                let byte_len = checked_byte_len(view.row_count, 8, "values overflow")?;
-                write_u32_le(
-                    out,
-                    byte_len
-                        .try_into()
-                        .map_err(|_| Error::Other("payload too large".to_string()))?,
-                );
-                out.reserve(byte_len);
-                #[cfg(target_endian = "little")]
-                {
-                    let values_bytes =
-                        unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len) };
-                    out.extend_from_slice(values_bytes);
-                }
-                #[cfg(not(target_endian = "little"))]
-                {
-                    for &v in *values {
-                        out.extend_from_slice(&v.to_le_bytes());
-                    }
-                }
-                write_u32_le(out, 0);
+            _ => {
+                return Err(Error::Internal("invalid encoding for varlen column"));
             }
-            ColumnDataView::FixedUuid { values, .. } => {
-                if values.len() != view.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                let byte_len = checked_byte_len(view.row_count, 16, "values overflow")?;
-                write_u32_le(
-                    out,
-                    byte_len
-                        .try_into()
-                        .map_err(|_| Error::Other("payload too large".to_string()))?,
-                );
-                out.reserve(byte_len);
-                let values_bytes =
-                    unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len) };
-                out.extend_from_slice(values_bytes);
-                write_u32_le(out, 0);
+        },
+        ColumnDataView::List { offsets, child, .. } => {
+            let ColumnarType::List(item) = field_ty else {
+                return Err(Error::Internal("internal type mismatch"));
+            };
+            write_offsets(out, col_idx, offsets, row_count)?;
+            write_u32_le(out, 0);
+            // The item column follows as a nested, unnamed descriptor.
+            let child_rows = offsets[row_count] as usize;
+            encode_column_view(out, ws, col_idx, item, None, child, child_rows)?;
+        }
+        ColumnDataView::Struct { children, .. } => {
+            let ColumnarType::Struct(fields) = field_ty else {
+                return Err(Error::Internal("internal type mismatch"));
+            };
+            if children.len() != fields.len() {
+                return Err(invalid_column(col_idx, "struct field count mismatch"));
             }
-            ColumnDataView::FixedTimestampMicros { values, .. } => {
-                if values.len() != view.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                if encoding_id == ENC_DELTA_VARINT_I64 {
-                    let payload =
-                        delta_payload.ok_or_else(|| Error::Other("missing delta payload".to_string()))?;
-                    write_u32_len_bytes(out, payload)?;
-                    write_u32_le(out, 0);
-                } else {
-                    let byte_len = checked_byte_len(view.row_count, 8, "values overflow")?;
-                    write_u32_le(
-                        out,
-                        byte_len
-                            .try_into()
-                            .map_err(|_| Error::Other("payload too large".to_string()))?,
-                    );
-                    out.reserve(byte_len);
-                    #[cfg(target_endian = "little")]
-                    {
-                        let values_bytes = unsafe {
-                            std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len)
-                        };
-                        out.extend_from_slice(values_bytes);
-                    }
-                    #[cfg(not(target_endian = "little"))]
-                    {
-                        for &v in *values {
-                            out.extend_from_slice(&v.to_le_bytes());
-                        }
-                    }
-                    write_u32_le(out, 0);
-                }
+            write_u32_le(out, 0);
+            write_u32_le(out, 0);
+            // One nested, unnamed descriptor per field follows, in field order.
+            for (field, child) in fields.iter().zip(children) {
+                encode_column_view(out, ws, col_idx, &field.ty, None, child, row_count)?;
             }
-            ColumnDataView::Var {
-                ty, offsets, data, ..
-            } => match encoding_id {
-                ENC_PLAIN => {
-                    if *ty != field.ty {
-                        return Err(Error::Other("internal type mismatch".to_string()));
-                    }
-                    if offsets.len()
-                        != view
-                            .row_count
-                            .checked_add(1)
-                            .ok_or_else(|| Error::Other("row_count too large".to_string()))?
-                    {
-                        return Err(Error::Other("offsets length mismatch".to_string()));
-                    }
-                    let offsets_bytes_len =
-                        checked_byte_len(view.row_count + 1, 4, "offsets overflow")?;
-                    write_u32_le(
-                        out,
-                        offsets_bytes_len
-                            .try_into()
-                            .map_err(|_| Error::Other("payload too large".to_string()))?,
-                    );
-                    out.reserve(offsets_bytes_len);
-                    #[cfg(target_endian = "little")]
-                    {
-                        let offsets_bytes = unsafe {
-                            std::slice::from_raw_parts(offsets.as_ptr() as *const u8, offsets_bytes_len)
-                        };
-                        out.extend_from_slice(offsets_bytes);
-                    }
-                    #[cfg(not(target_endian = "little"))]
-                    {
-                        for &o in *offsets {
-                            out.extend_from_slice(&o.to_le_bytes());
-                        }
-                    }
+        }
+    }
 
-                    match data {
-                        VarDataView::Contiguous(bytes) => {
-                            write_u32_len_bytes(out, bytes)?;
-                        }
-                        VarDataView::Chunks { inline, chunks } => {
-                            let data_len = data.len()?;
-                            let data_len_u32: u32 = data_len
-                                .try_into()
-                                .map_err(|_| Error::Other("payload too large".to_string()))?;
-                            write_u32_le(out, data_len_u32);
-                            out.reserve(data_len);
-                            out.extend_from_slice(inline);
-                            for &c in *chunks {
-                                out.extend_from_slice(c);
-                            }
-                        }
-                    }
-                }
-                ENC_DICT_UTF8 => {
-                    if *ty != field.ty {
-                        return Err(Error::Other("internal type mismatch".to_string()));
-                    }
-                    let (idx_bytes, dict_blob) = dict_payload
-                        .ok_or_else(|| Error::Other("missing dict payload".to_string()))?;
-                    write_u32_len_bytes(out, idx_bytes)?;
-                    write_u32_len_bytes(out, dict_blob)?;
-                }
-                _ => {
-                    return Err(Error::Other(
-                        "invalid encoding for varlen column".to_string(),
-                    ));
-                }
-            },
-        }
+    if let Some(coalesced) = view_var_coalesce_to_restore {
+        ws.view_var_coalesce = coalesced;
+    }
 
-        if let Some(coalesced) = view_var_coalesce_to_restore {
-            ws.view_var_coalesce = coalesced;
-        }
+    Ok(())
+}
+
+pub fn encode_mathldbt_v1_fast_path_into_with_workspace(
+    view: &ColumnarBatchView<'_>,
+    out: &mut Vec<u8>,
+    ws: &mut MathldbtV1EncodeWorkspace,
+) -> Result<()> {
+    encode_mathldbt_v1_fast_path_into_uncommitted(view, out, ws)?;
+    ws.commit_pending_dict_entries();
+    Ok(())
+}
+
+/// Encodes without committing new `DictDelta` entries, for callers that still have to emit
+/// the envelope; they call `commit_pending_dict_entries` once it is written. Uncommitted
+/// entries are dropped by the next encode.
+pub(crate) fn encode_mathldbt_v1_fast_path_into_uncommitted(
+    view: &ColumnarBatchView<'_>,
+    out: &mut Vec<u8>,
+    ws: &mut MathldbtV1EncodeWorkspace,
+) -> Result<()> {
+    view.validate()?;
+    ws.discard_pending_dict_entries();
+    out.clear();
+
+    write_envelope_header(out, view.row_count, view.columns.len())?;
+
+    for (col_idx, (field, col)) in view
+        .schema
+        .fields()
+        .iter()
+        .zip(view.columns.iter())
+        .enumerate()
+    {
+        let name_bytes = field.name.as_deref().unwrap_or("").as_bytes();
+        encode_column_view(
+            out,
+            ws,
+            col_idx,
+            &field.ty,
+            Some(name_bytes),
+            col,
+            view.row_count,
+        )?;
     }
 
     Ok(())
@@ -995,1543 +2793,1694 @@ pub fn encode_mathldbt_v1_into_with_workspace(
     batch: &ColumnarBatch,
     out: &mut Vec<u8>,
     ws: &mut MathldbtV1EncodeWorkspace,
+) -> Result<()> {
+    encode_mathldbt_v1_into_uncommitted(batch, out, ws)?;
+    ws.commit_pending_dict_entries();
+    Ok(())
+}
+
+/// As [`encode_mathldbt_v1_fast_path_into_uncommitted`], for owned batches.
+pub(crate) fn encode_mathldbt_v1_into_uncommitted(
+    batch: &ColumnarBatch,
+    out: &mut Vec<u8>,
+    ws: &mut MathldbtV1EncodeWorkspace,
 ) -> Result<()> {
     batch.validate()?;
+    ws.discard_pending_dict_entries();
     out.clear();
 
-    out.extend_from_slice(MAGIC);
-    write_u16_le(out, VERSION);
-    write_u16_le(out, 0); // flags
-
-    let row_count_u32: u32 = batch
-        .row_count
-        .try_into()
-        .map_err(|_| Error::Other("row_count too large".to_string()))?;
-    write_u32_le(out, row_count_u32);
+    write_envelope_header(out, batch.row_count, batch.columns.len())?;
 
-    let col_count_u16: u16 = batch
-        .columns
-        .len()
-        .try_into()
-        .map_err(|_| Error::Other("col_count too large".to_string()))?;
-    if col_count_u16 == 0 {
-        return Err(Error::Other(
-            "MATHLDBT must have at least one column".to_string(),
-        ));
+    for (col_idx, (field, col)) in batch
+        .schema
+        .fields()
+        .iter()
+        .zip(batch.columns.iter())
+        .enumerate()
+    {
+        let name_bytes = field.name.as_deref().unwrap_or("").as_bytes();
+        encode_column_view(
+            out,
+            ws,
+            col_idx,
+            &field.ty,
+            Some(name_bytes),
+            &col.as_view(),
+            batch.row_count,
+        )?;
     }
-    write_u16_le(out, col_count_u16);
-
-    write_u16_le(out, 0); // schema_id_len (v1: none)
-
-    let expected_validity = ceil_div_8(batch.row_count)?;
 
-    for (field, col) in batch.schema.fields().iter().zip(batch.columns.iter()) {
-        write_u16_le(out, type_id(field.ty));
-
-        let name_bytes = field.name.as_deref().unwrap_or("").as_bytes();
+    Ok(())
+}
 
-        let validity = match col {
-            ColumnData::FixedBool { validity, .. } => validity.as_bytes(),
-            ColumnData::FixedI16 { validity, .. } => validity.as_bytes(),
-            ColumnData::FixedI32 { validity, .. } => validity.as_bytes(),
-            ColumnData::FixedI64 { validity, .. } => validity.as_bytes(),
-            ColumnData::FixedF32Bits { validity, .. } => validity.as_bytes(),
-            ColumnData::FixedF64Bits { validity, .. } => validity.as_bytes(),
-            ColumnData::FixedUuid { validity, .. } => validity.as_bytes(),
-            ColumnData::FixedTimestampMicros { validity, .. } => validity.as_bytes(),
-            ColumnData::Var { validity, .. } => validity.as_bytes(),
-        };
-        if validity.len() != expected_validity {
-            return Err(Error::Other("validity length mismatch".to_string()));
-        }
+trait FixedScalar: Copy + Default {
+    const SIZE: usize = std::mem::size_of::<Self>();
 
-        let mut dict_payload: Option<(&[u8], &[u8])> = None;
-        let mut delta_payload: Option<&[u8]> = None;
+    fn from_le_slice(b: &[u8]) -> Self;
+    fn from_be_slice(b: &[u8]) -> Self;
+    /// Appends the little-endian wire bytes; little-endian targets copy whole slices instead.
+    #[cfg_attr(target_endian = "little", allow(dead_code))]
+    fn write_le(self, out: &mut Vec<u8>);
+}
 
-        let encoding_id: u16 = match col {
-            ColumnData::Var {
-                offsets, data, ty, ..
-            } if ws.enable_dict_utf8
-                && matches!(ty, ColumnarType::Utf8 | ColumnarType::JsonbText) =>
-            {
-                if let Some((idx_bytes, dict_blob)) = build_dict_utf8_payload(
-                    ws,
-                    validity,
-                    batch.row_count,
-                    offsets.as_slice(),
-                    data.as_slice(),
-                )? {
-                    dict_payload = Some((idx_bytes, dict_blob));
-                    ENC_DICT_UTF8
-                } else {
-                    ENC_PLAIN
+macro_rules! impl_fixed_scalar {
+    ($($t:ty),*) => {
+        $(
+            impl FixedScalar for $t {
+                #[inline]
+                fn from_le_slice(b: &[u8]) -> Self {
+                    let mut a = [0u8; std::mem::size_of::<$t>()];
+                    a.copy_from_slice(b);
+                    <$t>::from_le_bytes(a)
                 }
-            }
-            ColumnData::FixedI64 { values, .. }
-                if ws.enable_delta_varint_i64
-                    && field.ty == ColumnarType::I64
-                    && validity_all_valid(validity, batch.row_count) =>
-            {
-                if let Some(payload) = build_delta_varint_i64_payload(ws, values.as_slice())? {
-                    delta_payload = Some(payload);
-                    ENC_DELTA_VARINT_I64
-                } else {
-                    ENC_PLAIN
+
+                #[inline]
+                fn from_be_slice(b: &[u8]) -> Self {
+                    let mut a = [0u8; std::mem::size_of::<$t>()];
+                    a.copy_from_slice(b);
+                    <$t>::from_be_bytes(a)
                 }
-            }
-            ColumnData::FixedTimestampMicros { values, .. }
-                if ws.enable_delta_varint_i64
-                    && field.ty == ColumnarType::TimestampTzMicros
-                    && validity_all_valid(validity, batch.row_count) =>
-            {
-                if let Some(payload) = build_delta_varint_i64_payload(ws, values.as_slice())? {
-                    delta_payload = Some(payload);
-                    ENC_DELTA_VARINT_I64
-                } else {
-                    ENC_PLAIN
+
+                #[inline]
+                fn write_le(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
                 }
             }
-            ColumnData::Var { .. } => ENC_PLAIN,
-            _ => FixedEncodingId::PlainLe as u16,
-        };
+        )*
+    };
+}
 
-        write_u16_le(out, encoding_id);
-        write_u16_le(out, 0); // col_flags
-        write_u16_len_bytes(out, name_bytes)?;
-        write_u32_len_bytes(out, validity)?;
+impl_fixed_scalar!(u8, i8, i16, u16, i32, i64, u32, u64, i128);
 
-        match col {
-            ColumnData::FixedBool { values, .. } => {
-                if values.len() != batch.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                write_u32_len_bytes(out, values.as_slice())?;
-                write_u32_le(out, 0);
-            }
-            ColumnData::FixedI16 { values, .. } => {
-                if values.len() != batch.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                let byte_len = checked_byte_len(batch.row_count, 2, "values overflow")?;
-                write_u32_le(
-                    out,
-                    byte_len
-                        .try_into()
-                        .map_err(|_| Error::Other("payload too large".to_string()))?,
-                );
-                out.reserve(byte_len);
-                #[cfg(target_endian = "little")]
-                {
-                    let values_bytes = unsafe {
-                        std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len)
-                    };
-                    out.extend_from_slice(values_bytes);
-                }
-                #[cfg(not(target_endian = "little"))]
-                {
-                    for &v in values {
-                        out.extend_from_slice(&v.to_le_bytes());
-                    }
-                }
-                write_u32_le(out, 0);
-            }
-            ColumnData::FixedI32 { values, .. } => {
-                if values.len() != batch.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                let byte_len = checked_byte_len(batch.row_count, 4, "values overflow")?;
-                write_u32_le(
-                    out,
-                    byte_len
-                        .try_into()
-                        .map_err(|_| Error::Other("payload too large".to_string()))?,
-                );
-                out.reserve(byte_len);
-                #[cfg(target_endian = "little")]
-                {
-                    let values_bytes = unsafe {
-                        std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len)
-                    };
-                    out.extend_from_slice(values_bytes);
-                }
-                #[cfg(not(target_endian = "little"))]
-                {
-                    for &v in values {
-                        out.extend_from_slice(&v.to_le_bytes());
-                    }
-                }
-                write_u32_le(out, 0);
-            }
-            ColumnData::FixedI64 { values, .. } => {
-                if values.len() != batch.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                if encoding_id == ENC_DELTA_VARINT_I64 {
-                    let payload = delta_payload
-                        .ok_or_else(|| Error::Other("missing delta payload".to_string()))?;
-                    write_u32_len_bytes(out, payload)?;
-                    write_u32_le(out, 0);
-                } else {
-                    let byte_len = checked_byte_len(batch.row_count, 8, "values overflow")?;
-                    write_u32_le(
-                        out,
-                        byte_len
-                            .try_into()
-                            .map_err(|_| Error::Other("payload too large".to_string()))?,
-                    );
-                    out.reserve(byte_len);
-                    #[cfg(target_endian = "little")]
-                    {
-                        let values_bytes = unsafe {
-                            std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len)
-                        };
-                        out.extend_from_slice(values_bytes);
-                    }
-                    #[cfg(not(target_endian = "little"))]
-                    {
-                        for &v in values {
-                            out.extend_from_slice(&v.to_le_bytes());
-                        }
-                    }
-                    write_u32_le(out, 0);
-                }
-            }
-            ColumnData::FixedF32Bits { values, .. } => {
-                if values.len() != batch.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                let byte_len = checked_byte_len(batch.row_count, 4, "values overflow")?;
-                write_u32_le(
-                    out,
-                    byte_len
-                        .try_into()
-                        .map_err(|_| Error::Other("payload too large".to_string()))?,
-                    );
-                out.reserve(byte_len);
-                #[cfg(target_endian = "little")]
-                {
-                    let values_bytes = unsafe {
-                        std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len)
-                    };
-                    out.extend_from_slice(values_bytes);
-                }
-                #[cfg(not(target_endian = "little"))]
-                {
-                    for &v in values {
-                        out.extend_from_slice(&v.to_le_bytes());
-                    }
-                }
-                write_u32_le(out, 0);
-            }
-            ColumnData::FixedF64Bits { values, .. } => {
-                if values.len() != batch.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                let byte_len = checked_byte_len(batch.row_count, 8, "values overflow")?;
-                write_u32_le(
-                    out,
-                    byte_len
-                        .try_into()
-                        .map_err(|_| Error::Other("payload too large".to_string()))?,
-                );
-                out.reserve(byte_len);
-                #[cfg(target_endian = "little")]
-                {
-                    let values_bytes = unsafe {
-                        std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len)
-                    };
-                    out.extend_from_slice(values_bytes);
-                }
-                #[cfg(not(target_endian = "little"))]
-                {
-                    for &v in values {
-                        out.extend_from_slice(&v.to_le_bytes());
-                    }
-                }
-                write_u32_le(out, 0);
-            }
-            ColumnData::FixedUuid { values, .. } => {
-                if values.len() != batch.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                let byte_len = checked_byte_len(batch.row_count, 16, "values overflow")?;
-                write_u32_le(
-                    out,
-                    byte_len
-                        .try_into()
-                        .map_err(|_| Error::Other("payload too large".to_string()))?,
-                    );
-                out.reserve(byte_len);
-                let values_bytes =
-                    unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len) };
-                out.extend_from_slice(values_bytes);
-                write_u32_le(out, 0);
-            }
-            ColumnData::FixedTimestampMicros { values, .. } => {
-                if values.len() != batch.row_count {
-                    return Err(Error::Other("values length mismatch".to_string()));
-                }
-                if encoding_id == ENC_DELTA_VARINT_I64 {
-                    let payload = delta_payload
-                        .ok_or_else(|| Error::Other("missing delta payload".to_string()))?;
-                    write_u32_len_bytes(out, payload)?;
-                    write_u32_le(out, 0);
-                } else {
-                    let byte_len = checked_byte_len(batch.row_count, 8, "values overflow")?;
-                    write_u32_le(
-                        out,
-                        byte_len
-                            .try_into()
-                            .map_err(|_| Error::Other("payload too large".to_string()))?,
-                    );
-                    out.reserve(byte_len);
-                    #[cfg(target_endian = "little")]
-                    {
-                        let values_bytes = unsafe {
-                            std::slice::from_raw_parts(values.as_ptr() as *const u8, byte_len)
-                        };
-                        out.extend_from_slice(values_bytes);
-                    }
-                    #[cfg(not(target_endian = "little"))]
-                    {
-                        for &v in values {
-                            out.extend_from_slice(&v.to_le_bytes());
-                        }
-                    }
-                    write_u32_le(out, 0);
-                }
-            }
-            ColumnData::Var {
-                ty, offsets, data, ..
-            } => match encoding_id {
-                ENC_PLAIN => {
-                    if *ty != field.ty {
-                        return Err(Error::Other("internal type mismatch".to_string()));
-                    }
-                    if offsets.len()
-                        != batch
-                            .row_count
-                            .checked_add(1)
-                            .ok_or_else(|| Error::Other("row_count too large".to_string()))?
-                    {
-                        return Err(Error::Other("offsets length mismatch".to_string()));
-                    }
-                    let offsets_bytes_len =
-                        checked_byte_len(batch.row_count + 1, 4, "offsets overflow")?;
-                    write_u32_le(
-                        out,
-                        offsets_bytes_len
-                            .try_into()
-                            .map_err(|_| Error::Other("payload too large".to_string()))?,
-                    );
-                    out.reserve(offsets_bytes_len);
-                    #[cfg(target_endian = "little")]
-                    {
-                        let offsets_bytes = unsafe {
-                            std::slice::from_raw_parts(
-                                offsets.as_ptr() as *const u8,
-                                offsets_bytes_len,
-                            )
-                        };
-                        out.extend_from_slice(offsets_bytes);
-                    }
-                    #[cfg(not(target_endian = "little"))]
-                    {
-                        for &o in offsets {
-                            out.extend_from_slice(&o.to_le_bytes());
-                        }
-                    }
-                    write_u32_len_bytes(out, data.as_slice())?;
-                }
-                ENC_DICT_UTF8 => {
-                    if *ty != field.ty {
-                        return Err(Error::Other("internal type mismatch".to_string()));
-                    }
-                    let (idx_bytes, dict_blob) = dict_payload
-                        .ok_or_else(|| Error::Other("missing dict payload".to_string()))?;
-                    write_u32_len_bytes(out, idx_bytes)?;
-                    write_u32_len_bytes(out, dict_blob)?;
-                }
-                _ => {
-                    return Err(Error::Other(
-                        "invalid encoding for varlen column".to_string(),
-                    ));
-                }
-            },
-        }
+impl FixedScalar for [u8; 16] {
+    #[inline]
+    fn from_le_slice(b: &[u8]) -> Self {
+        let mut a = [0u8; 16];
+        a.copy_from_slice(b);
+        a
     }
 
-    Ok(())
-}
+    #[inline]
+    fn from_be_slice(b: &[u8]) -> Self {
+        Self::from_le_slice(b)
+    }
 
-pub fn decode_mathldbt_v1(bytes: &[u8]) -> Result<ColumnarBatch> {
-    let mut ws = MathldbtV1DecodeWorkspace::default();
-    decode_mathldbt_v1_with_workspace(bytes, &mut ws)
+    #[inline]
+    fn write_le(self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self);
+    }
 }
 
-pub fn decode_mathldbt_v1_with_workspace(
-    bytes: &[u8],
-    ws: &mut MathldbtV1DecodeWorkspace,
-) -> Result<ColumnarBatch> {
-    let mut pos = 0usize;
-
-    fn take<'a>(bytes: &'a [u8], pos: &mut usize, n: usize) -> Result<&'a [u8]> {
-        let end = pos
-            .checked_add(n)
-            .ok_or_else(|| Error::Other("decode overflow".to_string()))?;
-        if end > bytes.len() {
-            return Err(Error::Other("truncated mathldbt".to_string()));
+impl FixedScalar for IntervalMonthDayMicros {
+    #[inline]
+    fn from_le_slice(b: &[u8]) -> Self {
+        Self {
+            months: i32::from_le_slice(&b[..4]),
+            days: i32::from_le_slice(&b[4..8]),
+            micros: i64::from_le_slice(&b[8..]),
         }
-        let slice = &bytes[*pos..end];
-        *pos = end;
-        Ok(slice)
     }
 
-    fn read_u16_le(bytes: &[u8], pos: &mut usize) -> Result<u16> {
-        let b = take(bytes, pos, 2)?;
-        Ok(u16::from_le_bytes([b[0], b[1]]))
+    #[inline]
+    fn from_be_slice(b: &[u8]) -> Self {
+        interval_from_be_bytes(b)
     }
 
-    fn read_u32_le(bytes: &[u8], pos: &mut usize) -> Result<u32> {
-        let b = take(bytes, pos, 4)?;
-        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    #[inline]
+    fn write_le(self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&interval_to_le_bytes(self));
     }
+}
 
-    let magic = take(bytes, &mut pos, 8)?;
-    if magic != MAGIC {
-        return Err(Error::Other("invalid MATHLDBT magic".to_string()));
-    }
-    let version = read_u16_le(bytes, &mut pos)?;
-    if version != VERSION {
-        return Err(Error::Other(format!(
-            "unsupported MATHLDBT version: {version}"
-        )));
+/// Integer types `ForBitPacked` applies to.
+trait ForInt: FixedScalar + Into<i64> {
+    /// Truncates to `Self`, keeping the low bits.
+    fn from_i64_wrapping(v: i64) -> Self;
+}
+
+impl ForInt for i16 {
+    fn from_i64_wrapping(v: i64) -> Self {
+        v as i16
     }
-    let _flags = read_u16_le(bytes, &mut pos)?;
-    let row_count = read_u32_le(bytes, &mut pos)? as usize;
-    let col_count = read_u16_le(bytes, &mut pos)? as usize;
-    if col_count == 0 {
-        return Err(Error::Other(
-            "MATHLDBT must have at least one column".to_string(),
-        ));
+}
+
+impl ForInt for i32 {
+    fn from_i64_wrapping(v: i64) -> Self {
+        v as i32
     }
+}
 
-    let schema_id_len = read_u16_le(bytes, &mut pos)? as usize;
-    if schema_id_len > 0 {
-        let _ = take(bytes, &mut pos, schema_id_len)?;
+impl ForInt for i64 {
+    fn from_i64_wrapping(v: i64) -> Self {
+        v
     }
+}
 
-    let expected_validity = ceil_div_8(row_count)?;
-    let mut fields: Vec<ColumnarField> = Vec::with_capacity(col_count);
-    let mut columns: Vec<ColumnData> = Vec::with_capacity(col_count);
+/// Float bit patterns `XorFloat` applies to.
+trait XorFloat: FixedScalar + Into<u64> {
+    /// Width of the leading-zero and window-length fields.
+    const FIELD_BITS: u32;
 
-    for _ in 0..col_count {
-        let tid = read_u16_le(bytes, &mut pos)?;
-        let ty = type_from_id(tid)?;
-        let encoding_id_u16 = read_u16_le(bytes, &mut pos)?;
-        let _col_flags = read_u16_le(bytes, &mut pos)?;
-
-        let name_len = read_u16_le(bytes, &mut pos)? as usize;
-        let name_bytes = take(bytes, &mut pos, name_len)?;
-        let name = if name_len == 0 {
-            None
-        } else {
-            Some(
-                std::str::from_utf8(name_bytes)
-                    .map_err(|_| Error::Other("invalid UTF-8 column name".to_string()))?
-                    .to_string(),
-            )
-        };
+    fn from_u64_bits(v: u64) -> Self;
+}
 
-        let validity_len = read_u32_le(bytes, &mut pos)? as usize;
-        if validity_len != expected_validity {
-            return Err(Error::Other("validity length mismatch".to_string()));
-        }
-        let validity_bytes = take(bytes, &mut pos, validity_len)?.to_vec();
+impl XorFloat for u32 {
+    const FIELD_BITS: u32 = 5;
 
-        let payload1_len = read_u32_le(bytes, &mut pos)? as usize;
-        let payload1 = take(bytes, &mut pos, payload1_len)?;
-        let payload2_len = read_u32_le(bytes, &mut pos)? as usize;
-        let payload2 = take(bytes, &mut pos, payload2_len)?;
+    fn from_u64_bits(v: u64) -> Self {
+        v as u32
+    }
+}
 
-        fields.push(ColumnarField { name, ty });
+impl XorFloat for u64 {
+    const FIELD_BITS: u32 = 6;
 
-        match ty {
-            ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText => {
-                match encoding_id_u16 {
-                    ENC_PLAIN => {
-                        let expected_offsets_len = (row_count + 1)
-                            .checked_mul(4)
-                            .ok_or_else(|| Error::Other("offsets overflow".to_string()))?;
-                        if payload1.len() != expected_offsets_len {
-                            return Err(Error::Other("offsets length mismatch".to_string()));
-                        }
-                        let mut offsets: Vec<u32> = Vec::new();
-                        offsets.resize(row_count + 1, 0u32);
-                        #[cfg(target_endian = "little")]
-                        {
-                            let dst = unsafe {
-                                std::slice::from_raw_parts_mut(
-                                    offsets.as_mut_ptr() as *mut u8,
-                                    expected_offsets_len,
-                                )
-                            };
-                            dst.copy_from_slice(payload1);
-                        }
-                        #[cfg(not(target_endian = "little"))]
-                        {
-                            for i in 0..(row_count + 1) {
-                                let j = i * 4;
-                                offsets[i] = u32::from_le_bytes([
-                                    payload1[j],
-                                    payload1[j + 1],
-                                    payload1[j + 2],
-                                    payload1[j + 3],
-                                ]);
-                            }
-                        }
-                        let mut prev = 0u32;
-                        for &o in &offsets {
-                            if o < prev {
-                                return Err(Error::Other(
-                                    "offsets must be non-decreasing".to_string(),
-                                ));
-                            }
-                            prev = o;
-                        }
-                        let final_off = *offsets.last().unwrap_or(&0);
-                        let data_len_u32: u32 = payload2
-                            .len()
-                            .try_into()
-                            .map_err(|_| Error::Other("data too large".to_string()))?;
-                        if final_off != data_len_u32 {
-                            return Err(Error::Other("final offset mismatch".to_string()));
-                        }
+    fn from_u64_bits(v: u64) -> Self {
+        v
+    }
+}
 
-                        let validity = ValidityBitmap {
-                            bytes: validity_bytes,
-                        };
-                        columns.push(ColumnData::Var {
-                            ty,
-                            validity,
-                            offsets,
-                            data: payload2.to_vec(),
-                        });
-                    }
-                    ENC_DICT_UTF8 => {
-                        if ty == ColumnarType::Bytes {
-                            return Err(Error::Other(
-                                "DictUtf8 is not supported for Bytes".to_string(),
-                            ));
-                        }
-                        let mut offsets: Vec<u32> = Vec::new();
-                        let mut data: Vec<u8> = Vec::new();
-                        decode_dict_utf8_to_var_col(
-                            ws,
-                            row_count,
-                            validity_bytes.as_slice(),
-                            payload1,
-                            payload2,
-                            &mut offsets,
-                            &mut data,
-                        )?;
-                        let validity = ValidityBitmap {
-                            bytes: validity_bytes,
-                        };
-                        columns.push(ColumnData::Var {
-                            ty,
-                            validity,
-                            offsets,
-                            data,
-                        });
-                    }
-                    _ => {
-                        return Err(Error::Other(
-                            "invalid encoding for varlen column".to_string(),
-                        ));
-                    }
-                }
-            }
-            _ => {
-                if encoding_id_u16 == ENC_DELTA_VARINT_I64
-                    && !(ty == ColumnarType::I64 || ty == ColumnarType::TimestampTzMicros)
-                {
-                    return Err(Error::Other(
-                        "invalid encoding for fixed column".to_string(),
-                    ));
-                }
-                let enc = if encoding_id_u16 == ENC_DELTA_VARINT_I64 {
-                    FixedEncodingId::PlainLe
-                } else {
-                    FixedEncodingId::from_u16(encoding_id_u16).ok_or_else(|| {
-                        Error::Other("invalid encoding for fixed column".to_string())
-                    })?
-                };
-                if payload2_len != 0 {
-                    return Err(Error::Other(
-                        "fixed-width payload_2 must be empty".to_string(),
-                    ));
-                }
+/// Values `ByteStreamSplit` applies to.
+trait ByteStreamSplit: FixedScalar {
+    /// The value's little-endian bytes in the low `SIZE` bytes.
+    fn to_le_u64(self) -> u64;
+}
 
-                let validity = ValidityBitmap {
-                    bytes: validity_bytes,
-                };
+impl ByteStreamSplit for u32 {
+    fn to_le_u64(self) -> u64 {
+        u64::from(self)
+    }
+}
 
-                match ty {
-                    ColumnarType::Bool => {
-                        if payload1.len() != row_count {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        columns.push(ColumnData::FixedBool {
-                            validity,
-                            values: payload1.to_vec(),
-                        });
-                    }
-                    ColumnarType::I16 => {
-                        if payload1.len() != row_count * 2 {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        let mut values: Vec<i16> = vec![0i16; row_count];
-                        match enc {
-                            FixedEncodingId::PlainLe => {
-                                #[cfg(target_endian = "little")]
-                                {
-                                    let dst = unsafe {
-                                        std::slice::from_raw_parts_mut(
-                                            values.as_mut_ptr() as *mut u8,
-                                            row_count * 2,
-                                        )
-                                    };
-                                    dst.copy_from_slice(payload1);
-                                }
-                                #[cfg(not(target_endian = "little"))]
-                                {
-                                    for i in 0..row_count {
-                                        let j = i * 2;
-                                        let b = [payload1[j], payload1[j + 1]];
-                                        values[i] = i16::from_le_bytes(b);
-                                    }
-                                }
-                            }
-                            FixedEncodingId::PgBeFixed => {
-                                for i in 0..row_count {
-                                    let j = i * 2;
-                                    let b = [payload1[j], payload1[j + 1]];
-                                    values[i] = i16::from_be_bytes(b);
-                                }
-                            }
-                        }
-                        columns.push(ColumnData::FixedI16 { validity, values });
-                    }
-                    ColumnarType::I32 => {
-                        if payload1.len() != row_count * 4 {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        let mut values: Vec<i32> = vec![0i32; row_count];
-                        match enc {
-                            FixedEncodingId::PlainLe => {
-                                #[cfg(target_endian = "little")]
-                                {
-                                    let dst = unsafe {
-                                        std::slice::from_raw_parts_mut(
-                                            values.as_mut_ptr() as *mut u8,
-                                            row_count * 4,
-                                        )
-                                    };
-                                    dst.copy_from_slice(payload1);
-                                }
-                                #[cfg(not(target_endian = "little"))]
-                                {
-                                    for i in 0..row_count {
-                                        let j = i * 4;
-                                        let b = [
-                                            payload1[j],
-                                            payload1[j + 1],
-                                            payload1[j + 2],
-                                            payload1[j + 3],
-                                        ];
-                                        values[i] = i32::from_le_bytes(b);
-                                    }
-                                }
-                            }
-                            FixedEncodingId::PgBeFixed => {
-                                for i in 0..row_count {
-                                    let j = i * 4;
-                                    let b = [
-                                        payload1[j],
-                                        payload1[j + 1],
-                                        payload1[j + 2],
-                                        payload1[j + 3],
-                                    ];
-                                    values[i] = i32::from_be_bytes(b);
-                                }
-                            }
-                        }
-                        columns.push(ColumnData::FixedI32 { validity, values });
-                    }
-                    ColumnarType::I64 => {
-                        let mut values: Vec<i64> = vec![0i64; row_count];
-                        if encoding_id_u16 == ENC_DELTA_VARINT_I64 {
-                            decode_delta_varint_i64_from_payload(
-                                payload1,
-                                row_count,
-                                values.as_mut_slice(),
-                            )?;
-                        } else {
-                            if payload1.len() != row_count * 8 {
-                                return Err(Error::Other("values length mismatch".to_string()));
-                            }
-                            match enc {
-                                FixedEncodingId::PlainLe => {
-                                    #[cfg(target_endian = "little")]
-                                    {
-                                        let dst = unsafe {
-                                            std::slice::from_raw_parts_mut(
-                                                values.as_mut_ptr() as *mut u8,
-                                                row_count * 8,
-                                            )
-                                        };
-                                        dst.copy_from_slice(payload1);
-                                    }
-                                    #[cfg(not(target_endian = "little"))]
-                                    {
-                                        for i in 0..row_count {
-                                            let j = i * 8;
-                                            let b = [
-                                                payload1[j],
-                                                payload1[j + 1],
-                                                payload1[j + 2],
-                                                payload1[j + 3],
-                                                payload1[j + 4],
-                                                payload1[j + 5],
-                                                payload1[j + 6],
-                                                payload1[j + 7],
-                                            ];
-                                            values[i] = i64::from_le_bytes(b);
-                                        }
-                                    }
-                                }
-                                FixedEncodingId::PgBeFixed => {
-                                    for i in 0..row_count {
-                                        let j = i * 8;
-                                        let b = [
-                                            payload1[j],
-                                            payload1[j + 1],
-                                            payload1[j + 2],
-                                            payload1[j + 3],
-                                            payload1[j + 4],
-                                            payload1[j + 5],
-                                            payload1[j + 6],
-                                            payload1[j + 7],
-                                        ];
-                                        values[i] = i64::from_be_bytes(b);
-                                    }
-                                }
-                            }
-                        }
-                        columns.push(ColumnData::FixedI64 { validity, values });
-                    }
-                    ColumnarType::F32 => {
-                        if payload1.len() != row_count * 4 {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        let mut values: Vec<u32> = vec![0u32; row_count];
-                        match enc {
-                            FixedEncodingId::PlainLe => {
-                                #[cfg(target_endian = "little")]
-                                {
-                                    let dst = unsafe {
-                                        std::slice::from_raw_parts_mut(
-                                            values.as_mut_ptr() as *mut u8,
-                                            row_count * 4,
-                                        )
-                                    };
-                                    dst.copy_from_slice(payload1);
-                                }
-                                #[cfg(not(target_endian = "little"))]
-                                {
-                                    for i in 0..row_count {
-                                        let j = i * 4;
-                                        let b = [
-                                            payload1[j],
-                                            payload1[j + 1],
-                                            payload1[j + 2],
-                                            payload1[j + 3],
-                                        ];
-                                        values[i] = u32::from_le_bytes(b);
-                                    }
-                                }
-                            }
-                            FixedEncodingId::PgBeFixed => {
-                                for i in 0..row_count {
-                                    let j = i * 4;
-                                    let b = [
-                                        payload1[j],
-                                        payload1[j + 1],
-                                        payload1[j + 2],
-                                        payload1[j + 3],
-                                    ];
-                                    values[i] = u32::from_be_bytes(b);
-                                }
-                            }
-                        }
-                        columns.push(ColumnData::FixedF32Bits { validity, values });
-                    }
-                    ColumnarType::F64 => {
-                        if payload1.len() != row_count * 8 {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        let mut values: Vec<u64> = vec![0u64; row_count];
-                        match enc {
-                            FixedEncodingId::PlainLe => {
-                                #[cfg(target_endian = "little")]
-                                {
-                                    let dst = unsafe {
-                                        std::slice::from_raw_parts_mut(
-                                            values.as_mut_ptr() as *mut u8,
-                                            row_count * 8,
-                                        )
-                                    };
-                                    dst.copy_from_slice(payload1);
-                                }
-                                #[cfg(not(target_endian = "little"))]
-                                {
-                                    for i in 0..row_count {
-                                        let j = i * 8;
-                                        let b = [
-                                            payload1[j],
-                                            payload1[j + 1],
-                                            payload1[j + 2],
-                                            payload1[j + 3],
-                                            payload1[j + 4],
-                                            payload1[j + 5],
-                                            payload1[j + 6],
-                                            payload1[j + 7],
-                                        ];
-                                        values[i] = u64::from_le_bytes(b);
-                                    }
-                                }
-                            }
-                            FixedEncodingId::PgBeFixed => {
-                                for i in 0..row_count {
-                                    let j = i * 8;
-                                    let b = [
-                                        payload1[j],
-                                        payload1[j + 1],
-                                        payload1[j + 2],
-                                        payload1[j + 3],
-                                        payload1[j + 4],
-                                        payload1[j + 5],
-                                        payload1[j + 6],
-                                        payload1[j + 7],
-                                    ];
-                                    values[i] = u64::from_be_bytes(b);
-                                }
-                            }
-                        }
-                        columns.push(ColumnData::FixedF64Bits { validity, values });
-                    }
-                    ColumnarType::Uuid => {
-                        if payload1.len() != row_count * 16 {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        let mut values: Vec<[u8; 16]> = vec![[0u8; 16]; row_count];
-                        let dst = unsafe {
-                            std::slice::from_raw_parts_mut(
-                                values.as_mut_ptr() as *mut u8,
-                                row_count * 16,
-                            )
-                        };
-                        dst.copy_from_slice(payload1);
-                        columns.push(ColumnData::FixedUuid { validity, values });
-                    }
-                    ColumnarType::TimestampTzMicros => {
-                        let mut values: Vec<i64> = vec![0i64; row_count];
-                        if encoding_id_u16 == ENC_DELTA_VARINT_I64 {
-                            decode_delta_varint_i64_from_payload(
-                                payload1,
-                                row_count,
-                                values.as_mut_slice(),
-                            )?;
-                        } else {
-                            if payload1.len() != row_count * 8 {
-                                return Err(Error::Other("values length mismatch".to_string()));
-                            }
-                            match enc {
-                                FixedEncodingId::PlainLe => {
-                                    #[cfg(target_endian = "little")]
-                                    {
-                                        let dst = unsafe {
-                                            std::slice::from_raw_parts_mut(
-                                                values.as_mut_ptr() as *mut u8,
-                                                row_count * 8,
-                                            )
-                                        };
-                                        dst.copy_from_slice(payload1);
-                                    }
-                                    #[cfg(not(target_endian = "little"))]
-                                    {
-                                        for i in 0..row_count {
-                                            let j = i * 8;
-                                            let b = [
-                                                payload1[j],
-                                                payload1[j + 1],
-                                                payload1[j + 2],
-                                                payload1[j + 3],
-                                                payload1[j + 4],
-                                                payload1[j + 5],
-                                                payload1[j + 6],
-                                                payload1[j + 7],
-                                            ];
-                                            values[i] = i64::from_le_bytes(b);
-                                        }
-                                    }
-                                }
-                                FixedEncodingId::PgBeFixed => {
-                                    for i in 0..row_count {
-                                        let j = i * 8;
-                                        let b = [
-                                            payload1[j],
-                                            payload1[j + 1],
-                                            payload1[j + 2],
-                                            payload1[j + 3],
-                                            payload1[j + 4],
-                                            payload1[j + 5],
-                                            payload1[j + 6],
-                                            payload1[j + 7],
-                                        ];
-                                        values[i] = i64::from_be_bytes(b);
-                                    }
-                                }
-                            }
-                        }
-                        columns.push(ColumnData::FixedTimestampMicros { validity, values });
-                    }
-                    ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText => {
-                        return Err(Error::Other("invalid fixed type".to_string()));
-                    }
+impl ByteStreamSplit for u64 {
+    fn to_le_u64(self) -> u64 {
+        self
+    }
+}
+
+impl ByteStreamSplit for i64 {
+    fn to_le_u64(self) -> u64 {
+        self as u64
+    }
+}
+
+fn decode_fixed_into<T: FixedScalar>(
+    body: &ColumnBody<'_>,
+    row_count: usize,
+    enc: FixedEncodingId,
+    out: &mut Vec<T>,
+) -> Result<()> {
+    let payload = body.payload1;
+    let byte_len = checked_byte_len(row_count, T::SIZE)?;
+    if payload.len() != byte_len {
+        return Err(body.malformed(body.payload1_offset, "values length mismatch"));
+    }
+    out.clear();
+    out.resize(row_count, T::default());
+    match enc {
+        FixedEncodingId::PlainLe => {
+            #[cfg(target_endian = "little")]
+            {
+                let dst = unsafe {
+                    std::slice::from_raw_parts_mut(out.as_mut_ptr() as *mut u8, byte_len)
+                };
+                dst.copy_from_slice(payload);
+            }
+            #[cfg(not(target_endian = "little"))]
+            {
+                for (dst, src) in out.iter_mut().zip(payload.chunks_exact(T::SIZE)) {
+                    *dst = T::from_le_slice(src);
                 }
             }
         }
+        FixedEncodingId::PgBeFixed => {
+            for (dst, src) in out.iter_mut().zip(payload.chunks_exact(T::SIZE)) {
+                *dst = T::from_be_slice(src);
+            }
+        }
     }
+    Ok(())
+}
 
-    let schema = ColumnarSchema::new(fields)?;
-    ColumnarBatch::new(schema, row_count, columns)
+/// Reinterprets little-endian wire bytes as `&[T]` without copying.
+///
+/// Returns `None` when the slice is not aligned for `T` (or on big-endian targets); callers then
+/// fall back to a materializing decode.
+#[cfg(target_endian = "little")]
+fn borrow_le_slice<T: FixedScalar>(bytes: &[u8]) -> Option<&[T]> {
+    if bytes.len() % T::SIZE != 0 {
+        return None;
+    }
+    if bytes.as_ptr().align_offset(std::mem::align_of::<T>()) != 0 {
+        return None;
+    }
+    Some(unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const T, bytes.len() / T::SIZE) })
 }
 
-pub fn decode_mathldbt_v1_into(bytes: &[u8], out: &mut ColumnarBatch) -> Result<()> {
-    let mut ws = MathldbtV1DecodeWorkspace::default();
-    decode_mathldbt_v1_into_with_workspace(bytes, out, &mut ws)
+#[cfg(not(target_endian = "little"))]
+fn borrow_le_slice<T: FixedScalar>(_bytes: &[u8]) -> Option<&[T]> {
+    None
 }
 
-pub fn decode_mathldbt_v1_into_with_workspace(
-    bytes: &[u8],
-    out: &mut ColumnarBatch,
-    ws: &mut MathldbtV1DecodeWorkspace,
-) -> Result<()> {
-    let schema_err = || {
-        Error::Other("decode_mathldbt_v1_into requires matching schema".to_string())
+fn take<'a>(bytes: &'a [u8], pos: &mut usize, n: usize) -> Result<&'a [u8]> {
+    let truncated = Error::Truncated {
+        column: None,
+        offset: *pos,
+        needed: n,
     };
+    let end = pos.checked_add(n).ok_or(truncated.clone())?;
+    if end > bytes.len() {
+        return Err(truncated);
+    }
+    let slice = &bytes[*pos..end];
+    *pos = end;
+    Ok(slice)
+}
 
-    let mut pos = 0usize;
+fn read_u16_le(bytes: &[u8], pos: &mut usize) -> Result<u16> {
+    let b = take(bytes, pos, 2)?;
+    Ok(u16::from_le_bytes([b[0], b[1]]))
+}
 
-    fn take<'a>(bytes: &'a [u8], pos: &mut usize, n: usize) -> Result<&'a [u8]> {
-        let end = pos
-            .checked_add(n)
-            .ok_or_else(|| Error::Other("decode overflow".to_string()))?;
-        if end > bytes.len() {
-            return Err(Error::Other("truncated mathldbt".to_string()));
-        }
-        let slice = &bytes[*pos..end];
-        *pos = end;
-        Ok(slice)
-    }
+fn read_u32_le(bytes: &[u8], pos: &mut usize) -> Result<u32> {
+    let b = take(bytes, pos, 4)?;
+    Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
 
-    fn read_u16_le(bytes: &[u8], pos: &mut usize) -> Result<u16> {
-        let b = take(bytes, pos, 2)?;
-        Ok(u16::from_le_bytes([b[0], b[1]]))
-    }
+struct EnvelopeHeader {
+    flags: u16,
+    row_count: usize,
+    col_count: usize,
+}
 
-    fn read_u32_le(bytes: &[u8], pos: &mut usize) -> Result<u32> {
-        let b = take(bytes, pos, 4)?;
-        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+fn read_envelope_header(bytes: &[u8], pos: &mut usize) -> Result<EnvelopeHeader> {
+    let magic_offset = *pos;
+    let magic = take(bytes, pos, 8)?;
+    if magic != MAGIC {
+        return Err(Error::BadMagic {
+            offset: magic_offset,
+        });
     }
-
-    let magic = take(bytes, &mut pos, 8)?;
-    if magic != MAGIC {
-        return Err(Error::Other("invalid MATHLDBT magic".to_string()));
-    }
-    let version = read_u16_le(bytes, &mut pos)?;
+    let version_offset = *pos;
+    let version = read_u16_le(bytes, pos)?;
     if version != VERSION {
-        return Err(Error::Other(format!(
-            "unsupported MATHLDBT version: {version}"
-        )));
+        return Err(Error::UnsupportedVersion {
+            offset: version_offset,
+            version,
+        });
     }
-    let _flags = read_u16_le(bytes, &mut pos)?;
-    let row_count = read_u32_le(bytes, &mut pos)? as usize;
-    let col_count = read_u16_le(bytes, &mut pos)? as usize;
+    let flags = read_u16_le(bytes, pos)?;
+    let row_count = read_u32_le(bytes, pos)? as usize;
+    let col_count_offset = *pos;
+    let col_count = read_u16_le(bytes, pos)? as usize;
     if col_count == 0 {
-        return Err(Error::Other(
-            "MATHLDBT must have at least one column".to_string(),
-        ));
+        return Err(Error::Malformed {
+            column: None,
+            offset: col_count_offset,
+            reason: "MATHLDBT must have at least one column",
+        });
     }
 
-    let schema_id_len = read_u16_le(bytes, &mut pos)? as usize;
+    let schema_id_len = read_u16_le(bytes, pos)? as usize;
     if schema_id_len > 0 {
-        let _ = take(bytes, &mut pos, schema_id_len)?;
+        let _ = take(bytes, pos, schema_id_len)?;
     }
 
-    if col_count != out.schema.len() {
-        return Err(schema_err());
+    Ok(EnvelopeHeader {
+        flags,
+        row_count,
+        col_count,
+    })
+}
+
+struct ColumnHead<'a> {
+    column: usize,
+    ty: ColumnarType,
+    encoding_id: u16,
+    encoding_offset: usize,
+    name: Option<&'a str>,
+    /// The `Timestamp` time zone, borrowed from the descriptor for views.
+    tz: Option<&'a str>,
+    /// The `Struct` field names, borrowed likewise.
+    field_names: Vec<Option<&'a str>>,
+}
+
+impl ColumnHead<'_> {
+    fn unknown_encoding(&self) -> Error {
+        Error::UnknownEncoding {
+            column: self.column,
+            offset: self.encoding_offset,
+            encoding_id: self.encoding_id,
+        }
     }
-    if col_count != out.columns.len() {
-        return Err(schema_err());
+}
+
+/// Encoded length of a descriptor type, as written by `write_type`.
+fn type_len(ty: &ColumnarType) -> usize {
+    let params = match ty {
+        ColumnarType::Decimal { .. } => 2,
+        ColumnarType::Timestamp { tz, .. } => 3 + tz.as_deref().map_or(0, str::len),
+        ColumnarType::List(item) => type_len(item),
+        ColumnarType::FixedSizeBinary(_) => 4,
+        ColumnarType::FixedSizeList(item, _) => 4 + type_len(item),
+        ColumnarType::Struct(fields) => {
+            let field_len =
+                |f: &ColumnarField| 2 + f.name.as_deref().map_or(0, str::len) + type_len(&f.ty);
+            2 + fields.iter().map(field_len).sum::<usize>()
+        }
+        _ => 0,
+    };
+    2 + params
+}
+
+fn read_column_head<'a>(bytes: &'a [u8], pos: &mut usize, column: usize) -> Result<ColumnHead<'a>> {
+    let type_offset = *pos;
+    let ty = read_type(column, &mut |buf| {
+        let offset = *pos;
+        buf.copy_from_slice(take(bytes, pos, buf.len())?);
+        Ok(offset)
+    })?;
+    // `read_type` ends with the zone name, which it has already checked to be UTF-8.
+    let tz = match &ty {
+        ColumnarType::Timestamp { tz: Some(tz), .. } => {
+            std::str::from_utf8(&bytes[*pos - tz.len()..*pos]).ok()
+        }
+        _ => None,
+    };
+    // Each field is name_len u16 | name | type, after the type id and field count.
+    let field_names = match &ty {
+        ColumnarType::Struct(fields) => {
+            let mut at = type_offset + 4;
+            let mut names = Vec::with_capacity(fields.len());
+            for f in fields {
+                let len = f.name.as_deref().map_or(0, str::len);
+                let name = &bytes[at + 2..at + 2 + len];
+                names.push(f.name.as_ref().and_then(|_| std::str::from_utf8(name).ok()));
+                at += 2 + len + type_len(&f.ty);
+            }
+            names
+        }
+        _ => Vec::new(),
+    };
+    let encoding_offset = *pos;
+    let encoding_id = read_u16_le(bytes, pos)?;
+    let _col_flags = read_u16_le(bytes, pos)?;
+
+    let name_len = read_u16_le(bytes, pos)? as usize;
+    let name_offset = *pos;
+    let name_bytes = take(bytes, pos, name_len)?;
+    let name = if name_len == 0 {
+        None
+    } else {
+        Some(
+            std::str::from_utf8(name_bytes).map_err(|_| Error::Malformed {
+                column: Some(column),
+                offset: name_offset,
+                reason: "invalid UTF-8 column name",
+            })?,
+        )
+    };
+
+    Ok(ColumnHead {
+        column,
+        ty,
+        encoding_id,
+        encoding_offset,
+        name,
+        tz,
+        field_names,
+    })
+}
+
+/// Column buffers plus the absolute input offsets of each payload, for error reporting.
+struct ColumnBody<'a> {
+    column: usize,
+    row_count: usize,
+    validity: &'a [u8],
+    validity_offset: usize,
+    payload1: &'a [u8],
+    payload1_offset: usize,
+    payload2: &'a [u8],
+    payload2_offset: usize,
+    /// Nested descriptors that follow this one (the item column of a `List`, the field columns
+    /// of a `Struct`).
+    children: Vec<(ColumnHead<'a>, ColumnBody<'a>)>,
+}
+
+impl ColumnBody<'_> {
+    fn malformed(&self, offset: usize, reason: &'static str) -> Error {
+        Error::Malformed {
+            column: Some(self.column),
+            offset,
+            reason,
+        }
     }
 
-    let expected_validity = ceil_div_8(row_count)?;
-    let out_fields = out.schema.fields();
+    fn invalid_offsets(&self, offset: usize, reason: &'static str) -> Error {
+        Error::InvalidOffsets {
+            column: self.column,
+            offset,
+            reason,
+        }
+    }
+}
 
-    for col_idx in 0..col_count {
-        let tid = read_u16_le(bytes, &mut pos)?;
-        let ty = type_from_id(tid)?;
-        let encoding_id_u16 = read_u16_le(bytes, &mut pos)?;
-        let _col_flags = read_u16_le(bytes, &mut pos)?;
-
-        let name_len = read_u16_le(bytes, &mut pos)? as usize;
-        let name_bytes = take(bytes, &mut pos, name_len)?;
-        let name = if name_len == 0 {
-            None
-        } else {
-            Some(
-                std::str::from_utf8(name_bytes)
-                    .map_err(|_| Error::Other("invalid UTF-8 column name".to_string()))?,
-            )
-        };
+fn read_column_body<'a>(
+    bytes: &'a [u8],
+    pos: &mut usize,
+    head: &ColumnHead<'a>,
+    row_count: usize,
+) -> Result<ColumnBody<'a>> {
+    let column = head.column;
+    let validity_len_offset = *pos;
+    let validity_len = read_u32_le(bytes, pos)? as usize;
+    if validity_len != ceil_div_8(row_count)? {
+        return Err(Error::Malformed {
+            column: Some(column),
+            offset: validity_len_offset,
+            reason: "validity length mismatch",
+        });
+    }
+    let validity_offset = *pos;
+    let validity = take(bytes, pos, validity_len)?;
 
-        let out_field = &out_fields[col_idx];
-        if out_field.ty != ty {
-            return Err(schema_err());
+    let payload1_len = read_u32_le(bytes, pos)? as usize;
+    let payload1_offset = *pos;
+    let payload1 = take(bytes, pos, payload1_len)?;
+    let payload2_len = read_u32_le(bytes, pos)? as usize;
+    let payload2_offset = *pos;
+    let payload2 = take(bytes, pos, payload2_len)?;
+
+    let mut children = Vec::new();
+    if let ColumnarType::Struct(fields) = &head.ty {
+        // Every field column has the struct's row count.
+        for field in fields {
+            let child_offset = *pos;
+            let child_head = read_column_head(bytes, pos, column)?;
+            if child_head.ty != field.ty {
+                return Err(Error::Malformed {
+                    column: Some(column),
+                    offset: child_offset,
+                    reason: "struct field type mismatch",
+                });
+            }
+            let child_body = read_column_body(bytes, pos, &child_head, row_count)?;
+            children.push((child_head, child_body));
         }
-        if out_field.name.as_deref() != name {
-            return Err(schema_err());
+    }
+    if let ColumnarType::List(item) = &head.ty {
+        // The final offset sizes the item column; the other offsets are checked on decode.
+        let offsets_len = row_count
+            .checked_add(1)
+            .and_then(|n| n.checked_mul(4))
+            .ok_or(payload_too_large())?;
+        if payload1.len() != offsets_len {
+            return Err(Error::InvalidOffsets {
+                column,
+                offset: payload1_offset,
+                reason: "offsets length mismatch",
+            });
         }
+        let child_rows = u32::from_le_slice(&payload1[offsets_len - 4..]) as usize;
+        let child_offset = *pos;
+        let child_head = read_column_head(bytes, pos, column)?;
+        if child_head.ty != **item {
+            return Err(Error::Malformed {
+                column: Some(column),
+                offset: child_offset,
+                reason: "list item type mismatch",
+            });
+        }
+        let child_body = read_column_body(bytes, pos, &child_head, child_rows)?;
+        children.push((child_head, child_body));
+    }
 
-        let validity_len = read_u32_le(bytes, &mut pos)? as usize;
-        if validity_len != expected_validity {
-            return Err(Error::Other("validity length mismatch".to_string()));
+    Ok(ColumnBody {
+        column,
+        row_count,
+        validity,
+        validity_offset,
+        payload1,
+        payload1_offset,
+        payload2,
+        payload2_offset,
+        children,
+    })
+}
+
+fn schema_mismatch(column: Option<usize>) -> Error {
+    Error::Schema {
+        column,
+        reason: "decode_mathldbt_v1_into requires matching schema",
+    }
+}
+
+/// Checks plain offsets (stored in payload_1) against the length of the data they index:
+/// payload_2 for varlen columns, the item column for lists.
+fn check_plain_offsets(body: &ColumnBody<'_>, offsets: &[u32], data_len: usize) -> Result<()> {
+    let entry_offset = |i: usize| body.payload1_offset + i * 4;
+    if offsets.first().copied().unwrap_or(1) != 0 {
+        return Err(body.invalid_offsets(entry_offset(0), "offsets[0] must be 0"));
+    }
+    let mut prev = 0u32;
+    for (i, &o) in offsets.iter().enumerate() {
+        if o < prev {
+            return Err(body.invalid_offsets(entry_offset(i), "offsets must be non-decreasing"));
         }
-        let validity_src = take(bytes, &mut pos, validity_len)?;
+        prev = o;
+    }
+    if prev as usize != data_len {
+        return Err(body.invalid_offsets(entry_offset(offsets.len() - 1), "final offset mismatch"));
+    }
+    Ok(())
+}
 
-        let payload1_len = read_u32_le(bytes, &mut pos)? as usize;
-        let payload1 = take(bytes, &mut pos, payload1_len)?;
-        let payload2_len = read_u32_le(bytes, &mut pos)? as usize;
-        let payload2 = take(bytes, &mut pos, payload2_len)?;
+fn decode_plain_offsets_into(
+    body: &ColumnBody<'_>,
+    row_count: usize,
+    data_len: usize,
+    out: &mut Vec<u32>,
+) -> Result<()> {
+    let offsets_count = row_count.checked_add(1).ok_or(Error::LimitExceeded {
+        kind: LimitKind::RowCount,
+        column: Some(body.column),
+        offset: Some(body.payload1_offset),
+    })?;
+    if body.payload1.len() != checked_byte_len(offsets_count, 4)? {
+        return Err(body.invalid_offsets(body.payload1_offset, "offsets length mismatch"));
+    }
+    decode_fixed_into(body, offsets_count, FixedEncodingId::PlainLe, out)?;
+    check_plain_offsets(body, out, data_len)
+}
 
-        let out_col = &mut out.columns[col_idx];
+/// Checks a `BitPackedBool` payload_1: exactly `ceil(row_count / 8)` bytes with zero padding.
+fn check_bit_packed_bool(body: &ColumnBody<'_>, row_count: usize) -> Result<()> {
+    let payload = body.payload1;
+    if payload.len() != ceil_div_8(row_count)? {
+        return Err(body.malformed(body.payload1_offset, "values length mismatch"));
+    }
+    let rem = row_count % 8;
+    if rem != 0 && payload.last().is_some_and(|&b| b >> rem != 0) {
+        return Err(body.malformed(body.payload1_offset, "bit-packed padding must be zero"));
+    }
+    Ok(())
+}
 
-        match ty {
-            ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText => {
-                let (col_ty, validity, offsets, data) = match out_col {
-                    ColumnData::Var {
-                        ty,
-                        validity,
-                        offsets,
-                        data,
-                    } => (*ty, validity, offsets, data),
-                    _ => return Err(schema_err()),
-                };
-                if col_ty != ty {
-                    return Err(schema_err());
-                }
-                validity.bytes.clear();
-                validity.bytes.extend_from_slice(validity_src);
-
-                match encoding_id_u16 {
-                    ENC_PLAIN => {
-                        let expected_offsets_len = (row_count + 1)
-                            .checked_mul(4)
-                            .ok_or_else(|| Error::Other("offsets overflow".to_string()))?;
-                        if payload1.len() != expected_offsets_len {
-                            return Err(Error::Other("offsets length mismatch".to_string()));
-                        }
+/// Decodes a `Bool` column into either in-memory shape, expanding or packing the bits when the
+/// wire encoding differs.
+fn decode_bool_into(
+    head: &ColumnHead<'_>,
+    row_count: usize,
+    body: &ColumnBody<'_>,
+    out_col: &mut ColumnData,
+) -> Result<()> {
+    let packed = head.encoding_id == ENC_BIT_PACKED_BOOL;
+    let enc = if packed {
+        check_bit_packed_bool(body, row_count)?;
+        FixedEncodingId::PlainLe
+    } else {
+        FixedEncodingId::from_u16(head.encoding_id).ok_or_else(|| head.unknown_encoding())?
+    };
+    if !body.payload2.is_empty() {
+        return Err(body.malformed(body.payload2_offset, "fixed-width payload_2 must be empty"));
+    }
+    let keep = matches!(out_col, ColumnData::BitPackedBool { .. });
+    let (validity, values) = match out_col {
+        ColumnData::FixedBool { validity, values }
+        | ColumnData::BitPackedBool { validity, values } => (validity, values),
+        _ => return Err(schema_mismatch(Some(head.column))),
+    };
+    validity.bytes.clear();
+    validity.bytes.extend_from_slice(body.validity);
 
-                        offsets.clear();
-                        offsets.resize(row_count + 1, 0u32);
-                        #[cfg(target_endian = "little")]
-                        {
-                            let dst = unsafe {
-                                std::slice::from_raw_parts_mut(
-                                    offsets.as_mut_ptr() as *mut u8,
-                                    expected_offsets_len,
-                                )
-                            };
-                            dst.copy_from_slice(payload1);
-                        }
-                        #[cfg(not(target_endian = "little"))]
-                        {
-                            for i in 0..(row_count + 1) {
-                                let j = i * 4;
-                                offsets[i] = u32::from_le_bytes([
-                                    payload1[j],
-                                    payload1[j + 1],
-                                    payload1[j + 2],
-                                    payload1[j + 3],
-                                ]);
-                            }
-                        }
+    let payload = body.payload1;
+    match (packed, keep) {
+        (true, true) => {
+            values.clear();
+            values.extend_from_slice(payload);
+        }
+        (true, false) => {
+            values.clear();
+            values.extend((0..row_count).map(|i| (payload[i / 8] >> (i % 8)) & 1));
+        }
+        (false, true) => {
+            if payload.len() != row_count {
+                return Err(body.malformed(body.payload1_offset, "values length mismatch"));
+            }
+            values.clear();
+            values.extend(pack_bool_bytes(payload));
+        }
+        (false, false) => decode_fixed_into(body, row_count, enc, values)?,
+    }
+    Ok(())
+}
 
-                        let mut prev = 0u32;
-                        for &o in offsets.iter() {
-                            if o < prev {
-                                return Err(Error::Other(
-                                    "offsets must be non-decreasing".to_string(),
-                                ));
-                            }
-                            prev = o;
-                        }
-                        let final_off = offsets.last().copied().unwrap_or(0);
-                        let data_len_u32: u32 = payload2
-                            .len()
-                            .try_into()
-                            .map_err(|_| Error::Other("data too large".to_string()))?;
-                        if final_off != data_len_u32 {
-                            return Err(Error::Other("final offset mismatch".to_string()));
-                        }
+/// Expands the runs in payload_1 (varint run length | `width` value bytes) into `out`, passing
+/// each run value and its absolute offset to `check`.
+fn expand_runs(
+    body: &ColumnBody<'_>,
+    row_count: usize,
+    width: usize,
+    out: &mut Vec<u8>,
+    mut check: impl FnMut(usize, &[u8]) -> Result<()>,
+) -> Result<()> {
+    let payload = body.payload1;
+    let at = body.payload1_offset;
+    out.clear();
+    out.reserve(checked_byte_len(row_count, width)?);
+    let (mut pos, mut rows) = (0usize, 0usize);
+    while rows < row_count {
+        if pos == payload.len() {
+            return Err(body.malformed(at + pos, "RLE payload truncated"));
+        }
+        let run_at = at + pos;
+        let run_len = read_u64_varint(payload, &mut pos).map_err(|r| body.malformed(run_at, r))?;
+        let run_len = match usize::try_from(run_len) {
+            Ok(n) if n > 0 && n <= row_count - rows => n,
+            _ => return Err(body.malformed(run_at, "invalid RLE run length")),
+        };
+        let Some(value) = payload.get(pos..pos + width) else {
+            return Err(body.malformed(at + pos, "RLE payload truncated"));
+        };
+        check(at + pos, value)?;
+        for _ in 0..run_len {
+            out.extend_from_slice(value);
+        }
+        pos += width;
+        rows += run_len;
+    }
+    if pos != payload.len() {
+        return Err(body.malformed(at + pos, "trailing bytes in RLE payload"));
+    }
+    Ok(())
+}
 
-                        data.clear();
-                        data.extend_from_slice(payload2);
-                    }
-                    ENC_DICT_UTF8 => {
-                        if ty == ColumnarType::Bytes {
-                            return Err(Error::Other(
-                                "DictUtf8 is not supported for Bytes".to_string(),
-                            ));
-                        }
-                        decode_dict_utf8_to_var_col(
-                            ws,
-                            row_count,
-                            validity_src,
-                            payload1,
-                            payload2,
-                            offsets,
-                            data,
-                        )?;
-                    }
-                    _ => {
-                        return Err(Error::Other(
-                            "invalid encoding for varlen column".to_string(),
-                        ));
-                    }
+/// Decodes an `Rle` or `Constant` column by expanding it to the layout it was built from
+/// (plain values, or `Dict` indices for varlen columns) and decoding that.
+fn decode_run_length_into(
+    dicts: &mut DictDecodeState,
+    head: &ColumnHead<'_>,
+    row_count: usize,
+    body: &ColumnBody<'_>,
+    out_col: &mut ColumnData,
+) -> Result<()> {
+    let constant = head.encoding_id == ENC_CONSTANT;
+    let mut payload1 = Vec::new();
+    let mut blob = Vec::new();
+    let encoding_id = match head.ty {
+        ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText if constant => {
+            if !body.payload2.is_empty() {
+                return Err(
+                    body.malformed(body.payload2_offset, "constant payload_2 must be empty")
+                );
+            }
+            // A one-entry dictionary that every row points to.
+            let len = u32::try_from(body.payload1.len()).map_err(|_| payload_too_large())?;
+            blob.push(1);
+            blob.extend_from_slice(&1u32.to_le_bytes());
+            blob.extend_from_slice(&0u32.to_le_bytes());
+            blob.extend_from_slice(&len.to_le_bytes());
+            blob.extend_from_slice(body.payload1);
+            payload1.resize(row_count, 0);
+            ENC_DICT
+        }
+        ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText => {
+            let (index_width, _) = read_dict_blob(
+                body,
+                body.payload2,
+                body.payload2_offset,
+                &mut dicts.offsets,
+            )?;
+            let dict_count = dicts.offsets.len() - 1;
+            expand_runs(body, row_count, index_width, &mut payload1, |at, value| {
+                if dict_index(value, index_width, 0) < dict_count {
+                    Ok(())
+                } else {
+                    Err(body.malformed(at, "dict index out of bounds"))
                 }
+            })?;
+            ENC_DICT
+        }
+        _ => {
+            let Some(width) = run_length_value_width(&head.ty) else {
+                return Err(head.unknown_encoding());
+            };
+            if !body.payload2.is_empty() {
+                return Err(
+                    body.malformed(body.payload2_offset, "fixed-width payload_2 must be empty")
+                );
             }
-            _ => {
-                if encoding_id_u16 == ENC_DELTA_VARINT_I64
-                    && !(ty == ColumnarType::I64 || ty == ColumnarType::TimestampTzMicros)
-                {
-                    return Err(Error::Other(
-                        "invalid encoding for fixed column".to_string(),
-                    ));
+            if !constant {
+                expand_runs(body, row_count, width, &mut payload1, |_, _| Ok(()))?;
+            } else if body.payload1.len() == width {
+                payload1 = body.payload1.repeat(row_count);
+            } else {
+                return Err(body.malformed(body.payload1_offset, "values length mismatch"));
+            }
+            ENC_PLAIN
+        }
+    };
+    // Only the constant varlen case builds a dictionary; the others keep payload_2 as is.
+    let payload2 = if blob.is_empty() {
+        body.payload2
+    } else {
+        blob.as_slice()
+    };
+
+    let expanded_head = ColumnHead {
+        column: head.column,
+        ty: head.ty.clone(),
+        encoding_id,
+        encoding_offset: head.encoding_offset,
+        name: head.name,
+        tz: head.tz,
+        field_names: Vec::new(),
+    };
+    let expanded_body = ColumnBody {
+        column: body.column,
+        row_count: body.row_count,
+        validity: body.validity,
+        validity_offset: body.validity_offset,
+        payload1: &payload1,
+        payload1_offset: body.payload1_offset,
+        payload2,
+        payload2_offset: body.payload2_offset,
+        children: Vec::new(),
+    };
+    decode_column_into(dicts, &expanded_head, row_count, &expanded_body, out_col)
+}
+
+fn decode_column_into(
+    dicts: &mut DictDecodeState,
+    head: &ColumnHead<'_>,
+    row_count: usize,
+    body: &ColumnBody<'_>,
+    out_col: &mut ColumnData,
+) -> Result<()> {
+    let ty = &head.ty;
+    let encoding_id = head.encoding_id;
+    if matches!(encoding_id, ENC_RLE | ENC_CONSTANT) {
+        return decode_run_length_into(dicts, head, row_count, body, out_col);
+    }
+
+    match ty {
+        ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText => {
+            let (validity, offsets, data) = match out_col {
+                ColumnData::Var {
+                    ty: col_ty,
+                    validity,
+                    offsets,
+                    data,
+                } if col_ty == ty => (validity, offsets, data),
+                _ => return Err(schema_mismatch(Some(head.column))),
+            };
+            validity.bytes.clear();
+            validity.bytes.extend_from_slice(body.validity);
+
+            match encoding_id {
+                ENC_PLAIN => {
+                    decode_plain_offsets_into(body, row_count, body.payload2.len(), offsets)?;
+                    data.clear();
+                    data.extend_from_slice(body.payload2);
                 }
-                let enc = if encoding_id_u16 == ENC_DELTA_VARINT_I64 {
-                    FixedEncodingId::PlainLe
-                } else {
-                    FixedEncodingId::from_u16(encoding_id_u16).ok_or_else(|| {
-                        Error::Other("invalid encoding for fixed column".to_string())
-                    })?
-                };
-                if payload2_len != 0 {
-                    return Err(Error::Other(
-                        "fixed-width payload_2 must be empty".to_string(),
-                    ));
+                ENC_DICT => {
+                    decode_dict_to_var_col(dicts, body, row_count, offsets, data)?;
                 }
+                ENC_DICT_DELTA => {
+                    decode_dict_delta_to_var_col(dicts, body, row_count, offsets, data)?;
+                }
+                _ => return Err(head.unknown_encoding()),
+            }
+        }
+        ColumnarType::List(_) => {
+            let ColumnData::List {
+                validity,
+                offsets,
+                child,
+            } = out_col
+            else {
+                return Err(schema_mismatch(Some(head.column)));
+            };
+            if encoding_id != ENC_PLAIN {
+                return Err(head.unknown_encoding());
+            }
+            if !body.payload2.is_empty() {
+                return Err(body.malformed(body.payload2_offset, "list payload_2 must be empty"));
+            }
+            let [(child_head, child_body)] = body.children.as_slice() else {
+                return Err(Error::Internal("list without item column"));
+            };
+            validity.bytes.clear();
+            validity.bytes.extend_from_slice(body.validity);
+            decode_plain_offsets_into(body, row_count, child_body.row_count, offsets)?;
+            decode_column_into(dicts, child_head, child_body.row_count, child_body, child)?;
+        }
+        ColumnarType::Bool => decode_bool_into(head, row_count, body, out_col)?,
+        ColumnarType::Struct(_) => {
+            let ColumnData::Struct {
+                validity, children, ..
+            } = out_col
+            else {
+                return Err(schema_mismatch(Some(head.column)));
+            };
+            if children.len() != body.children.len() {
+                return Err(schema_mismatch(Some(head.column)));
+            }
+            if encoding_id != ENC_PLAIN {
+                return Err(head.unknown_encoding());
+            }
+            if !body.payload1.is_empty() || !body.payload2.is_empty() {
+                return Err(body.malformed(body.payload1_offset, "struct payloads must be empty"));
+            }
+            validity.bytes.clear();
+            validity.bytes.extend_from_slice(body.validity);
+            for ((child_head, child_body), child) in body.children.iter().zip(children) {
+                decode_column_into(dicts, child_head, row_count, child_body, child)?;
+            }
+        }
+        _ => {
+            let is_delta = matches!(
+                encoding_id,
+                ENC_DELTA_VARINT_I64 | ENC_NULLABLE_DELTA_VARINT_I64
+            );
+            if is_delta
+                && !matches!(
+                    ty,
+                    ColumnarType::I64
+                        | ColumnarType::U64
+                        | ColumnarType::TimestampTzMicros
+                        | ColumnarType::Timestamp { .. }
+                )
+            {
+                return Err(head.unknown_encoding());
+            }
+            if encoding_id == ENC_FOR_BIT_PACKED
+                && !matches!(
+                    ty,
+                    ColumnarType::I16 | ColumnarType::I32 | ColumnarType::I64
+                )
+            {
+                return Err(head.unknown_encoding());
+            }
+            if encoding_id == ENC_XOR_FLOAT && !matches!(ty, ColumnarType::F32 | ColumnarType::F64)
+            {
+                return Err(head.unknown_encoding());
+            }
+            if encoding_id == ENC_BYTE_STREAM_SPLIT
+                && !matches!(
+                    ty,
+                    ColumnarType::F32 | ColumnarType::F64 | ColumnarType::I64
+                )
+            {
+                return Err(head.unknown_encoding());
+            }
+            let enc = if is_delta
+                || matches!(
+                    encoding_id,
+                    ENC_FOR_BIT_PACKED | ENC_XOR_FLOAT | ENC_BYTE_STREAM_SPLIT
+                ) {
+                FixedEncodingId::PlainLe
+            } else {
+                FixedEncodingId::from_u16(encoding_id).ok_or_else(|| head.unknown_encoding())?
+            };
+            if !body.payload2.is_empty() {
+                return Err(
+                    body.malformed(body.payload2_offset, "fixed-width payload_2 must be empty")
+                );
+            }
 
-                match ty {
-                    ColumnarType::Bool => {
-                        let (validity, values) = match out_col {
-                            ColumnData::FixedBool { validity, values } => (validity, values),
-                            _ => return Err(schema_err()),
-                        };
-                        validity.bytes.clear();
-                        validity.bytes.extend_from_slice(validity_src);
-                        if payload1.len() != row_count {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        values.clear();
-                        values.extend_from_slice(payload1);
-                    }
-                    ColumnarType::I16 => {
-                        let (validity, values) = match out_col {
-                            ColumnData::FixedI16 { validity, values } => (validity, values),
-                            _ => return Err(schema_err()),
-                        };
-                        validity.bytes.clear();
-                        validity.bytes.extend_from_slice(validity_src);
-                        if payload1.len() != row_count * 2 {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        values.clear();
-                        values.resize(row_count, 0i16);
-                        match enc {
-                            FixedEncodingId::PlainLe => {
-                                #[cfg(target_endian = "little")]
-                                {
-                                    let dst = unsafe {
-                                        std::slice::from_raw_parts_mut(
-                                            values.as_mut_ptr() as *mut u8,
-                                            row_count * 2,
-                                        )
-                                    };
-                                    dst.copy_from_slice(payload1);
-                                }
-                                #[cfg(not(target_endian = "little"))]
-                                {
-                                    for i in 0..row_count {
-                                        let j = i * 2;
-                                        let b = [payload1[j], payload1[j + 1]];
-                                        values[i] = i16::from_le_bytes(b);
-                                    }
-                                }
-                            }
-                            FixedEncodingId::PgBeFixed => {
-                                for i in 0..row_count {
-                                    let j = i * 2;
-                                    let b = [payload1[j], payload1[j + 1]];
-                                    values[i] = i16::from_be_bytes(b);
-                                }
-                            }
-                        }
-                    }
-                    ColumnarType::I32 => {
-                        let (validity, values) = match out_col {
-                            ColumnData::FixedI32 { validity, values } => (validity, values),
-                            _ => return Err(schema_err()),
-                        };
-                        validity.bytes.clear();
-                        validity.bytes.extend_from_slice(validity_src);
-                        if payload1.len() != row_count * 4 {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        values.clear();
-                        values.resize(row_count, 0i32);
-                        match enc {
-                            FixedEncodingId::PlainLe => {
-                                #[cfg(target_endian = "little")]
-                                {
-                                    let dst = unsafe {
-                                        std::slice::from_raw_parts_mut(
-                                            values.as_mut_ptr() as *mut u8,
-                                            row_count * 4,
-                                        )
-                                    };
-                                    dst.copy_from_slice(payload1);
-                                }
-                                #[cfg(not(target_endian = "little"))]
-                                {
-                                    for i in 0..row_count {
-                                        let j = i * 4;
-                                        let b = [
-                                            payload1[j],
-                                            payload1[j + 1],
-                                            payload1[j + 2],
-                                            payload1[j + 3],
-                                        ];
-                                        values[i] = i32::from_le_bytes(b);
-                                    }
-                                }
-                            }
-                            FixedEncodingId::PgBeFixed => {
-                                for i in 0..row_count {
-                                    let j = i * 4;
-                                    let b = [
-                                        payload1[j],
-                                        payload1[j + 1],
-                                        payload1[j + 2],
-                                        payload1[j + 3],
-                                    ];
-                                    values[i] = i32::from_be_bytes(b);
-                                }
-                            }
-                        }
-                    }
-                    ColumnarType::I64 => {
-                        let (validity, values) = match out_col {
-                            ColumnData::FixedI64 { validity, values } => (validity, values),
-                            _ => return Err(schema_err()),
-                        };
-                        validity.bytes.clear();
-                        validity.bytes.extend_from_slice(validity_src);
-                        values.clear();
-                        values.resize(row_count, 0i64);
-                        if encoding_id_u16 == ENC_DELTA_VARINT_I64 {
-                            decode_delta_varint_i64_from_payload(
-                                payload1,
-                                row_count,
-                                values.as_mut_slice(),
-                            )?;
-                        } else {
-                            if payload1.len() != row_count * 8 {
-                                return Err(Error::Other("values length mismatch".to_string()));
-                            }
-                            match enc {
-                                FixedEncodingId::PlainLe => {
-                                    #[cfg(target_endian = "little")]
-                                    {
-                                        let dst = unsafe {
-                                            std::slice::from_raw_parts_mut(
-                                                values.as_mut_ptr() as *mut u8,
-                                                row_count * 8,
-                                            )
-                                        };
-                                        dst.copy_from_slice(payload1);
-                                    }
-                                    #[cfg(not(target_endian = "little"))]
-                                    {
-                                        for i in 0..row_count {
-                                            let j = i * 8;
-                                            let b = [
-                                                payload1[j],
-                                                payload1[j + 1],
-                                                payload1[j + 2],
-                                                payload1[j + 3],
-                                                payload1[j + 4],
-                                                payload1[j + 5],
-                                                payload1[j + 6],
-                                                payload1[j + 7],
-                                            ];
-                                            values[i] = i64::from_le_bytes(b);
-                                        }
-                                    }
-                                }
-                                FixedEncodingId::PgBeFixed => {
-                                    for i in 0..row_count {
-                                        let j = i * 8;
-                                        let b = [
-                                            payload1[j],
-                                            payload1[j + 1],
-                                            payload1[j + 2],
-                                            payload1[j + 3],
-                                            payload1[j + 4],
-                                            payload1[j + 5],
-                                            payload1[j + 6],
-                                            payload1[j + 7],
-                                        ];
-                                        values[i] = i64::from_be_bytes(b);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    ColumnarType::F32 => {
-                        let (validity, values) = match out_col {
-                            ColumnData::FixedF32Bits { validity, values } => (validity, values),
-                            _ => return Err(schema_err()),
-                        };
-                        validity.bytes.clear();
-                        validity.bytes.extend_from_slice(validity_src);
-                        if payload1.len() != row_count * 4 {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        values.clear();
-                        values.resize(row_count, 0u32);
-                        match enc {
-                            FixedEncodingId::PlainLe => {
-                                #[cfg(target_endian = "little")]
-                                {
-                                    let dst = unsafe {
-                                        std::slice::from_raw_parts_mut(
-                                            values.as_mut_ptr() as *mut u8,
-                                            row_count * 4,
-                                        )
-                                    };
-                                    dst.copy_from_slice(payload1);
-                                }
-                                #[cfg(not(target_endian = "little"))]
-                                {
-                                    for i in 0..row_count {
-                                        let j = i * 4;
-                                        let b = [
-                                            payload1[j],
-                                            payload1[j + 1],
-                                            payload1[j + 2],
-                                            payload1[j + 3],
-                                        ];
-                                        values[i] = u32::from_le_bytes(b);
-                                    }
-                                }
-                            }
-                            FixedEncodingId::PgBeFixed => {
-                                for i in 0..row_count {
-                                    let j = i * 4;
-                                    let b = [
-                                        payload1[j],
-                                        payload1[j + 1],
-                                        payload1[j + 2],
-                                        payload1[j + 3],
-                                    ];
-                                    values[i] = u32::from_be_bytes(b);
-                                }
-                            }
-                        }
+            match (ty, out_col) {
+                (ColumnarType::U8, ColumnData::FixedU8 { validity, values }) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    decode_fixed_into(body, row_count, enc, values)?;
+                }
+                (ColumnarType::I16, ColumnData::FixedI16 { validity, values }) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    if encoding_id == ENC_FOR_BIT_PACKED {
+                        decode_for_bit_packed_into(body, row_count, values)?;
+                    } else {
+                        decode_fixed_into(body, row_count, enc, values)?;
                     }
-                    ColumnarType::F64 => {
-                        let (validity, values) = match out_col {
-                            ColumnData::FixedF64Bits { validity, values } => (validity, values),
-                            _ => return Err(schema_err()),
-                        };
-                        validity.bytes.clear();
-                        validity.bytes.extend_from_slice(validity_src);
-                        if payload1.len() != row_count * 8 {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
+                }
+                (ColumnarType::I8, ColumnData::FixedI8 { validity, values }) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    decode_fixed_into(body, row_count, enc, values)?;
+                }
+                (ColumnarType::U16, ColumnData::FixedU16 { validity, values }) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    decode_fixed_into(body, row_count, enc, values)?;
+                }
+                (ColumnarType::U64, ColumnData::FixedU64 { validity, values }) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    if is_delta {
                         values.clear();
                         values.resize(row_count, 0u64);
-                        match enc {
-                            FixedEncodingId::PlainLe => {
-                                #[cfg(target_endian = "little")]
-                                {
-                                    let dst = unsafe {
-                                        std::slice::from_raw_parts_mut(
-                                            values.as_mut_ptr() as *mut u8,
-                                            row_count * 8,
-                                        )
-                                    };
-                                    dst.copy_from_slice(payload1);
-                                }
-                                #[cfg(not(target_endian = "little"))]
-                                {
-                                    for i in 0..row_count {
-                                        let j = i * 8;
-                                        let b = [
-                                            payload1[j],
-                                            payload1[j + 1],
-                                            payload1[j + 2],
-                                            payload1[j + 3],
-                                            payload1[j + 4],
-                                            payload1[j + 5],
-                                            payload1[j + 6],
-                                            payload1[j + 7],
-                                        ];
-                                        values[i] = u64::from_le_bytes(b);
-                                    }
-                                }
-                            }
-                            FixedEncodingId::PgBeFixed => {
-                                for i in 0..row_count {
-                                    let j = i * 8;
-                                    let b = [
-                                        payload1[j],
-                                        payload1[j + 1],
-                                        payload1[j + 2],
-                                        payload1[j + 3],
-                                        payload1[j + 4],
-                                        payload1[j + 5],
-                                        payload1[j + 6],
-                                        payload1[j + 7],
-                                    ];
-                                    values[i] = u64::from_be_bytes(b);
-                                }
-                            }
-                        }
+                        decode_delta_varint_i64_from_payload(
+                            body,
+                            row_count,
+                            encoding_id == ENC_NULLABLE_DELTA_VARINT_I64,
+                            u64_as_i64_mut(values.as_mut_slice()),
+                        )?;
+                    } else {
+                        decode_fixed_into(body, row_count, enc, values)?;
                     }
-                    ColumnarType::Uuid => {
-                        let (validity, values) = match out_col {
-                            ColumnData::FixedUuid { validity, values } => (validity, values),
-                            _ => return Err(schema_err()),
-                        };
-                        validity.bytes.clear();
-                        validity.bytes.extend_from_slice(validity_src);
-                        if payload1.len() != row_count * 16 {
-                            return Err(Error::Other("values length mismatch".to_string()));
-                        }
-                        values.clear();
-                        values.resize(row_count, [0u8; 16]);
-                        let dst = unsafe {
-                            std::slice::from_raw_parts_mut(
-                                values.as_mut_ptr() as *mut u8,
-                                row_count * 16,
-                            )
-                        };
-                        dst.copy_from_slice(payload1);
+                }
+                (ColumnarType::I32, ColumnData::FixedI32 { validity, values })
+                | (ColumnarType::Date, ColumnData::FixedDate32 { validity, values }) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    if encoding_id == ENC_FOR_BIT_PACKED {
+                        decode_for_bit_packed_into(body, row_count, values)?;
+                    } else {
+                        decode_fixed_into(body, row_count, enc, values)?;
                     }
-                    ColumnarType::TimestampTzMicros => {
-                        let (validity, values) = match out_col {
-                            ColumnData::FixedTimestampMicros { validity, values } => {
-                                (validity, values)
-                            }
-                            _ => return Err(schema_err()),
-                        };
-                        validity.bytes.clear();
-                        validity.bytes.extend_from_slice(validity_src);
+                }
+                (ColumnarType::I64, ColumnData::FixedI64 { validity, values })
+                | (
+                    ColumnarType::TimestampTzMicros,
+                    ColumnData::FixedTimestampMicros { validity, values },
+                )
+                | (
+                    ColumnarType::Timestamp { .. },
+                    ColumnData::FixedTimestamp {
+                        validity, values, ..
+                    },
+                )
+                | (ColumnarType::TimeMicros, ColumnData::FixedTimeMicros { validity, values }) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    if is_delta {
                         values.clear();
                         values.resize(row_count, 0i64);
-                        if encoding_id_u16 == ENC_DELTA_VARINT_I64 {
-                            decode_delta_varint_i64_from_payload(
-                                payload1,
-                                row_count,
-                                values.as_mut_slice(),
-                            )?;
-                        } else {
-                            if payload1.len() != row_count * 8 {
-                                return Err(Error::Other("values length mismatch".to_string()));
-                            }
-                            match enc {
-                                FixedEncodingId::PlainLe => {
-                                    #[cfg(target_endian = "little")]
-                                    {
-                                        let dst = unsafe {
-                                            std::slice::from_raw_parts_mut(
-                                                values.as_mut_ptr() as *mut u8,
-                                                row_count * 8,
-                                            )
-                                        };
-                                        dst.copy_from_slice(payload1);
-                                    }
-                                    #[cfg(not(target_endian = "little"))]
-                                    {
-                                        for i in 0..row_count {
-                                            let j = i * 8;
-                                            let b = [
-                                                payload1[j],
-                                                payload1[j + 1],
-                                                payload1[j + 2],
-                                                payload1[j + 3],
-                                                payload1[j + 4],
-                                                payload1[j + 5],
-                                                payload1[j + 6],
-                                                payload1[j + 7],
-                                            ];
-                                            values[i] = i64::from_le_bytes(b);
-                                        }
-                                    }
-                                }
-                                FixedEncodingId::PgBeFixed => {
-                                    for i in 0..row_count {
-                                        let j = i * 8;
-                                        let b = [
-                                            payload1[j],
-                                            payload1[j + 1],
-                                            payload1[j + 2],
-                                            payload1[j + 3],
-                                            payload1[j + 4],
-                                            payload1[j + 5],
-                                            payload1[j + 6],
-                                            payload1[j + 7],
-                                        ];
-                                        values[i] = i64::from_be_bytes(b);
-                                    }
-                                }
-                            }
-                        }
+                        decode_delta_varint_i64_from_payload(
+                            body,
+                            row_count,
+                            encoding_id == ENC_NULLABLE_DELTA_VARINT_I64,
+                            values.as_mut_slice(),
+                        )?;
+                    } else if encoding_id == ENC_FOR_BIT_PACKED {
+                        decode_for_bit_packed_into(body, row_count, values)?;
+                    } else if encoding_id == ENC_BYTE_STREAM_SPLIT {
+                        decode_byte_stream_split_into(body, row_count, values)?;
+                    } else {
+                        decode_fixed_into(body, row_count, enc, values)?;
+                    }
+                }
+                (ColumnarType::F32, ColumnData::FixedF32Bits { validity, values })
+                | (ColumnarType::U32, ColumnData::FixedU32 { validity, values }) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    if encoding_id == ENC_XOR_FLOAT {
+                        decode_xor_float_into(body, row_count, values)?;
+                    } else if encoding_id == ENC_BYTE_STREAM_SPLIT {
+                        decode_byte_stream_split_into(body, row_count, values)?;
+                    } else {
+                        decode_fixed_into(body, row_count, enc, values)?;
                     }
-                    ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText => {
-                        return Err(Error::Other("invalid fixed type".to_string()));
+                }
+                (ColumnarType::F64, ColumnData::FixedF64Bits { validity, values }) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    if encoding_id == ENC_XOR_FLOAT {
+                        decode_xor_float_into(body, row_count, values)?;
+                    } else if encoding_id == ENC_BYTE_STREAM_SPLIT {
+                        decode_byte_stream_split_into(body, row_count, values)?;
+                    } else {
+                        decode_fixed_into(body, row_count, enc, values)?;
                     }
                 }
+                (ColumnarType::Uuid, ColumnData::FixedUuid { validity, values }) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    decode_fixed_into(body, row_count, enc, values)?;
+                }
+                (
+                    ColumnarType::IntervalMonthDayMicros,
+                    ColumnData::FixedInterval { validity, values },
+                ) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    decode_fixed_into(body, row_count, enc, values)?;
+                }
+                (
+                    ColumnarType::Decimal { .. },
+                    ColumnData::FixedDecimal128 {
+                        validity, values, ..
+                    },
+                ) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    decode_fixed_into(body, row_count, enc, values)?;
+                }
+                (
+                    ColumnarType::FixedSizeBinary(size),
+                    ColumnData::FixedSizeBinary {
+                        validity, values, ..
+                    },
+                ) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    decode_fixed_into(body, fixed_size_len(row_count, *size)?, enc, values)?;
+                }
+                (
+                    ColumnarType::FixedSizeList(_, size),
+                    ColumnData::FixedSizeListF32Bits {
+                        validity, values, ..
+                    },
+                ) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    decode_fixed_into(body, fixed_size_len(row_count, *size)?, enc, values)?;
+                }
+                (
+                    ColumnarType::FixedSizeList(_, size),
+                    ColumnData::FixedSizeListF64Bits {
+                        validity, values, ..
+                    },
+                ) => {
+                    validity.bytes.clear();
+                    validity.bytes.extend_from_slice(body.validity);
+                    decode_fixed_into(body, fixed_size_len(row_count, *size)?, enc, values)?;
+                }
+                (ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText, _) => {
+                    return Err(Error::Internal("invalid fixed type"));
+                }
+                _ => return Err(schema_mismatch(Some(head.column))),
             }
         }
     }
+    Ok(())
+}
+
+fn borrow_fixed<T: FixedScalar>(payload: &[u8], row_count: usize) -> Option<&[T]> {
+    if payload.len() != row_count.checked_mul(T::SIZE)? {
+        return None;
+    }
+    borrow_le_slice(payload)
+}
+
+fn borrow_fixed_view<'a>(
+    head: &ColumnHead<'a>,
+    validity: &'a [u8],
+    payload: &'a [u8],
+    row_count: usize,
+) -> Option<ColumnDataView<'a>> {
+    match head.ty {
+        ColumnarType::Bool => Some(ColumnDataView::FixedBool {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::I16 => Some(ColumnDataView::FixedI16 {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::I32 => Some(ColumnDataView::FixedI32 {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::I64 => Some(ColumnDataView::FixedI64 {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::I8 => Some(ColumnDataView::FixedI8 {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::U8 => Some(ColumnDataView::FixedU8 {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::U16 => Some(ColumnDataView::FixedU16 {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::U32 => Some(ColumnDataView::FixedU32 {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::U64 => Some(ColumnDataView::FixedU64 {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::F32 => Some(ColumnDataView::FixedF32Bits {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::F64 => Some(ColumnDataView::FixedF64Bits {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::Uuid => Some(ColumnDataView::FixedUuid {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::TimestampTzMicros => Some(ColumnDataView::FixedTimestampMicros {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::Timestamp { unit, .. } => Some(ColumnDataView::FixedTimestamp {
+            unit,
+            tz: head.tz,
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::Date => Some(ColumnDataView::FixedDate32 {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::TimeMicros => Some(ColumnDataView::FixedTimeMicros {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::IntervalMonthDayMicros => Some(ColumnDataView::FixedInterval {
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::Decimal { precision, scale } => Some(ColumnDataView::FixedDecimal128 {
+            precision,
+            scale,
+            validity,
+            values: borrow_fixed(payload, row_count)?,
+        }),
+        ColumnarType::FixedSizeBinary(size) => Some(ColumnDataView::FixedSizeBinary {
+            size,
+            validity,
+            values: borrow_fixed(payload, row_count.checked_mul(size as usize)?)?,
+        }),
+        ColumnarType::FixedSizeList(ref item, size) if **item == ColumnarType::F32 => {
+            Some(ColumnDataView::FixedSizeListF32Bits {
+                size,
+                validity,
+                values: borrow_fixed(payload, row_count.checked_mul(size as usize)?)?,
+            })
+        }
+        ColumnarType::FixedSizeList(_, size) => Some(ColumnDataView::FixedSizeListF64Bits {
+            size,
+            validity,
+            values: borrow_fixed(payload, row_count.checked_mul(size as usize)?)?,
+        }),
+        ColumnarType::Utf8
+        | ColumnarType::Bytes
+        | ColumnarType::JsonbText
+        | ColumnarType::List(_)
+        | ColumnarType::Struct(_) => None,
+    }
+}
+
+/// Returns a zero-copy view of a plain-encoded column, or `None` if the column has to be
+/// materialized (non-plain encoding, misaligned payload, or malformed lengths, which the
+/// materializing decode then reports). Borrowed varlen offsets are checked here.
+fn borrow_column_view<'a>(
+    head: &ColumnHead<'a>,
+    row_count: usize,
+    body: &ColumnBody<'a>,
+) -> Result<Option<ColumnDataView<'a>>> {
+    let (encoding_id, validity) = (head.encoding_id, body.validity);
+    match head.ty {
+        ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText => {
+            if encoding_id != ENC_PLAIN {
+                return Ok(None);
+            }
+            let Some(offsets) = row_count
+                .checked_add(1)
+                .and_then(|n| borrow_fixed::<u32>(body.payload1, n))
+            else {
+                return Ok(None);
+            };
+            check_plain_offsets(body, offsets, body.payload2.len())?;
+            Ok(Some(ColumnDataView::Var {
+                ty: head.ty.clone(),
+                validity,
+                offsets,
+                data: VarDataView::Contiguous(body.payload2),
+            }))
+        }
+        ColumnarType::List(_) => {
+            let [(child_head, child_body)] = body.children.as_slice() else {
+                return Ok(None);
+            };
+            if encoding_id != ENC_PLAIN || !body.payload2.is_empty() {
+                return Ok(None);
+            }
+            let Some(offsets) = row_count
+                .checked_add(1)
+                .and_then(|n| borrow_fixed::<u32>(body.payload1, n))
+            else {
+                return Ok(None);
+            };
+            let Some(child) = borrow_column_view(child_head, child_body.row_count, child_body)?
+            else {
+                return Ok(None);
+            };
+            check_plain_offsets(body, offsets, child_body.row_count)?;
+            Ok(Some(ColumnDataView::List {
+                validity,
+                offsets,
+                child: Box::new(child),
+            }))
+        }
+        ColumnarType::Struct(_) => {
+            if encoding_id != ENC_PLAIN || !body.payload1.is_empty() || !body.payload2.is_empty() {
+                return Ok(None);
+            }
+            let mut children = Vec::with_capacity(body.children.len());
+            for (child_head, child_body) in &body.children {
+                match borrow_column_view(child_head, row_count, child_body)? {
+                    Some(child) => children.push(child),
+                    None => return Ok(None),
+                }
+            }
+            Ok(Some(ColumnDataView::Struct {
+                names: head.field_names.clone(),
+                validity,
+                children,
+            }))
+        }
+        ColumnarType::Bool if encoding_id == ENC_BIT_PACKED_BOOL => {
+            if !body.payload2.is_empty() {
+                return Ok(None);
+            }
+            check_bit_packed_bool(body, row_count)?;
+            Ok(Some(ColumnDataView::BitPackedBool {
+                validity,
+                values: body.payload1,
+            }))
+        }
+        _ => {
+            if encoding_id != FixedEncodingId::PlainLe as u16 || !body.payload2.is_empty() {
+                return Ok(None);
+            }
+            Ok(borrow_fixed_view(head, validity, body.payload1, row_count))
+        }
+    }
+}
+
+pub fn decode_mathldbt_v1(bytes: &[u8]) -> Result<ColumnarBatch> {
+    let mut ws = MathldbtV1DecodeWorkspace::default();
+    decode_mathldbt_v1_with_workspace(bytes, &mut ws)
+}
+
+/// Swaps empty `FixedBool` columns, nested ones included, for `BitPackedBool`.
+fn pack_bool_columns(col: &mut ColumnData) {
+    match col {
+        ColumnData::FixedBool { .. } => {
+            *col = ColumnData::BitPackedBool {
+                validity: ValidityBitmap { bytes: Vec::new() },
+                values: Vec::new(),
+            }
+        }
+        ColumnData::List { child, .. } => pack_bool_columns(child),
+        ColumnData::Struct { children, .. } => children.iter_mut().for_each(pack_bool_columns),
+        _ => {}
+    }
+}
+
+/// Empty destination column for `ty`, with `Bool` columns bit-packed when
+/// `keep_bit_packed_bool` is set.
+fn new_decoded_column(ty: &ColumnarType, keep_bit_packed_bool: bool) -> Result<ColumnData> {
+    let mut col = ColumnData::new_all_invalid(ty.clone(), 0)?;
+    if keep_bit_packed_bool {
+        pack_bool_columns(&mut col);
+    }
+    Ok(col)
+}
+
+pub fn decode_mathldbt_v1_with_workspace(
+    bytes: &[u8],
+    ws: &mut MathldbtV1DecodeWorkspace,
+) -> Result<ColumnarBatch> {
+    ws.dicts.discard_pending();
+    let mut pos = 0usize;
+    let header = read_envelope_header(bytes, &mut pos)?;
+    let row_count = header.row_count;
+    let col_count = header.col_count;
+
+    let mut fields: Vec<ColumnarField> = Vec::with_capacity(col_count);
+    let mut columns: Vec<ColumnData> = Vec::with_capacity(col_count);
+
+    for col_idx in 0..col_count {
+        let head = read_column_head(bytes, &mut pos, col_idx).map_err(|e| e.at_column(col_idx))?;
+        let body = read_column_body(bytes, &mut pos, &head, row_count)
+            .map_err(|e| e.at_column(col_idx))?;
+
+        let mut col = new_decoded_column(&head.ty, ws.keep_bit_packed_bool)?;
+        decode_column_into(&mut ws.dicts, &head, row_count, &body, &mut col)
+            .map_err(|e| e.at_column(col_idx))?;
+
+        fields.push(ColumnarField {
+            name: head.name.map(str::to_string),
+            ty: head.ty,
+        });
+        columns.push(col);
+    }
+
+    let schema = ColumnarSchema::new(fields)?;
+    let batch = ColumnarBatch::new(schema, row_count, columns)?;
+    ws.dicts.commit_pending();
+    Ok(batch)
+}
+
+pub fn decode_mathldbt_v1_into(bytes: &[u8], out: &mut ColumnarBatch) -> Result<()> {
+    let mut ws = MathldbtV1DecodeWorkspace::default();
+    decode_mathldbt_v1_into_with_workspace(bytes, out, &mut ws)
+}
+
+pub fn decode_mathldbt_v1_into_with_workspace(
+    bytes: &[u8],
+    out: &mut ColumnarBatch,
+    ws: &mut MathldbtV1DecodeWorkspace,
+) -> Result<()> {
+    ws.dicts.discard_pending();
+    let mut pos = 0usize;
+    let header = read_envelope_header(bytes, &mut pos)?;
+    let row_count = header.row_count;
+    let col_count = header.col_count;
+
+    if col_count != out.schema.len() {
+        return Err(schema_mismatch(None));
+    }
+    if col_count != out.columns.len() {
+        return Err(schema_mismatch(None));
+    }
+
+
+    for (col_idx, (out_field, out_col)) in out
+        .schema
+        .fields()
+        .iter()
+        .zip(out.columns.iter_mut())
+        .enumerate()
+    {
+        let head = read_column_head(bytes, &mut pos, col_idx).map_err(|e| e.at_column(col_idx))?;
+        if out_field.ty != head.ty {
+            return Err(schema_mismatch(Some(col_idx)));
+        }
+        if out_field.name.as_deref() != head.name {
+            return Err(schema_mismatch(Some(col_idx)));
+        }
+
+        let body = read_column_body(bytes, &mut pos, &head, row_count)
+            .map_err(|e| e.at_column(col_idx))?;
+        decode_column_into(&mut ws.dicts, &head, row_count, &body, out_col)
+            .map_err(|e| e.at_column(col_idx))?;
+    }
 
     out.row_count = row_count;
     out.validate()?;
+    ws.dicts.commit_pending();
     Ok(())
 }
+
+/// Selects a column of an encoded batch, by position or by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnSelector<'a> {
+    Index(usize),
+    /// The first column with this name.
+    Name(&'a str),
+}
+
+impl From<usize> for ColumnSelector<'_> {
+    fn from(idx: usize) -> Self {
+        ColumnSelector::Index(idx)
+    }
+}
+
+impl<'a> From<&'a str> for ColumnSelector<'a> {
+    fn from(name: &'a str) -> Self {
+        ColumnSelector::Name(name)
+    }
+}
+
+pub fn decode_mathldbt_v1_projected(
+    bytes: &[u8],
+    projection: &[ColumnSelector<'_>],
+) -> Result<ColumnarBatch> {
+    let mut ws = MathldbtV1DecodeWorkspace::default();
+    decode_mathldbt_v1_projected_with_workspace(bytes, projection, &mut ws)
+}
+
+/// Decodes only the selected columns, in `projection` order.
+///
+/// Every descriptor is still parsed and its payload lengths bounds-checked, but payloads of
+/// unselected columns are skipped without being decoded or validated, apart from `DictDelta`
+/// dictionaries, which are applied to `ws`.
+pub fn decode_mathldbt_v1_projected_with_workspace(
+    bytes: &[u8],
+    projection: &[ColumnSelector<'_>],
+    ws: &mut MathldbtV1DecodeWorkspace,
+) -> Result<ColumnarBatch> {
+    ws.dicts.discard_pending();
+    let mut pos = 0usize;
+    let header = read_envelope_header(bytes, &mut pos)?;
+    let row_count = header.row_count;
+    let col_count = header.col_count;
+
+    let mut parsed = Vec::with_capacity(col_count);
+    for col_idx in 0..col_count {
+        let head = read_column_head(bytes, &mut pos, col_idx).map_err(|e| e.at_column(col_idx))?;
+        let body = read_column_body(bytes, &mut pos, &head, row_count)
+            .map_err(|e| e.at_column(col_idx))?;
+        parsed.push((head, body));
+    }
+
+    let mut selected = Vec::with_capacity(projection.len());
+    for sel in projection {
+        let idx = match *sel {
+            ColumnSelector::Index(idx) => {
+                Some(idx)
+                    .filter(|&idx| idx < parsed.len())
+                    .ok_or(Error::Schema {
+                        column: Some(idx),
+                        reason: "projected column index out of range",
+                    })?
+            }
+            ColumnSelector::Name(name) => parsed
+                .iter()
+                .position(|(head, _)| head.name == Some(name))
+                .ok_or(Error::Schema {
+                    column: None,
+                    reason: "projected column name not found",
+                })?,
+        };
+        selected.push(idx);
+    }
+
+    // Delta dictionaries of unselected columns are still applied, so later batches decode.
+    for (idx, (head, body)) in parsed.iter().enumerate() {
+        if head.encoding_id == ENC_DICT_DELTA
+            && matches!(
+                head.ty,
+                ColumnarType::Utf8 | ColumnarType::Bytes | ColumnarType::JsonbText
+            )
+            && !selected.contains(&idx)
+        {
+            apply_dict_delta(&mut ws.dicts, body, row_count)?;
+        }
+    }
+
+    let mut fields: Vec<ColumnarField> = Vec::with_capacity(projection.len());
+    let mut columns: Vec<ColumnData> = Vec::with_capacity(projection.len());
+    for (i, &idx) in selected.iter().enumerate() {
+        let (head, body) = &parsed[idx];
+        // A column selected twice is decoded once, so its dictionary delta is applied once.
+        let col = match selected[..i].iter().position(|&prev| prev == idx) {
+            Some(prev) => columns[prev].clone(),
+            None => {
+                let mut col = new_decoded_column(&head.ty, ws.keep_bit_packed_bool)?;
+                decode_column_into(&mut ws.dicts, head, row_count, body, &mut col)
+                    .map_err(|e| e.at_column(head.column))?;
+                col
+            }
+        };
+        fields.push(ColumnarField {
+            name: head.name.map(str::to_string),
+            ty: head.ty.clone(),
+        });
+        columns.push(col);
+    }
+
+    let schema = ColumnarSchema::new(fields)?;
+    let batch = ColumnarBatch::new(schema, row_count, columns)?;
+    ws.dicts.commit_pending();
+    Ok(batch)
+}
+
+/// Envelope metadata returned by [`inspect_mathldbt_v1`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvelopeInfo {
+    pub version: u16,
+    pub flags: u16,
+    pub row_count: usize,
+    pub schema: ColumnarSchema,
+    pub columns: Vec<ColumnInfo>,
+    /// Bytes consumed by the envelope (header plus all column descriptors).
+    pub encoded_len: usize,
+}
+
+/// Per-column descriptor metadata. Ranges are byte offsets into the inspected buffer, so
+/// `validity.len()` etc. are the stored lengths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnInfo {
+    /// Raw encoding id as stored; not checked against the column type.
+    pub encoding_id: u16,
+    pub validity: Range<usize>,
+    pub payload1: Range<usize>,
+    pub payload2: Range<usize>,
+    /// Nested descriptors: the item column of a `List` or the field columns of a `Struct`.
+    pub children: Vec<ColumnInfo>,
+}
+
+fn column_info(head: &ColumnHead<'_>, body: &ColumnBody<'_>) -> ColumnInfo {
+    ColumnInfo {
+        encoding_id: head.encoding_id,
+        validity: body.validity_offset..body.validity_offset + body.validity.len(),
+        payload1: body.payload1_offset..body.payload1_offset + body.payload1.len(),
+        payload2: body.payload2_offset..body.payload2_offset + body.payload2.len(),
+        children: body
+            .children
+            .iter()
+            .map(|(head, body)| column_info(head, body))
+            .collect(),
+    }
+}
+
+/// Walks the header and column descriptors without decoding any column data.
+///
+/// Structural errors (truncation, bad magic/version, unknown type ids, validity length
+/// mismatches) are reported as in `decode_mathldbt_v1`; payload contents are not validated.
+pub fn inspect_mathldbt_v1(bytes: &[u8]) -> Result<EnvelopeInfo> {
+    let mut pos = 0usize;
+    let header = read_envelope_header(bytes, &mut pos)?;
+    let row_count = header.row_count;
+    let col_count = header.col_count;
+
+    let mut fields: Vec<ColumnarField> = Vec::with_capacity(col_count);
+    let mut columns: Vec<ColumnInfo> = Vec::with_capacity(col_count);
+    for col_idx in 0..col_count {
+        let head = read_column_head(bytes, &mut pos, col_idx).map_err(|e| e.at_column(col_idx))?;
+        let body = read_column_body(bytes, &mut pos, &head, row_count)
+            .map_err(|e| e.at_column(col_idx))?;
+
+        columns.push(column_info(&head, &body));
+        fields.push(ColumnarField {
+            name: head.name.map(str::to_string),
+            ty: head.ty,
+        });
+    }
+
+    Ok(EnvelopeInfo {
+        version: VERSION,
+        flags: header.flags,
+        row_count,
+        schema: ColumnarSchema::new(fields)?,
+        columns,
+        encoded_len: pos,
+    })
+}
+
+/// A decoded batch whose column buffers borrow from the encoded input where possible.
+///
+/// Plain-encoded columns whose payloads are suitably aligned reference the input bytes directly;
+/// all other columns (Dict, DictDelta, the delta encodings, ForBitPacked, XorFloat,
+/// ByteStreamSplit, Rle, Constant, PgBeFixed, misaligned payloads) are materialized into the
+/// `MathldbtV1DecodeWorkspace` arena the view was decoded with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MathldbtV1DecodedView<'a> {
+    schema: ColumnarSchema,
+    row_count: usize,
+    columns: Vec<ColumnDataView<'a>>,
+}
+
+impl<'a> MathldbtV1DecodedView<'a> {
+    pub fn view(&self) -> ColumnarBatchView<'_> {
+        ColumnarBatchView {
+            schema: &self.schema,
+            row_count: self.row_count,
+            columns: self.columns.as_slice(),
+        }
+    }
+
+    pub fn schema(&self) -> &ColumnarSchema {
+        &self.schema
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn columns(&self) -> &[ColumnDataView<'a>] {
+        self.columns.as_slice()
+    }
+
+    pub fn to_batch(&self) -> Result<ColumnarBatch> {
+        let mut columns = Vec::with_capacity(self.columns.len());
+        for col in &self.columns {
+            columns.push(col.to_column_data()?);
+        }
+        ColumnarBatch::new(self.schema.clone(), self.row_count, columns)
+    }
+}
+
+pub fn decode_mathldbt_v1_view_with_workspace<'a>(
+    bytes: &'a [u8],
+    ws: &'a mut MathldbtV1DecodeWorkspace,
+) -> Result<MathldbtV1DecodedView<'a>> {
+    ws.dicts.discard_pending();
+    let mut pos = 0usize;
+    let header = read_envelope_header(bytes, &mut pos)?;
+    let row_count = header.row_count;
+    let col_count = header.col_count;
+
+    let mut fields: Vec<ColumnarField> = Vec::with_capacity(col_count);
+    let mut borrowed: Vec<Option<ColumnDataView<'a>>> = Vec::with_capacity(col_count);
+
+    while ws.view_arena.len() < col_count {
+        ws.view_arena
+            .push(ColumnData::new_all_invalid(ColumnarType::Bool, 0)?);
+    }
+
+    for col_idx in 0..col_count {
+        let head = read_column_head(bytes, &mut pos, col_idx).map_err(|e| e.at_column(col_idx))?;
+        let body = read_column_body(bytes, &mut pos, &head, row_count)
+            .map_err(|e| e.at_column(col_idx))?;
+
+        match borrow_column_view(&head, row_count, &body)? {
+            Some(col) => borrowed.push(Some(col)),
+            None => {
+                let slot = &mut ws.view_arena[col_idx];
+                if slot.ty() != head.ty {
+                    *slot = new_decoded_column(&head.ty, ws.keep_bit_packed_bool)?;
+                }
+                decode_column_into(&mut ws.dicts, &head, row_count, &body, slot)
+                    .map_err(|e| e.at_column(col_idx))?;
+                borrowed.push(None);
+            }
+        }
+
+        fields.push(ColumnarField {
+            name: head.name.map(str::to_string),
+            ty: head.ty,
+        });
+    }
+
+    let arena: &'a [ColumnData] = &ws.view_arena;
+    let columns: Vec<ColumnDataView<'a>> = borrowed
+        .into_iter()
+        .zip(arena)
+        .map(|(b, arena_col)| b.unwrap_or_else(|| arena_col.as_view()))
+        .collect();
+
+    let decoded = MathldbtV1DecodedView {
+        schema: ColumnarSchema::new(fields)?,
+        row_count,
+        columns,
+    };
+    decoded.view().validate()?;
+    ws.dicts.commit_pending();
+    Ok(decoded)
+}
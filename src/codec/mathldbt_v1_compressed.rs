@@ -1,10 +1,11 @@
 use crate::batch::ColumnarBatch;
 use crate::codec::mathldbt_v1::{
-    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1_into_with_workspace,
-    decode_mathldbt_v1_with_workspace, encode_mathldbt_v1_fast_path_into_with_workspace,
-    encode_mathldbt_v1_into_with_workspace,
+    ENC_BYTE_STREAM_SPLIT, MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace,
+    decode_mathldbt_v1_into_with_workspace, decode_mathldbt_v1_with_workspace,
+    encode_mathldbt_v1_fast_path_into_uncommitted, encode_mathldbt_v1_into_uncommitted,
+    inspect_mathldbt_v1,
 };
-use crate::{Error, Result};
+use crate::{Error, LimitKind, Result};
 use crate::batch_view::ColumnarBatchView;
 
 #[cfg(any(feature = "compression-zstd", feature = "compression-gzip"))]
@@ -54,6 +55,12 @@ impl fmt::Debug for ZstdBulkDecodeCtx {
 
 pub struct MathldbtV1CompressedEncodeWorkspace {
     plain: Vec<u8>,
+    /// The envelope with `ByteStreamSplit` tried on every candidate column.
+    trial: Vec<u8>,
+    /// Compressed column payloads, only measured.
+    scratch: Vec<u8>,
+    split_columns: Vec<usize>,
+    auto_byte_stream_split: bool,
     #[cfg(feature = "compression-zstd")]
     zstd: ZstdBulkEncodeCtx,
 }
@@ -62,6 +69,10 @@ impl Default for MathldbtV1CompressedEncodeWorkspace {
     fn default() -> Self {
         Self {
             plain: Vec::new(),
+            trial: Vec::new(),
+            scratch: Vec::new(),
+            split_columns: Vec::new(),
+            auto_byte_stream_split: true,
             #[cfg(feature = "compression-zstd")]
             zstd: ZstdBulkEncodeCtx::default(),
         }
@@ -72,39 +83,171 @@ impl Clone for MathldbtV1CompressedEncodeWorkspace {
     fn clone(&self) -> Self {
         Self {
             plain: self.plain.clone(),
+            trial: Vec::new(),
+            scratch: Vec::new(),
+            split_columns: Vec::new(),
+            auto_byte_stream_split: self.auto_byte_stream_split,
             #[cfg(feature = "compression-zstd")]
             zstd: ZstdBulkEncodeCtx::default(),
         }
     }
 }
 
+impl MathldbtV1CompressedEncodeWorkspace {
+    /// With zstd or gzip, tries `ByteStreamSplit` on each top-level `F32`, `F64` and `I64`
+    /// column the codec workspace leaves unset (see
+    /// [`MathldbtV1EncodeWorkspace::set_column_byte_stream_split`]) and no smaller encoding
+    /// takes, and keeps it where its payload compresses smaller than the column's payload
+    /// without it. This encodes the batch up to three times. Decoders that predate encoding
+    /// id 9 cannot read split columns. On by default.
+    pub fn set_auto_byte_stream_split(&mut self, enabled: bool) -> &mut Self {
+        self.auto_byte_stream_split = enabled;
+        self
+    }
+
+    /// Encodes the envelope into `self.plain` with `encode`, choosing `ByteStreamSplit` per
+    /// column as described in [`Self::set_auto_byte_stream_split`].
+    fn encode_plain(
+        &mut self,
+        c: Compression,
+        col_count: usize,
+        codec_ws: &mut MathldbtV1EncodeWorkspace,
+        mut encode: impl FnMut(&mut Vec<u8>, &mut MathldbtV1EncodeWorkspace) -> Result<()>,
+    ) -> Result<()> {
+        self.plain.clear();
+        let mut candidates = std::mem::take(&mut self.split_columns);
+        candidates.clear();
+        if self.auto_byte_stream_split && c != Compression::None {
+            candidates.extend((0..col_count).filter(|&i| codec_ws.byte_stream_split_unset(i)));
+        }
+        if candidates.is_empty() {
+            self.split_columns = candidates;
+            return encode(&mut self.plain, codec_ws);
+        }
+
+        // The envelope that is emitted is encoded last, so that its dictionary entries are
+        // the pending ones.
+        let mut trial = std::mem::take(&mut self.trial);
+        let mut result = codec_ws
+            .with_byte_stream_split(&candidates, |codec_ws| encode(&mut trial, codec_ws))
+            .and_then(|()| encode(&mut self.plain, codec_ws))
+            .and_then(|()| self.keep_smaller_splits(&mut candidates, &trial, c));
+        if result.is_ok() && !candidates.is_empty() {
+            result = codec_ws
+                .with_byte_stream_split(&candidates, |codec_ws| encode(&mut self.plain, codec_ws));
+        }
+        self.trial = trial;
+        self.split_columns = candidates;
+        result
+    }
+
+    /// Keeps in `candidates` the columns that are `ByteStreamSplit` in `trial` and whose
+    /// payload there compresses smaller than in `self.plain`.
+    fn keep_smaller_splits(
+        &mut self,
+        candidates: &mut Vec<usize>,
+        trial: &[u8],
+        c: Compression,
+    ) -> Result<()> {
+        let split_info = inspect_mathldbt_v1(trial)?;
+        let plain_info = inspect_mathldbt_v1(&self.plain)?;
+        let plain = std::mem::take(&mut self.plain);
+        let mut scratch = std::mem::take(&mut self.scratch);
+        let mut kept = 0;
+        let mut result = Ok(());
+        for j in 0..candidates.len() {
+            let i = candidates[j];
+            let split = &split_info.columns[i];
+            if split.encoding_id != ENC_BYTE_STREAM_SPLIT {
+                continue;
+            }
+            let smaller = self.compresses_smaller(
+                &mut scratch,
+                &trial[split.payload1.clone()],
+                &plain[plain_info.columns[i].payload1.clone()],
+                c,
+            );
+            match smaller {
+                Ok(true) => {
+                    candidates[kept] = i;
+                    kept += 1;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        candidates.truncate(kept);
+        self.plain = plain;
+        self.scratch = scratch;
+        result
+    }
+
+    /// Whether `a` compresses strictly smaller than `b`.
+    fn compresses_smaller(
+        &mut self,
+        scratch: &mut Vec<u8>,
+        a: &[u8],
+        b: &[u8],
+        c: Compression,
+    ) -> Result<bool> {
+        self.compress_into(scratch, a, c)?;
+        let a_len = scratch.len();
+        self.compress_into(scratch, b, c)?;
+        Ok(a_len < scratch.len())
+    }
+
+    /// Compresses the envelope in `self.plain` into `out`.
+    fn compress_plain_into(&mut self, out: &mut Vec<u8>, c: Compression) -> Result<()> {
+        let plain = std::mem::take(&mut self.plain);
+        let result = self.compress_into(out, &plain, c);
+        self.plain = plain;
+        result
+    }
+
+    /// Compresses `plain` into `out`.
+    fn compress_into(&mut self, out: &mut Vec<u8>, plain: &[u8], c: Compression) -> Result<()> {
+        match c {
+            Compression::None => {
+                compress_none_into(out, plain);
+                Ok(())
+            }
+            Compression::Zstd { level } => {
+                #[cfg(feature = "compression-zstd")]
+                {
+                    compress_zstd_into(out, plain, level, &mut self.zstd)
+                }
+                #[cfg(not(feature = "compression-zstd"))]
+                {
+                    compress_zstd_into(out, plain, level)
+                }
+            }
+            Compression::Gzip { level } => compress_gzip_into(out, plain, level),
+        }
+    }
+}
+
 impl fmt::Debug for MathldbtV1CompressedEncodeWorkspace {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut d = f.debug_struct("MathldbtV1CompressedEncodeWorkspace");
         d.field("plain_len", &self.plain.len())
-            .field("plain_cap", &self.plain.capacity());
+            .field("plain_cap", &self.plain.capacity())
+            .field("auto_byte_stream_split", &self.auto_byte_stream_split);
         #[cfg(feature = "compression-zstd")]
         d.field("zstd", &self.zstd);
         d.finish()
     }
 }
 
+#[derive(Default)]
 pub struct MathldbtV1CompressedDecodeWorkspace {
     plain: Vec<u8>,
     #[cfg(feature = "compression-zstd")]
     zstd: ZstdBulkDecodeCtx,
 }
 
-impl Default for MathldbtV1CompressedDecodeWorkspace {
-    fn default() -> Self {
-        Self {
-            plain: Vec::new(),
-            #[cfg(feature = "compression-zstd")]
-            zstd: ZstdBulkDecodeCtx::default(),
-        }
-    }
-}
-
 impl Clone for MathldbtV1CompressedDecodeWorkspace {
     fn clone(&self) -> Self {
         Self {
@@ -126,6 +269,33 @@ impl fmt::Debug for MathldbtV1CompressedDecodeWorkspace {
     }
 }
 
+/// Decompression errors point at the start of the compressed input.
+fn decompressed_too_large() -> Error {
+    Error::LimitExceeded {
+        kind: LimitKind::DecompressedLength,
+        column: None,
+        offset: Some(0),
+    }
+}
+
+#[cfg(any(feature = "compression-zstd", feature = "compression-gzip"))]
+fn compress_error(message: impl ToString) -> Error {
+    Error::Compression {
+        column: None,
+        offset: None,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(any(feature = "compression-zstd", feature = "compression-gzip"))]
+fn decompress_error(message: impl ToString) -> Error {
+    Error::Compression {
+        column: None,
+        offset: Some(0),
+        message: message.to_string(),
+    }
+}
+
 fn compress_none_into(out: &mut Vec<u8>, plain: &[u8]) {
     out.clear();
     out.extend_from_slice(plain);
@@ -139,31 +309,26 @@ fn compress_zstd_into(
     zstd_ctx: &mut ZstdBulkEncodeCtx,
 ) -> Result<()> {
     if !(-7..=22).contains(&level) {
-        return Err(Error::Other("invalid zstd level".to_string()));
+        return Err(compress_error("invalid zstd level"));
     }
     out.clear();
     let bound = zstd::zstd_safe::compress_bound(plain.len());
-    out.try_reserve(bound)
-        .map_err(|e| Error::Other(e.to_string()))?;
+    out.try_reserve(bound).map_err(compress_error)?;
 
     let level_changed = zstd_ctx.level != Some(level);
     if zstd_ctx.compressor.is_none() {
-        zstd_ctx.compressor = Some(
-            zstd::bulk::Compressor::new(level).map_err(|e| Error::Other(e.to_string()))?,
-        );
+        zstd_ctx.compressor = Some(zstd::bulk::Compressor::new(level).map_err(compress_error)?);
         zstd_ctx.level = Some(level);
     } else if level_changed {
         let mut compressor = match zstd_ctx.compressor.take() {
             Some(c) => c,
             None => {
-                return Err(Error::Other(
-                    "internal error: zstd compressor not initialized".to_string(),
-                ));
+                return Err(Error::Internal("zstd compressor not initialized"));
             }
         };
         compressor
             .set_compression_level(level)
-            .map_err(|e| Error::Other(e.to_string()))?;
+            .map_err(compress_error)?;
         zstd_ctx.compressor = Some(compressor);
         zstd_ctx.level = Some(level);
     }
@@ -171,14 +336,12 @@ fn compress_zstd_into(
     let compressor = match zstd_ctx.compressor.as_mut() {
         Some(c) => c,
         None => {
-            return Err(Error::Other(
-                "internal error: zstd compressor not initialized".to_string(),
-            ));
+            return Err(Error::Internal("zstd compressor not initialized"));
         }
     };
     let _written = compressor
         .compress_to_buffer(plain, out)
-        .map_err(|e| Error::Other(e.to_string()))?;
+        .map_err(compress_error)?;
     Ok(())
 }
 
@@ -188,31 +351,30 @@ fn compress_zstd_into(
     _plain: &[u8],
     _level: i32,
 ) -> Result<()> {
-    Err(Error::Other(
-        "zstd compression feature not enabled".to_string(),
-    ))
+    Err(Error::FeatureDisabled {
+        feature: "compression-zstd",
+    })
 }
 
 #[cfg(feature = "compression-gzip")]
 fn compress_gzip_into(out: &mut Vec<u8>, plain: &[u8], level: u32) -> Result<()> {
     if level > 9 {
-        return Err(Error::Other("invalid gzip level".to_string()));
+        return Err(compress_error("invalid gzip level"));
     }
     out.clear();
     let mut enc = flate2::GzBuilder::new()
         .mtime(0)
         .write(out, flate2::Compression::new(level));
-    enc.write_all(plain)
-        .map_err(|e| Error::Other(e.to_string()))?;
-    let _ = enc.finish().map_err(|e| Error::Other(e.to_string()))?;
+    enc.write_all(plain).map_err(compress_error)?;
+    let _ = enc.finish().map_err(compress_error)?;
     Ok(())
 }
 
 #[cfg(not(feature = "compression-gzip"))]
 fn compress_gzip_into(_out: &mut Vec<u8>, _plain: &[u8], _level: u32) -> Result<()> {
-    Err(Error::Other(
-        "gzip compression feature not enabled".to_string(),
-    ))
+    Err(Error::FeatureDisabled {
+        feature: "compression-gzip",
+    })
 }
 
 #[cfg(any(feature = "compression-zstd", feature = "compression-gzip"))]
@@ -227,21 +389,15 @@ fn decode_with_max_bound<R: Read>(
         match reader.read(&mut tmp) {
             Ok(0) => return Ok(()),
             Ok(_) => {
-                return Err(Error::Other(
-                    "decompressed payload exceeds max_uncompressed_len".to_string(),
-                ));
+                return Err(decompressed_too_large());
             }
-            Err(e) => return Err(Error::Other(e.to_string())),
+            Err(e) => return Err(decompress_error(e)),
         }
     }
     let mut limited = reader.take((max_uncompressed_len as u64) + 1);
-    limited
-        .read_to_end(out)
-        .map_err(|e| Error::Other(e.to_string()))?;
+    limited.read_to_end(out).map_err(decompress_error)?;
     if out.len() > max_uncompressed_len {
-        return Err(Error::Other(
-            "decompressed payload exceeds max_uncompressed_len".to_string(),
-        ));
+        return Err(decompressed_too_large());
     }
     Ok(())
 }
@@ -260,44 +416,35 @@ fn decompress_zstd_into(
     };
 
     if let Some(size_u64) = size_u64 {
-        let size_usize = usize::try_from(size_u64).map_err(|_| {
-            Error::Other("decompressed payload exceeds max_uncompressed_len".to_string())
-        })?;
+        let size_usize = usize::try_from(size_u64).map_err(|_| decompressed_too_large())?;
         if size_usize > max_uncompressed_len {
-            return Err(Error::Other(
-                "decompressed payload exceeds max_uncompressed_len".to_string(),
-            ));
+            return Err(decompressed_too_large());
         }
 
         out.clear();
-        out.try_reserve(size_usize)
-            .map_err(|e| Error::Other(e.to_string()))?;
+        out.try_reserve(size_usize).map_err(decompress_error)?;
 
         if zstd_ctx.decompressor.is_none() {
             zstd_ctx.decompressor =
-                Some(zstd::bulk::Decompressor::new().map_err(|e| Error::Other(e.to_string()))?);
+                Some(zstd::bulk::Decompressor::new().map_err(decompress_error)?);
         }
         let decompressor = match zstd_ctx.decompressor.as_mut() {
             Some(d) => d,
             None => {
-                return Err(Error::Other(
-                    "internal error: zstd decompressor not initialized".to_string(),
-                ));
+                return Err(Error::Internal("zstd decompressor not initialized"));
             }
         };
 
         let _written = decompressor
             .decompress_to_buffer(bytes, out)
-            .map_err(|e| Error::Other(e.to_string()))?;
+            .map_err(decompress_error)?;
         if out.len() != size_usize {
-            return Err(Error::Other(
-                "zstd decompressed length mismatch".to_string(),
-            ));
+            return Err(decompress_error("zstd decompressed length mismatch"));
         }
         return Ok(());
     }
 
-    let dec = zstd::stream::read::Decoder::new(bytes).map_err(|e| Error::Other(e.to_string()))?;
+    let dec = zstd::stream::read::Decoder::new(bytes).map_err(decompress_error)?;
     decode_with_max_bound(dec, max_uncompressed_len, out)
 }
 
@@ -307,9 +454,9 @@ fn decompress_zstd_into(
     _max_uncompressed_len: usize,
     _out: &mut Vec<u8>,
 ) -> Result<()> {
-    Err(Error::Other(
-        "zstd compression feature not enabled".to_string(),
-    ))
+    Err(Error::FeatureDisabled {
+        feature: "compression-zstd",
+    })
 }
 
 #[cfg(feature = "compression-gzip")]
@@ -328,9 +475,9 @@ fn decompress_gzip_into(
     _max_uncompressed_len: usize,
     _out: &mut Vec<u8>,
 ) -> Result<()> {
-    Err(Error::Other(
-        "gzip compression feature not enabled".to_string(),
-    ))
+    Err(Error::FeatureDisabled {
+        feature: "compression-gzip",
+    })
 }
 
 pub fn encode_mathldbt_v1_compressed_into(
@@ -350,27 +497,12 @@ pub fn encode_mathldbt_v1_compressed_into_with_workspace(
     codec_ws: &mut MathldbtV1EncodeWorkspace,
     ws: &mut MathldbtV1CompressedEncodeWorkspace,
 ) -> Result<()> {
-    ws.plain.clear();
-    encode_mathldbt_v1_into_with_workspace(batch, &mut ws.plain, codec_ws)?;
-
-    match c {
-        Compression::None => {
-            compress_none_into(out, ws.plain.as_slice());
-            Ok(())
-        }
-        Compression::Zstd { level } => {
-            let plain = ws.plain.as_slice();
-            #[cfg(feature = "compression-zstd")]
-            {
-                compress_zstd_into(out, plain, level, &mut ws.zstd)
-            }
-            #[cfg(not(feature = "compression-zstd"))]
-            {
-                compress_zstd_into(out, plain, level)
-            }
-        }
-        Compression::Gzip { level } => compress_gzip_into(out, ws.plain.as_slice(), level),
-    }
+    ws.encode_plain(c, batch.columns.len(), codec_ws, |plain, codec_ws| {
+        encode_mathldbt_v1_into_uncommitted(batch, plain, codec_ws)
+    })?;
+    ws.compress_plain_into(out, c)?;
+    codec_ws.commit_pending_dict_entries();
+    Ok(())
 }
 
 pub fn encode_mathldbt_v1_compressed_fast_path_into(
@@ -390,26 +522,12 @@ pub fn encode_mathldbt_v1_compressed_fast_path_into_with_workspace(
     codec_ws: &mut MathldbtV1EncodeWorkspace,
     ws: &mut MathldbtV1CompressedEncodeWorkspace,
 ) -> Result<()> {
-    ws.plain.clear();
-    encode_mathldbt_v1_fast_path_into_with_workspace(view, &mut ws.plain, codec_ws)?;
-
-    match c {
-        Compression::None => {
-            compress_none_into(out, ws.plain.as_slice());
-            Ok(())
-        }
-        Compression::Zstd { level } => {
-            #[cfg(feature = "compression-zstd")]
-            {
-                compress_zstd_into(out, ws.plain.as_slice(), level, &mut ws.zstd)
-            }
-            #[cfg(not(feature = "compression-zstd"))]
-            {
-                compress_zstd_into(out, ws.plain.as_slice(), level)
-            }
-        }
-        Compression::Gzip { level } => compress_gzip_into(out, ws.plain.as_slice(), level),
-    }
+    ws.encode_plain(c, view.columns.len(), codec_ws, |plain, codec_ws| {
+        encode_mathldbt_v1_fast_path_into_uncommitted(view, plain, codec_ws)
+    })?;
+    ws.compress_plain_into(out, c)?;
+    codec_ws.commit_pending_dict_entries();
+    Ok(())
 }
 
 pub fn encode_mathldbt_v1_compressed_fast_path_into_opt(
@@ -463,12 +581,8 @@ pub fn decode_mathldbt_v1_compressed_with_workspace(
 ) -> Result<ColumnarBatch> {
     match c {
         Compression::None => decode_mathldbt_v1_with_workspace(bytes, codec_ws),
-        Compression::Zstd { .. } if max_uncompressed_len == 0 => Err(Error::Other(
-            "decompressed payload exceeds max_uncompressed_len".to_string(),
-        )),
-        Compression::Gzip { .. } if max_uncompressed_len == 0 => Err(Error::Other(
-            "decompressed payload exceeds max_uncompressed_len".to_string(),
-        )),
+        Compression::Zstd { .. } if max_uncompressed_len == 0 => Err(decompressed_too_large()),
+        Compression::Gzip { .. } if max_uncompressed_len == 0 => Err(decompressed_too_large()),
         Compression::Zstd { .. } => {
             #[cfg(feature = "compression-zstd")]
             {
@@ -515,12 +629,8 @@ pub fn decode_mathldbt_v1_compressed_into_with_workspace(
 ) -> Result<()> {
     match c {
         Compression::None => decode_mathldbt_v1_into_with_workspace(bytes, out, codec_ws),
-        Compression::Zstd { .. } if max_uncompressed_len == 0 => Err(Error::Other(
-            "decompressed payload exceeds max_uncompressed_len".to_string(),
-        )),
-        Compression::Gzip { .. } if max_uncompressed_len == 0 => Err(Error::Other(
-            "decompressed payload exceeds max_uncompressed_len".to_string(),
-        )),
+        Compression::Zstd { .. } if max_uncompressed_len == 0 => Err(decompressed_too_large()),
+        Compression::Gzip { .. } if max_uncompressed_len == 0 => Err(decompressed_too_large()),
         Compression::Zstd { .. } => {
             #[cfg(feature = "compression-zstd")]
             {
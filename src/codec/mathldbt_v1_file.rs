@@ -0,0 +1,476 @@
+use crate::batch::ColumnarBatch;
+use crate::batch_view::{ColumnDataView, ColumnarBatchView};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_into_with_workspace, encode_mathldbt_v1_fast_path_into_with_workspace,
+    encode_mathldbt_v1_into_with_workspace,
+};
+use crate::codec::mathldbt_v1_stream::{check_stream_schema, read_schema, write_schema};
+use crate::schema::{ColumnarSchema, ColumnarType};
+use crate::{Error, LimitKind, Result};
+use std::io::Write;
+
+// File layout (all integers little-endian):
+//
+//   header:  magic "MATHLDBF" | version u16 | flags u16 | schema (as in the stream header)
+//   batches: MATHLDBT v1 envelopes, back to back
+//   footer:  time_column u16 (0xFFFF = none) | reserved u16 | batch_count u32
+//            | batch_count x entry
+//   entry:   offset u64 | len u64 | row_count u32 | entry_flags u32
+//            | time_min i64 | time_max i64
+//   trailer: footer_offset u64 | magic "MATHLDBF"
+//
+// `entry_flags` bit 0 marks that time_min/time_max are present (the time column had at least
+// one valid row). Offsets are absolute from the start of the file. Every batch has the header
+// schema, and the time column (if any) is an `I64` or timestamp column of it.
+
+const FILE_MAGIC: &[u8; 8] = b"MATHLDBF";
+const FILE_VERSION: u16 = 1;
+const HEADER_FIXED_LEN: usize = 12;
+const FOOTER_FIXED_LEN: usize = 8;
+const ENTRY_LEN: usize = 40;
+const TRAILER_LEN: usize = 16;
+const NO_TIME_COLUMN: u16 = u16::MAX;
+const ENTRY_HAS_TIME_RANGE: u32 = 1;
+
+/// Footer index entry for one batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileIndexEntry {
+    /// Absolute byte offset of the batch's `MATHLDBT` envelope.
+    pub offset: u64,
+    pub len: u64,
+    pub row_count: u32,
+    /// Min/max over the valid rows of the time column, if one was chosen and had valid rows.
+    pub time_range: Option<(i64, i64)>,
+}
+
+impl FileIndexEntry {
+    pub fn overlaps(&self, start: i64, end: i64) -> bool {
+        match self.time_range {
+            Some((min, max)) => start <= end && min <= end && max >= start,
+            None => false,
+        }
+    }
+}
+
+fn time_range_of(col: &ColumnDataView<'_>) -> Option<(i64, i64)> {
+    let (validity, values) = match *col {
+        ColumnDataView::FixedI64 { validity, values }
+        | ColumnDataView::FixedTimestampMicros { validity, values }
+        | ColumnDataView::FixedTimestamp {
+            validity, values, ..
+        } => (validity, values),
+        _ => return None,
+    };
+    let mut range: Option<(i64, i64)> = None;
+    for (i, &v) in values.iter().enumerate() {
+        if validity[i / 8] & (1u8 << (i % 8)) == 0 {
+            continue;
+        }
+        range = Some(match range {
+            Some((min, max)) => (min.min(v), max.max(v)),
+            None => (v, v),
+        });
+    }
+    range
+}
+
+fn is_time_column_type(ty: &ColumnarType) -> bool {
+    matches!(
+        ty,
+        ColumnarType::I64 | ColumnarType::TimestampTzMicros | ColumnarType::Timestamp { .. }
+    )
+}
+
+/// Also checked per batch, since `FileWriter::workspace_mut` can change the options.
+fn check_no_dict_delta(ws: &MathldbtV1EncodeWorkspace) -> Result<()> {
+    if ws.dict_delta_enabled() {
+        return Err(Error::InvalidBatch {
+            column: None,
+            reason: "DictDelta is not supported in files",
+        });
+    }
+    Ok(())
+}
+
+/// Writes a random-access `MATHLDBT` file: batches back to back, then a footer index.
+///
+/// Offsets are counted from the first byte written, so `inner` should be positioned at the
+/// start of the file. The file is only readable after [`FileWriter::finish`] writes the footer.
+pub struct FileWriter<W: Write> {
+    inner: W,
+    schema: ColumnarSchema,
+    time_column: Option<usize>,
+    ws: MathldbtV1EncodeWorkspace,
+    frame: Vec<u8>,
+    pos: u64,
+    entries: Vec<FileIndexEntry>,
+}
+
+impl<W: Write> FileWriter<W> {
+    /// `time_column` selects an `I64`, `TimestampTzMicros` or `ColumnarType::Timestamp { unit,
+    /// tz }` column whose per-batch min/max is recorded in the index. `Timestamp` columns of any
+    /// unit are accepted; the range is kept in the column's own unit, not converted to micros.
+    pub fn new(inner: W, schema: ColumnarSchema, time_column: Option<usize>) -> Result<Self> {
+        Self::with_workspace(
+            inner,
+            schema,
+            time_column,
+            MathldbtV1EncodeWorkspace::default(),
+        )
+    }
+
+    /// Delta dictionaries (`set_enable_dict_delta`) assume batches are decoded in order, which
+    /// a file read by index does not guarantee, so a `ws` that enables them is rejected.
+    pub fn with_workspace(
+        mut inner: W,
+        schema: ColumnarSchema,
+        time_column: Option<usize>,
+        ws: MathldbtV1EncodeWorkspace,
+    ) -> Result<Self> {
+        check_no_dict_delta(&ws)?;
+        if let Some(c) = time_column {
+            let field = schema.fields().get(c).ok_or(Error::Schema {
+                column: Some(c),
+                reason: "time column out of range",
+            })?;
+            if !is_time_column_type(&field.ty) {
+                return Err(Error::Schema {
+                    column: Some(c),
+                    reason: "time column must be I64 or a timestamp",
+                });
+            }
+        }
+
+        let mut header = Vec::new();
+        header.extend_from_slice(FILE_MAGIC);
+        header.extend_from_slice(&FILE_VERSION.to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes()); // flags
+        write_schema(&mut header, &schema)?;
+        inner.write_all(&header)?;
+        Ok(Self {
+            inner,
+            schema,
+            time_column,
+            ws,
+            frame: Vec::new(),
+            pos: header.len() as u64,
+            entries: Vec::new(),
+        })
+    }
+
+    pub fn schema(&self) -> &ColumnarSchema {
+        &self.schema
+    }
+
+    pub fn entries(&self) -> &[FileIndexEntry] {
+        &self.entries
+    }
+
+    pub fn workspace_mut(&mut self) -> &mut MathldbtV1EncodeWorkspace {
+        &mut self.ws
+    }
+
+    pub fn write_batch(&mut self, batch: &ColumnarBatch) -> Result<()> {
+        check_stream_schema(&self.schema, &batch.schema)?;
+        check_no_dict_delta(&self.ws)?;
+        encode_mathldbt_v1_into_with_workspace(batch, &mut self.frame, &mut self.ws)?;
+        let time_range = self
+            .time_column
+            .and_then(|c| time_range_of(&batch.columns[c].as_view()));
+        self.write_frame(batch.row_count, time_range)
+    }
+
+    pub fn write_batch_view(&mut self, view: &ColumnarBatchView<'_>) -> Result<()> {
+        check_stream_schema(&self.schema, view.schema)?;
+        check_no_dict_delta(&self.ws)?;
+        encode_mathldbt_v1_fast_path_into_with_workspace(view, &mut self.frame, &mut self.ws)?;
+        let time_range = self
+            .time_column
+            .and_then(|c| time_range_of(&view.columns[c]));
+        self.write_frame(view.row_count, time_range)
+    }
+
+    fn write_frame(&mut self, row_count: usize, time_range: Option<(i64, i64)>) -> Result<()> {
+        let row_count: u32 = row_count.try_into().map_err(|_| Error::LimitExceeded {
+            kind: LimitKind::RowCount,
+            column: None,
+            offset: None,
+        })?;
+        let len = self.frame.len() as u64;
+        self.inner.write_all(&self.frame)?;
+        self.entries.push(FileIndexEntry {
+            offset: self.pos,
+            len,
+            row_count,
+            time_range,
+        });
+        self.pos += len;
+        Ok(())
+    }
+
+    /// Writes the footer index and trailer, flushes, and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        let batch_count: u32 = self
+            .entries
+            .len()
+            .try_into()
+            .map_err(|_| Error::LimitExceeded {
+                kind: LimitKind::BatchCount,
+                column: None,
+                offset: None,
+            })?;
+        let time_column = match self.time_column {
+            Some(c) => u16::try_from(c)
+                .ok()
+                .filter(|&c| c != NO_TIME_COLUMN)
+                .ok_or(Error::LimitExceeded {
+                    kind: LimitKind::ColumnCount,
+                    column: Some(c),
+                    offset: None,
+                })?,
+            None => NO_TIME_COLUMN,
+        };
+
+        let mut footer =
+            Vec::with_capacity(FOOTER_FIXED_LEN + self.entries.len() * ENTRY_LEN + TRAILER_LEN);
+        footer.extend_from_slice(&time_column.to_le_bytes());
+        footer.extend_from_slice(&0u16.to_le_bytes()); // reserved
+        footer.extend_from_slice(&batch_count.to_le_bytes());
+        for e in &self.entries {
+            footer.extend_from_slice(&e.offset.to_le_bytes());
+            footer.extend_from_slice(&e.len.to_le_bytes());
+            footer.extend_from_slice(&e.row_count.to_le_bytes());
+            let (flags, (min, max)) = match e.time_range {
+                Some(r) => (ENTRY_HAS_TIME_RANGE, r),
+                None => (0, (0, 0)),
+            };
+            footer.extend_from_slice(&flags.to_le_bytes());
+            footer.extend_from_slice(&min.to_le_bytes());
+            footer.extend_from_slice(&max.to_le_bytes());
+        }
+        footer.extend_from_slice(&self.pos.to_le_bytes());
+        footer.extend_from_slice(FILE_MAGIC);
+        self.inner.write_all(&footer)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+fn u16_at(bytes: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([bytes[pos], bytes[pos + 1]])
+}
+
+fn u32_at(bytes: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes(bytes[pos..pos + 4].try_into().unwrap_or([0; 4]))
+}
+
+fn u64_at(bytes: &[u8], pos: usize) -> u64 {
+    u64::from_le_bytes(bytes[pos..pos + 8].try_into().unwrap_or([0; 8]))
+}
+
+fn malformed(offset: usize, reason: &'static str) -> Error {
+    Error::Malformed {
+        column: None,
+        offset,
+        reason,
+    }
+}
+
+/// Random-access reader over a complete `MATHLDBT` file held in memory (e.g. a memory map).
+///
+/// Only the header schema and the footer are parsed up front; batches are sliced out of
+/// `bytes` without copying and decoded on demand. Error offsets are relative to the start of
+/// the file.
+#[derive(Debug, Clone)]
+pub struct FileReader<'a> {
+    bytes: &'a [u8],
+    schema: ColumnarSchema,
+    time_column: Option<usize>,
+    entries: Vec<FileIndexEntry>,
+}
+
+impl<'a> FileReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        let min_len = HEADER_FIXED_LEN + FOOTER_FIXED_LEN + TRAILER_LEN;
+        if bytes.len() < min_len {
+            return Err(Error::Truncated {
+                column: None,
+                offset: 0,
+                needed: min_len,
+            });
+        }
+        if &bytes[..8] != FILE_MAGIC {
+            return Err(Error::BadMagic { offset: 0 });
+        }
+        let version = u16_at(bytes, 8);
+        if version != FILE_VERSION {
+            return Err(Error::UnsupportedVersion { offset: 8, version });
+        }
+        let mut pos = HEADER_FIXED_LEN;
+        let schema = read_schema(&mut &bytes[pos..], &mut pos)?;
+        let data_start = pos;
+        let trailer = bytes.len() - TRAILER_LEN;
+        if &bytes[trailer + 8..] != FILE_MAGIC {
+            return Err(Error::BadMagic {
+                offset: trailer + 8,
+            });
+        }
+
+        let footer = usize::try_from(u64_at(bytes, trailer))
+            .ok()
+            .filter(|&f| f >= data_start && f <= trailer - FOOTER_FIXED_LEN)
+            .ok_or(malformed(trailer, "footer offset out of bounds"))?;
+        let time_column = match u16_at(bytes, footer) {
+            NO_TIME_COLUMN => None,
+            c => Some(c as usize),
+        };
+        if let Some(c) = time_column {
+            if !schema
+                .fields()
+                .get(c)
+                .is_some_and(|f| is_time_column_type(&f.ty))
+            {
+                return Err(Error::Malformed {
+                    column: Some(c),
+                    offset: footer,
+                    reason: "invalid time column",
+                });
+            }
+        }
+        let batch_count = u32_at(bytes, footer + 4) as usize;
+        let entries_len = batch_count
+            .checked_mul(ENTRY_LEN)
+            .ok_or(malformed(footer + 4, "footer length mismatch"))?;
+        if footer + FOOTER_FIXED_LEN + entries_len != trailer {
+            return Err(malformed(footer + 4, "footer length mismatch"));
+        }
+
+        let mut entries = Vec::with_capacity(batch_count);
+        let mut prev_end = data_start as u64;
+        for i in 0..batch_count {
+            let at = footer + FOOTER_FIXED_LEN + i * ENTRY_LEN;
+            let offset = u64_at(bytes, at);
+            let len = u64_at(bytes, at + 8);
+            let in_bounds = offset >= data_start as u64
+                && len > 0
+                && offset
+                    .checked_add(len)
+                    .is_some_and(|end| end <= footer as u64);
+            if !in_bounds {
+                return Err(malformed(at, "index entry out of bounds"));
+            }
+            // Batches are written back to back, so entries are ordered and disjoint.
+            if offset < prev_end {
+                return Err(malformed(at, "index entries overlap or out of order"));
+            }
+            prev_end = offset + len;
+            let flags = u32_at(bytes, at + 20);
+            let time_range = match flags {
+                0 => None,
+                ENTRY_HAS_TIME_RANGE if time_column.is_some() => {
+                    let (min, max) = (u64_at(bytes, at + 24) as i64, u64_at(bytes, at + 32) as i64);
+                    if min > max {
+                        return Err(malformed(at + 24, "index time range inverted"));
+                    }
+                    Some((min, max))
+                }
+                _ => return Err(malformed(at + 20, "invalid index entry flags")),
+            };
+            entries.push(FileIndexEntry {
+                offset,
+                len,
+                row_count: u32_at(bytes, at + 16),
+                time_range,
+            });
+        }
+
+        Ok(Self {
+            bytes,
+            schema,
+            time_column,
+            entries,
+        })
+    }
+
+    /// The schema shared by every batch, available even when the file has none.
+    pub fn schema(&self) -> &ColumnarSchema {
+        &self.schema
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn time_column(&self) -> Option<usize> {
+        self.time_column
+    }
+
+    pub fn entries(&self) -> &[FileIndexEntry] {
+        &self.entries
+    }
+
+    fn entry(&self, index: usize) -> Result<&FileIndexEntry> {
+        self.entries.get(index).ok_or(Error::BatchIndexOutOfRange {
+            index,
+            count: self.entries.len(),
+        })
+    }
+
+    /// The encoded `MATHLDBT` envelope of batch `index`, borrowed from the file bytes.
+    pub fn batch_bytes(&self, index: usize) -> Result<&'a [u8]> {
+        let e = self.entry(index)?;
+        // Bounds were validated against the file length in `new`.
+        let start = e.offset as usize;
+        Ok(&self.bytes[start..start + e.len as usize])
+    }
+
+    pub fn batch(&self, index: usize) -> Result<ColumnarBatch> {
+        let base = self.entry(index)?.offset as usize;
+        let batch =
+            decode_mathldbt_v1(self.batch_bytes(index)?).map_err(|e| e.at_base_offset(base))?;
+        check_stream_schema(&self.schema, &batch.schema)?;
+        self.check_row_count(index, batch.row_count)?;
+        Ok(batch)
+    }
+
+    pub fn batch_into_with_workspace(
+        &self,
+        index: usize,
+        out: &mut ColumnarBatch,
+        ws: &mut MathldbtV1DecodeWorkspace,
+    ) -> Result<()> {
+        let base = self.entry(index)?.offset as usize;
+        // `decode_into` checks the envelope against `out.schema`.
+        check_stream_schema(&self.schema, &out.schema)?;
+        decode_mathldbt_v1_into_with_workspace(self.batch_bytes(index)?, out, ws)
+            .map_err(|e| e.at_base_offset(base))?;
+        self.check_row_count(index, out.row_count)
+    }
+
+    fn check_row_count(&self, index: usize, row_count: usize) -> Result<()> {
+        let e = self.entry(index)?;
+        if row_count != e.row_count as usize {
+            return Err(malformed(
+                e.offset as usize,
+                "batch row count does not match index",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Indices of the batches whose indexed time range intersects `[start, end]` (inclusive).
+    ///
+    /// Batches without a recorded range (no time column, or no valid rows) never match.
+    pub fn batches_in_time_range(&self, start: i64, end: i64) -> impl Iterator<Item = usize> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(move |(_, e)| e.overlaps(start, end))
+            .map(|(i, _)| i)
+    }
+}
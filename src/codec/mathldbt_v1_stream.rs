@@ -0,0 +1,345 @@
+use crate::batch::{ColumnData, ColumnarBatch};
+use crate::batch_view::ColumnarBatchView;
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1_into_with_workspace,
+    encode_mathldbt_v1_fast_path_into_uncommitted, encode_mathldbt_v1_into_uncommitted, read_type,
+    write_type,
+};
+use crate::schema::{ColumnarField, ColumnarSchema};
+use crate::{Error, LimitKind, Result};
+use std::io::{Read, Write};
+
+// Stream layout (all integers little-endian):
+//
+//   header:  magic "MATHLDBS" | version u16 | flags u16 | schema
+//   schema:  col_count u16 | col_count x (type | name_len u16 | name bytes)
+//   type:    type_id u16, then its parameters as in the MATHLDBT v1 column descriptor:
+//            Decimal: precision u8 | scale i8
+//            Timestamp: unit u8 | tz_len u16 | tz bytes (tz_len = 0 for no zone)
+//            List: item type
+//            Struct: field_count u16 | field_count x (name_len u16 | name bytes | type)
+//            FixedSizeBinary: size u32
+//            FixedSizeList: size u32 | item type
+//   frames:  frame_len u32 (> 0) | MATHLDBT v1 envelope (frame_len bytes)
+//   end:     frame_len u32 = 0 | batch_count u64
+//
+// A MATHLDBT v1 envelope is never empty, so a zero length unambiguously marks the end.
+
+const STREAM_MAGIC: &[u8; 8] = b"MATHLDBS";
+const STREAM_VERSION: u16 = 1;
+
+fn write_stream_header<W: Write>(w: &mut W, schema: &ColumnarSchema) -> Result<()> {
+    let mut header = Vec::new();
+    header.extend_from_slice(STREAM_MAGIC);
+    header.extend_from_slice(&STREAM_VERSION.to_le_bytes());
+    header.extend_from_slice(&0u16.to_le_bytes()); // flags
+    write_schema(&mut header, schema)?;
+    w.write_all(&header)?;
+    Ok(())
+}
+
+/// Writes `col_count u16 | col_count x (type | name_len u16 | name bytes)`, the schema section
+/// shared by the stream and file headers.
+pub(crate) fn write_schema(out: &mut Vec<u8>, schema: &ColumnarSchema) -> Result<()> {
+    let col_count: u16 = schema.len().try_into().map_err(|_| Error::LimitExceeded {
+        kind: LimitKind::ColumnCount,
+        column: None,
+        offset: None,
+    })?;
+    out.extend_from_slice(&col_count.to_le_bytes());
+    for (i, field) in schema.fields().iter().enumerate() {
+        write_type(out, &field.ty);
+        let name = field.name.as_deref().unwrap_or("").as_bytes();
+        let name_len: u16 = name.len().try_into().map_err(|_| Error::LimitExceeded {
+            kind: LimitKind::NameLength,
+            column: Some(i),
+            offset: None,
+        })?;
+        out.extend_from_slice(&name_len.to_le_bytes());
+        out.extend_from_slice(name);
+    }
+    Ok(())
+}
+
+fn stream_schema_mismatch(column: Option<usize>) -> Error {
+    Error::Schema {
+        column,
+        reason: "batch schema does not match stream schema",
+    }
+}
+
+pub(crate) fn check_stream_schema(stream: &ColumnarSchema, batch: &ColumnarSchema) -> Result<()> {
+    if stream.len() != batch.len() {
+        return Err(stream_schema_mismatch(None));
+    }
+    for (i, (a, b)) in stream.fields().iter().zip(batch.fields()).enumerate() {
+        if a != b {
+            return Err(stream_schema_mismatch(Some(i)));
+        }
+    }
+    Ok(())
+}
+
+/// Writes a MATHLDBT stream: the schema once, then one length-prefixed frame per batch.
+///
+/// The stream is only complete after [`StreamWriter::finish`] writes the end marker.
+pub struct StreamWriter<W: Write> {
+    inner: W,
+    schema: ColumnarSchema,
+    ws: MathldbtV1EncodeWorkspace,
+    frame: Vec<u8>,
+    batch_count: u64,
+}
+
+impl<W: Write> StreamWriter<W> {
+    pub fn new(inner: W, schema: ColumnarSchema) -> Result<Self> {
+        Self::with_workspace(inner, schema, MathldbtV1EncodeWorkspace::default())
+    }
+
+    /// Like `new`, but frames are encoded with `ws` (e.g. with opt-in encodings enabled).
+    pub fn with_workspace(
+        mut inner: W,
+        schema: ColumnarSchema,
+        ws: MathldbtV1EncodeWorkspace,
+    ) -> Result<Self> {
+        write_stream_header(&mut inner, &schema)?;
+        Ok(Self {
+            inner,
+            schema,
+            ws,
+            frame: Vec::new(),
+            batch_count: 0,
+        })
+    }
+
+    pub fn schema(&self) -> &ColumnarSchema {
+        &self.schema
+    }
+
+    pub fn batch_count(&self) -> u64 {
+        self.batch_count
+    }
+
+    pub fn workspace_mut(&mut self) -> &mut MathldbtV1EncodeWorkspace {
+        &mut self.ws
+    }
+
+    pub fn write_batch(&mut self, batch: &ColumnarBatch) -> Result<()> {
+        check_stream_schema(&self.schema, &batch.schema)?;
+        encode_mathldbt_v1_into_uncommitted(batch, &mut self.frame, &mut self.ws)?;
+        self.write_frame()
+    }
+
+    pub fn write_batch_view(&mut self, view: &ColumnarBatchView<'_>) -> Result<()> {
+        check_stream_schema(&self.schema, view.schema)?;
+        encode_mathldbt_v1_fast_path_into_uncommitted(view, &mut self.frame, &mut self.ws)?;
+        self.write_frame()
+    }
+
+    /// New `DictDelta` entries are only kept once the frame has been written.
+    fn write_frame(&mut self) -> Result<()> {
+        let len: u32 = self
+            .frame
+            .len()
+            .try_into()
+            .map_err(|_| Error::LimitExceeded {
+                kind: LimitKind::FrameLength,
+                column: None,
+                offset: None,
+            })?;
+        self.inner.write_all(&len.to_le_bytes())?;
+        self.inner.write_all(&self.frame)?;
+        self.ws.commit_pending_dict_entries();
+        self.batch_count += 1;
+        Ok(())
+    }
+
+    /// Writes the end-of-stream marker, flushes, and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        let mut end = [0u8; 12];
+        end[4..].copy_from_slice(&self.batch_count.to_le_bytes());
+        self.inner.write_all(&end)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+pub(crate) fn read_exact_at<R: Read>(r: &mut R, pos: &mut usize, buf: &mut [u8]) -> Result<()> {
+    match r.read_exact(buf) {
+        Ok(()) => {
+            *pos += buf.len();
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Err(Error::Truncated {
+            column: None,
+            offset: *pos,
+            needed: buf.len(),
+        }),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(crate) fn read_array<R: Read, const N: usize>(r: &mut R, pos: &mut usize) -> Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    read_exact_at(r, pos, &mut buf)?;
+    Ok(buf)
+}
+
+fn read_stream_header<R: Read>(r: &mut R, pos: &mut usize) -> Result<ColumnarSchema> {
+    let magic_offset = *pos;
+    let magic: [u8; 8] = read_array(r, pos)?;
+    if &magic != STREAM_MAGIC {
+        return Err(Error::BadMagic {
+            offset: magic_offset,
+        });
+    }
+    let version_offset = *pos;
+    let version = u16::from_le_bytes(read_array(r, pos)?);
+    if version != STREAM_VERSION {
+        return Err(Error::UnsupportedVersion {
+            offset: version_offset,
+            version,
+        });
+    }
+    let _flags = u16::from_le_bytes(read_array(r, pos)?);
+    read_schema(r, pos)
+}
+
+/// Reads the schema section written by [`write_schema`].
+pub(crate) fn read_schema<R: Read>(r: &mut R, pos: &mut usize) -> Result<ColumnarSchema> {
+    let col_count_offset = *pos;
+    let col_count = u16::from_le_bytes(read_array(r, pos)?) as usize;
+    if col_count == 0 {
+        return Err(Error::Malformed {
+            column: None,
+            offset: col_count_offset,
+            reason: "schema must have at least one column",
+        });
+    }
+
+    let mut fields = Vec::with_capacity(col_count);
+    for column in 0..col_count {
+        let ty = read_type(column, &mut |buf| {
+            let offset = *pos;
+            read_exact_at(r, pos, buf).map_err(|e| e.at_column(column))?;
+            Ok(offset)
+        })?;
+        let name_len = read_array(r, pos).map_err(|e| e.at_column(column))?;
+        let name_len = u16::from_le_bytes(name_len) as usize;
+        let name_offset = *pos;
+        let mut name = vec![0u8; name_len];
+        read_exact_at(r, pos, &mut name).map_err(|e| e.at_column(column))?;
+        let name = if name_len == 0 {
+            None
+        } else {
+            Some(String::from_utf8(name).map_err(|_| Error::Malformed {
+                column: Some(column),
+                offset: name_offset,
+                reason: "invalid UTF-8 column name",
+            })?)
+        };
+        fields.push(ColumnarField { name, ty });
+    }
+    ColumnarSchema::new(fields)
+}
+
+/// Reads a MATHLDBT stream written by [`StreamWriter`].
+///
+/// Frames longer than `max_frame_len` are rejected before any allocation. Error offsets are
+/// relative to the start of the stream.
+pub struct StreamReader<R: Read> {
+    inner: R,
+    schema: ColumnarSchema,
+    max_frame_len: usize,
+    ws: MathldbtV1DecodeWorkspace,
+    frame: Vec<u8>,
+    pos: usize,
+    batches_read: u64,
+    done: bool,
+}
+
+impl<R: Read> StreamReader<R> {
+    /// Reads the stream header.
+    pub fn new(mut inner: R, max_frame_len: usize) -> Result<Self> {
+        let mut pos = 0usize;
+        let schema = read_stream_header(&mut inner, &mut pos)?;
+        Ok(Self {
+            inner,
+            schema,
+            max_frame_len,
+            ws: MathldbtV1DecodeWorkspace::default(),
+            frame: Vec::new(),
+            pos,
+            batches_read: 0,
+            done: false,
+        })
+    }
+
+    pub fn schema(&self) -> &ColumnarSchema {
+        &self.schema
+    }
+
+    pub fn batches_read(&self) -> u64 {
+        self.batches_read
+    }
+
+    /// The workspace frames are decoded with, e.g. to cap its `DictDelta` dictionaries.
+    pub fn workspace_mut(&mut self) -> &mut MathldbtV1DecodeWorkspace {
+        &mut self.ws
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns an empty batch with the stream schema, suitable as a `read_batch_into` target.
+    pub fn new_batch(&self) -> Result<ColumnarBatch> {
+        let mut columns = Vec::with_capacity(self.schema.len());
+        for field in self.schema.fields() {
+            columns.push(ColumnData::new_all_invalid(field.ty.clone(), 0)?);
+        }
+        ColumnarBatch::new(self.schema.clone(), 0, columns)
+    }
+
+    /// Decodes the next frame into `out`, reusing its buffers.
+    ///
+    /// Returns `Ok(false)` once the end marker has been read (and its batch count verified).
+    pub fn read_batch_into(&mut self, out: &mut ColumnarBatch) -> Result<bool> {
+        if self.done {
+            return Ok(false);
+        }
+        check_stream_schema(&self.schema, &out.schema)?;
+
+        let len_offset = self.pos;
+        let len = u32::from_le_bytes(read_array(&mut self.inner, &mut self.pos)?) as usize;
+        if len == 0 {
+            let count_offset = self.pos;
+            let count = u64::from_le_bytes(read_array(&mut self.inner, &mut self.pos)?);
+            if count != self.batches_read {
+                return Err(Error::Malformed {
+                    column: None,
+                    offset: count_offset,
+                    reason: "stream batch count mismatch",
+                });
+            }
+            self.done = true;
+            return Ok(false);
+        }
+        if len > self.max_frame_len {
+            return Err(Error::LimitExceeded {
+                kind: LimitKind::FrameLength,
+                column: None,
+                offset: Some(len_offset),
+            });
+        }
+
+        let frame_offset = self.pos;
+        self.frame.clear();
+        self.frame.resize(len, 0);
+        read_exact_at(&mut self.inner, &mut self.pos, &mut self.frame)?;
+        decode_mathldbt_v1_into_with_workspace(&self.frame, out, &mut self.ws)
+            .map_err(|e| e.at_base_offset(frame_offset))?;
+        self.batches_read += 1;
+        Ok(true)
+    }
+}
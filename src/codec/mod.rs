@@ -1,5 +1,7 @@
 mod exports;
 pub mod mathldbt_v1;
 pub mod mathldbt_v1_compressed;
+pub mod mathldbt_v1_file;
+pub mod mathldbt_v1_stream;
 
 pub use exports::*;
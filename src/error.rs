@@ -2,17 +2,326 @@ use core::fmt;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    RowCount,
+    ColumnCount,
+    NameLength,
+    PayloadLength,
+    DictEntries,
+    DecompressedLength,
+    FrameLength,
+    BatchCount,
+}
+
+impl fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            LimitKind::RowCount => "row count",
+            LimitKind::ColumnCount => "column count",
+            LimitKind::NameLength => "name length",
+            LimitKind::PayloadLength => "payload length",
+            LimitKind::DictEntries => "dictionary entries",
+            LimitKind::DecompressedLength => "decompressed length",
+            LimitKind::FrameLength => "frame length",
+            LimitKind::BatchCount => "batch count",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Crate error type.
+///
+/// Decode errors carry the byte `offset` (from the start of the decoded buffer) and, where the
+/// failure is tied to a column descriptor, the zero-based `column` index. Errors raised while
+/// validating or encoding in-memory batches have no byte offset.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error {
-    Other(String),
+    /// The input ended before `needed` bytes could be read at `offset`.
+    Truncated {
+        column: Option<usize>,
+        offset: usize,
+        needed: usize,
+    },
+    BadMagic {
+        offset: usize,
+    },
+    UnsupportedVersion {
+        offset: usize,
+        version: u16,
+    },
+    UnknownTypeId {
+        column: usize,
+        offset: usize,
+        type_id: u16,
+    },
+    /// The encoding id is unknown or not valid for the column type.
+    UnknownEncoding {
+        column: usize,
+        offset: usize,
+        encoding_id: u16,
+    },
+    LimitExceeded {
+        kind: LimitKind,
+        column: Option<usize>,
+        offset: Option<usize>,
+    },
+    InvalidOffsets {
+        column: usize,
+        offset: usize,
+        reason: &'static str,
+    },
+    /// Structurally corrupt input (length mismatches, malformed encoded payloads).
+    Malformed {
+        column: Option<usize>,
+        offset: usize,
+        reason: &'static str,
+    },
+    /// An in-memory batch or view violates its invariants.
+    InvalidBatch {
+        column: Option<usize>,
+        reason: &'static str,
+    },
+    Schema {
+        column: Option<usize>,
+        reason: &'static str,
+    },
+    FeatureDisabled {
+        feature: &'static str,
+    },
+    /// A batch index past the end of a file's footer index.
+    BatchIndexOutOfRange {
+        index: usize,
+        count: usize,
+    },
+    /// A compression library error; on decode `offset` is the start of the compressed bytes.
+    Compression {
+        column: Option<usize>,
+        offset: Option<usize>,
+        message: String,
+    },
+    Io {
+        kind: std::io::ErrorKind,
+        message: String,
+    },
+    Internal(&'static str),
+}
+
+impl Error {
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            Error::UnknownTypeId { column, .. }
+            | Error::UnknownEncoding { column, .. }
+            | Error::InvalidOffsets { column, .. } => Some(*column),
+            Error::Truncated { column, .. }
+            | Error::LimitExceeded { column, .. }
+            | Error::Malformed { column, .. }
+            | Error::InvalidBatch { column, .. }
+            | Error::Schema { column, .. }
+            | Error::Compression { column, .. } => *column,
+            _ => None,
+        }
+    }
+
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            Error::Truncated { offset, .. }
+            | Error::BadMagic { offset }
+            | Error::UnsupportedVersion { offset, .. }
+            | Error::UnknownTypeId { offset, .. }
+            | Error::UnknownEncoding { offset, .. }
+            | Error::InvalidOffsets { offset, .. }
+            | Error::Malformed { offset, .. } => Some(*offset),
+            Error::LimitExceeded { offset, .. } | Error::Compression { offset, .. } => *offset,
+            _ => None,
+        }
+    }
+
+    /// Rebases a decode error onto an enclosing container, e.g. a frame inside a stream.
+    pub(crate) fn at_base_offset(mut self, base: usize) -> Self {
+        match &mut self {
+            Error::Truncated { offset, .. }
+            | Error::BadMagic { offset }
+            | Error::UnsupportedVersion { offset, .. }
+            | Error::UnknownTypeId { offset, .. }
+            | Error::UnknownEncoding { offset, .. }
+            | Error::InvalidOffsets { offset, .. }
+            | Error::Malformed { offset, .. } => *offset = offset.saturating_add(base),
+            Error::LimitExceeded {
+                offset: Some(offset),
+                ..
+            }
+            | Error::Compression {
+                offset: Some(offset),
+                ..
+            } => *offset = offset.saturating_add(base),
+            _ => {}
+        }
+        self
+    }
+
+    pub(crate) fn at_column(self, idx: usize) -> Self {
+        match self {
+            Error::Truncated {
+                column: None,
+                offset,
+                needed,
+            } => Error::Truncated {
+                column: Some(idx),
+                offset,
+                needed,
+            },
+            Error::LimitExceeded {
+                kind,
+                column: None,
+                offset,
+            } => Error::LimitExceeded {
+                kind,
+                column: Some(idx),
+                offset,
+            },
+            Error::Malformed {
+                column: None,
+                offset,
+                reason,
+            } => Error::Malformed {
+                column: Some(idx),
+                offset,
+                reason,
+            },
+            Error::InvalidBatch {
+                column: None,
+                reason,
+            } => Error::InvalidBatch {
+                column: Some(idx),
+                reason,
+            },
+            Error::Schema {
+                column: None,
+                reason,
+            } => Error::Schema {
+                column: Some(idx),
+                reason,
+            },
+            Error::Compression {
+                column: None,
+                offset,
+                message,
+            } => Error::Compression {
+                column: Some(idx),
+                offset,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+fn write_column(f: &mut fmt::Formatter<'_>, column: Option<usize>) -> fmt::Result {
+    match column {
+        Some(c) => write!(f, " (column {c})"),
+        None => Ok(()),
+    }
+}
+
+fn write_context(
+    f: &mut fmt::Formatter<'_>,
+    column: Option<usize>,
+    offset: Option<usize>,
+) -> fmt::Result {
+    match (column, offset) {
+        (Some(c), Some(o)) => write!(f, " (column {c}, offset {o})"),
+        (None, Some(o)) => write!(f, " (offset {o})"),
+        _ => write_column(f, column),
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::Other(msg) => write!(f, "{msg}"),
+            Error::Truncated {
+                column,
+                offset,
+                needed,
+            } => {
+                write!(
+                    f,
+                    "truncated input: needed {needed} bytes at offset {offset}"
+                )?;
+                write_column(f, *column)
+            }
+            Error::BadMagic { offset } => write!(f, "invalid magic at offset {offset}"),
+            Error::UnsupportedVersion { offset, version } => {
+                write!(f, "unsupported version {version} at offset {offset}")
+            }
+            Error::UnknownTypeId {
+                column,
+                offset,
+                type_id,
+            } => write!(
+                f,
+                "unknown column type id {type_id} (column {column}, offset {offset})"
+            ),
+            Error::UnknownEncoding {
+                column,
+                offset,
+                encoding_id,
+            } => write!(
+                f,
+                "invalid encoding id {encoding_id} (column {column}, offset {offset})"
+            ),
+            Error::LimitExceeded {
+                kind,
+                column,
+                offset,
+            } => {
+                write!(f, "{kind} limit exceeded")?;
+                write_context(f, *column, *offset)
+            }
+            Error::InvalidOffsets {
+                column,
+                offset,
+                reason,
+            } => write!(f, "{reason} (column {column}, offset {offset})"),
+            Error::Malformed {
+                column: Some(column),
+                offset,
+                reason,
+            } => write!(f, "{reason} (column {column}, offset {offset})"),
+            Error::Malformed {
+                column: None,
+                offset,
+                reason,
+            } => write!(f, "{reason} (offset {offset})"),
+            Error::InvalidBatch { column, reason } | Error::Schema { column, reason } => {
+                f.write_str(reason)?;
+                write_column(f, *column)
+            }
+            Error::FeatureDisabled { feature } => write!(f, "feature not enabled: {feature}"),
+            Error::BatchIndexOutOfRange { index, count } => {
+                write!(f, "batch index {index} out of range ({count} batches)")
+            }
+            Error::Compression {
+                column,
+                offset,
+                message,
+            } => {
+                write!(f, "compression error: {message}")?;
+                write_context(f, *column, *offset)
+            }
+            Error::Io { message, .. } => write!(f, "io error: {message}"),
+            Error::Internal(msg) => write!(f, "internal error: {msg}"),
         }
     }
 }
 
 impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io {
+            kind: e.kind(),
+            message: e.to_string(),
+        }
+    }
+}
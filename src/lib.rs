@@ -1,10 +1,13 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod batch;
 pub mod batch_view;
 pub mod codec;
 pub mod error;
+pub mod pg_copy;
 pub mod schema;
 
-pub use crate::error::{Error, Result};
+pub use crate::error::{Error, LimitKind, Result};
 
 #[cfg(test)]
 mod tests;
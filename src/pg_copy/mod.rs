@@ -0,0 +1,63 @@
+//! Postgres `COPY ... (FORMAT binary)` interop.
+//!
+//! All COPY integers are big-endian. A stream is the 11-byte signature, a flags word, a header
+//! extension, then tuples (`i16` field count, then per field an `i32` length, `-1` for NULL,
+//! and the value bytes), terminated by a field count of `-1`.
+
+mod numeric;
+mod reader;
+mod writer;
+
+pub use reader::PgCopyReader;
+pub use writer::PgCopyWriter;
+
+use crate::schema::{ColumnarSchema, ColumnarType, TimeUnit};
+use crate::{Error, Result};
+
+const SIGNATURE: &[u8; 11] = b"PGCOPY\n\xff\r\n\0";
+const FLAG_HAS_OIDS: u32 = 1 << 16;
+const JSONB_VERSION: u8 = 1;
+
+/// Microseconds between the Unix epoch and the Postgres epoch (2000-01-01T00:00:00Z).
+pub const PG_EPOCH_OFFSET_MICROS: i64 = 946_684_800_000_000;
+
+/// Days between the Unix epoch and the Postgres epoch (2000-01-01).
+pub const PG_EPOCH_OFFSET_DAYS: i32 = 10_957;
+
+/// Rejects types without a Postgres counterpart. Postgres `timestamp`/`timestamptz` hold
+/// microseconds; other units are rejected up front rather than rescaled per value.
+fn check_copy_schema(schema: &ColumnarSchema) -> Result<()> {
+    for (i, field) in schema.fields().iter().enumerate() {
+        let reason = match field.ty {
+            ColumnarType::Timestamp { unit, .. } if unit != TimeUnit::Microsecond => {
+                "COPY timestamps must have microsecond unit"
+            }
+            ColumnarType::I8
+            | ColumnarType::U8
+            | ColumnarType::U16
+            | ColumnarType::U32
+            | ColumnarType::U64
+            | ColumnarType::List(_)
+            | ColumnarType::Struct(_)
+            | ColumnarType::FixedSizeBinary(_)
+            | ColumnarType::FixedSizeList(..) => "column type has no Postgres COPY mapping",
+            _ => continue,
+        };
+        return Err(Error::Schema {
+            column: Some(i),
+            reason,
+        });
+    }
+    Ok(())
+}
+
+fn unsupported_copy_type() -> Error {
+    Error::Internal("column type has no Postgres COPY mapping")
+}
+
+fn copy_schema_mismatch() -> Error {
+    Error::Schema {
+        column: None,
+        reason: "batch schema does not match COPY schema",
+    }
+}
@@ -0,0 +1,119 @@
+//! Postgres `numeric` binary format (`numeric_send`/`numeric_recv`):
+//!
+//!   ndigits i16 | weight i16 | sign u16 | dscale u16 | ndigits x digit i16 (base 10000)
+//!
+//! The value is `sum(digit[i] * 10000^(weight - i))`.
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NBASE: i128 = 10_000;
+
+/// Converts a `numeric` field to a mantissa at `scale`, failing unless the value is exactly
+/// representable with `precision` digits.
+pub(crate) fn numeric_to_decimal(
+    bytes: &[u8],
+    precision: u8,
+    scale: i8,
+) -> Result<i128, &'static str> {
+    const INVALID: &str = "invalid COPY numeric";
+    const DOES_NOT_FIT: &str = "COPY numeric does not fit decimal type";
+
+    let word = |i: usize| bytes.get(2 * i..2 * i + 2).map(|w| [w[0], w[1]]);
+    let header = |i| word(i).ok_or(INVALID);
+    let ndigits = i16::from_be_bytes(header(0)?);
+    let weight = i16::from_be_bytes(header(1)?);
+    let sign = u16::from_be_bytes(header(2)?);
+    let ndigits = usize::try_from(ndigits).map_err(|_| INVALID)?;
+    if bytes.len() != 8 + 2 * ndigits {
+        return Err(INVALID);
+    }
+    let negative = match sign {
+        NUMERIC_POS => false,
+        NUMERIC_NEG => true,
+        // NaN and +/-Infinity have no decimal representation.
+        _ => return Err("unsupported COPY numeric value"),
+    };
+
+    let mut mantissa = 0i128;
+    for i in 0..ndigits {
+        let digit = i128::from(i16::from_be_bytes(header(4 + i)?));
+        if !(0..NBASE).contains(&digit) {
+            return Err(INVALID);
+        }
+        mantissa = mantissa
+            .checked_mul(NBASE)
+            .and_then(|m| m.checked_add(digit))
+            .ok_or(DOES_NOT_FIT)?;
+    }
+
+    // Decimal exponent of the last digit, moved to the target scale.
+    let shift = 4 * (i32::from(weight) - ndigits as i32 + 1) + i32::from(scale);
+    if mantissa != 0 {
+        if shift >= 0 {
+            let factor = 10i128.checked_pow(shift as u32).ok_or(DOES_NOT_FIT)?;
+            mantissa = mantissa.checked_mul(factor).ok_or(DOES_NOT_FIT)?;
+        } else {
+            let divisor = 10i128
+                .checked_pow(shift.unsigned_abs())
+                .ok_or(DOES_NOT_FIT)?;
+            if mantissa % divisor != 0 {
+                return Err(DOES_NOT_FIT);
+            }
+            mantissa /= divisor;
+        }
+    }
+    if mantissa.unsigned_abs() >= 10u128.pow(u32::from(precision)) {
+        return Err(DOES_NOT_FIT);
+    }
+    Ok(if negative { -mantissa } else { mantissa })
+}
+
+/// Writes `mantissa * 10^-scale` as a length-prefixed `numeric` field.
+pub(crate) fn write_numeric_field(buf: &mut Vec<u8>, mantissa: i128, scale: i8) {
+    // Base-10000 digits, least significant first; 38 decimal digits need at most 11.
+    let mut digits = [0u16; 12];
+    let mut n = 0usize;
+    let mut v = mantissa.unsigned_abs();
+    // Align the unit exponent (-scale) down to a multiple of 4 by giving the lowest digit
+    // only `4 - r` decimal places.
+    let r = (-i32::from(scale)).rem_euclid(4) as u32;
+    let mut unit_exp = -i32::from(scale) - r as i32;
+    if v != 0 && r != 0 {
+        let low = 10u128.pow(4 - r);
+        digits[0] = ((v % low) * 10u128.pow(r)) as u16;
+        v /= low;
+        n = 1;
+    }
+    while v != 0 {
+        digits[n] = (v % NBASE as u128) as u16;
+        v /= NBASE as u128;
+        n += 1;
+    }
+    // Postgres strips trailing zero digits.
+    let mut low = 0usize;
+    while low < n && digits[low] == 0 {
+        low += 1;
+        unit_exp += 4;
+    }
+    let ndigits = n - low;
+    let weight = if ndigits == 0 {
+        0
+    } else {
+        (ndigits as i32 - 1) + unit_exp / 4
+    };
+    let sign = if mantissa < 0 {
+        NUMERIC_NEG
+    } else {
+        NUMERIC_POS
+    };
+    let dscale = scale.max(0) as u16;
+
+    buf.extend_from_slice(&(8 + 2 * ndigits as i32).to_be_bytes());
+    buf.extend_from_slice(&(ndigits as i16).to_be_bytes());
+    buf.extend_from_slice(&(weight as i16).to_be_bytes());
+    buf.extend_from_slice(&sign.to_be_bytes());
+    buf.extend_from_slice(&dscale.to_be_bytes());
+    for &d in digits[low..n].iter().rev() {
+        buf.extend_from_slice(&d.to_be_bytes());
+    }
+}
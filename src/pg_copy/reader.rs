@@ -0,0 +1,392 @@
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::codec::mathldbt_v1::interval_from_be_bytes;
+use crate::codec::mathldbt_v1_stream::{read_array, read_exact_at};
+use crate::pg_copy::numeric::numeric_to_decimal;
+use crate::pg_copy::{
+    FLAG_HAS_OIDS, JSONB_VERSION, PG_EPOCH_OFFSET_DAYS, PG_EPOCH_OFFSET_MICROS, SIGNATURE,
+    check_copy_schema, copy_schema_mismatch, unsupported_copy_type,
+};
+use crate::schema::{ColumnarSchema, ColumnarType};
+use crate::{Error, LimitKind, Result};
+use std::io::Read;
+
+fn validity_mut(col: &mut ColumnData) -> &mut ValidityBitmap {
+    match col {
+        ColumnData::FixedBool { validity, .. }
+        | ColumnData::BitPackedBool { validity, .. }
+        | ColumnData::FixedI16 { validity, .. }
+        | ColumnData::FixedI32 { validity, .. }
+        | ColumnData::FixedI64 { validity, .. }
+        | ColumnData::FixedI8 { validity, .. }
+        | ColumnData::FixedU8 { validity, .. }
+        | ColumnData::FixedU16 { validity, .. }
+        | ColumnData::FixedU32 { validity, .. }
+        | ColumnData::FixedU64 { validity, .. }
+        | ColumnData::FixedF32Bits { validity, .. }
+        | ColumnData::FixedF64Bits { validity, .. }
+        | ColumnData::FixedUuid { validity, .. }
+        | ColumnData::FixedTimestampMicros { validity, .. }
+        | ColumnData::FixedTimestamp { validity, .. }
+        | ColumnData::FixedDate32 { validity, .. }
+        | ColumnData::FixedTimeMicros { validity, .. }
+        | ColumnData::FixedInterval { validity, .. }
+        | ColumnData::FixedDecimal128 { validity, .. }
+        | ColumnData::FixedSizeBinary { validity, .. }
+        | ColumnData::FixedSizeListF32Bits { validity, .. }
+        | ColumnData::FixedSizeListF64Bits { validity, .. }
+        | ColumnData::Var { validity, .. }
+        | ColumnData::List { validity, .. }
+        | ColumnData::Struct { validity, .. } => validity,
+    }
+}
+
+fn clear_column(col: &mut ColumnData) {
+    validity_mut(col).bytes.clear();
+    match col {
+        ColumnData::FixedBool { values, .. } => values.clear(),
+        ColumnData::BitPackedBool { values, .. } => values.clear(),
+        ColumnData::FixedI16 { values, .. } => values.clear(),
+        ColumnData::FixedI8 { values, .. } => values.clear(),
+        ColumnData::FixedU8 { values, .. } => values.clear(),
+        ColumnData::FixedU16 { values, .. } => values.clear(),
+        ColumnData::FixedU32 { values, .. } => values.clear(),
+        ColumnData::FixedU64 { values, .. } => values.clear(),
+        ColumnData::FixedI32 { values, .. } | ColumnData::FixedDate32 { values, .. } => {
+            values.clear()
+        }
+        ColumnData::FixedI64 { values, .. }
+        | ColumnData::FixedTimestampMicros { values, .. }
+        | ColumnData::FixedTimestamp { values, .. }
+        | ColumnData::FixedTimeMicros { values, .. } => values.clear(),
+        ColumnData::FixedInterval { values, .. } => values.clear(),
+        ColumnData::FixedF32Bits { values, .. } => values.clear(),
+        ColumnData::FixedF64Bits { values, .. } => values.clear(),
+        ColumnData::FixedUuid { values, .. } => values.clear(),
+        ColumnData::FixedDecimal128 { values, .. } => values.clear(),
+        ColumnData::FixedSizeBinary { values, .. } => values.clear(),
+        ColumnData::FixedSizeListF32Bits { values, .. } => values.clear(),
+        ColumnData::FixedSizeListF64Bits { values, .. } => values.clear(),
+        ColumnData::Var { offsets, data, .. } => {
+            offsets.clear();
+            offsets.push(0);
+            data.clear();
+        }
+        ColumnData::List { offsets, child, .. } => {
+            offsets.clear();
+            offsets.push(0);
+            clear_column(child);
+        }
+        ColumnData::Struct { children, .. } => children.iter_mut().for_each(clear_column),
+    }
+}
+
+/// Postgres timestamps count microseconds from 2000-01-01; `±infinity` are kept as the
+/// `i64::MAX`/`i64::MIN` sentinels.
+fn timestamp_from_pg(v: i64) -> Option<i64> {
+    match v {
+        i64::MAX | i64::MIN => Some(v),
+        _ => v.checked_add(PG_EPOCH_OFFSET_MICROS),
+    }
+}
+
+/// Dates count days from 2000-01-01, with `i32::MAX`/`i32::MIN` for `±infinity`.
+fn date_from_pg(v: i32) -> Option<i32> {
+    match v {
+        i32::MAX | i32::MIN => Some(v),
+        _ => v.checked_add(PG_EPOCH_OFFSET_DAYS),
+    }
+}
+
+struct Field<'a> {
+    column: usize,
+    offset: usize,
+    value: Option<&'a [u8]>,
+}
+
+impl Field<'_> {
+    fn malformed(&self, reason: &'static str) -> Error {
+        Error::Malformed {
+            column: Some(self.column),
+            offset: self.offset,
+            reason,
+        }
+    }
+
+    fn fixed<const N: usize>(&self) -> Result<[u8; N]> {
+        match self.value {
+            Some(v) => v
+                .try_into()
+                .map_err(|_| self.malformed("COPY field length mismatch")),
+            None => Ok([0u8; N]),
+        }
+    }
+}
+
+fn push_field(col: &mut ColumnData, row: usize, field: &Field<'_>) -> Result<()> {
+    let validity = validity_mut(col);
+    if row % 8 == 0 {
+        validity.bytes.push(0);
+    }
+    if field.value.is_some() {
+        validity.set(row, true)?;
+    }
+
+    match col {
+        ColumnData::FixedBool { values, .. } => {
+            let [b] = field.fixed::<1>()?;
+            if b > 1 {
+                return Err(field.malformed("invalid COPY bool value"));
+            }
+            values.push(b);
+        }
+        ColumnData::BitPackedBool { values, .. } => {
+            let [b] = field.fixed::<1>()?;
+            if b > 1 {
+                return Err(field.malformed("invalid COPY bool value"));
+            }
+            if row % 8 == 0 {
+                values.push(0);
+            }
+            values[row / 8] |= b << (row % 8);
+        }
+        ColumnData::FixedI16 { values, .. } => values.push(i16::from_be_bytes(field.fixed()?)),
+        ColumnData::FixedI32 { values, .. } => values.push(i32::from_be_bytes(field.fixed()?)),
+        ColumnData::FixedI64 { values, .. } => values.push(i64::from_be_bytes(field.fixed()?)),
+        ColumnData::FixedI8 { .. }
+        | ColumnData::FixedU8 { .. }
+        | ColumnData::FixedU16 { .. }
+        | ColumnData::FixedU32 { .. }
+        | ColumnData::FixedU64 { .. }
+        | ColumnData::FixedSizeBinary { .. }
+        | ColumnData::FixedSizeListF32Bits { .. }
+        | ColumnData::FixedSizeListF64Bits { .. }
+        | ColumnData::List { .. }
+        | ColumnData::Struct { .. } => return Err(unsupported_copy_type()),
+        ColumnData::FixedF32Bits { values, .. } => values.push(u32::from_be_bytes(field.fixed()?)),
+        ColumnData::FixedF64Bits { values, .. } => values.push(u64::from_be_bytes(field.fixed()?)),
+        ColumnData::FixedUuid { values, .. } => values.push(field.fixed()?),
+        ColumnData::FixedTimestampMicros { values, .. }
+        | ColumnData::FixedTimestamp { values, .. } => {
+            let v = match field.value {
+                Some(_) => timestamp_from_pg(i64::from_be_bytes(field.fixed()?))
+                    .ok_or(field.malformed("COPY timestamp out of range"))?,
+                None => 0,
+            };
+            values.push(v);
+        }
+        ColumnData::FixedDate32 { values, .. } => {
+            let v = match field.value {
+                Some(_) => date_from_pg(i32::from_be_bytes(field.fixed()?))
+                    .ok_or(field.malformed("COPY date out of range"))?,
+                None => 0,
+            };
+            values.push(v);
+        }
+        ColumnData::FixedTimeMicros { values, .. } => {
+            values.push(i64::from_be_bytes(field.fixed()?))
+        }
+        ColumnData::FixedInterval { values, .. } => {
+            values.push(interval_from_be_bytes(&field.fixed::<16>()?))
+        }
+        ColumnData::FixedDecimal128 {
+            precision,
+            scale,
+            values,
+            ..
+        } => {
+            let v = match field.value {
+                Some(bytes) => numeric_to_decimal(bytes, *precision, *scale)
+                    .map_err(|reason| field.malformed(reason))?,
+                None => 0,
+            };
+            values.push(v);
+        }
+        ColumnData::Var {
+            ty, offsets, data, ..
+        } => {
+            let mut v = field.value.unwrap_or_default();
+            if *ty == ColumnarType::JsonbText && field.value.is_some() {
+                match v.split_first() {
+                    Some((&JSONB_VERSION, rest)) => v = rest,
+                    _ => return Err(field.malformed("unsupported jsonb version")),
+                }
+            }
+            data.extend_from_slice(v);
+            let end = u32::try_from(data.len()).map_err(|_| Error::LimitExceeded {
+                kind: LimitKind::PayloadLength,
+                column: Some(field.column),
+                offset: Some(field.offset),
+            })?;
+            offsets.push(end);
+        }
+    }
+    Ok(())
+}
+
+/// Reads a Postgres `COPY ... TO STDOUT (FORMAT binary)` stream into batches of `schema`.
+///
+/// Columns are matched by position. Field encodings follow the Postgres binary send functions
+/// for `bool`, `int2`/`int4`/`int8`, `float4`/`float8`, `uuid`, `timestamptz`, `timestamp`,
+/// `date`, `time`, `interval`, `text`/`varchar` (`Utf8`), `bytea` (`Bytes`), `jsonb`
+/// (`JsonbText`) and `numeric` (`Decimal`, which must hold the value exactly). `Timestamp`
+/// columns must use [`TimeUnit::Microsecond`](crate::schema::TimeUnit). Error offsets are
+/// relative to the start of the stream. Reads are small; wrap unbuffered sources in a `BufReader`.
+pub struct PgCopyReader<R: Read> {
+    inner: R,
+    schema: ColumnarSchema,
+    batch_rows: usize,
+    has_oids: bool,
+    pos: usize,
+    field: Vec<u8>,
+    done: bool,
+}
+
+impl<R: Read> PgCopyReader<R> {
+    /// Reads the COPY header. Each batch holds at most `batch_rows` rows.
+    pub fn new(mut inner: R, schema: ColumnarSchema, batch_rows: usize) -> Result<Self> {
+        if batch_rows == 0 {
+            return Err(Error::InvalidBatch {
+                column: None,
+                reason: "batch_rows must be non-zero",
+            });
+        }
+        check_copy_schema(&schema)?;
+        let mut pos = 0usize;
+        let signature: [u8; 11] = read_array(&mut inner, &mut pos)?;
+        if &signature != SIGNATURE {
+            return Err(Error::BadMagic { offset: 0 });
+        }
+        let flags_offset = pos;
+        let flags = u32::from_be_bytes(read_array(&mut inner, &mut pos)?);
+        // Bits 0-15 flag critical format changes; bits 17-31 may be ignored.
+        if flags & 0xFFFF != 0 {
+            return Err(Error::Malformed {
+                column: None,
+                offset: flags_offset,
+                reason: "unknown critical COPY header flags",
+            });
+        }
+        let ext_len = u32::from_be_bytes(read_array(&mut inner, &mut pos)?) as usize;
+        let mut ext = vec![0u8; ext_len.min(1024)];
+        let mut remaining = ext_len;
+        while remaining > 0 {
+            let n = remaining.min(ext.len());
+            read_exact_at(&mut inner, &mut pos, &mut ext[..n])?;
+            remaining -= n;
+        }
+
+        Ok(Self {
+            inner,
+            schema,
+            batch_rows,
+            has_oids: flags & FLAG_HAS_OIDS != 0,
+            pos,
+            field: Vec::new(),
+            done: false,
+        })
+    }
+
+    pub fn schema(&self) -> &ColumnarSchema {
+        &self.schema
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns an empty batch with the reader's schema, suitable as a `read_batch_into` target.
+    pub fn new_batch(&self) -> Result<ColumnarBatch> {
+        let mut columns = Vec::with_capacity(self.schema.len());
+        for field in self.schema.fields() {
+            columns.push(ColumnData::new_all_invalid(field.ty.clone(), 0)?);
+        }
+        ColumnarBatch::new(self.schema.clone(), 0, columns)
+    }
+
+    /// Reads the next batch, or `None` once the COPY trailer has been reached.
+    pub fn next_batch(&mut self) -> Result<Option<ColumnarBatch>> {
+        let mut out = self.new_batch()?;
+        Ok(self.read_batch_into(&mut out)?.then_some(out))
+    }
+
+    /// Reads the field length word; `Ok(false)` for NULL, otherwise the value is in `self.field`.
+    fn read_field(&mut self, column: usize) -> Result<bool> {
+        let len_offset = self.pos;
+        let len = read_array(&mut self.inner, &mut self.pos).map_err(|e| e.at_column(column))?;
+        let len = i32::from_be_bytes(len);
+        if len == -1 {
+            return Ok(false);
+        }
+        let len = usize::try_from(len).map_err(|_| Error::Malformed {
+            column: Some(column),
+            offset: len_offset,
+            reason: "negative COPY field length",
+        })?;
+        // Grow with the data actually read rather than trusting the length word up front.
+        self.field.clear();
+        let read = (&mut self.inner)
+            .take(len as u64)
+            .read_to_end(&mut self.field)?;
+        if read < len {
+            return Err(Error::Truncated {
+                column: Some(column),
+                offset: self.pos + read,
+                needed: len - read,
+            });
+        }
+        self.pos += len;
+        Ok(true)
+    }
+
+    /// Fills `out` with up to `batch_rows` rows, reusing its buffers.
+    ///
+    /// Returns `Ok(false)` (with `out` left empty) once the trailer has been read.
+    pub fn read_batch_into(&mut self, out: &mut ColumnarBatch) -> Result<bool> {
+        if out.schema != self.schema || out.columns.len() != self.schema.len() {
+            return Err(copy_schema_mismatch());
+        }
+        for col in out.columns.iter_mut() {
+            clear_column(col);
+        }
+        out.row_count = 0;
+
+        let mut rows = 0usize;
+        while rows < self.batch_rows && !self.done {
+            let count_offset = self.pos;
+            let count = i16::from_be_bytes(read_array(&mut self.inner, &mut self.pos)?);
+            if count == -1 {
+                self.done = true;
+                break;
+            }
+            if count as usize != self.schema.len() {
+                return Err(Error::Malformed {
+                    column: None,
+                    offset: count_offset,
+                    reason: "COPY tuple field count does not match schema",
+                });
+            }
+            if self.has_oids {
+                // The OID field precedes the counted fields; it has no schema column.
+                self.read_field(0)?;
+            }
+            for (column, col) in out.columns.iter_mut().enumerate() {
+                let offset = self.pos;
+                let value = self.read_field(column)?.then_some(self.field.as_slice());
+                push_field(
+                    col,
+                    rows,
+                    &Field {
+                        column,
+                        offset,
+                        value,
+                    },
+                )?;
+            }
+            rows += 1;
+        }
+
+        out.row_count = rows;
+        out.validate()?;
+        Ok(rows > 0)
+    }
+}
@@ -0,0 +1,243 @@
+use crate::batch::{ColumnData, ColumnarBatch};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView, VarDataView};
+use crate::codec::mathldbt_v1::interval_to_be_bytes;
+use crate::pg_copy::numeric::write_numeric_field;
+use crate::pg_copy::{
+    JSONB_VERSION, PG_EPOCH_OFFSET_DAYS, PG_EPOCH_OFFSET_MICROS, SIGNATURE, check_copy_schema,
+    copy_schema_mismatch, unsupported_copy_type,
+};
+use crate::schema::{ColumnarSchema, ColumnarType};
+use crate::{Error, LimitKind, Result};
+use std::io::Write;
+
+/// Inverse of the reader's conversion; `±infinity` sentinels pass through unchanged.
+fn timestamp_to_pg(v: i64) -> Option<i64> {
+    match v {
+        i64::MAX | i64::MIN => Some(v),
+        _ => v.checked_sub(PG_EPOCH_OFFSET_MICROS),
+    }
+}
+
+fn date_to_pg(v: i32) -> Option<i32> {
+    match v {
+        i32::MAX | i32::MIN => Some(v),
+        _ => v.checked_sub(PG_EPOCH_OFFSET_DAYS),
+    }
+}
+
+fn push_fixed<const N: usize>(buf: &mut Vec<u8>, value: [u8; N]) {
+    buf.extend_from_slice(&(N as i32).to_be_bytes());
+    buf.extend_from_slice(&value);
+}
+
+fn push_var(buf: &mut Vec<u8>, prefix: Option<u8>, value: &[u8]) -> Result<()> {
+    let len = value.len() + usize::from(prefix.is_some());
+    let len = i32::try_from(len).map_err(|_| Error::LimitExceeded {
+        kind: LimitKind::PayloadLength,
+        column: None,
+        offset: None,
+    })?;
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend(prefix);
+    buf.extend_from_slice(value);
+    Ok(())
+}
+
+fn push_field(
+    buf: &mut Vec<u8>,
+    column: usize,
+    col: &ColumnDataView<'_>,
+    var_data: &[u8],
+    row: usize,
+) -> Result<()> {
+    let validity = match col {
+        ColumnDataView::FixedBool { validity, .. }
+        | ColumnDataView::BitPackedBool { validity, .. }
+        | ColumnDataView::FixedI16 { validity, .. }
+        | ColumnDataView::FixedI32 { validity, .. }
+        | ColumnDataView::FixedI64 { validity, .. }
+        | ColumnDataView::FixedI8 { validity, .. }
+        | ColumnDataView::FixedU8 { validity, .. }
+        | ColumnDataView::FixedU16 { validity, .. }
+        | ColumnDataView::FixedU32 { validity, .. }
+        | ColumnDataView::FixedU64 { validity, .. }
+        | ColumnDataView::FixedF32Bits { validity, .. }
+        | ColumnDataView::FixedF64Bits { validity, .. }
+        | ColumnDataView::FixedUuid { validity, .. }
+        | ColumnDataView::FixedTimestampMicros { validity, .. }
+        | ColumnDataView::FixedTimestamp { validity, .. }
+        | ColumnDataView::FixedDate32 { validity, .. }
+        | ColumnDataView::FixedTimeMicros { validity, .. }
+        | ColumnDataView::FixedInterval { validity, .. }
+        | ColumnDataView::FixedDecimal128 { validity, .. }
+        | ColumnDataView::FixedSizeBinary { validity, .. }
+        | ColumnDataView::FixedSizeListF32Bits { validity, .. }
+        | ColumnDataView::FixedSizeListF64Bits { validity, .. }
+        | ColumnDataView::Var { validity, .. }
+        | ColumnDataView::List { validity, .. }
+        | ColumnDataView::Struct { validity, .. } => validity,
+    };
+    if (validity[row / 8] >> (row % 8)) & 1 == 0 {
+        buf.extend_from_slice(&(-1i32).to_be_bytes());
+        return Ok(());
+    }
+
+    match col {
+        ColumnDataView::FixedBool { values, .. } => push_fixed(buf, [u8::from(values[row] != 0)]),
+        ColumnDataView::BitPackedBool { values, .. } => {
+            push_fixed(buf, [(values[row / 8] >> (row % 8)) & 1])
+        }
+        ColumnDataView::FixedI16 { values, .. } => push_fixed(buf, values[row].to_be_bytes()),
+        ColumnDataView::FixedI32 { values, .. } => push_fixed(buf, values[row].to_be_bytes()),
+        ColumnDataView::FixedI64 { values, .. } => push_fixed(buf, values[row].to_be_bytes()),
+        ColumnDataView::FixedI8 { .. }
+        | ColumnDataView::FixedU8 { .. }
+        | ColumnDataView::FixedU16 { .. }
+        | ColumnDataView::FixedU32 { .. }
+        | ColumnDataView::FixedU64 { .. }
+        | ColumnDataView::FixedSizeBinary { .. }
+        | ColumnDataView::FixedSizeListF32Bits { .. }
+        | ColumnDataView::FixedSizeListF64Bits { .. }
+        | ColumnDataView::List { .. }
+        | ColumnDataView::Struct { .. } => return Err(unsupported_copy_type()),
+        ColumnDataView::FixedF32Bits { values, .. } => push_fixed(buf, values[row].to_be_bytes()),
+        ColumnDataView::FixedF64Bits { values, .. } => push_fixed(buf, values[row].to_be_bytes()),
+        ColumnDataView::FixedUuid { values, .. } => push_fixed(buf, values[row]),
+        ColumnDataView::FixedTimestampMicros { values, .. }
+        | ColumnDataView::FixedTimestamp { values, .. } => {
+            let v = timestamp_to_pg(values[row]).ok_or(Error::InvalidBatch {
+                column: Some(column),
+                reason: "timestamp out of Postgres range",
+            })?;
+            push_fixed(buf, v.to_be_bytes());
+        }
+        ColumnDataView::FixedDate32 { values, .. } => {
+            let v = date_to_pg(values[row]).ok_or(Error::InvalidBatch {
+                column: Some(column),
+                reason: "date out of Postgres range",
+            })?;
+            push_fixed(buf, v.to_be_bytes());
+        }
+        ColumnDataView::FixedTimeMicros { values, .. } => {
+            push_fixed(buf, values[row].to_be_bytes())
+        }
+        ColumnDataView::FixedInterval { values, .. } => {
+            push_fixed(buf, interval_to_be_bytes(values[row]))
+        }
+        ColumnDataView::FixedDecimal128 { scale, values, .. } => {
+            write_numeric_field(buf, values[row], *scale);
+        }
+        ColumnDataView::Var { ty, offsets, .. } => {
+            let value = &var_data[offsets[row] as usize..offsets[row + 1] as usize];
+            let prefix = (*ty == ColumnarType::JsonbText).then_some(JSONB_VERSION);
+            push_var(buf, prefix, value).map_err(|e| e.at_column(column))?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes batches as a Postgres `COPY ... FROM STDIN (FORMAT binary)` stream.
+///
+/// Values use the same type mapping as [`PgCopyReader`](crate::pg_copy::PgCopyReader), so the
+/// output loads into a table whose columns match the schema by position. The stream is only
+/// complete after [`PgCopyWriter::finish`] writes the trailer.
+pub struct PgCopyWriter<W: Write> {
+    inner: W,
+    schema: ColumnarSchema,
+    buf: Vec<u8>,
+    coalesced: Vec<Vec<u8>>,
+    row_count: u64,
+}
+
+impl<W: Write> PgCopyWriter<W> {
+    /// Writes the COPY header (no flags, empty header extension).
+    pub fn new(mut inner: W, schema: ColumnarSchema) -> Result<Self> {
+        if i16::try_from(schema.len()).is_err() {
+            return Err(Error::LimitExceeded {
+                kind: LimitKind::ColumnCount,
+                column: None,
+                offset: None,
+            });
+        }
+        check_copy_schema(&schema)?;
+        let mut header = Vec::with_capacity(19);
+        header.extend_from_slice(SIGNATURE);
+        header.extend_from_slice(&0u32.to_be_bytes()); // flags
+        header.extend_from_slice(&0u32.to_be_bytes()); // header extension length
+        inner.write_all(&header)?;
+        let coalesced = vec![Vec::new(); schema.len()];
+        Ok(Self {
+            inner,
+            schema,
+            buf: Vec::new(),
+            coalesced,
+            row_count: 0,
+        })
+    }
+
+    pub fn schema(&self) -> &ColumnarSchema {
+        &self.schema
+    }
+
+    pub fn row_count(&self) -> u64 {
+        self.row_count
+    }
+
+    pub fn write_batch(&mut self, batch: &ColumnarBatch) -> Result<()> {
+        let columns: Vec<ColumnDataView<'_>> =
+            batch.columns.iter().map(ColumnData::as_view).collect();
+        self.write_batch_view(&ColumnarBatchView {
+            schema: &batch.schema,
+            row_count: batch.row_count,
+            columns: &columns,
+        })
+    }
+
+    pub fn write_batch_view(&mut self, view: &ColumnarBatchView<'_>) -> Result<()> {
+        if *view.schema != self.schema {
+            return Err(copy_schema_mismatch());
+        }
+        view.validate()?;
+
+        // Chunked var data is coalesced once per column so rows can be sliced by offset.
+        for (col, scratch) in view.columns.iter().zip(self.coalesced.iter_mut()) {
+            scratch.clear();
+            if let ColumnDataView::Var {
+                data: VarDataView::Chunks { inline, chunks },
+                ..
+            } = col
+            {
+                scratch.extend_from_slice(inline);
+                for c in *chunks {
+                    scratch.extend_from_slice(c);
+                }
+            }
+        }
+
+        let field_count = (self.schema.len() as i16).to_be_bytes();
+        self.buf.clear();
+        for row in 0..view.row_count {
+            self.buf.extend_from_slice(&field_count);
+            for (column, col) in view.columns.iter().enumerate() {
+                let var_data = match col {
+                    ColumnDataView::Var {
+                        data: VarDataView::Contiguous(bytes),
+                        ..
+                    } => bytes,
+                    _ => self.coalesced[column].as_slice(),
+                };
+                push_field(&mut self.buf, column, col, var_data, row)?;
+            }
+        }
+        self.inner.write_all(&self.buf)?;
+        self.row_count += view.row_count as u64;
+        Ok(())
+    }
+
+    /// Writes the COPY trailer, flushes, and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        self.inner.write_all(&(-1i16).to_be_bytes())?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
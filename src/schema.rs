@@ -1,18 +1,145 @@
 use crate::{Error, Result};
+use std::sync::Arc;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TimeUnit {
+    Second,
+    Millisecond,
+    Microsecond,
+    Nanosecond,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ColumnarType {
     Bool,
     I16,
     I32,
     I64,
+    I8,
+    U8,
+    U16,
+    U32,
+    U64,
     F32,
     F64,
     Uuid,
     TimestampTzMicros,
+    /// `i64` count of `unit`s since 1970-01-01T00:00:00. With a time zone (an IANA name or a
+    /// fixed offset such as `+05:30`) values are UTC instants; without one they are wall-clock
+    /// times in no particular zone. Microseconds in `"UTC"` are spelled `TimestampTzMicros`, so
+    /// `ColumnarSchema::new` rejects that combination here.
+    Timestamp {
+        unit: TimeUnit,
+        tz: Option<Arc<str>>,
+    },
+    /// Calendar date, in days since 1970-01-01.
+    Date,
+    /// Time of day, in microseconds since midnight.
+    TimeMicros,
+    /// Calendar interval; see [`IntervalMonthDayMicros`].
+    IntervalMonthDayMicros,
     Utf8,
     Bytes,
     JsonbText,
+    /// Exact decimal: values are `i128` mantissas scaled by `10^-scale`.
+    Decimal {
+        precision: u8,
+        scale: i8,
+    },
+    /// Variable-length list; each row is a range of items of the given type.
+    List(Box<ColumnarType>),
+    /// Nested record; each row holds one value of every field.
+    Struct(Vec<ColumnarField>),
+    /// Opaque bytes of the given width, such as hashes or exchange order ids.
+    FixedSizeBinary(u32),
+    /// The given number of `F32` or `F64` items per row, such as embedding vectors. Items have
+    /// no validity of their own.
+    FixedSizeList(Box<ColumnarType>, u32),
+}
+
+/// An interval value. The parts are kept separate because months and days have no fixed
+/// length in microseconds.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalMonthDayMicros {
+    pub months: i32,
+    pub days: i32,
+    pub micros: i64,
+}
+
+/// Largest decimal precision whose mantissas fit in an `i128`.
+pub const MAX_DECIMAL_PRECISION: u8 = 38;
+
+pub(crate) fn decimal_params_valid(precision: u8, scale: i8) -> bool {
+    (1..=MAX_DECIMAL_PRECISION).contains(&precision) && i16::from(scale) <= i16::from(precision)
+}
+
+/// Time zone names are non-empty and their length must fit the descriptor's `u16` prefix.
+pub(crate) fn timestamp_tz_valid(tz: &str) -> bool {
+    !tz.is_empty() && tz.len() <= u16::MAX as usize
+}
+
+/// `Timestamp { unit: Microsecond, tz: Some("UTC") }` duplicates `TimestampTzMicros`.
+pub(crate) fn timestamp_is_tz_micros(unit: TimeUnit, tz: Option<&str>) -> bool {
+    unit == TimeUnit::Microsecond && tz == Some("UTC")
+}
+
+/// Fixed sizes are positive and bounded by Arrow's `i32` sizes.
+pub(crate) fn fixed_size_valid(size: u32) -> bool {
+    (1..=i32::MAX as u32).contains(&size)
+}
+
+/// Deepest nesting of `List` and `Struct` types accepted in a schema or descriptor.
+pub const MAX_NESTING_DEPTH: usize = 32;
+
+/// Checks a type and its nested types; `depth` is the number of enclosing lists and structs.
+fn check_type(ty: &ColumnarType, depth: usize) -> core::result::Result<(), &'static str> {
+    match ty {
+        ColumnarType::Decimal { precision, scale } if !decimal_params_valid(*precision, *scale) => {
+            Err("invalid decimal precision/scale")
+        }
+        ColumnarType::Timestamp { tz: Some(tz), .. } if !timestamp_tz_valid(tz) => {
+            Err("invalid timestamp time zone")
+        }
+        ColumnarType::Timestamp { unit, tz } if timestamp_is_tz_micros(*unit, tz.as_deref()) => {
+            Err("UTC microsecond timestamps must be TimestampTzMicros")
+        }
+        ColumnarType::List(_) | ColumnarType::Struct(_) if depth == MAX_NESTING_DEPTH => {
+            Err("type nesting too deep")
+        }
+        ColumnarType::List(item) => check_type(item, depth + 1),
+        ColumnarType::FixedSizeBinary(size) | ColumnarType::FixedSizeList(_, size)
+            if !fixed_size_valid(*size) =>
+        {
+            Err("invalid fixed size")
+        }
+        ColumnarType::FixedSizeList(item, _)
+            if !matches!(**item, ColumnarType::F32 | ColumnarType::F64) =>
+        {
+            Err("fixed-size list items must be F32 or F64")
+        }
+        ColumnarType::Struct(fields) => {
+            if fields.is_empty() {
+                return Err("struct must have at least one field");
+            }
+            // Field count and names are stored with `u16` lengths in the descriptor.
+            if fields.len() > u16::MAX as usize {
+                return Err("too many struct fields");
+            }
+            for field in fields {
+                if field
+                    .name
+                    .as_ref()
+                    .is_some_and(|n| n.len() > u16::MAX as usize)
+                {
+                    return Err("struct field name too long");
+                }
+                check_type(&field.ty, depth + 1)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -29,9 +156,16 @@ pub struct ColumnarSchema {
 impl ColumnarSchema {
     pub fn new(fields: Vec<ColumnarField>) -> Result<Self> {
         if fields.is_empty() {
-            return Err(Error::Other(
-                "columnar schema must have at least one field".to_string(),
-            ));
+            return Err(Error::Schema {
+                column: None,
+                reason: "columnar schema must have at least one field",
+            });
+        }
+        for (i, field) in fields.iter().enumerate() {
+            check_type(&field.ty, 0).map_err(|reason| Error::Schema {
+                column: Some(i),
+                reason,
+            })?;
         }
         Ok(Self { fields })
     }
@@ -0,0 +1,59 @@
+use crate::batch::{ColumnData, ColumnarBatch};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_into, decode_mathldbt_v1_view_with_workspace,
+    encode_mathldbt_v1_fast_path_into_with_workspace, encode_mathldbt_v1_into_with_workspace,
+    inspect_mathldbt_v1,
+};
+use crate::schema::{ColumnarField, ColumnarType};
+
+/// A field named `name`, or unnamed when `name` is empty.
+pub(crate) fn field(name: &str, ty: ColumnarType) -> ColumnarField {
+    ColumnarField {
+        name: (!name.is_empty()).then(|| name.to_string()),
+        ty,
+    }
+}
+
+/// Encodes `batch` through the owned and the fast path, asserting identical bytes.
+pub(crate) fn encode_both(batch: &ColumnarBatch, ws: &mut MathldbtV1EncodeWorkspace) -> Vec<u8> {
+    let mut owned = Vec::new();
+    encode_mathldbt_v1_into_with_workspace(batch, &mut owned, ws).unwrap();
+    let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+    let view = ColumnarBatchView {
+        schema: &batch.schema,
+        row_count: batch.row_count,
+        columns: &cols,
+    };
+    let mut fast = Vec::new();
+    encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, ws).unwrap();
+    assert_eq!(fast, owned);
+    owned
+}
+
+/// The `encoding_id` of each top-level column.
+pub(crate) fn encodings(bytes: &[u8]) -> Vec<u16> {
+    let info = inspect_mathldbt_v1(bytes).unwrap();
+    info.columns.iter().map(|c| c.encoding_id).collect()
+}
+
+/// Decodes `bytes` through the owned, `decode_into` (into an empty and then a filled batch)
+/// and view paths, asserting each yields `batch`.
+pub(crate) fn assert_decodes_all_paths(bytes: &[u8], batch: &ColumnarBatch) {
+    assert_eq!(decode_mathldbt_v1(bytes).unwrap(), *batch);
+    let columns = batch
+        .schema
+        .fields()
+        .iter()
+        .map(|f| ColumnData::new_all_invalid(f.ty.clone(), 0).unwrap())
+        .collect();
+    let mut out = ColumnarBatch::new(batch.schema.clone(), 0, columns).unwrap();
+    for _ in 0..2 {
+        decode_mathldbt_v1_into(bytes, &mut out).unwrap();
+        assert_eq!(out, *batch);
+    }
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    let decoded = decode_mathldbt_v1_view_with_workspace(bytes, &mut dws).unwrap();
+    assert_eq!(decoded.to_batch().unwrap(), *batch);
+}
@@ -1,7 +1,31 @@
+mod common;
+#[cfg(feature = "arrow")]
+mod test_arrow;
 mod test_batch;
 mod test_mathldbt_v1;
 mod test_mathldbt_v1_fast_path;
 mod test_mathldbt_v1_decode_into_equivalence;
 mod test_mathldbt_v1_decode_into_reuse_smoke;
 mod test_mathldbt_v1_adversarial;
+mod test_mathldbt_v1_bool;
+mod test_mathldbt_v1_byte_stream_split;
 mod test_mathldbt_v1_compressed;
+mod test_mathldbt_v1_decimal;
+mod test_mathldbt_v1_dict;
+mod test_mathldbt_v1_decode_view;
+mod test_mathldbt_v1_decode_projected;
+mod test_mathldbt_v1_file;
+mod test_mathldbt_v1_fixed_size;
+mod test_mathldbt_v1_for;
+mod test_mathldbt_v1_inspect;
+mod test_mathldbt_v1_integers;
+mod test_mathldbt_v1_list;
+mod test_mathldbt_v1_nullable_delta;
+mod test_mathldbt_v1_rle;
+mod test_mathldbt_v1_stream;
+mod test_mathldbt_v1_struct;
+mod test_mathldbt_v1_temporal;
+mod test_mathldbt_v1_timestamp;
+mod test_mathldbt_v1_xor_float;
+mod test_pg_copy_reader;
+mod test_pg_copy_writer;
@@ -0,0 +1,741 @@
+use crate::Error;
+use crate::arrow::{from_record_batch, into_record_batch, to_record_batch};
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::schema::{
+    ColumnarSchema, ColumnarType, IntervalMonthDayMicros, TimeUnit as ColumnarTimeUnit,
+};
+use crate::tests::common::field;
+use arrow_array::types::{Int64Type, IntervalMonthDayNanoType, UInt64Type};
+use arrow_array::{
+    Array, BooleanArray, FixedSizeListArray, Float32Array, LargeStringArray, PrimitiveArray,
+    RecordBatch, StringArray,
+};
+use arrow_buffer::{IntervalMonthDayNano, NullBuffer};
+use arrow_schema::{DataType, Field, IntervalUnit, Schema, TimeUnit};
+use std::sync::Arc;
+
+fn var(ty: ColumnarType, validity: &ValidityBitmap, rows: usize) -> ColumnData {
+    let mut offsets = vec![0u32];
+    let mut data = Vec::new();
+    for i in 0..rows {
+        if validity.is_valid(i).unwrap() {
+            data.extend_from_slice(format!("{{\"i\":{i}}}").as_bytes());
+        }
+        offsets.push(data.len() as u32);
+    }
+    ColumnData::Var {
+        ty,
+        validity: validity.clone(),
+        offsets,
+        data,
+    }
+}
+
+fn all_types_batch(rows: usize) -> ColumnarBatch {
+    let mut v = ValidityBitmap::new_all_valid(rows).unwrap();
+    for i in (0..rows).step_by(3) {
+        v.set(i, false).unwrap();
+    }
+    let schema = ColumnarSchema::new(vec![
+        field("b", ColumnarType::Bool),
+        field("i16", ColumnarType::I16),
+        field("i32", ColumnarType::I32),
+        field("i64", ColumnarType::I64),
+        field("f32", ColumnarType::F32),
+        field("f64", ColumnarType::F64),
+        field("id", ColumnarType::Uuid),
+        field("t", ColumnarType::TimestampTzMicros),
+        field("s", ColumnarType::Utf8),
+        field("", ColumnarType::Bytes),
+        field("j", ColumnarType::JsonbText),
+        field(
+            "px",
+            ColumnarType::Decimal {
+                precision: 20,
+                scale: 8,
+            },
+        ),
+        field(
+            "local_t",
+            ColumnarType::Timestamp {
+                unit: ColumnarTimeUnit::Microsecond,
+                tz: None,
+            },
+        ),
+        field("d", ColumnarType::Date),
+        field("tod", ColumnarType::TimeMicros),
+        field("iv", ColumnarType::IntervalMonthDayMicros),
+        field(
+            "exch_ns",
+            ColumnarType::Timestamp {
+                unit: ColumnarTimeUnit::Nanosecond,
+                tz: Some("+05:30".into()),
+            },
+        ),
+    ])
+    .unwrap();
+    let columns = vec![
+        ColumnData::FixedBool {
+            validity: v.clone(),
+            values: (0..rows).map(|i| (i % 2) as u8).collect(),
+        },
+        ColumnData::FixedI16 {
+            validity: v.clone(),
+            values: (0..rows).map(|i| i as i16 - 7).collect(),
+        },
+        ColumnData::FixedI32 {
+            validity: v.clone(),
+            values: (0..rows).map(|i| i as i32 * -1_000).collect(),
+        },
+        ColumnData::FixedI64 {
+            validity: v.clone(),
+            values: (0..rows).map(|i| i as i64 * 1_000_000_007).collect(),
+        },
+        ColumnData::FixedF32Bits {
+            validity: v.clone(),
+            // Includes a NaN with a payload, which must survive the round trip.
+            values: (0..rows)
+                .map(|i| {
+                    if i == 1 {
+                        0x7fc0_1234
+                    } else {
+                        (i as f32 * 0.5).to_bits()
+                    }
+                })
+                .collect(),
+        },
+        ColumnData::FixedF64Bits {
+            validity: v.clone(),
+            values: (0..rows).map(|i| (i as f64 / 3.0).to_bits()).collect(),
+        },
+        ColumnData::FixedUuid {
+            validity: v.clone(),
+            values: (0..rows).map(|i| [i as u8; 16]).collect(),
+        },
+        ColumnData::FixedTimestampMicros {
+            validity: v.clone(),
+            values: (0..rows)
+                .map(|i| 1_700_000_000_000_000 + i as i64)
+                .collect(),
+        },
+        var(ColumnarType::Utf8, &v, rows),
+        var(ColumnarType::Bytes, &v, rows),
+        var(ColumnarType::JsonbText, &v, rows),
+        ColumnData::FixedDecimal128 {
+            precision: 20,
+            scale: 8,
+            validity: v.clone(),
+            values: (0..rows).map(|i| 4_200_050_000_000 - i as i128).collect(),
+        },
+        ColumnData::FixedTimestamp {
+            unit: ColumnarTimeUnit::Microsecond,
+            tz: None,
+            validity: v.clone(),
+            values: (0..rows).map(|i| -(i as i64)).collect(),
+        },
+        ColumnData::FixedDate32 {
+            validity: v.clone(),
+            values: (0..rows).map(|i| 19_000 + i as i32).collect(),
+        },
+        ColumnData::FixedTimeMicros {
+            validity: v.clone(),
+            values: (0..rows).map(|i| i as i64 * 1_000_000).collect(),
+        },
+        ColumnData::FixedInterval {
+            validity: v.clone(),
+            values: (0..rows)
+                .map(|i| IntervalMonthDayMicros {
+                    months: i as i32,
+                    days: 1,
+                    micros: -(i as i64),
+                })
+                .collect(),
+        },
+        ColumnData::FixedTimestamp {
+            unit: ColumnarTimeUnit::Nanosecond,
+            tz: Some("+05:30".into()),
+            validity: v.clone(),
+            values: (0..rows)
+                .map(|i| 1_700_000_000_123_456_789 + i as i64)
+                .collect(),
+        },
+    ];
+    ColumnarBatch::new(schema, rows, columns).unwrap()
+}
+
+#[test]
+fn arrow_round_trip_all_types() {
+    for rows in [0, 1, 13] {
+        let batch = all_types_batch(rows);
+        let rb = to_record_batch(&batch).unwrap();
+        assert_eq!(rb.num_rows(), rows);
+
+        let schema = rb.schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::Boolean);
+        assert_eq!(schema.field(6).data_type(), &DataType::FixedSizeBinary(16));
+        assert_eq!(
+            schema.field(7).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        );
+        assert_eq!(schema.field(9).name(), "");
+        assert_eq!(
+            schema.field(10).metadata().get("ARROW:extension:name"),
+            Some(&"arrow.json".to_string())
+        );
+        assert_eq!(schema.field(11).data_type(), &DataType::Decimal128(20, 8));
+        assert_eq!(
+            schema.field(12).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+        assert_eq!(schema.field(13).data_type(), &DataType::Date32);
+        assert_eq!(
+            schema.field(14).data_type(),
+            &DataType::Time64(TimeUnit::Microsecond)
+        );
+        assert_eq!(
+            schema.field(15).data_type(),
+            &DataType::Interval(IntervalUnit::MonthDayNano)
+        );
+        assert_eq!(
+            schema.field(16).data_type(),
+            &DataType::Timestamp(TimeUnit::Nanosecond, Some("+05:30".into()))
+        );
+        if rows > 0 {
+            assert_eq!(rb.column(3).null_count(), rows.div_ceil(3));
+        }
+
+        assert_eq!(from_record_batch(&rb).unwrap(), batch);
+    }
+}
+
+#[test]
+fn arrow_into_record_batch_moves_buffers() {
+    let batch = all_types_batch(8);
+    let (i64_ptr, str_ptr) = match (&batch.columns[3], &batch.columns[8]) {
+        (ColumnData::FixedI64 { values, .. }, ColumnData::Var { data, .. }) => {
+            (values.as_ptr() as *const u8, data.as_ptr())
+        }
+        _ => unreachable!(),
+    };
+
+    let rb = into_record_batch(batch).unwrap();
+    let ints = rb
+        .column(3)
+        .as_any()
+        .downcast_ref::<PrimitiveArray<Int64Type>>()
+        .unwrap();
+    assert_eq!(ints.values().as_ptr() as *const u8, i64_ptr);
+    let strs = rb.column(8).as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(strs.values().as_ptr(), str_ptr);
+}
+
+#[test]
+fn arrow_bit_packed_bool_moves_bits() {
+    let mut validity = ValidityBitmap::new_all_valid(10).unwrap();
+    validity.set(2, false).unwrap();
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field("flag", ColumnarType::Bool)]).unwrap(),
+        10,
+        vec![ColumnData::BitPackedBool {
+            validity: validity.clone(),
+            values: vec![0b0100_0011, 0b10],
+        }],
+    )
+    .unwrap();
+    let bits_ptr = match &batch.columns[0] {
+        ColumnData::BitPackedBool { values, .. } => values.as_ptr(),
+        _ => unreachable!(),
+    };
+
+    let rb = into_record_batch(batch).unwrap();
+    let flags = rb
+        .column(0)
+        .as_any()
+        .downcast_ref::<BooleanArray>()
+        .unwrap();
+    assert_eq!(flags.values().values().as_ptr(), bits_ptr);
+    assert!(flags.value(1) && flags.value(9) && !flags.value(5) && flags.is_null(2));
+
+    // Arrow input always converts back to one byte per row.
+    assert_eq!(
+        from_record_batch(&rb).unwrap().columns[0],
+        ColumnData::FixedBool {
+            validity,
+            values: vec![1, 1, 0, 0, 0, 0, 1, 0, 0, 1],
+        }
+    );
+}
+
+#[test]
+fn arrow_sliced_record_batch_is_rebased() {
+    let batch = all_types_batch(20);
+    let rb = to_record_batch(&batch).unwrap();
+    let sliced = rb.slice(5, 9);
+
+    let got = from_record_batch(&sliced).unwrap();
+    let expected = from_record_batch(&to_record_batch(&got).unwrap()).unwrap();
+    assert_eq!(got, expected);
+    assert_eq!(got.row_count, 9);
+    match &got.columns[8] {
+        ColumnData::Var { offsets, data, .. } => {
+            assert_eq!(offsets[0], 0);
+            assert_eq!(*offsets.last().unwrap() as usize, data.len());
+        }
+        _ => unreachable!(),
+    }
+    // Row 6 of the original (row 1 of the slice) is null: 6 % 3 == 0.
+    let validity = match &got.columns[3] {
+        ColumnData::FixedI64 { validity, values } => {
+            assert_eq!(values[0], 5 * 1_000_000_007);
+            validity
+        }
+        _ => unreachable!(),
+    };
+    assert!(validity.is_valid(0).unwrap());
+    assert!(!validity.is_valid(1).unwrap());
+}
+
+#[test]
+fn arrow_unsigned_and_i8_round_trip() {
+    let mut v = ValidityBitmap::new_all_valid(3).unwrap();
+    v.set(1, false).unwrap();
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![
+            field("i8", ColumnarType::I8),
+            field("u8", ColumnarType::U8),
+            field("u16", ColumnarType::U16),
+            field("u32", ColumnarType::U32),
+            field("u64", ColumnarType::U64),
+        ])
+        .unwrap(),
+        3,
+        vec![
+            ColumnData::FixedI8 {
+                validity: v.clone(),
+                values: vec![i8::MIN, 0, i8::MAX],
+            },
+            ColumnData::FixedU8 {
+                validity: v.clone(),
+                values: vec![0, 0, u8::MAX],
+            },
+            ColumnData::FixedU16 {
+                validity: v.clone(),
+                values: vec![1, 0, u16::MAX],
+            },
+            ColumnData::FixedU32 {
+                validity: v.clone(),
+                values: vec![2, 0, u32::MAX],
+            },
+            ColumnData::FixedU64 {
+                validity: v,
+                values: vec![3, 0, u64::MAX],
+            },
+        ],
+    )
+    .unwrap();
+    let u64_ptr = match &batch.columns[4] {
+        ColumnData::FixedU64 { values, .. } => values.as_ptr(),
+        _ => unreachable!(),
+    };
+
+    let rb = to_record_batch(&batch).unwrap();
+    let types: Vec<_> = rb
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.data_type().clone())
+        .collect();
+    assert_eq!(
+        types,
+        [
+            DataType::Int8,
+            DataType::UInt8,
+            DataType::UInt16,
+            DataType::UInt32,
+            DataType::UInt64
+        ]
+    );
+    assert_eq!(from_record_batch(&rb).unwrap(), batch);
+
+    let rb = into_record_batch(batch.clone()).unwrap();
+    assert_eq!(from_record_batch(&rb).unwrap(), batch);
+    let moved = into_record_batch(batch).unwrap();
+    let u64s = moved
+        .column(4)
+        .as_any()
+        .downcast_ref::<PrimitiveArray<UInt64Type>>()
+        .unwrap();
+    assert_eq!(u64s.values().as_ptr(), u64_ptr);
+}
+
+#[test]
+fn arrow_list_round_trip() {
+    let list = |item| ColumnarType::List(Box::new(item));
+    let mut v = ValidityBitmap::new_all_valid(4).unwrap();
+    v.set(2, false).unwrap();
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![
+            field("docs", list(ColumnarType::JsonbText)),
+            field("grid", list(list(ColumnarType::I64))),
+        ])
+        .unwrap(),
+        4,
+        vec![
+            ColumnData::List {
+                validity: v.clone(),
+                offsets: vec![0, 2, 3, 3, 5],
+                child: Box::new(var(
+                    ColumnarType::JsonbText,
+                    &ValidityBitmap::new_all_valid(5).unwrap(),
+                    5,
+                )),
+            },
+            ColumnData::List {
+                validity: v,
+                offsets: vec![0, 1, 3, 3, 4],
+                child: Box::new(ColumnData::List {
+                    validity: ValidityBitmap::new_all_valid(4).unwrap(),
+                    offsets: vec![0, 2, 2, 5, 6],
+                    child: Box::new(ColumnData::FixedI64 {
+                        validity: ValidityBitmap::new_all_valid(6).unwrap(),
+                        values: vec![1, 2, 3, 4, 5, 6],
+                    }),
+                }),
+            },
+        ],
+    )
+    .unwrap();
+
+    let rb = to_record_batch(&batch).unwrap();
+    let DataType::List(item) = rb.schema().field(0).data_type().clone() else {
+        unreachable!()
+    };
+    assert_eq!(item.name(), "item");
+    assert_eq!(
+        item.metadata()
+            .get("ARROW:extension:name")
+            .map(String::as_str),
+        Some("arrow.json")
+    );
+    assert_eq!(from_record_batch(&rb).unwrap(), batch);
+    assert_eq!(
+        from_record_batch(&into_record_batch(batch.clone()).unwrap()).unwrap(),
+        batch
+    );
+
+    // Slicing leaves the list offsets, and the item arrays they index, mid-buffer.
+    let got = from_record_batch(&rb.slice(1, 3)).unwrap();
+    assert_eq!(got.row_count, 3);
+    match &got.columns[1] {
+        ColumnData::List { offsets, child, .. } => {
+            assert_eq!(offsets, &[0, 2, 2, 3]);
+            assert_eq!(
+                child.as_ref(),
+                &ColumnData::List {
+                    validity: ValidityBitmap::new_all_valid(3).unwrap(),
+                    offsets: vec![0, 0, 3, 4],
+                    child: Box::new(ColumnData::FixedI64 {
+                        validity: ValidityBitmap::new_all_valid(4).unwrap(),
+                        values: vec![3, 4, 5, 6],
+                    }),
+                }
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn arrow_struct_round_trip() {
+    let mut v = ValidityBitmap::new_all_valid(4).unwrap();
+    v.set(1, false).unwrap();
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field(
+            "quote",
+            ColumnarType::Struct(vec![
+                field("bid", ColumnarType::I64),
+                field("", ColumnarType::Utf8),
+            ]),
+        )])
+        .unwrap(),
+        4,
+        vec![ColumnData::Struct {
+            names: vec![Some("bid".to_string()), None],
+            validity: v,
+            children: vec![
+                ColumnData::FixedI64 {
+                    validity: ValidityBitmap::new_all_valid(4).unwrap(),
+                    values: vec![10, 11, 12, 13],
+                },
+                var(
+                    ColumnarType::Utf8,
+                    &ValidityBitmap::new_all_valid(4).unwrap(),
+                    4,
+                ),
+            ],
+        }],
+    )
+    .unwrap();
+
+    let rb = to_record_batch(&batch).unwrap();
+    let DataType::Struct(fields) = rb.schema().field(0).data_type().clone() else {
+        unreachable!()
+    };
+    let names: Vec<_> = fields.iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(names, ["bid", ""]);
+    assert_eq!(rb.column(0).null_count(), 1);
+    assert_eq!(from_record_batch(&rb).unwrap(), batch);
+    assert_eq!(
+        from_record_batch(&into_record_batch(batch.clone()).unwrap()).unwrap(),
+        batch
+    );
+
+    let got = from_record_batch(&rb.slice(1, 2)).unwrap();
+    match &got.columns[0] {
+        ColumnData::Struct {
+            validity, children, ..
+        } => {
+            assert!(!validity.is_valid(0).unwrap());
+            assert_eq!(
+                children[0],
+                ColumnData::FixedI64 {
+                    validity: ValidityBitmap::new_all_valid(2).unwrap(),
+                    values: vec![11, 12],
+                }
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn arrow_fixed_size_round_trip() {
+    let mut v = ValidityBitmap::new_all_valid(3).unwrap();
+    v.set(1, false).unwrap();
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![
+            field("id", ColumnarType::Uuid),
+            field("digest", ColumnarType::FixedSizeBinary(16)),
+            field("order_id", ColumnarType::FixedSizeBinary(20)),
+            field(
+                "embedding",
+                ColumnarType::FixedSizeList(Box::new(ColumnarType::F32), 2),
+            ),
+        ])
+        .unwrap(),
+        3,
+        vec![
+            ColumnData::FixedUuid {
+                validity: v.clone(),
+                values: vec![[1; 16], [2; 16], [3; 16]],
+            },
+            ColumnData::FixedSizeBinary {
+                size: 16,
+                validity: v.clone(),
+                values: (0..48).collect(),
+            },
+            ColumnData::FixedSizeBinary {
+                size: 20,
+                validity: ValidityBitmap::new_all_valid(3).unwrap(),
+                values: (0..60).collect(),
+            },
+            ColumnData::FixedSizeListF32Bits {
+                size: 2,
+                validity: v,
+                values: (0..6).map(|i| (i as f32 * 0.5).to_bits()).collect(),
+            },
+        ],
+    )
+    .unwrap();
+
+    let rb = to_record_batch(&batch).unwrap();
+    let schema = rb.schema();
+    // Only the extension name tells a `Uuid` from 16 opaque bytes.
+    assert_eq!(
+        schema.field(0).metadata().get("ARROW:extension:name"),
+        Some(&"arrow.uuid".to_string())
+    );
+    assert_eq!(schema.field(1).data_type(), &DataType::FixedSizeBinary(16));
+    assert!(schema.field(1).metadata().is_empty());
+    assert!(matches!(
+        schema.field(3).data_type(),
+        DataType::FixedSizeList(item, 2) if item.data_type() == &DataType::Float32
+    ));
+    assert_eq!(from_record_batch(&rb).unwrap(), batch);
+    assert_eq!(
+        from_record_batch(&into_record_batch(batch.clone()).unwrap()).unwrap(),
+        batch
+    );
+
+    let got = from_record_batch(&rb.slice(1, 2)).unwrap();
+    assert_eq!(
+        got.columns[2],
+        ColumnData::FixedSizeBinary {
+            size: 20,
+            validity: ValidityBitmap::new_all_valid(2).unwrap(),
+            values: (20..60).collect(),
+        }
+    );
+    match &got.columns[3] {
+        ColumnData::FixedSizeListF32Bits { values, .. } => {
+            assert_eq!(values, &[1.0f32, 1.5, 2.0, 2.5].map(f32::to_bits))
+        }
+        _ => unreachable!(),
+    }
+
+    // Null items are only accepted under null rows.
+    let item = Arc::new(Field::new("item", DataType::Float32, true));
+    let values = Float32Array::from(vec![Some(1.0), None, Some(2.0), Some(3.0)]);
+    let list = |nulls| {
+        let array = FixedSizeListArray::new(item.clone(), 2, Arc::new(values.clone()), nulls);
+        let schema = Schema::new(vec![Field::new("v", array.data_type().clone(), true)]);
+        from_record_batch(&RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array)]).unwrap())
+    };
+    assert_eq!(
+        list(None).unwrap_err(),
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "fixed-size list items must not be null",
+        }
+    );
+    assert!(list(Some(NullBuffer::from(vec![false, true]))).is_ok());
+}
+
+#[test]
+fn arrow_utc_timestamps_round_trip() {
+    let utc = |unit| ColumnarType::Timestamp {
+        unit,
+        tz: Some("UTC".into()),
+    };
+    assert_eq!(
+        ColumnarSchema::new(vec![field("t", utc(ColumnarTimeUnit::Microsecond))]).unwrap_err(),
+        Error::Schema {
+            column: Some(0),
+            reason: "UTC microsecond timestamps must be TimestampTzMicros",
+        }
+    );
+
+    // Either way round, every UTC unit comes back as the same type.
+    let v = ValidityBitmap::new_all_valid(2).unwrap();
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![
+            field("us", ColumnarType::TimestampTzMicros),
+            field("ms", utc(ColumnarTimeUnit::Millisecond)),
+            field("ns", utc(ColumnarTimeUnit::Nanosecond)),
+        ])
+        .unwrap(),
+        2,
+        vec![
+            ColumnData::FixedTimestampMicros {
+                validity: v.clone(),
+                values: vec![1, -1],
+            },
+            ColumnData::FixedTimestamp {
+                unit: ColumnarTimeUnit::Millisecond,
+                tz: Some("UTC".into()),
+                validity: v.clone(),
+                values: vec![2, -2],
+            },
+            ColumnData::FixedTimestamp {
+                unit: ColumnarTimeUnit::Nanosecond,
+                tz: Some("UTC".into()),
+                validity: v,
+                values: vec![3, -3],
+            },
+        ],
+    )
+    .unwrap();
+    let rb = to_record_batch(&batch).unwrap();
+    assert_eq!(
+        rb.schema().field(0).data_type(),
+        &DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+    );
+    let back = from_record_batch(&rb).unwrap();
+    assert_eq!(back, batch);
+    assert_eq!(to_record_batch(&back).unwrap(), rb);
+}
+
+#[test]
+fn arrow_unsupported_type_is_rejected() {
+    let schema = Schema::new(vec![Field::new("u", DataType::LargeUtf8, true)]);
+    let rb = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(LargeStringArray::from(vec!["a", "b"]))],
+    )
+    .unwrap();
+    assert_eq!(
+        from_record_batch(&rb).unwrap_err(),
+        Error::Schema {
+            column: Some(0),
+            reason: "unsupported Arrow data type",
+        }
+    );
+}
+
+#[test]
+fn arrow_invalid_utf8_is_rejected() {
+    let v = ValidityBitmap::new_all_valid(1).unwrap();
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![
+            field("i", ColumnarType::I16),
+            field("s", ColumnarType::Utf8),
+        ])
+        .unwrap(),
+        1,
+        vec![
+            ColumnData::FixedI16 {
+                validity: v.clone(),
+                values: vec![1],
+            },
+            ColumnData::Var {
+                ty: ColumnarType::Utf8,
+                validity: v,
+                offsets: vec![0, 2],
+                data: vec![0xff, 0xfe],
+            },
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        to_record_batch(&batch).unwrap_err(),
+        Error::InvalidBatch {
+            column: Some(1),
+            reason: "invalid UTF-8 in string column",
+        }
+    );
+}
+
+#[test]
+fn arrow_sub_microsecond_interval_is_rejected() {
+    let schema = Schema::new(vec![Field::new(
+        "iv",
+        DataType::Interval(IntervalUnit::MonthDayNano),
+        true,
+    )]);
+    let values = vec![
+        IntervalMonthDayNano::new(1, 2, 3_000),
+        IntervalMonthDayNano::new(0, 0, 1),
+    ];
+    let array = PrimitiveArray::<IntervalMonthDayNanoType>::from(values);
+    let rb = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array)]).unwrap();
+    assert_eq!(
+        from_record_batch(&rb).unwrap_err(),
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "interval has sub-microsecond precision",
+        }
+    );
+
+    let batch = from_record_batch(&rb.slice(0, 1)).unwrap();
+    assert_eq!(
+        batch.columns[0],
+        ColumnData::FixedInterval {
+            validity: ValidityBitmap::new_all_valid(1).unwrap(),
+            values: vec![IntervalMonthDayMicros {
+                months: 1,
+                days: 2,
+                micros: 3,
+            }],
+        }
+    );
+}
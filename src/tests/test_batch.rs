@@ -20,7 +20,10 @@ fn batch_rejects_schema_columns_len_mismatch() {
     let err = ColumnarBatch::new(schema, 3, vec![]).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("schema/columns length mismatch".to_string())
+        Error::Schema {
+            column: None,
+            reason: "schema/columns length mismatch",
+        }
     );
 }
 
@@ -36,7 +39,13 @@ fn fixed_column_length_must_match_row_count() {
         values: vec![0i32; 2],
     };
     let err = ColumnarBatch::new(schema, 3, vec![col]).unwrap_err();
-    assert_eq!(err, Error::Other("values length mismatch".to_string()));
+    assert_eq!(
+        err,
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "values length mismatch",
+        }
+    );
 }
 
 #[test]
@@ -55,7 +64,10 @@ fn var_offsets_must_be_monotonic_and_match_data_len() {
     let err = ColumnarBatch::new(schema, 2, vec![col]).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("offsets must be non-decreasing".to_string())
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "offsets must be non-decreasing",
+        }
     );
 
     let schema = ColumnarSchema::new(vec![ColumnarField {
@@ -70,5 +82,11 @@ fn var_offsets_must_be_monotonic_and_match_data_len() {
         data: b"ab".to_vec(),
     };
     let err = ColumnarBatch::new(schema, 2, vec![col]).unwrap_err();
-    assert_eq!(err, Error::Other("final offset mismatch".to_string()));
+    assert_eq!(
+        err,
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "final offset mismatch",
+        }
+    );
 }
@@ -1,8 +1,9 @@
 use crate::Error;
 use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
 use crate::codec::mathldbt_v1::{
-    MathldbtV1EncodeWorkspace, decode_mathldbt_v1, encode_mathldbt_v1_into,
-    encode_mathldbt_v1_into_with_workspace,
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_view_with_workspace, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
 };
 use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
 
@@ -106,6 +107,101 @@ fn round_trip_with_dict_and_delta() {
     assert_eq!(decoded, batch);
 }
 
+fn all_fixed_batch(rows: usize) -> ColumnarBatch {
+    let f = |name: &str, ty| ColumnarField {
+        name: Some(name.to_string()),
+        ty,
+    };
+    let schema = ColumnarSchema::new(vec![
+        f("b", ColumnarType::Bool),
+        f("i16", ColumnarType::I16),
+        f("i32", ColumnarType::I32),
+        f("i64", ColumnarType::I64),
+        f("f32", ColumnarType::F32),
+        f("f64", ColumnarType::F64),
+        f("id", ColumnarType::Uuid),
+        f("t", ColumnarType::TimestampTzMicros),
+    ])
+    .unwrap();
+    let mut v = ValidityBitmap::new_all_valid(rows).unwrap();
+    for i in (0..rows).step_by(5) {
+        v.set(i, false).unwrap();
+    }
+    let columns = vec![
+        ColumnData::FixedBool {
+            validity: v.clone(),
+            values: (0..rows).map(|i| (i % 2) as u8).collect(),
+        },
+        ColumnData::FixedI16 {
+            validity: v.clone(),
+            values: (0..rows).map(|i| i as i16 - 300).collect(),
+        },
+        ColumnData::FixedI32 {
+            validity: v.clone(),
+            values: (0..rows).map(|i| i as i32 * -70_001).collect(),
+        },
+        ColumnData::FixedI64 {
+            validity: v.clone(),
+            values: (0..rows)
+                .map(|i| 0x0102_0304_0506_0708 + i as i64)
+                .collect(),
+        },
+        ColumnData::FixedF32Bits {
+            validity: v.clone(),
+            values: (0..rows).map(|i| (i as f32 * 1.5).to_bits()).collect(),
+        },
+        ColumnData::FixedF64Bits {
+            validity: v.clone(),
+            values: (0..rows).map(|i| (i as f64 / 7.0).to_bits()).collect(),
+        },
+        ColumnData::FixedUuid {
+            validity: v.clone(),
+            values: (0..rows).map(|i| [i as u8; 16]).collect(),
+        },
+        ColumnData::FixedTimestampMicros {
+            validity: v,
+            values: (0..rows)
+                .map(|i| 1_700_000_000_000_000 + i as i64)
+                .collect(),
+        },
+    ];
+    ColumnarBatch::new(schema, rows, columns).unwrap()
+}
+
+#[test]
+fn round_trip_with_pg_be_fixed() {
+    let batch = all_fixed_batch(37);
+    let mut encoded = Vec::new();
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_pg_be_fixed(true);
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut encoded, &mut ws).unwrap();
+
+    let info = inspect_mathldbt_v1(&encoded).unwrap();
+    assert!(info.columns.iter().all(|c| c.encoding_id == 1));
+    let i64_payload = &encoded[info.columns[3].payload1.clone()];
+    assert_eq!(&i64_payload[..8], &0x0102_0304_0506_0708i64.to_be_bytes());
+
+    assert_eq!(decode_mathldbt_v1(&encoded).unwrap(), batch);
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    let view = decode_mathldbt_v1_view_with_workspace(&encoded, &mut dws).unwrap();
+    for (got, want) in view.columns().iter().zip(batch.columns.iter()) {
+        assert_eq!(*got, want.as_view());
+    }
+
+    // Varlen columns are unaffected; DeltaVarintI64 wins for eligible columns.
+    let batch = sample_batch(64);
+    ws.set_enable_delta_varint_i64(true);
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut encoded, &mut ws).unwrap();
+    let ids: Vec<u16> = inspect_mathldbt_v1(&encoded)
+        .unwrap()
+        .columns
+        .iter()
+        .map(|c| c.encoding_id)
+        .collect();
+    assert_eq!(ids, vec![0, 3, 1]);
+    assert_eq!(decode_mathldbt_v1(&encoded).unwrap(), batch);
+}
+
 #[test]
 fn decode_rejects_bad_magic() {
     let batch = sample_batch(1);
@@ -113,5 +209,5 @@ fn decode_rejects_bad_magic() {
     encode_mathldbt_v1_into(&batch, &mut encoded).unwrap();
     encoded[0] = b'X';
     let err = decode_mathldbt_v1(&encoded).unwrap_err();
-    assert_eq!(err, Error::Other("invalid MATHLDBT magic".to_string()));
+    assert_eq!(err, Error::BadMagic { offset: 0 });
 }
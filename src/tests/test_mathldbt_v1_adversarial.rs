@@ -1,6 +1,9 @@
 use crate::Error;
 use crate::batch::{ColumnData, ColumnarBatch};
-use crate::codec::mathldbt_v1::{decode_mathldbt_v1, decode_mathldbt_v1_into};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, decode_mathldbt_v1, decode_mathldbt_v1_into,
+    decode_mathldbt_v1_view_with_workspace,
+};
 use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
 
 fn write_u16_le(out: &mut Vec<u8>, v: u16) {
@@ -22,6 +25,7 @@ fn build_minimal_header(row_count: u32, col_count: u16) -> Vec<u8> {
     out
 }
 
+#[allow(clippy::too_many_arguments)]
 fn push_col_descriptor(
     out: &mut Vec<u8>,
     type_id: u16,
@@ -52,7 +56,28 @@ fn push_col_descriptor(
 fn truncated_header_is_rejected() {
     let bytes = b"MATHLDBT".to_vec(); // missing version+...
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
-    assert_eq!(err, Error::Other("truncated mathldbt".to_string()));
+    assert_eq!(
+        err,
+        Error::Truncated {
+            column: None,
+            offset: 8,
+            needed: 2,
+        }
+    );
+}
+
+#[test]
+fn unsupported_version_is_rejected() {
+    let mut bytes = build_minimal_header(1, 1);
+    bytes[8..10].copy_from_slice(&2u16.to_le_bytes());
+    let err = decode_mathldbt_v1(&bytes).unwrap_err();
+    assert_eq!(
+        err,
+        Error::UnsupportedVersion {
+            offset: 8,
+            version: 2,
+        }
+    );
 }
 
 #[test]
@@ -61,7 +86,11 @@ fn col_count_zero_is_rejected() {
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("MATHLDBT must have at least one column".to_string())
+        Error::Malformed {
+            column: None,
+            offset: 16,
+            reason: "MATHLDBT must have at least one column",
+        }
     );
 }
 
@@ -73,7 +102,95 @@ fn invalid_utf8_column_name_is_rejected() {
     let payload1 = [1u8];
     push_col_descriptor(&mut bytes, 1, 0, &[0xFF], 1, &validity, &payload1, &[]);
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
-    assert_eq!(err, Error::Other("invalid UTF-8 column name".to_string()));
+    assert_eq!(
+        err,
+        Error::Malformed {
+            column: Some(0),
+            offset: 28,
+            reason: "invalid UTF-8 column name",
+        }
+    );
+}
+
+#[test]
+fn unknown_type_id_is_rejected() {
+    let mut bytes = build_minimal_header(1, 1);
+    push_col_descriptor(&mut bytes, 99, 0, b"", 1, &[1u8], &[1u8], &[]);
+    let err = decode_mathldbt_v1(&bytes).unwrap_err();
+    assert_eq!(
+        err,
+        Error::UnknownTypeId {
+            column: 0,
+            offset: 20,
+            type_id: 99,
+        }
+    );
+}
+
+#[test]
+fn encoding_not_valid_for_type_is_rejected() {
+    // Dictionary encoding on an I32 column.
+    let mut bytes = build_minimal_header(1, 1);
+    push_col_descriptor(&mut bytes, 3, 2, b"", 1, &[1u8], &[0u8; 4], &[]);
+    let err = decode_mathldbt_v1(&bytes).unwrap_err();
+    assert_eq!(
+        err,
+        Error::UnknownEncoding {
+            column: 0,
+            offset: 22,
+            encoding_id: 2,
+        }
+    );
+
+    // DeltaVarintI64 on an I32 column.
+    let mut bytes = build_minimal_header(1, 1);
+    push_col_descriptor(&mut bytes, 3, 3, b"", 1, &[1u8], &[0u8; 4], &[]);
+    let err = decode_mathldbt_v1(&bytes).unwrap_err();
+    assert_eq!(
+        err,
+        Error::UnknownEncoding {
+            column: 0,
+            offset: 22,
+            encoding_id: 3,
+        }
+    );
+}
+
+#[test]
+fn error_reports_failing_column_index() {
+    let mut bytes = build_minimal_header(1, 2);
+    // Column 0: valid Bool (descriptor spans bytes 20..42).
+    push_col_descriptor(&mut bytes, 1, 0, b"", 1, &[1u8], &[1u8], &[]);
+    // Column 1: I32 with a 3-byte payload starting at byte 59.
+    push_col_descriptor(&mut bytes, 3, 0, b"", 1, &[1u8], &[0u8; 3], &[]);
+    let err = decode_mathldbt_v1(&bytes).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Malformed {
+            column: Some(1),
+            offset: 59,
+            reason: "values length mismatch",
+        }
+    );
+    assert_eq!(err.column(), Some(1));
+    assert_eq!(err.offset(), Some(59));
+}
+
+#[test]
+fn truncated_payload_is_rejected() {
+    let mut bytes = build_minimal_header(1, 1);
+    push_col_descriptor(&mut bytes, 3, 0, b"", 1, &[1u8], &[0u8; 4], &[]);
+    // Drop payload_2 length and the last payload_1 byte.
+    bytes.truncate(bytes.len() - 5);
+    let err = decode_mathldbt_v1(&bytes).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Truncated {
+            column: Some(0),
+            offset: 37,
+            needed: 4,
+        }
+    );
 }
 
 #[test]
@@ -82,7 +199,14 @@ fn validity_length_mismatch_is_rejected() {
     // expected validity_len = 1, but set 0 so the decoder errors before consuming bytes.
     push_col_descriptor(&mut bytes, 1, 0, b"", 0, &[], &[1u8], &[]);
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
-    assert_eq!(err, Error::Other("validity length mismatch".to_string()));
+    assert_eq!(
+        err,
+        Error::Malformed {
+            column: Some(0),
+            offset: 28,
+            reason: "validity length mismatch",
+        }
+    );
 }
 
 #[test]
@@ -92,7 +216,14 @@ fn var_offsets_length_mismatch_is_rejected() {
     // Utf8 PlainVar expects offsets len = (rows+1)*4 = 8 bytes; provide 0.
     push_col_descriptor(&mut bytes, 9, 0, b"", 1, &validity, &[], b"a");
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
-    assert_eq!(err, Error::Other("offsets length mismatch".to_string()));
+    assert_eq!(
+        err,
+        Error::InvalidOffsets {
+            column: 0,
+            offset: 37,
+            reason: "offsets length mismatch",
+        }
+    );
 }
 
 #[test]
@@ -108,10 +239,36 @@ fn var_offsets_non_monotonic_is_rejected() {
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("offsets must be non-decreasing".to_string())
+        Error::InvalidOffsets {
+            column: 0,
+            offset: 45,
+            reason: "offsets must be non-decreasing",
+        }
     );
 }
 
+#[test]
+fn var_first_offset_nonzero_is_rejected() {
+    let mut bytes = build_minimal_header(2, 1);
+    let validity = [0b0000_0011u8];
+    let mut offsets_bytes = Vec::new();
+    offsets_bytes.extend_from_slice(&1u32.to_le_bytes());
+    offsets_bytes.extend_from_slice(&1u32.to_le_bytes());
+    offsets_bytes.extend_from_slice(&2u32.to_le_bytes());
+    push_col_descriptor(&mut bytes, 9, 0, b"", 1, &validity, &offsets_bytes, b"ab");
+    let expected = Error::InvalidOffsets {
+        column: 0,
+        offset: 37,
+        reason: "offsets[0] must be 0",
+    };
+    assert_eq!(decode_mathldbt_v1(&bytes).unwrap_err(), expected);
+
+    // Borrowed or materialized, the view decode reports the same location.
+    let mut ws = MathldbtV1DecodeWorkspace::default();
+    let err = decode_mathldbt_v1_view_with_workspace(&bytes, &mut ws).unwrap_err();
+    assert_eq!(err, expected);
+}
+
 #[test]
 fn var_final_offset_mismatch_is_rejected() {
     let mut bytes = build_minimal_header(2, 1);
@@ -123,7 +280,14 @@ fn var_final_offset_mismatch_is_rejected() {
     offsets_bytes.extend_from_slice(&2u32.to_le_bytes());
     push_col_descriptor(&mut bytes, 9, 0, b"", 1, &validity, &offsets_bytes, b"a");
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
-    assert_eq!(err, Error::Other("final offset mismatch".to_string()));
+    assert_eq!(
+        err,
+        Error::InvalidOffsets {
+            column: 0,
+            offset: 45,
+            reason: "final offset mismatch",
+        }
+    );
 }
 
 #[test]
@@ -140,7 +304,14 @@ fn dict_utf8_invalid_width_is_rejected() {
     dict.push(b'a');
     push_col_descriptor(&mut bytes, 9, 2, b"", 1, &validity, &indices, &dict);
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
-    assert_eq!(err, Error::Other("invalid dict index width".to_string()));
+    assert_eq!(
+        err,
+        Error::Malformed {
+            column: Some(0),
+            offset: 42,
+            reason: "invalid dict index width",
+        }
+    );
 }
 
 #[test]
@@ -156,7 +327,14 @@ fn dict_utf8_index_out_of_bounds_is_rejected() {
     dict.push(b'a');
     push_col_descriptor(&mut bytes, 9, 2, b"", 1, &validity, &indices, &dict);
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
-    assert_eq!(err, Error::Other("dict index out of bounds".to_string()));
+    assert_eq!(
+        err,
+        Error::Malformed {
+            column: Some(0),
+            offset: 37,
+            reason: "dict index out of bounds",
+        }
+    );
 }
 
 #[test]
@@ -166,7 +344,14 @@ fn delta_truncated_base_is_rejected() {
     let payload = [0u8; 7]; // must be at least 8
     push_col_descriptor(&mut bytes, 4, 3, b"", 1, &validity, &payload, &[]);
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
-    assert_eq!(err, Error::Other("delta payload truncated".to_string()));
+    assert_eq!(
+        err,
+        Error::Malformed {
+            column: Some(0),
+            offset: 37,
+            reason: "delta payload truncated",
+        }
+    );
 }
 
 #[test]
@@ -179,7 +364,14 @@ fn delta_truncated_varint_is_rejected() {
     payload.push(0x80);
     push_col_descriptor(&mut bytes, 4, 3, b"", 1, &validity, &payload, &[]);
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
-    assert_eq!(err, Error::Other("truncated varint".to_string()));
+    assert_eq!(
+        err,
+        Error::Malformed {
+            column: Some(0),
+            offset: 45,
+            reason: "truncated varint",
+        }
+    );
 }
 
 #[test]
@@ -195,7 +387,11 @@ fn delta_trailing_bytes_is_rejected() {
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("trailing bytes in delta payload".to_string())
+        Error::Malformed {
+            column: Some(0),
+            offset: 46,
+            reason: "trailing bytes in delta payload",
+        }
     );
 }
 
@@ -209,7 +405,11 @@ fn fixed_width_payload_2_nonempty_is_rejected() {
     let err = decode_mathldbt_v1(&bytes).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("fixed-width payload_2 must be empty".to_string())
+        Error::Malformed {
+            column: Some(0),
+            offset: 45,
+            reason: "fixed-width payload_2 must be empty",
+        }
     );
 }
 
@@ -237,6 +437,9 @@ fn decode_into_schema_mismatch_is_rejected() {
     let err = decode_mathldbt_v1_into(&bytes, &mut out).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("decode_mathldbt_v1_into requires matching schema".to_string())
+        Error::Schema {
+            column: Some(0),
+            reason: "decode_mathldbt_v1_into requires matching schema",
+        }
     );
 }
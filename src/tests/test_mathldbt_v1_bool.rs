@@ -0,0 +1,225 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::ColumnDataView;
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_into, decode_mathldbt_v1_view_with_workspace,
+    decode_mathldbt_v1_with_workspace, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+use crate::pg_copy::{PgCopyReader, PgCopyWriter};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+use crate::tests::common::encode_both;
+
+/// Every third row is `true` and every fifth row is null (and `false`, as COPY reads it).
+fn flags(rows: usize, packed: bool) -> ColumnData {
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    for i in (4..rows).step_by(5) {
+        validity.set(i, false).unwrap();
+    }
+    let bytes: Vec<u8> = (0..rows)
+        .map(|i| u8::from(i % 3 == 0 && i % 5 != 4))
+        .collect();
+    if !packed {
+        return ColumnData::FixedBool {
+            validity,
+            values: bytes,
+        };
+    }
+    let mut bits = vec![0u8; rows.div_ceil(8)];
+    for (i, b) in bytes.iter().enumerate() {
+        bits[i / 8] |= b << (i % 8);
+    }
+    ColumnData::BitPackedBool {
+        validity,
+        values: bits,
+    }
+}
+
+/// A top-level flag column and a list holding one flag per row.
+fn bool_batch(rows: usize, packed: bool) -> ColumnarBatch {
+    let f = |name: &str, ty| ColumnarField {
+        name: Some(name.to_string()),
+        ty,
+    };
+    let schema = ColumnarSchema::new(vec![
+        f("is_buy", ColumnarType::Bool),
+        f("fills", ColumnarType::List(Box::new(ColumnarType::Bool))),
+    ])
+    .unwrap();
+    let fills = ColumnData::List {
+        validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+        offsets: (0..=rows as u32).collect(),
+        child: Box::new(flags(rows, packed)),
+    };
+    ColumnarBatch::new(schema, rows, vec![flags(rows, packed), fills]).unwrap()
+}
+
+#[test]
+fn bit_packed_bool_round_trips_through_all_paths() {
+    for rows in [0, 1, 8, 9, 100] {
+        let bytes_batch = bool_batch(rows, false);
+        let bits_batch = bool_batch(rows, true);
+
+        // Without the option both shapes go on the wire one byte per row.
+        let mut plain = Vec::new();
+        encode_mathldbt_v1_into(&bytes_batch, &mut plain).unwrap();
+        let mut plain_bits = Vec::new();
+        encode_mathldbt_v1_into(&bits_batch, &mut plain_bits).unwrap();
+        assert_eq!(plain_bits, plain);
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        ws.set_enable_pg_be_fixed(true);
+        assert_eq!(
+            encode_both(&bits_batch, &mut ws),
+            encode_both(&bytes_batch, &mut ws)
+        );
+
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        ws.set_enable_bit_packed_bool(true)
+            .set_enable_pg_be_fixed(true);
+        let packed = encode_both(&bytes_batch, &mut ws);
+        assert_eq!(encode_both(&bits_batch, &mut ws), packed);
+
+        let info = inspect_mathldbt_v1(&packed).unwrap();
+        assert_eq!(info.columns[0].encoding_id, 4);
+        assert_eq!(info.columns[0].payload1.len(), rows.div_ceil(8));
+        assert_eq!(info.columns[1].children[0].encoding_id, 4);
+        assert_eq!(packed.len() + rows * 2, plain.len() + rows.div_ceil(8) * 2);
+
+        assert_eq!(decode_mathldbt_v1(&packed).unwrap(), bytes_batch);
+        let mut dws = MathldbtV1DecodeWorkspace::default();
+        dws.set_keep_bit_packed_bool(true);
+        assert_eq!(
+            decode_mathldbt_v1_with_workspace(&packed, &mut dws).unwrap(),
+            bits_batch
+        );
+        assert_eq!(
+            decode_mathldbt_v1_with_workspace(&plain, &mut dws).unwrap(),
+            bits_batch
+        );
+
+        // `decode_*_into` follows the destination's shape whatever the wire encoding.
+        for bytes in [&plain, &packed] {
+            let mut out = bool_batch(3, true);
+            decode_mathldbt_v1_into(bytes, &mut out).unwrap();
+            assert_eq!(out, bits_batch);
+            let mut out = bool_batch(3, false);
+            decode_mathldbt_v1_into(bytes, &mut out).unwrap();
+            assert_eq!(out, bytes_batch);
+        }
+    }
+}
+
+#[test]
+fn bit_packed_bool_view_borrows_bits() {
+    let batch = bool_batch(20, false);
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_bit_packed_bool(true);
+    let bytes = encode_both(&batch, &mut ws);
+
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    let decoded = decode_mathldbt_v1_view_with_workspace(&bytes, &mut dws).unwrap();
+    match decoded.columns()[0] {
+        ColumnDataView::BitPackedBool { values, .. } => {
+            assert_eq!(values, [0x49, 0x90, 0x04]);
+            assert!(bytes.as_ptr_range().contains(&values.as_ptr()));
+        }
+        _ => unreachable!(),
+    }
+    let columns = decoded.to_batch().unwrap().columns;
+    assert_eq!(columns[0], bool_batch(20, true).columns[0]);
+
+    // COPY has no bit-packed form; values are written and read as one-byte `bool` fields.
+    let bits_batch = bool_batch(20, true);
+    let single = |batch: &ColumnarBatch| {
+        ColumnarBatch::new(
+            ColumnarSchema::new(vec![batch.schema.fields()[0].clone()]).unwrap(),
+            batch.row_count,
+            vec![batch.columns[0].clone()],
+        )
+        .unwrap()
+    };
+    let mut w = PgCopyWriter::new(Vec::new(), single(&bits_batch).schema).unwrap();
+    w.write_batch(&single(&bits_batch)).unwrap();
+    let copy = w.finish().unwrap();
+    let mut w = PgCopyWriter::new(Vec::new(), single(&batch).schema).unwrap();
+    w.write_batch(&single(&batch)).unwrap();
+    assert_eq!(w.finish().unwrap(), copy);
+    let mut r = PgCopyReader::new(copy.as_slice(), single(&batch).schema, 64).unwrap();
+    let mut out = single(&bool_batch(3, true));
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out, single(&bits_batch));
+}
+
+#[test]
+fn bit_packed_bool_adversarial_inputs_are_rejected() {
+    let mut columns = bool_batch(9, true).columns;
+    columns[0] = ColumnData::BitPackedBool {
+        validity: ValidityBitmap::new_all_valid(9).unwrap(),
+        values: vec![0; 1],
+    };
+    assert_eq!(
+        ColumnarBatch::new(bool_batch(9, true).schema, 9, columns).unwrap_err(),
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "values length mismatch",
+        }
+    );
+
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![ColumnarField {
+            name: None,
+            ty: ColumnarType::Bool,
+        }])
+        .unwrap(),
+        3,
+        vec![ColumnData::FixedBool {
+            validity: ValidityBitmap::new_all_valid(3).unwrap(),
+            values: vec![1, 0, 1],
+        }],
+    )
+    .unwrap();
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_bit_packed_bool(true);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut bytes, &mut ws).unwrap();
+
+    // Header (20) | type id (2) | enc (2) | flags (2) | name_len (2) | validity (4 + 1) |
+    // payload_1 length (4) at 33, then the packed byte at 37.
+    assert_eq!(bytes[37], 0b101);
+    let mut padded = bytes.clone();
+    padded[37] |= 0x80;
+    assert_eq!(
+        decode_mathldbt_v1(&padded).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 37,
+            reason: "bit-packed padding must be zero",
+        }
+    );
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    assert!(decode_mathldbt_v1_view_with_workspace(&padded, &mut dws).is_err());
+
+    let mut long = bytes.clone();
+    long[33] = 2;
+    long.insert(38, 0);
+    assert_eq!(
+        decode_mathldbt_v1(&long).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 37,
+            reason: "values length mismatch",
+        }
+    );
+
+    let mut retyped = bytes.clone();
+    retyped[20] = 3;
+    assert_eq!(
+        decode_mathldbt_v1(&retyped).unwrap_err(),
+        Error::UnknownEncoding {
+            column: 0,
+            offset: 22,
+            encoding_id: 4,
+        }
+    );
+}
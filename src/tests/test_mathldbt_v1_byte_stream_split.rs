@@ -0,0 +1,311 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1EncodeWorkspace, decode_mathldbt_v1, inspect_mathldbt_v1,
+};
+use crate::schema::{ColumnarSchema, ColumnarType};
+use crate::tests::common::{assert_decodes_all_paths, encode_both, encodings, field};
+
+/// Prices with noisy mantissas (`close` as `F64`, `vwap` as `F32` with every sixth row null),
+/// a signed `qty` (`I64`), an `F64` list and a `U64` column, which the encoding does not apply to.
+fn bars_batch(rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![
+        field("close", ColumnarType::F64),
+        field("vwap", ColumnarType::F32),
+        field("qty", ColumnarType::I64),
+        field("levels", ColumnarType::List(Box::new(ColumnarType::F64))),
+        field("seq", ColumnarType::U64),
+    ])
+    .unwrap();
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    for i in (5..rows).step_by(6) {
+        validity.set(i, false).unwrap();
+    }
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        state >> 11
+    };
+    let mut close = Vec::with_capacity(rows);
+    let mut vwap = Vec::with_capacity(rows);
+    let mut qty = Vec::with_capacity(rows);
+    for _ in 0..rows {
+        close.push((42_000.0 + next() as f64 / (1u64 << 53) as f64 * 50.0).to_bits());
+        vwap.push((17.5 + next() as f32 / (1u64 << 53) as f32).to_bits());
+        qty.push((next() % 2_000_000) as i64 - 1_000_000);
+    }
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::FixedF64Bits {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: close.clone(),
+            },
+            ColumnData::FixedF32Bits {
+                validity,
+                values: vwap,
+            },
+            ColumnData::FixedI64 {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: qty,
+            },
+            ColumnData::List {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                offsets: (0..=rows as u32).collect(),
+                child: Box::new(ColumnData::FixedF64Bits {
+                    validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                    values: close.iter().rev().copied().collect(),
+                }),
+            },
+            ColumnData::FixedU64 {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows as u64).collect(),
+            },
+        ],
+    )
+    .unwrap()
+}
+
+/// `batch` with a sixth column, `noise`, of full-range `F64` bit patterns.
+#[cfg(feature = "compression-zstd")]
+fn with_noise_column(batch: &ColumnarBatch) -> ColumnarBatch {
+    let mut fields = batch.schema.fields().to_vec();
+    fields.push(field("noise", ColumnarType::F64));
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut columns = batch.columns.clone();
+    columns.push(ColumnData::FixedF64Bits {
+        validity: ValidityBitmap::new_all_valid(batch.row_count).unwrap(),
+        values: (0..batch.row_count)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            })
+            .collect(),
+    });
+    ColumnarBatch::new(
+        ColumnarSchema::new(fields).unwrap(),
+        batch.row_count,
+        columns,
+    )
+    .unwrap()
+}
+
+fn split_workspace() -> MathldbtV1EncodeWorkspace {
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_byte_stream_split(true);
+    ws
+}
+
+#[test]
+fn byte_stream_split_round_trips_through_all_paths() {
+    let mut ws = split_workspace();
+    for rows in [0, 1, 7, 300] {
+        let batch = bars_batch(rows);
+        let bytes = encode_both(&batch, &mut ws);
+        assert_eq!(encodings(&bytes), [9, 9, 9, 0, 0], "rows={rows}");
+        let info = inspect_mathldbt_v1(&bytes).unwrap();
+        assert_eq!(info.columns[3].children[0].encoding_id, 9);
+        assert_eq!(info.columns[0].payload1.len(), rows * 8);
+
+        assert_decodes_all_paths(&bytes, &batch);
+    }
+
+    // Plane `k` holds byte `k` of every value: here the sign and exponent bytes sit at the end.
+    let batch = bars_batch(3);
+    let bytes = encode_both(&batch, &mut ws);
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    let ColumnData::FixedF64Bits { values, .. } = &batch.columns[0] else {
+        unreachable!()
+    };
+    let planes = &bytes[info.columns[0].payload1.clone()];
+    assert_eq!(planes[21..], [0x40; 3]);
+    for (i, v) in values.iter().enumerate() {
+        let le = v.to_le_bytes();
+        assert!((0..8).all(|k| planes[k * 3 + i] == le[k]));
+    }
+}
+
+#[test]
+fn byte_stream_split_is_selectable_per_column() {
+    let batch = bars_batch(50);
+    let bytes = encode_both(&batch, &mut MathldbtV1EncodeWorkspace::default());
+    assert_eq!(encodings(&bytes), [0, 0, 0, 0, 0]);
+
+    // Top-level columns can opt in alone, or opt out of the workspace-wide setting.
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_column_byte_stream_split(2, Some(true));
+    let bytes = encode_both(&batch, &mut ws);
+    assert_eq!(encodings(&bytes), [0, 0, 9, 0, 0]);
+    assert_eq!(decode_mathldbt_v1(&bytes).unwrap(), batch);
+    let mut ws = split_workspace();
+    ws.set_column_byte_stream_split(0, Some(false))
+        .set_column_byte_stream_split(4, Some(true));
+    assert_eq!(encodings(&encode_both(&batch, &mut ws)), [0, 9, 9, 0, 0]);
+    ws.set_column_byte_stream_split(0, None);
+    assert_eq!(encodings(&encode_both(&batch, &mut ws)), [9, 9, 9, 0, 0]);
+
+    // ByteStreamSplit takes precedence over PgBeFixed; smaller encodings over both.
+    ws.set_enable_pg_be_fixed(true);
+    let bytes = encode_both(&batch, &mut ws);
+    assert_eq!(encodings(&bytes), [9, 9, 9, 0, 1]);
+    assert_eq!(decode_mathldbt_v1(&bytes).unwrap(), batch);
+    ws.set_enable_xor_float(true)
+        .set_enable_delta_varint_i64(true);
+    let bytes = encode_both(&batch, &mut ws);
+    assert_eq!(encodings(&bytes), [8, 8, 3, 0, 3]);
+    assert_eq!(decode_mathldbt_v1(&bytes).unwrap(), batch);
+}
+
+#[cfg(feature = "compression-zstd")]
+#[test]
+fn compressed_output_is_unchanged_where_split_does_not_help() {
+    use crate::codec::mathldbt_v1_compressed::{
+        Compression, MathldbtV1CompressedEncodeWorkspace,
+        encode_mathldbt_v1_compressed_into_with_workspace,
+    };
+
+    let c = Compression::Zstd { level: 3 };
+    let mut codec_ws = MathldbtV1EncodeWorkspace::default();
+    let mut out = Vec::new();
+
+    // Full-range bit patterns compress no better split, so the column stays plain.
+    let batch = with_noise_column(&bars_batch(2_000));
+    let noise = ColumnarBatch::new(
+        ColumnarSchema::new(vec![batch.schema.fields()[5].clone()]).unwrap(),
+        batch.row_count,
+        vec![batch.columns[5].clone()],
+    )
+    .unwrap();
+    let plain = encode_both(&noise, &mut codec_ws);
+    let mut ws = MathldbtV1CompressedEncodeWorkspace::default();
+    encode_mathldbt_v1_compressed_into_with_workspace(&noise, &mut out, c, &mut codec_ws, &mut ws)
+        .unwrap();
+    assert_eq!(out, zstd::bulk::compress(&plain, 3).unwrap());
+
+    // Next to columns that do compress better split, only those are.
+    encode_mathldbt_v1_compressed_into_with_workspace(&batch, &mut out, c, &mut codec_ws, &mut ws)
+        .unwrap();
+    let plain = zstd::stream::decode_all(out.as_slice()).unwrap();
+    assert_eq!(encodings(&plain), [9, 9, 9, 0, 0, 0]);
+    assert_decodes_all_paths(&plain, &batch);
+
+    // Turning the automatic split off leaves every column as the codec workspace says.
+    let batch = bars_batch(2_000);
+    let plain = encode_both(&batch, &mut codec_ws);
+    let mut ws = MathldbtV1CompressedEncodeWorkspace::default();
+    ws.set_auto_byte_stream_split(false);
+    encode_mathldbt_v1_compressed_into_with_workspace(&batch, &mut out, c, &mut codec_ws, &mut ws)
+        .unwrap();
+    assert_eq!(out, zstd::bulk::compress(&plain, 3).unwrap());
+}
+
+#[cfg(feature = "compression-zstd")]
+#[test]
+fn compressed_encoders_pick_byte_stream_split() {
+    use crate::codec::mathldbt_v1_compressed::{
+        Compression, MathldbtV1CompressedEncodeWorkspace, decode_mathldbt_v1_compressed,
+        encode_mathldbt_v1_compressed_into_with_workspace,
+    };
+
+    let batch = bars_batch(2_000);
+    let c = Compression::Zstd { level: 3 };
+    let mut codec_ws = MathldbtV1EncodeWorkspace::default();
+    let mut ws = MathldbtV1CompressedEncodeWorkspace::default();
+    let mut auto = Vec::new();
+    encode_mathldbt_v1_compressed_into_with_workspace(&batch, &mut auto, c, &mut codec_ws, &mut ws)
+        .unwrap();
+    let plain = zstd::stream::decode_all(auto.as_slice()).unwrap();
+    assert_eq!(encodings(&plain), [9, 9, 9, 0, 0]);
+    assert_eq!(
+        decode_mathldbt_v1_compressed(&auto, c, 1 << 20).unwrap(),
+        batch
+    );
+
+    // The workspace settings are restored, and `Compression::None` leaves the bytes plain.
+    assert_eq!(
+        encodings(&encode_both(&batch, &mut codec_ws)),
+        [0, 0, 0, 0, 0]
+    );
+    let mut none = Vec::new();
+    encode_mathldbt_v1_compressed_into_with_workspace(
+        &batch,
+        &mut none,
+        Compression::None,
+        &mut codec_ws,
+        &mut ws,
+    )
+    .unwrap();
+    assert_eq!(encodings(&none), [0, 0, 0, 0, 0]);
+
+    // A column that opts out stays plain.
+    codec_ws.set_column_byte_stream_split(1, Some(false));
+    let mut opted_out = Vec::new();
+    encode_mathldbt_v1_compressed_into_with_workspace(
+        &batch,
+        &mut opted_out,
+        c,
+        &mut codec_ws,
+        &mut ws,
+    )
+    .unwrap();
+    let plain = zstd::stream::decode_all(opted_out.as_slice()).unwrap();
+    assert_eq!(encodings(&plain), [9, 0, 9, 0, 0]);
+    codec_ws.set_column_byte_stream_split(1, None);
+
+    let mut off = Vec::new();
+    ws.set_auto_byte_stream_split(false);
+    encode_mathldbt_v1_compressed_into_with_workspace(&batch, &mut off, c, &mut codec_ws, &mut ws)
+        .unwrap();
+    let plain = zstd::stream::decode_all(off.as_slice()).unwrap();
+    assert_eq!(encodings(&plain), [0, 0, 0, 0, 0]);
+    assert!(auto.len() < off.len(), "{} >= {}", auto.len(), off.len());
+}
+
+#[test]
+fn byte_stream_split_adversarial_inputs_are_rejected() {
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field("", ColumnarType::F64)]).unwrap(),
+        2,
+        vec![ColumnData::FixedF64Bits {
+            validity: ValidityBitmap::new_all_valid(2).unwrap(),
+            values: vec![1.0f64.to_bits(), 2.0f64.to_bits()],
+        }],
+    )
+    .unwrap();
+    let bytes = encode_both(&batch, &mut split_workspace());
+
+    // Header (20) | type id (2) | enc (2) | flags (2) | name_len (2) | validity (4 + 1) |
+    // payload_1 length (4) at 33, then eight planes of two bytes at 37..53.
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(info.columns[0].payload1, 37..53);
+    assert_eq!(bytes[49..53], [0xf0, 0x00, 0x3f, 0x40]);
+
+    let mut short = bytes[..33].to_vec();
+    short.extend_from_slice(&15u32.to_le_bytes());
+    short.extend_from_slice(&bytes[37..52]);
+    short.extend_from_slice(&0u32.to_le_bytes());
+    assert_eq!(
+        decode_mathldbt_v1(&short).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 37,
+            reason: "values length mismatch",
+        }
+    );
+
+    let mut retyped = bytes.clone();
+    retyped[20] = 21;
+    assert_eq!(
+        decode_mathldbt_v1(&retyped).unwrap_err(),
+        Error::UnknownEncoding {
+            column: 0,
+            offset: 22,
+            encoding_id: 9,
+        }
+    );
+}
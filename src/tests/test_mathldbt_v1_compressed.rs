@@ -129,7 +129,11 @@ fn zstd_decompression_bound_is_enforced() {
     let err = decode_mathldbt_v1_compressed(bytes.as_slice(), c, 0).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("decompressed payload exceeds max_uncompressed_len".to_string())
+        Error::LimitExceeded {
+            kind: crate::LimitKind::DecompressedLength,
+            column: None,
+            offset: Some(0),
+        }
     );
 }
 
@@ -145,7 +149,11 @@ fn gzip_decompression_bound_is_enforced() {
     let err = decode_mathldbt_v1_compressed(bytes.as_slice(), c, 0).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("decompressed payload exceeds max_uncompressed_len".to_string())
+        Error::LimitExceeded {
+            kind: crate::LimitKind::DecompressedLength,
+            column: None,
+            offset: Some(0),
+        }
     );
 }
 
@@ -176,14 +184,18 @@ fn zstd_feature_not_enabled_errors_deterministically() {
         .unwrap_err();
     assert_eq!(
         err,
-        Error::Other("zstd compression feature not enabled".to_string())
+        Error::FeatureDisabled {
+            feature: "compression-zstd",
+        }
     );
 
     let err =
         decode_mathldbt_v1_compressed(b"x", Compression::Zstd { level: 3 }, 1024).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("zstd compression feature not enabled".to_string())
+        Error::FeatureDisabled {
+            feature: "compression-zstd",
+        }
     );
 }
 
@@ -196,14 +208,18 @@ fn gzip_feature_not_enabled_errors_deterministically() {
         .unwrap_err();
     assert_eq!(
         err,
-        Error::Other("gzip compression feature not enabled".to_string())
+        Error::FeatureDisabled {
+            feature: "compression-gzip",
+        }
     );
 
     let err =
         decode_mathldbt_v1_compressed(b"x", Compression::Gzip { level: 6 }, 1024).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("gzip compression feature not enabled".to_string())
+        Error::FeatureDisabled {
+            feature: "compression-gzip",
+        }
     );
 }
 
@@ -216,7 +232,14 @@ fn invalid_zstd_level_is_rejected_deterministically() {
     let err =
         encode_mathldbt_v1_compressed_into(&batch, &mut out, Compression::Zstd { level: 999 })
             .unwrap_err();
-    assert_eq!(err, Error::Other("invalid zstd level".to_string()));
+    assert_eq!(
+        err,
+        Error::Compression {
+            column: None,
+            offset: None,
+            message: "invalid zstd level".to_string(),
+        }
+    );
 }
 
 #[cfg(feature = "compression-gzip")]
@@ -228,5 +251,12 @@ fn invalid_gzip_level_is_rejected_deterministically() {
     let err =
         encode_mathldbt_v1_compressed_into(&batch, &mut out, Compression::Gzip { level: 999 })
             .unwrap_err();
-    assert_eq!(err, Error::Other("invalid gzip level".to_string()));
+    assert_eq!(
+        err,
+        Error::Compression {
+            column: None,
+            offset: None,
+            message: "invalid gzip level".to_string(),
+        }
+    );
 }
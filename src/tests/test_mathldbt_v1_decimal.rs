@@ -0,0 +1,188 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_into, decode_mathldbt_v1_view_with_workspace,
+    encode_mathldbt_v1_fast_path_into_with_workspace, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+use crate::codec::{StreamReader, StreamWriter};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+
+const PRICE: ColumnarType = ColumnarType::Decimal {
+    precision: 38,
+    scale: 8,
+};
+
+fn decimal_batch(rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![
+        ColumnarField {
+            name: Some("e_ms".to_string()),
+            ty: ColumnarType::I64,
+        },
+        ColumnarField {
+            name: Some("close".to_string()),
+            ty: PRICE,
+        },
+    ])
+    .unwrap();
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    if rows > 1 {
+        validity.set(1, false).unwrap();
+    }
+    let mut values: Vec<i128> = (0..rows)
+        .map(|i| 4_200_050_000_000 + i as i128 * 12_345)
+        .collect();
+    if rows > 2 {
+        // Largest and smallest 38-digit mantissas.
+        values[0] = 10i128.pow(38) - 1;
+        values[2] = -(10i128.pow(38) - 1);
+    }
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::FixedI64 {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows).map(|i| i as i64).collect(),
+            },
+            ColumnData::FixedDecimal128 {
+                precision: 38,
+                scale: 8,
+                validity,
+                values,
+            },
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn decimal_round_trips_through_all_paths() {
+    for rows in [0, 1, 3, 100] {
+        let batch = decimal_batch(rows);
+        let mut owned = Vec::new();
+        encode_mathldbt_v1_into(&batch, &mut owned).unwrap();
+        assert_eq!(decode_mathldbt_v1(&owned).unwrap(), batch);
+
+        let info = inspect_mathldbt_v1(&owned).unwrap();
+        assert_eq!(info.schema.fields()[1].ty, PRICE);
+
+        let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+        let view = ColumnarBatchView {
+            schema: &batch.schema,
+            row_count: batch.row_count,
+            columns: &cols,
+        };
+        let mut fast = Vec::new();
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut ws).unwrap();
+        assert_eq!(fast, owned);
+
+        let mut dws = MathldbtV1DecodeWorkspace::default();
+        let decoded = decode_mathldbt_v1_view_with_workspace(&owned, &mut dws).unwrap();
+        assert_eq!(decoded.to_batch().unwrap(), batch);
+
+        let mut out = decimal_batch(7);
+        decode_mathldbt_v1_into(&owned, &mut out).unwrap();
+        assert_eq!(out, batch);
+
+        let mut be = Vec::new();
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        ws.set_enable_pg_be_fixed(true);
+        encode_mathldbt_v1_into_with_workspace(&batch, &mut be, &mut ws).unwrap();
+        assert_eq!(decode_mathldbt_v1(&be).unwrap(), batch);
+    }
+}
+
+#[test]
+fn decimal_precision_and_scale_are_part_of_the_schema() {
+    let batch = decimal_batch(4);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    // decode_into rejects a target with the same values type but a different scale.
+    let mut other = decimal_batch(4);
+    other.schema = ColumnarSchema::new(vec![
+        other.schema.fields()[0].clone(),
+        ColumnarField {
+            name: Some("close".to_string()),
+            ty: ColumnarType::Decimal {
+                precision: 38,
+                scale: 2,
+            },
+        },
+    ])
+    .unwrap();
+    assert!(matches!(
+        decode_mathldbt_v1_into(&bytes, &mut other).unwrap_err(),
+        Error::Schema {
+            column: Some(1),
+            ..
+        }
+    ));
+
+    let mut w = StreamWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    let stream = w.finish().unwrap();
+    let mut r = StreamReader::new(stream.as_slice(), 1 << 20).unwrap();
+    assert_eq!(r.schema(), &batch.schema);
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out, batch);
+}
+
+#[test]
+fn decimal_validation_errors() {
+    assert_eq!(
+        ColumnarSchema::new(vec![ColumnarField {
+            name: None,
+            ty: ColumnarType::Decimal {
+                precision: 39,
+                scale: 0,
+            },
+        }])
+        .unwrap_err(),
+        Error::Schema {
+            column: Some(0),
+            reason: "invalid decimal precision/scale",
+        }
+    );
+
+    let mut batch = decimal_batch(4);
+    if let ColumnData::FixedDecimal128 { values, .. } = &mut batch.columns[1] {
+        values[3] = 10i128.pow(38);
+    }
+    let mut bytes = Vec::new();
+    assert_eq!(
+        encode_mathldbt_v1_into(&batch, &mut bytes).unwrap_err(),
+        Error::InvalidBatch {
+            column: Some(1),
+            reason: "decimal value exceeds precision",
+        }
+    );
+    // Out-of-range mantissas in null slots are ignored.
+    if let ColumnData::FixedDecimal128 { values, .. } = &mut batch.columns[1] {
+        values[3] = 0;
+        values[1] = i128::MAX;
+    }
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    // Header (20 bytes), then column 0 (I64, 4 rows); column 1's precision byte follows its
+    // type id.
+    let decimal_type_at = 20 + 2 + 2 + 2 + 2 + 4 + 4 + 1 + 4 + 32 + 4;
+    assert_eq!(
+        &bytes[decimal_type_at..decimal_type_at + 4],
+        &[12, 0, 38, 8]
+    );
+    bytes[decimal_type_at + 2] = 0;
+    assert_eq!(
+        decode_mathldbt_v1(&bytes).unwrap_err(),
+        Error::Malformed {
+            column: Some(1),
+            offset: decimal_type_at + 2,
+            reason: "invalid decimal precision/scale",
+        }
+    );
+}
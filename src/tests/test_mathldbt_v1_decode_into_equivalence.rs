@@ -76,7 +76,7 @@ fn sample_batch(rows: usize) -> ColumnarBatch {
 fn make_out(schema: ColumnarSchema) -> ColumnarBatch {
     let mut cols = Vec::with_capacity(schema.len());
     for f in schema.fields().iter() {
-        cols.push(ColumnData::new_all_invalid(f.ty, 0).unwrap());
+        cols.push(ColumnData::new_all_invalid(f.ty.clone(), 0).unwrap());
     }
     ColumnarBatch::new(schema, 0, cols).unwrap()
 }
@@ -0,0 +1,186 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::codec::mathldbt_v1::{
+    ColumnSelector, MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_projected, decode_mathldbt_v1_projected_with_workspace,
+    encode_mathldbt_v1_into, encode_mathldbt_v1_into_with_workspace,
+};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+
+fn sample_batch(rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![
+        ColumnarField {
+            name: Some("pair".to_string()),
+            ty: ColumnarType::Utf8,
+        },
+        ColumnarField {
+            name: Some("e_ms".to_string()),
+            ty: ColumnarType::I64,
+        },
+        ColumnarField {
+            name: Some("close".to_string()),
+            ty: ColumnarType::F64,
+        },
+    ])
+    .unwrap();
+
+    let mut pair_validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    if rows > 0 {
+        pair_validity.set(0, false).unwrap();
+    }
+    let mut offsets = vec![0u32; rows + 1];
+    let mut data = Vec::new();
+    for i in 0..rows {
+        if pair_validity.is_valid(i).unwrap() {
+            let v = if i % 2 == 0 { b"BTCUSDT" } else { b"ETHUSDT" };
+            data.extend_from_slice(v);
+            offsets[i + 1] = data.len() as u32;
+        } else {
+            offsets[i + 1] = offsets[i];
+        }
+    }
+
+    let e_validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    let mut e_vals = Vec::with_capacity(rows);
+    for i in 0..rows {
+        e_vals.push(1_700_000_000_000i64 + i as i64 * 60_000);
+    }
+
+    let f_validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    let mut f_bits = Vec::with_capacity(rows);
+    for i in 0..rows {
+        f_bits.push((10_000.0 + i as f64 * 0.25).to_bits());
+    }
+
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::Var {
+                ty: ColumnarType::Utf8,
+                validity: pair_validity,
+                offsets,
+                data,
+            },
+            ColumnData::FixedI64 {
+                validity: e_validity,
+                values: e_vals,
+            },
+            ColumnData::FixedF64Bits {
+                validity: f_validity,
+                values: f_bits,
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn project(full: &ColumnarBatch, indices: &[usize]) -> ColumnarBatch {
+    let fields = indices
+        .iter()
+        .map(|&i| full.schema.fields()[i].clone())
+        .collect();
+    let columns = indices.iter().map(|&i| full.columns[i].clone()).collect();
+    ColumnarBatch::new(
+        ColumnarSchema::new(fields).unwrap(),
+        full.row_count,
+        columns,
+    )
+    .unwrap()
+}
+
+#[test]
+fn projected_decode_matches_selected_columns() {
+    let batch = sample_batch(33);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    let by_index = decode_mathldbt_v1_projected(&bytes, &[2.into(), 1.into()]).unwrap();
+    assert_eq!(by_index, project(&batch, &[2, 1]));
+
+    let by_name = decode_mathldbt_v1_projected(&bytes, &["e_ms".into(), "close".into()]).unwrap();
+    assert_eq!(by_name, project(&batch, &[1, 2]));
+
+    let mixed = [ColumnSelector::Name("pair"), ColumnSelector::Index(0)];
+    let got = decode_mathldbt_v1_projected(&bytes, &mixed).unwrap();
+    assert_eq!(got, project(&batch, &[0, 0]));
+}
+
+#[test]
+fn projected_decode_handles_opt_encodings() {
+    let batch = sample_batch(64);
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_dict_utf8(true)
+        .set_enable_delta_varint_i64(true);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut bytes, &mut ws).unwrap();
+
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    for _ in 0..2 {
+        let got = decode_mathldbt_v1_projected_with_workspace(
+            &bytes,
+            &["pair".into(), "e_ms".into()],
+            &mut dws,
+        )
+        .unwrap();
+        assert_eq!(got, project(&batch, &[0, 1]));
+    }
+}
+
+#[test]
+fn projected_decode_skips_unselected_payloads() {
+    let batch = sample_batch(4);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    // "pair" offsets[0] lives at 41: header 20 | head 8 + name 4 | validity 4 + 1 | p1 len 4.
+    bytes[41] = 1;
+    assert_eq!(
+        decode_mathldbt_v1(&bytes).unwrap_err(),
+        Error::InvalidOffsets {
+            column: 0,
+            offset: 41,
+            reason: "offsets[0] must be 0",
+        }
+    );
+    let got = decode_mathldbt_v1_projected(&bytes, &["close".into()]).unwrap();
+    assert_eq!(got, project(&batch, &[2]));
+}
+
+#[test]
+fn projected_decode_bounds_checks_skipped_columns() {
+    let batch = sample_batch(4);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    // Cut into the last column's payload; selecting only the first column must still fail.
+    let cut = &bytes[..bytes.len() - 3];
+    let err = decode_mathldbt_v1_projected(cut, &[0.into()]).unwrap_err();
+    assert!(matches!(err, Error::Truncated { .. }), "{err:?}");
+}
+
+#[test]
+fn projected_decode_rejects_unknown_columns() {
+    let batch = sample_batch(4);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    assert_eq!(
+        decode_mathldbt_v1_projected(&bytes, &[3.into()]).unwrap_err(),
+        Error::Schema {
+            column: Some(3),
+            reason: "projected column index out of range",
+        }
+    );
+    assert_eq!(
+        decode_mathldbt_v1_projected(&bytes, &["open".into()]).unwrap_err(),
+        Error::Schema {
+            column: None,
+            reason: "projected column name not found",
+        }
+    );
+    assert!(matches!(
+        decode_mathldbt_v1_projected(&bytes, &[]).unwrap_err(),
+        Error::Schema { column: None, .. }
+    ));
+}
@@ -0,0 +1,193 @@
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::{ColumnDataView, VarDataView};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_view_with_workspace, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace,
+};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+
+fn sample_batch(rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![
+        ColumnarField {
+            name: Some("pair".to_string()),
+            ty: ColumnarType::Utf8,
+        },
+        ColumnarField {
+            name: Some("e_ms".to_string()),
+            ty: ColumnarType::I64,
+        },
+        ColumnarField {
+            name: Some("close".to_string()),
+            ty: ColumnarType::F64,
+        },
+    ])
+    .unwrap();
+
+    let mut pair_validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    if rows > 0 {
+        pair_validity.set(0, false).unwrap();
+    }
+    let mut offsets = vec![0u32; rows + 1];
+    let mut data = Vec::new();
+    for i in 0..rows {
+        if pair_validity.is_valid(i).unwrap() {
+            let v = if i % 2 == 0 { b"BTCUSDT" } else { b"ETHUSDT" };
+            data.extend_from_slice(v);
+            offsets[i + 1] = data.len() as u32;
+        } else {
+            offsets[i + 1] = offsets[i];
+        }
+    }
+
+    let e_validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    let mut e_vals = Vec::with_capacity(rows);
+    for i in 0..rows {
+        e_vals.push(1_700_000_000_000i64 + i as i64 * 60_000);
+    }
+
+    let f_validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    let mut f_bits = Vec::with_capacity(rows);
+    for i in 0..rows {
+        f_bits.push((10_000.0 + i as f64 * 0.25).to_bits());
+    }
+
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::Var {
+                ty: ColumnarType::Utf8,
+                validity: pair_validity,
+                offsets,
+                data,
+            },
+            ColumnData::FixedI64 {
+                validity: e_validity,
+                values: e_vals,
+            },
+            ColumnData::FixedF64Bits {
+                validity: f_validity,
+                values: f_bits,
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn points_into(input: &[u8], ptr: *const u8) -> bool {
+    let start = input.as_ptr() as usize;
+    let end = start + input.len();
+    let p = ptr as usize;
+    p >= start && p < end
+}
+
+#[test]
+fn decode_view_matches_owned_decode_plain() {
+    let batch = sample_batch(256);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    let mut ws = MathldbtV1DecodeWorkspace::default();
+    let decoded = decode_mathldbt_v1_view_with_workspace(&bytes, &mut ws).unwrap();
+    assert_eq!(decoded.schema(), &batch.schema);
+    assert_eq!(decoded.row_count(), batch.row_count);
+    for (got, want) in decoded.columns().iter().zip(batch.columns.iter()) {
+        assert_eq!(got.to_column_data().unwrap(), *want);
+    }
+    assert_eq!(decoded.to_batch().unwrap(), decode_mathldbt_v1(&bytes).unwrap());
+}
+
+#[test]
+fn decode_view_matches_owned_decode_dict_and_delta() {
+    let batch = sample_batch(1024);
+    let mut bytes = Vec::new();
+    let mut enc_ws = MathldbtV1EncodeWorkspace::default();
+    enc_ws
+        .set_enable_dict_utf8(true)
+        .set_enable_delta_varint_i64(true);
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut bytes, &mut enc_ws).unwrap();
+
+    let mut ws = MathldbtV1DecodeWorkspace::default();
+    let decoded = decode_mathldbt_v1_view_with_workspace(&bytes, &mut ws).unwrap();
+    assert_eq!(decoded.to_batch().unwrap(), batch);
+
+    // Dict and delta columns are materialized, so they never point into the input.
+    match decoded.columns()[0] {
+        ColumnDataView::Var {
+            data: VarDataView::Contiguous(data),
+            ..
+        } => assert!(!points_into(&bytes, data.as_ptr())),
+        _ => panic!("expected contiguous varlen column"),
+    }
+    match decoded.columns()[1] {
+        ColumnDataView::FixedI64 { values, .. } => {
+            assert!(!points_into(&bytes, values.as_ptr() as *const u8))
+        }
+        _ => panic!("expected i64 column"),
+    }
+}
+
+#[test]
+fn decode_view_borrows_plain_columns_when_aligned() {
+    let batch = sample_batch(64);
+    let mut encoded = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut encoded).unwrap();
+
+    let mut borrowed_i64 = 0usize;
+    let mut copied_i64 = 0usize;
+    let mut borrowed_var = 0usize;
+    let mut ws = MathldbtV1DecodeWorkspace::default();
+    for shift in 0..8 {
+        let mut buf = vec![0u8; shift + encoded.len()];
+        buf[shift..].copy_from_slice(&encoded);
+        let input = &buf[shift..];
+
+        let decoded = decode_mathldbt_v1_view_with_workspace(input, &mut ws).unwrap();
+        assert_eq!(decoded.to_batch().unwrap(), batch);
+
+        match decoded.columns()[0] {
+            ColumnDataView::Var {
+                offsets,
+                data: VarDataView::Contiguous(data),
+                ..
+            } => {
+                // Offsets and data are borrowed (or materialized) together.
+                let offsets_borrowed = points_into(input, offsets.as_ptr() as *const u8);
+                assert_eq!(points_into(input, data.as_ptr()), offsets_borrowed);
+                if offsets_borrowed {
+                    borrowed_var += 1;
+                }
+            }
+            _ => panic!("expected contiguous varlen column"),
+        }
+        match decoded.columns()[1] {
+            ColumnDataView::FixedI64 { values, .. } => {
+                if points_into(input, values.as_ptr() as *const u8) {
+                    borrowed_i64 += 1;
+                } else {
+                    copied_i64 += 1;
+                }
+            }
+            _ => panic!("expected i64 column"),
+        }
+    }
+
+    if cfg!(target_endian = "little") {
+        assert!(borrowed_i64 > 0);
+        assert!(borrowed_var > 0);
+    }
+    assert!(copied_i64 > 0);
+}
+
+#[test]
+fn decode_view_errors_match_owned_decode() {
+    let batch = sample_batch(16);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    let truncated = &bytes[..bytes.len() - 3];
+    let mut ws = MathldbtV1DecodeWorkspace::default();
+    let err = decode_mathldbt_v1_view_with_workspace(truncated, &mut ws).unwrap_err();
+    assert_eq!(err, decode_mathldbt_v1(truncated).unwrap_err());
+}
@@ -0,0 +1,602 @@
+use crate::Error;
+use crate::LimitKind;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView};
+use crate::codec::mathldbt_v1::{
+    ColumnSelector, MathldbtV1DecodeWorkspace, MathldbtV1Dictionary, MathldbtV1EncodeWorkspace,
+    decode_mathldbt_v1, decode_mathldbt_v1_into, decode_mathldbt_v1_into_with_workspace,
+    decode_mathldbt_v1_projected_with_workspace, decode_mathldbt_v1_view_with_workspace,
+    decode_mathldbt_v1_with_workspace, encode_mathldbt_v1_fast_path_into_with_workspace,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+#[cfg(feature = "compression-zstd")]
+use crate::codec::mathldbt_v1_compressed::{
+    Compression, MathldbtV1CompressedDecodeWorkspace, MathldbtV1CompressedEncodeWorkspace,
+    decode_mathldbt_v1_compressed_with_workspace,
+    encode_mathldbt_v1_compressed_into_with_workspace,
+};
+use crate::codec::{StreamReader, StreamWriter};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+use crate::tests::common::encode_both;
+use std::cell::Cell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+const PAIRS: [&str; 4] = ["BTC-USD", "ETH-USD", "SOL-USD", "XRP-USD"];
+
+fn var(ty: ColumnarType, values: &[Option<&[u8]>]) -> ColumnData {
+    let mut validity = ValidityBitmap::new_all_valid(values.len()).unwrap();
+    let mut offsets = vec![0u32];
+    let mut data = Vec::new();
+    for (i, v) in values.iter().enumerate() {
+        match v {
+            Some(v) => data.extend_from_slice(v),
+            None => validity.set(i, false).unwrap(),
+        }
+        offsets.push(data.len() as u32);
+    }
+    ColumnData::Var {
+        ty,
+        validity,
+        offsets,
+        data,
+    }
+}
+
+/// `pair` (Utf8) and `symbol_id` (Bytes) cycle through `pairs`; every seventh row is null.
+fn pair_batch(rows: usize, pairs: &[&str]) -> ColumnarBatch {
+    let f = |name: &str, ty| ColumnarField {
+        name: Some(name.to_string()),
+        ty,
+    };
+    let schema = ColumnarSchema::new(vec![
+        f("pair", ColumnarType::Utf8),
+        f("symbol_id", ColumnarType::Bytes),
+    ])
+    .unwrap();
+    let values: Vec<Option<&[u8]>> = (0..rows)
+        .map(|i| (i % 7 != 6).then(|| pairs[i % pairs.len()].as_bytes()))
+        .collect();
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            var(ColumnarType::Utf8, &values),
+            var(ColumnarType::Bytes, &values),
+        ],
+    )
+    .unwrap()
+}
+
+fn assert_decodes_to(bytes: &[u8], batch: &ColumnarBatch) {
+    assert_eq!(&decode_mathldbt_v1(bytes).unwrap(), batch);
+    let mut out = pair_batch(3, &PAIRS);
+    decode_mathldbt_v1_into(bytes, &mut out).unwrap();
+    assert_eq!(&out, batch);
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    let decoded = decode_mathldbt_v1_view_with_workspace(bytes, &mut dws).unwrap();
+    assert_eq!(&decoded.to_batch().unwrap(), batch);
+}
+
+#[test]
+fn dict_encodes_bytes_columns_when_enabled() {
+    let batch = pair_batch(100, &PAIRS);
+
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_dict_utf8(true);
+    let utf8_only = encode_both(&batch, &mut ws);
+    let info = inspect_mathldbt_v1(&utf8_only).unwrap();
+    assert_eq!(info.columns[0].encoding_id, 2);
+    assert_eq!(info.columns[1].encoding_id, 0);
+
+    ws.set_enable_dict_bytes(true);
+    let bytes = encode_both(&batch, &mut ws);
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(info.columns[1].encoding_id, 2);
+    assert_eq!(info.columns[1].payload1.len(), 100);
+    // Both columns hold the same values, so they produce the same dictionary.
+    assert_eq!(
+        bytes[info.columns[0].payload2.clone()],
+        bytes[info.columns[1].payload2.clone()]
+    );
+    assert!(bytes.len() < utf8_only.len());
+    assert_decodes_to(&bytes, &batch);
+
+    // A dictionary that is not smaller than the plain layout is not used.
+    let batch = pair_batch(2, &PAIRS);
+    let bytes = encode_both(&batch, &mut ws);
+    assert_eq!(
+        inspect_mathldbt_v1(&bytes).unwrap().columns[1].encoding_id,
+        0
+    );
+}
+
+#[test]
+fn supplied_dictionary_keeps_indices_stable_across_batches() {
+    let dict = MathldbtV1Dictionary::new(PAIRS).unwrap();
+    assert_eq!(dict.len(), 4);
+    assert_eq!(dict.index_of(b"SOL-USD"), Some(2));
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_column_dictionary(0, Some(dict.clone()))
+        .set_column_dictionary(1, Some(dict));
+
+    // Each batch sees a different subset, in a different order, of the same pairs.
+    let first = pair_batch(10, &["SOL-USD", "BTC-USD"]);
+    let second = pair_batch(2, &["XRP-USD"]);
+    let first_bytes = encode_both(&first, &mut ws);
+    let second_bytes = encode_both(&second, &mut ws);
+    let a = inspect_mathldbt_v1(&first_bytes).unwrap();
+    let b = inspect_mathldbt_v1(&second_bytes).unwrap();
+    for (col_a, col_b) in a.columns.iter().zip(&b.columns) {
+        assert_eq!((col_a.encoding_id, col_b.encoding_id), (2, 2));
+        assert_eq!(
+            first_bytes[col_a.payload2.clone()],
+            second_bytes[col_b.payload2.clone()]
+        );
+    }
+    assert_eq!(&first_bytes[a.columns[0].payload1.clone()][..3], [2, 0, 2]);
+    assert_eq!(&second_bytes[b.columns[1].payload1.clone()], [3, 3]);
+    assert_decodes_to(&first_bytes, &first);
+    assert_decodes_to(&second_bytes, &second);
+
+    // Removing the dictionary restores the per-batch choice.
+    ws.set_column_dictionary(0, None);
+    let bytes = encode_both(&second, &mut ws);
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(
+        (info.columns[0].encoding_id, info.columns[1].encoding_id),
+        (0, 2)
+    );
+}
+
+#[test]
+fn supplied_dictionary_errors() {
+    assert_eq!(
+        MathldbtV1Dictionary::new(["BTC-USD", "ETH-USD", "BTC-USD"]).unwrap_err(),
+        Error::InvalidBatch {
+            column: None,
+            reason: "duplicate dictionary value",
+        }
+    );
+
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_column_dictionary(1, Some(MathldbtV1Dictionary::new(&PAIRS[..3]).unwrap()));
+    let batch = pair_batch(8, &PAIRS);
+    let mut out = Vec::new();
+    assert_eq!(
+        encode_mathldbt_v1_into_with_workspace(&batch, &mut out, &mut ws).unwrap_err(),
+        Error::InvalidBatch {
+            column: Some(1),
+            reason: "value not in dictionary",
+        }
+    );
+
+    // Null rows need no entry, even with an empty dictionary.
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_column_dictionary(0, Some(MathldbtV1Dictionary::new([""; 0]).unwrap()));
+    let schema = ColumnarSchema::new(vec![ColumnarField {
+        name: None,
+        ty: ColumnarType::Utf8,
+    }])
+    .unwrap();
+    let batch =
+        ColumnarBatch::new(schema, 2, vec![var(ColumnarType::Utf8, &[None, None])]).unwrap();
+    let bytes = encode_both(&batch, &mut ws);
+    assert_eq!(
+        inspect_mathldbt_v1(&bytes).unwrap().columns[0].encoding_id,
+        2
+    );
+    assert_eq!(decode_mathldbt_v1(&bytes).unwrap(), batch);
+}
+
+fn delta_workspace() -> MathldbtV1EncodeWorkspace {
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_dict_utf8(true)
+        .set_enable_dict_bytes(true)
+        .set_enable_dict_delta(true);
+    ws
+}
+
+fn encode_delta(batch: &ColumnarBatch, ws: &mut MathldbtV1EncodeWorkspace) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_mathldbt_v1_into_with_workspace(batch, &mut out, ws).unwrap();
+    out
+}
+
+/// Three batches whose pairs grow from two to four, then repeat.
+fn delta_batches() -> [ColumnarBatch; 3] {
+    [
+        pair_batch(30, &PAIRS[..2]),
+        pair_batch(20, &PAIRS),
+        pair_batch(2, &["XRP-USD"]),
+    ]
+}
+
+#[test]
+fn delta_dictionaries_send_only_new_entries() {
+    let batches = delta_batches();
+    let mut owned_ws = delta_workspace();
+    let mut view_ws = delta_workspace();
+    let frames: Vec<Vec<u8>> = batches
+        .iter()
+        .map(|batch| {
+            let mut owned = Vec::new();
+            encode_mathldbt_v1_into_with_workspace(batch, &mut owned, &mut owned_ws).unwrap();
+            let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+            let view = ColumnarBatchView {
+                schema: &batch.schema,
+                row_count: batch.row_count,
+                columns: &cols,
+            };
+            let mut fast = Vec::new();
+            encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut view_ws)
+                .unwrap();
+            assert_eq!(fast, owned);
+            owned
+        })
+        .collect();
+
+    // dict id u32 | base count u32 | index width u8 | new count u32 | offsets | bytes.
+    for (frame, (base, new)) in frames.iter().zip([(0u32, 2u32), (2, 2), (4, 0)]) {
+        let info = inspect_mathldbt_v1(frame).unwrap();
+        for (column, col) in info.columns.iter().enumerate() {
+            assert_eq!(col.encoding_id, 5);
+            let p2 = &frame[col.payload2.clone()];
+            assert_eq!(p2[..4], (column as u32).to_le_bytes());
+            assert_eq!(p2[4..8], base.to_le_bytes());
+            assert_eq!(p2[9..13], new.to_le_bytes());
+            assert_eq!(p2.len(), 13 + 4 * (new as usize + 1) + 7 * new as usize);
+        }
+    }
+    assert_eq!(
+        &frames[2][inspect_mathldbt_v1(&frames[2]).unwrap().columns[0]
+            .payload1
+            .clone()],
+        [3, 3]
+    );
+
+    // Every decode path shares the dictionaries a workspace has received, in stream order.
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&frames[0], &mut dws).unwrap(),
+        batches[0]
+    );
+    let mut out = pair_batch(3, &PAIRS);
+    decode_mathldbt_v1_into_with_workspace(&frames[1], &mut out, &mut dws).unwrap();
+    assert_eq!(out, batches[1]);
+    let decoded = decode_mathldbt_v1_view_with_workspace(&frames[2], &mut dws).unwrap();
+    assert_eq!(decoded.to_batch().unwrap(), batches[2]);
+
+    // Projection still applies the deltas of the columns it skips.
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    for (frame, batch) in frames.iter().zip(&batches).take(2) {
+        let projected = decode_mathldbt_v1_projected_with_workspace(
+            frame,
+            &[ColumnSelector::Index(0), ColumnSelector::Index(0)],
+            &mut dws,
+        )
+        .unwrap();
+        assert_eq!(projected.columns[1], batch.columns[0]);
+    }
+    let decoded = decode_mathldbt_v1_with_workspace(&frames[2], &mut dws).unwrap();
+    assert_eq!(decoded, batches[2]);
+
+    let mut w =
+        StreamWriter::with_workspace(Vec::new(), batches[0].schema.clone(), delta_workspace())
+            .unwrap();
+    for batch in &batches {
+        w.write_batch(batch).unwrap();
+    }
+    let stream = w.finish().unwrap();
+    let mut r = StreamReader::new(stream.as_slice(), 1 << 20).unwrap();
+    let mut out = r.new_batch().unwrap();
+    for batch in &batches {
+        assert!(r.read_batch_into(&mut out).unwrap());
+        assert_eq!(&out, batch);
+    }
+    assert!(!r.read_batch_into(&mut out).unwrap());
+}
+
+#[test]
+fn delta_dictionaries_reject_unknown_state() {
+    let batches = delta_batches();
+    let mut ws = delta_workspace();
+    let frames: Vec<Vec<u8>> = batches.iter().map(|b| encode_delta(b, &mut ws)).collect();
+    let p2 = inspect_mathldbt_v1(&frames[1]).unwrap().columns[0]
+        .payload2
+        .start;
+    let malformed = |offset, reason| Error::Malformed {
+        column: Some(0),
+        offset,
+        reason,
+    };
+
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&frames[1], &mut dws).unwrap_err(),
+        malformed(p2, "unknown dictionary id")
+    );
+    decode_mathldbt_v1_with_workspace(&frames[0], &mut dws).unwrap();
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&frames[2], &mut dws).unwrap_err(),
+        malformed(
+            inspect_mathldbt_v1(&frames[2]).unwrap().columns[0]
+                .payload2
+                .start
+                + 4,
+            "dictionary delta out of sequence"
+        )
+    );
+
+    // An index past the dictionary is rejected before the frame's delta is applied.
+    let p1 = inspect_mathldbt_v1(&frames[1]).unwrap().columns[0]
+        .payload1
+        .start;
+    let mut bad = frames[1].clone();
+    bad[p1] = 4;
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&bad, &mut dws).unwrap_err(),
+        malformed(p1, "dict index out of bounds")
+    );
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&frames[1], &mut dws).unwrap(),
+        batches[1]
+    );
+
+    // After a reset the encoder sends whole dictionaries again, which a decoder that still
+    // holds them rejects instead of starting over.
+    ws.reset_dict_deltas();
+    let restart = encode_delta(&batches[2], &mut ws);
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&restart, &mut dws).unwrap_err(),
+        malformed(
+            inspect_mathldbt_v1(&restart).unwrap().columns[0]
+                .payload2
+                .start
+                + 4,
+            "dictionary delta out of sequence"
+        )
+    );
+
+    dws.reset_dict_deltas();
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&frames[2], &mut dws).unwrap_err(),
+        malformed(
+            inspect_mathldbt_v1(&frames[2]).unwrap().columns[0]
+                .payload2
+                .start,
+            "unknown dictionary id"
+        )
+    );
+
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&restart, &mut dws).unwrap(),
+        batches[2]
+    );
+}
+
+#[test]
+fn failed_decode_does_not_advance_delta_dictionaries() {
+    let batches = delta_batches();
+    let mut ws = delta_workspace();
+    let frames: Vec<Vec<u8>> = batches.iter().map(|b| encode_delta(b, &mut ws)).collect();
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    decode_mathldbt_v1_with_workspace(&frames[0], &mut dws).unwrap();
+
+    // Column 0's delta applies before column 1's indices fail; no decode path keeps it.
+    let p1 = inspect_mathldbt_v1(&frames[1]).unwrap().columns[1]
+        .payload1
+        .start;
+    let mut bad = frames[1].clone();
+    bad[p1] = 4;
+    let rejected = Error::Malformed {
+        column: Some(1),
+        offset: p1,
+        reason: "dict index out of bounds",
+    };
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&bad, &mut dws).unwrap_err(),
+        rejected
+    );
+    let mut out = pair_batch(3, &PAIRS);
+    assert_eq!(
+        decode_mathldbt_v1_into_with_workspace(&bad, &mut out, &mut dws).unwrap_err(),
+        rejected
+    );
+    assert_eq!(
+        decode_mathldbt_v1_view_with_workspace(&bad, &mut dws).unwrap_err(),
+        rejected
+    );
+    assert_eq!(
+        decode_mathldbt_v1_projected_with_workspace(&bad, &[ColumnSelector::Index(0)], &mut dws)
+            .unwrap_err(),
+        rejected
+    );
+
+    for (frame, batch) in frames.iter().zip(&batches).skip(1) {
+        assert_eq!(
+            decode_mathldbt_v1_with_workspace(frame, &mut dws).unwrap(),
+            *batch
+        );
+    }
+}
+
+#[test]
+fn delta_dictionaries_respect_decode_limits() {
+    let batches = delta_batches();
+    let mut ws = delta_workspace();
+    let frames: Vec<Vec<u8>> = batches.iter().map(|b| encode_delta(b, &mut ws)).collect();
+    let p2 = |column: usize| {
+        inspect_mathldbt_v1(&frames[1]).unwrap().columns[column]
+            .payload2
+            .start
+    };
+    let exceeded = |column| Error::LimitExceeded {
+        kind: LimitKind::DictEntries,
+        column: Some(column),
+        offset: Some(p2(column)),
+    };
+
+    // Two columns of two entries each, then two more each.
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    dws.set_max_dict_delta_entries(Some(6));
+    decode_mathldbt_v1_with_workspace(&frames[0], &mut dws).unwrap();
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&frames[1], &mut dws).unwrap_err(),
+        exceeded(1)
+    );
+    dws.set_max_dict_delta_entries(Some(8));
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&frames[1], &mut dws).unwrap(),
+        batches[1]
+    );
+
+    // Every entry is 7 bytes.
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    dws.set_max_dict_delta_bytes(Some(28));
+    decode_mathldbt_v1_with_workspace(&frames[0], &mut dws).unwrap();
+    assert_eq!(
+        decode_mathldbt_v1_with_workspace(&frames[1], &mut dws).unwrap_err(),
+        exceeded(0)
+    );
+    dws.reset_dict_deltas();
+    decode_mathldbt_v1_with_workspace(&frames[0], &mut dws).unwrap();
+
+    // Stream readers expose their workspace for the same caps.
+    let mut w =
+        StreamWriter::with_workspace(Vec::new(), batches[0].schema.clone(), delta_workspace())
+            .unwrap();
+    for batch in &batches {
+        w.write_batch(batch).unwrap();
+    }
+    let stream = w.finish().unwrap();
+    let mut r = StreamReader::new(stream.as_slice(), 1 << 20).unwrap();
+    r.workspace_mut().set_max_dict_delta_entries(Some(4));
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert!(matches!(
+        r.read_batch_into(&mut out).unwrap_err(),
+        Error::LimitExceeded {
+            kind: LimitKind::DictEntries,
+            column: Some(0),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn failed_encode_does_not_advance_delta_dictionaries() {
+    let batches = delta_batches();
+    let mut ws = delta_workspace();
+    let first = encode_delta(&batches[0], &mut ws);
+
+    // Column 0 adds entries before column 1 fails; the batch is never sent.
+    ws.set_column_dictionary(1, Some(MathldbtV1Dictionary::new(&PAIRS[..2]).unwrap()));
+    let mut out = Vec::new();
+    assert_eq!(
+        encode_mathldbt_v1_into_with_workspace(&batches[1], &mut out, &mut ws).unwrap_err(),
+        Error::InvalidBatch {
+            column: Some(1),
+            reason: "value not in dictionary",
+        }
+    );
+    ws.set_column_dictionary(1, None);
+
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    for (bytes, batch) in [
+        (first, &batches[0]),
+        (encode_delta(&batches[1], &mut ws), &batches[1]),
+    ] {
+        assert_eq!(
+            decode_mathldbt_v1_with_workspace(&bytes, &mut dws).unwrap(),
+            *batch
+        );
+    }
+}
+
+/// Rejects writes, without writing anything, while `fail` is set.
+struct FlakyWriter {
+    out: Vec<u8>,
+    fail: Rc<Cell<bool>>,
+}
+
+impl Write for FlakyWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.fail.get() {
+            return Err(io::Error::other("disk full"));
+        }
+        self.out.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn failed_frame_write_does_not_advance_delta_dictionaries() {
+    let batches = delta_batches();
+    let fail = Rc::new(Cell::new(false));
+    let inner = FlakyWriter {
+        out: Vec::new(),
+        fail: fail.clone(),
+    };
+    let mut w =
+        StreamWriter::with_workspace(inner, batches[0].schema.clone(), delta_workspace()).unwrap();
+    w.write_batch(&batches[0]).unwrap();
+
+    // The second batch's new entries are encoded but never reach the stream.
+    fail.set(true);
+    assert_eq!(
+        w.write_batch(&batches[1]).unwrap_err(),
+        Error::Io {
+            kind: io::ErrorKind::Other,
+            message: "disk full".to_string(),
+        }
+    );
+    fail.set(false);
+    w.write_batch(&batches[1]).unwrap();
+    w.write_batch(&batches[2]).unwrap();
+
+    let stream = w.finish().unwrap().out;
+    let mut r = StreamReader::new(stream.as_slice(), 1 << 20).unwrap();
+    let mut out = r.new_batch().unwrap();
+    for batch in &batches {
+        assert!(r.read_batch_into(&mut out).unwrap());
+        assert_eq!(&out, batch);
+    }
+    assert!(!r.read_batch_into(&mut out).unwrap());
+}
+
+#[cfg(feature = "compression-zstd")]
+#[test]
+fn failed_compression_does_not_advance_delta_dictionaries() {
+    let batches = delta_batches();
+    let mut ws = delta_workspace();
+    let mut cws = MathldbtV1CompressedEncodeWorkspace::default();
+    let c = Compression::Zstd { level: 3 };
+    let mut frames = Vec::new();
+    for (i, batch) in batches.iter().enumerate() {
+        let mut out = Vec::new();
+        if i == 1 {
+            let bad = Compression::Zstd { level: 999 };
+            encode_mathldbt_v1_compressed_into_with_workspace(
+                batch, &mut out, bad, &mut ws, &mut cws,
+            )
+            .unwrap_err();
+        }
+        encode_mathldbt_v1_compressed_into_with_workspace(batch, &mut out, c, &mut ws, &mut cws)
+            .unwrap();
+        frames.push(out);
+    }
+
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    let mut cdws = MathldbtV1CompressedDecodeWorkspace::default();
+    for (bytes, batch) in frames.iter().zip(&batches) {
+        let decoded =
+            decode_mathldbt_v1_compressed_with_workspace(bytes, c, 1 << 20, &mut dws, &mut cdws)
+                .unwrap();
+        assert_eq!(&decoded, batch);
+    }
+}
@@ -2,8 +2,8 @@ use crate::Error;
 use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
 use crate::batch_view::{ColumnDataView, ColumnarBatchView, VarDataView};
 use crate::codec::mathldbt_v1::{
-    MathldbtV1EncodeWorkspace, encode_mathldbt_v1_fast_path_into_with_workspace,
-    encode_mathldbt_v1_into_with_workspace,
+    MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    encode_mathldbt_v1_fast_path_into_with_workspace, encode_mathldbt_v1_into_with_workspace,
 };
 use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
 
@@ -78,7 +78,7 @@ fn fast_path_plain_bytes_match_owned_plain() {
             offsets,
             data,
         } => (
-            *ty,
+            ty.clone(),
             validity.as_bytes(),
             offsets.as_slice(),
             data.as_slice(),
@@ -138,7 +138,7 @@ fn fast_path_plain_bytes_match_owned_plain_for_chunked_varlen() {
             offsets,
             data,
         } => (
-            *ty,
+            ty.clone(),
             validity.as_bytes(),
             offsets.as_slice(),
             data.as_slice(),
@@ -205,7 +205,7 @@ fn fast_path_opt_bytes_match_owned_opt_including_chunked_varlen() {
             offsets,
             data,
         } => (
-            *ty,
+            ty.clone(),
             validity.as_bytes(),
             offsets.as_slice(),
             data.as_slice(),
@@ -268,6 +268,32 @@ fn fast_path_opt_bytes_match_owned_opt_including_chunked_varlen() {
     assert_eq!(owned, fast);
 }
 
+#[test]
+fn fast_path_pg_be_fixed_bytes_match_owned() {
+    let batch = sample_batch(500);
+    let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+    let view = ColumnarBatchView {
+        schema: &batch.schema,
+        row_count: batch.row_count,
+        columns: cols.as_slice(),
+    };
+
+    for delta in [false, true] {
+        let mut ws_owned = MathldbtV1EncodeWorkspace::default();
+        ws_owned
+            .set_enable_pg_be_fixed(true)
+            .set_enable_delta_varint_i64(delta);
+        let mut ws_fast = ws_owned.clone();
+
+        let mut owned = Vec::new();
+        let mut fast = Vec::new();
+        encode_mathldbt_v1_into_with_workspace(&batch, &mut owned, &mut ws_owned).unwrap();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut ws_fast).unwrap();
+        assert_eq!(owned, fast);
+        assert_eq!(decode_mathldbt_v1(&fast).unwrap(), batch);
+    }
+}
+
 #[test]
 fn fast_path_is_deterministic_with_workspace_reuse() {
     let batch = sample_batch(2_000);
@@ -278,7 +304,7 @@ fn fast_path_is_deterministic_with_workspace_reuse() {
             offsets,
             data,
         } => (
-            *ty,
+            ty.clone(),
             validity.as_bytes(),
             offsets.as_slice(),
             data.as_slice(),
@@ -370,7 +396,13 @@ fn fast_path_invalid_validity_len_errors_deterministically() {
     let mut ws = MathldbtV1EncodeWorkspace::default();
     let err =
         encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut out, &mut ws).unwrap_err();
-    assert_eq!(err, Error::Other("validity length mismatch".to_string()));
+    assert_eq!(
+        err,
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "validity length mismatch",
+        }
+    );
 }
 
 #[test]
@@ -409,7 +441,13 @@ fn fast_path_invalid_offsets_len_errors_deterministically() {
     let mut ws = MathldbtV1EncodeWorkspace::default();
     let err =
         encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut out, &mut ws).unwrap_err();
-    assert_eq!(err, Error::Other("offsets length mismatch".to_string()));
+    assert_eq!(
+        err,
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "offsets length mismatch",
+        }
+    );
 }
 
 #[test]
@@ -453,7 +491,13 @@ fn fast_path_offsets_first_must_be_zero_errors_deterministically() {
     let mut ws = MathldbtV1EncodeWorkspace::default();
     let err =
         encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut out, &mut ws).unwrap_err();
-    assert_eq!(err, Error::Other("offsets[0] must be 0".to_string()));
+    assert_eq!(
+        err,
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "offsets[0] must be 0",
+        }
+    );
 }
 
 #[test]
@@ -499,7 +543,10 @@ fn fast_path_offsets_non_monotonic_errors_deterministically() {
         encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut out, &mut ws).unwrap_err();
     assert_eq!(
         err,
-        Error::Other("offsets must be non-decreasing".to_string())
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "offsets must be non-decreasing",
+        }
     );
 }
 
@@ -544,7 +591,13 @@ fn fast_path_final_offset_mismatch_errors_deterministically() {
     let mut ws = MathldbtV1EncodeWorkspace::default();
     let err =
         encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut out, &mut ws).unwrap_err();
-    assert_eq!(err, Error::Other("final offset mismatch".to_string()));
+    assert_eq!(
+        err,
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "final offset mismatch",
+        }
+    );
 }
 
 #[test]
@@ -582,7 +635,13 @@ fn fast_path_chunked_data_len_mismatch_errors_deterministically() {
     let mut ws = MathldbtV1EncodeWorkspace::default();
     let err =
         encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut out, &mut ws).unwrap_err();
-    assert_eq!(err, Error::Other("final offset mismatch".to_string()));
+    assert_eq!(
+        err,
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "final offset mismatch",
+        }
+    );
 }
 
 #[cfg(feature = "compression-zstd")]
@@ -602,7 +661,7 @@ fn compressed_fast_path_zstd_bytes_match_owned() {
             offsets,
             data,
         } => (
-            *ty,
+            ty.clone(),
             validity.as_bytes(),
             offsets.as_slice(),
             data.as_slice(),
@@ -699,7 +758,7 @@ fn compressed_fast_path_zstd_is_deterministic_with_workspace_reuse() {
             offsets,
             data,
         } => (
-            *ty,
+            ty.clone(),
             validity.as_bytes(),
             offsets.as_slice(),
             data.as_slice(),
@@ -790,7 +849,7 @@ fn compressed_fast_path_gzip_bytes_match_owned() {
             offsets,
             data,
         } => (
-            *ty,
+            ty.clone(),
             validity.as_bytes(),
             offsets.as_slice(),
             data.as_slice(),
@@ -887,7 +946,7 @@ fn compressed_fast_path_gzip_is_deterministic_with_workspace_reuse() {
             offsets,
             data,
         } => (
-            *ty,
+            ty.clone(),
             validity.as_bytes(),
             offsets.as_slice(),
             data.as_slice(),
@@ -0,0 +1,297 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::codec::{
+    FileReader, FileWriter, MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode,
+};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+
+fn bars_schema() -> ColumnarSchema {
+    ColumnarSchema::new(vec![
+        ColumnarField {
+            name: Some("t".to_string()),
+            ty: ColumnarType::TimestampTzMicros,
+        },
+        ColumnarField {
+            name: Some("close".to_string()),
+            ty: ColumnarType::F64,
+        },
+    ])
+    .unwrap()
+}
+
+fn bars(start: i64, rows: usize) -> ColumnarBatch {
+    let validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    ColumnarBatch::new(
+        bars_schema(),
+        rows,
+        vec![
+            ColumnData::FixedTimestampMicros {
+                validity: validity.clone(),
+                values: (0..rows).map(|i| start + i as i64 * 60).collect(),
+            },
+            ColumnData::FixedF64Bits {
+                validity,
+                values: (0..rows).map(|i| (100.0 + i as f64).to_bits()).collect(),
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn write_file(batches: &[ColumnarBatch], time_column: Option<usize>) -> Vec<u8> {
+    let mut w = FileWriter::new(Vec::new(), bars_schema(), time_column).unwrap();
+    for b in batches {
+        w.write_batch(b).unwrap();
+    }
+    w.finish().unwrap()
+}
+
+#[test]
+fn file_random_access_round_trip() {
+    let batches = vec![bars(0, 10), bars(600, 5), bars(900, 0), bars(1_000, 20)];
+    let bytes = write_file(&batches, Some(0));
+
+    let r = FileReader::new(&bytes).unwrap();
+    assert_eq!(r.len(), 4);
+    assert_eq!(r.time_column(), Some(0));
+    for i in [3, 0, 2, 1] {
+        assert_eq!(r.batch(i).unwrap(), batches[i]);
+        // Slices feed the plain decoder directly.
+        assert_eq!(decode(r.batch_bytes(i).unwrap()).unwrap(), batches[i]);
+        assert_eq!(r.entries()[i].row_count as usize, batches[i].row_count);
+    }
+
+    let mut ws = MathldbtV1DecodeWorkspace::default();
+    let mut out = bars(0, 0);
+    r.batch_into_with_workspace(3, &mut out, &mut ws).unwrap();
+    assert_eq!(out, batches[3]);
+
+    assert_eq!(
+        r.batch(4).unwrap_err(),
+        Error::BatchIndexOutOfRange { index: 4, count: 4 }
+    );
+}
+
+#[test]
+fn file_time_index_selects_overlapping_batches() {
+    let mut with_nulls = bars(2_000, 4);
+    if let ColumnData::FixedTimestampMicros { validity, values } = &mut with_nulls.columns[0] {
+        values[0] = i64::MIN;
+        validity.set(0, false).unwrap();
+    }
+    let batches = vec![bars(0, 10), bars(600, 5), bars(900, 0), with_nulls];
+    let bytes = write_file(&batches, Some(0));
+    let r = FileReader::new(&bytes).unwrap();
+
+    assert_eq!(r.entries()[0].time_range, Some((0, 540)));
+    assert_eq!(r.entries()[2].time_range, None);
+    assert_eq!(r.entries()[3].time_range, Some((2_060, 2_180)));
+
+    let hits = |a, b| r.batches_in_time_range(a, b).collect::<Vec<_>>();
+    assert_eq!(hits(0, 10_000), vec![0, 1, 3]);
+    assert_eq!(hits(540, 600), vec![0, 1]);
+    assert_eq!(hits(541, 599), Vec::<usize>::new());
+    assert_eq!(hits(2_000, 2_060), vec![3]);
+    assert_eq!(hits(10, 0), Vec::<usize>::new());
+}
+
+#[test]
+fn file_without_time_index() {
+    let bytes = write_file(&[bars(0, 3)], None);
+    let r = FileReader::new(&bytes).unwrap();
+    assert_eq!(r.time_column(), None);
+    assert_eq!(r.entries()[0].time_range, None);
+    assert_eq!(r.batches_in_time_range(i64::MIN, i64::MAX).count(), 0);
+
+    let empty = write_file(&[], None);
+    let r = FileReader::new(&empty).unwrap();
+    assert!(r.is_empty());
+    assert_eq!(r.schema(), &bars_schema());
+}
+
+#[test]
+fn file_reader_checks_batches_and_time_column_against_the_schema() {
+    let bytes = write_file(&[bars(0, 3)], Some(0));
+    let r = FileReader::new(&bytes).unwrap();
+    assert_eq!(r.schema(), &bars_schema());
+    let mut other = ColumnarBatch::new(
+        ColumnarSchema::new(vec![ColumnarField {
+            name: None,
+            ty: ColumnarType::I64,
+        }])
+        .unwrap(),
+        0,
+        vec![ColumnData::FixedI64 {
+            validity: ValidityBitmap::new_all_valid(0).unwrap(),
+            values: Vec::new(),
+        }],
+    )
+    .unwrap();
+    assert_eq!(
+        r.batch_into_with_workspace(0, &mut other, &mut MathldbtV1DecodeWorkspace::default())
+            .unwrap_err(),
+        Error::Schema {
+            column: None,
+            reason: "batch schema does not match stream schema",
+        }
+    );
+
+    // Rename "close" in the header: the batch no longer matches it.
+    let at = bytes.windows(5).position(|w| w == b"close").unwrap();
+    let mut renamed = bytes.clone();
+    renamed[at..at + 5].copy_from_slice(b"clost");
+    assert_eq!(
+        FileReader::new(&renamed).unwrap().batch(0).unwrap_err(),
+        Error::Schema {
+            column: Some(1),
+            reason: "batch schema does not match stream schema",
+        }
+    );
+
+    // Point the footer's time column at the F64 column, then past the schema.
+    let n = bytes.len();
+    let footer = u64::from_le_bytes(bytes[n - 16..n - 8].try_into().unwrap()) as usize;
+    for c in [1u16, 2] {
+        let mut bad = bytes.clone();
+        bad[footer..footer + 2].copy_from_slice(&c.to_le_bytes());
+        assert_eq!(
+            FileReader::new(&bad).unwrap_err(),
+            Error::Malformed {
+                column: Some(c as usize),
+                offset: footer,
+                reason: "invalid time column",
+            }
+        );
+    }
+}
+
+#[test]
+fn file_writer_rejects_bad_time_column() {
+    let err = FileWriter::new(Vec::new(), bars_schema(), Some(1))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        Error::Schema {
+            column: Some(1),
+            reason: "time column must be I64 or a timestamp",
+        }
+    );
+}
+
+#[test]
+fn file_writer_rejects_delta_dictionaries() {
+    let rejected = Error::InvalidBatch {
+        column: None,
+        reason: "DictDelta is not supported in files",
+    };
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_dict_utf8(true).set_enable_dict_delta(true);
+    let err = FileWriter::with_workspace(Vec::new(), bars_schema(), Some(0), ws)
+        .err()
+        .unwrap();
+    assert_eq!(err, rejected);
+
+    // Enabling them later through the workspace is rejected per batch.
+    let mut w = FileWriter::new(Vec::new(), bars_schema(), Some(0)).unwrap();
+    w.workspace_mut().set_enable_dict_delta(true);
+    assert_eq!(w.write_batch(&bars(0, 3)).unwrap_err(), rejected);
+    w.workspace_mut().set_enable_dict_delta(false);
+    w.write_batch(&bars(0, 3)).unwrap();
+    let bytes = w.finish().unwrap();
+    assert_eq!(
+        FileReader::new(&bytes).unwrap().batch(0).unwrap(),
+        bars(0, 3)
+    );
+}
+
+#[test]
+fn file_reader_rejects_corrupt_footer() {
+    let bytes = write_file(&[bars(0, 3), bars(180, 3)], Some(0));
+    let n = bytes.len();
+
+    let mut bad = bytes.clone();
+    bad[n - 1] = b'X';
+    assert_eq!(
+        FileReader::new(&bad).unwrap_err(),
+        Error::BadMagic { offset: n - 8 }
+    );
+
+    assert_eq!(
+        FileReader::new(&bytes[..20]).unwrap_err(),
+        Error::Truncated {
+            column: None,
+            offset: 0,
+            needed: 36,
+        }
+    );
+
+    // Point the footer offset past the trailer.
+    let mut bad = bytes.clone();
+    bad[n - 16..n - 8].copy_from_slice(&(n as u64).to_le_bytes());
+    assert_eq!(
+        FileReader::new(&bad).unwrap_err(),
+        Error::Malformed {
+            column: None,
+            offset: n - 16,
+            reason: "footer offset out of bounds",
+        }
+    );
+
+    // Make the second entry run into the footer.
+    let footer = u64::from_le_bytes(bytes[n - 16..n - 8].try_into().unwrap()) as usize;
+    let entry = footer + 8 + 40;
+    let mut bad = bytes.clone();
+    bad[entry + 8..entry + 16].copy_from_slice(&1_000u64.to_le_bytes());
+    assert_eq!(
+        FileReader::new(&bad).unwrap_err(),
+        Error::Malformed {
+            column: None,
+            offset: entry,
+            reason: "index entry out of bounds",
+        }
+    );
+
+    // An offset + len that overflows u64.
+    let mut bad = bytes.clone();
+    bad[entry..entry + 8].copy_from_slice(&(u64::MAX - 4).to_le_bytes());
+    assert_eq!(
+        FileReader::new(&bad).unwrap_err(),
+        Error::Malformed {
+            column: None,
+            offset: entry,
+            reason: "index entry out of bounds",
+        }
+    );
+
+    // Entries that overlap or are out of order.
+    let first = footer + 8;
+    let mut overlapping = bytes.clone();
+    overlapping.copy_within(first..first + 8, entry);
+    let mut swapped = bytes.clone();
+    swapped[first..entry].copy_from_slice(&bytes[entry..entry + 40]);
+    swapped[entry..entry + 40].copy_from_slice(&bytes[first..entry]);
+    for bad in [overlapping, swapped] {
+        assert_eq!(
+            FileReader::new(&bad).unwrap_err(),
+            Error::Malformed {
+                column: None,
+                offset: entry,
+                reason: "index entries overlap or out of order",
+            }
+        );
+    }
+}
+
+#[test]
+fn file_batch_errors_are_offset_into_the_file() {
+    let bytes = write_file(&[bars(0, 3), bars(180, 3)], Some(0));
+    let r = FileReader::new(&bytes).unwrap();
+    let start = r.entries()[1].offset as usize;
+
+    let mut bad = bytes.clone();
+    bad[start] = b'X';
+    let r = FileReader::new(&bad).unwrap();
+    assert_eq!(r.batch(1).unwrap_err(), Error::BadMagic { offset: start });
+    assert!(r.batch(0).is_ok());
+}
@@ -0,0 +1,246 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_into, decode_mathldbt_v1_view_with_workspace,
+    encode_mathldbt_v1_fast_path_into_with_workspace, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+use crate::codec::{StreamReader, StreamWriter};
+use crate::schema::{ColumnarSchema, ColumnarType};
+use crate::tests::common::field;
+
+fn vector_of(item: ColumnarType, size: u32) -> ColumnarType {
+    ColumnarType::FixedSizeList(Box::new(item), size)
+}
+
+/// 32-byte hashes, 20-byte order ids and `F32`/`F64` vectors; every fourth row is null.
+fn fixed_size_batch(rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![
+        field("hash", ColumnarType::FixedSizeBinary(32)),
+        field("order_id", ColumnarType::FixedSizeBinary(20)),
+        field("embedding", vector_of(ColumnarType::F32, 4)),
+        field("features", vector_of(ColumnarType::F64, 3)),
+    ])
+    .unwrap();
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    for i in (3..rows).step_by(4) {
+        validity.set(i, false).unwrap();
+    }
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::FixedSizeBinary {
+                size: 32,
+                validity: validity.clone(),
+                values: (0..rows * 32).map(|i| (i * 7) as u8).collect(),
+            },
+            ColumnData::FixedSizeBinary {
+                size: 20,
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows * 20).map(|i| (i % 251) as u8).collect(),
+            },
+            ColumnData::FixedSizeListF32Bits {
+                size: 4,
+                validity: validity.clone(),
+                values: (0..rows * 4)
+                    .map(|i| (i as f32 / 16.0 - 1.0).to_bits())
+                    .collect(),
+            },
+            ColumnData::FixedSizeListF64Bits {
+                size: 3,
+                validity,
+                values: (0..rows * 3).map(|i| (i as f64 * 0.25).to_bits()).collect(),
+            },
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn fixed_size_columns_round_trip_through_all_paths() {
+    for rows in [0, 1, 3, 100] {
+        let batch = fixed_size_batch(rows);
+        let mut owned = Vec::new();
+        encode_mathldbt_v1_into(&batch, &mut owned).unwrap();
+        assert_eq!(decode_mathldbt_v1(&owned).unwrap(), batch);
+        assert_eq!(inspect_mathldbt_v1(&owned).unwrap().schema, batch.schema);
+
+        let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+        let view = ColumnarBatchView {
+            schema: &batch.schema,
+            row_count: batch.row_count,
+            columns: &cols,
+        };
+        let mut fast = Vec::new();
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut ws).unwrap();
+        assert_eq!(fast, owned);
+
+        let mut dws = MathldbtV1DecodeWorkspace::default();
+        let decoded = decode_mathldbt_v1_view_with_workspace(&owned, &mut dws).unwrap();
+        assert_eq!(decoded.to_batch().unwrap(), batch);
+
+        let mut out = fixed_size_batch(7);
+        decode_mathldbt_v1_into(&owned, &mut out).unwrap();
+        assert_eq!(out, batch);
+
+        // Delta coding does not apply; PgBeFixed swaps each item.
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        ws.set_enable_pg_be_fixed(true);
+        ws.set_enable_delta_varint_i64(true);
+        let mut be = Vec::new();
+        encode_mathldbt_v1_into_with_workspace(&batch, &mut be, &mut ws).unwrap();
+        assert_eq!(decode_mathldbt_v1(&be).unwrap(), batch);
+        let mut fast_be = Vec::new();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast_be, &mut ws).unwrap();
+        assert_eq!(fast_be, be);
+    }
+
+    let batch = fixed_size_batch(5);
+    let mut w = StreamWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    let stream = w.finish().unwrap();
+    let mut r = StreamReader::new(stream.as_slice(), 1 << 20).unwrap();
+    assert_eq!(r.schema(), &batch.schema);
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out, batch);
+}
+
+#[test]
+fn fixed_size_values_are_stored_without_offsets() {
+    let batch = fixed_size_batch(10);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    for (col, len) in info.columns.iter().zip([320, 200, 160, 240]) {
+        assert_eq!(col.payload1.len(), len);
+        assert!(col.payload2.is_empty());
+        assert!(col.children.is_empty());
+    }
+
+    // Byte-wide values are always aligned, so hashes are borrowed from the input.
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    let decoded = decode_mathldbt_v1_view_with_workspace(&bytes, &mut dws).unwrap();
+    match decoded.columns()[0] {
+        ColumnDataView::FixedSizeBinary { size, values, .. } => {
+            assert_eq!(size, 32);
+            assert!(bytes.as_ptr_range().contains(&values.as_ptr()));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn fixed_size_validate_checks_values_length() {
+    let batch = fixed_size_batch(4);
+    let mut columns = batch.columns.clone();
+    columns[2] = ColumnData::FixedSizeListF32Bits {
+        size: 4,
+        validity: ValidityBitmap::new_all_valid(4).unwrap(),
+        values: vec![0; 15],
+    };
+    assert_eq!(
+        ColumnarBatch::new(batch.schema.clone(), 4, columns).unwrap_err(),
+        Error::InvalidBatch {
+            column: Some(2),
+            reason: "values length mismatch",
+        }
+    );
+
+    let mut columns = batch.columns.clone();
+    columns[0] = ColumnData::FixedSizeBinary {
+        size: 16,
+        validity: ValidityBitmap::new_all_valid(4).unwrap(),
+        values: vec![0; 64],
+    };
+    assert_eq!(
+        ColumnarBatch::new(batch.schema.clone(), 4, columns).unwrap_err(),
+        Error::Schema {
+            column: Some(0),
+            reason: "column type mismatch",
+        }
+    );
+
+    for (ty, reason) in [
+        (ColumnarType::FixedSizeBinary(0), "invalid fixed size"),
+        (vector_of(ColumnarType::F32, u32::MAX), "invalid fixed size"),
+        (
+            vector_of(ColumnarType::I64, 4),
+            "fixed-size list items must be F32 or F64",
+        ),
+    ] {
+        assert_eq!(
+            ColumnarSchema::new(vec![field("v", ty)]).unwrap_err(),
+            Error::Schema {
+                column: Some(0),
+                reason,
+            }
+        );
+    }
+}
+
+#[test]
+fn fixed_size_adversarial_inputs_are_rejected() {
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field("", vector_of(ColumnarType::F32, 2))]).unwrap(),
+        2,
+        vec![ColumnData::FixedSizeListF32Bits {
+            size: 2,
+            validity: ValidityBitmap::new_all_valid(2).unwrap(),
+            values: vec![1.0f32.to_bits(); 4],
+        }],
+    )
+    .unwrap();
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    // Header (20) | type id (2) | size (4) | item type id (2) | enc (2).
+    let mut zero = bytes.clone();
+    zero[22..26].copy_from_slice(&0u32.to_le_bytes());
+    assert_eq!(
+        decode_mathldbt_v1(&zero).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 22,
+            reason: "invalid fixed size",
+        }
+    );
+
+    let mut item = bytes.clone();
+    item[26] = 4;
+    assert_eq!(
+        decode_mathldbt_v1(&item).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 26,
+            reason: "fixed-size list items must be F32 or F64",
+        }
+    );
+
+    let mut delta = bytes.clone();
+    delta[28] = 3;
+    assert_eq!(
+        decode_mathldbt_v1(&delta).unwrap_err(),
+        Error::UnknownEncoding {
+            column: 0,
+            offset: 28,
+            encoding_id: 3,
+        }
+    );
+
+    // A larger size leaves the payload short by a whole row's worth of items.
+    let mut wider = bytes.clone();
+    wider[22] = 3;
+    assert!(matches!(
+        decode_mathldbt_v1(&wider).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            reason: "values length mismatch",
+            ..
+        }
+    ));
+}
@@ -0,0 +1,229 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1EncodeWorkspace, decode_mathldbt_v1, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+use crate::schema::{ColumnarSchema, ColumnarType};
+use crate::tests::common::{assert_decodes_all_paths, encode_both, encodings, field};
+
+/// Bounded, unordered trade sizes (`I16`), ids near a large base (`I32`) and signed
+/// quantities (`I64`) whose null rows still hold a value.
+fn trades_batch(rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![
+        field("size", ColumnarType::I16),
+        field("venue_id", ColumnarType::I32),
+        field("qty", ColumnarType::I64),
+    ])
+    .unwrap();
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    for i in (5..rows).step_by(9) {
+        validity.set(i, false).unwrap();
+    }
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::FixedI16 {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows).map(|i| ((i * 7919) % 1000) as i16).collect(),
+            },
+            ColumnData::FixedI32 {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows)
+                    .map(|i| 2_000_000_000 + ((i * 31) % 17) as i32)
+                    .collect(),
+            },
+            ColumnData::FixedI64 {
+                validity: validity.clone(),
+                values: (0..rows)
+                    .map(|i| match i % 9 {
+                        5 => 7,
+                        _ => ((i * 13) % 50) as i64 - 25,
+                    })
+                    .collect(),
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn single_i64(values: Vec<i64>) -> ColumnarBatch {
+    let rows = values.len();
+    ColumnarBatch::new(
+        ColumnarSchema::new(vec![field("v", ColumnarType::I64)]).unwrap(),
+        rows,
+        vec![ColumnData::FixedI64 {
+            validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+            values,
+        }],
+    )
+    .unwrap()
+}
+
+fn for_workspace() -> MathldbtV1EncodeWorkspace {
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_for_bit_packed(true);
+    ws
+}
+
+#[test]
+fn for_bit_packed_round_trips_through_all_paths() {
+    let mut ws = for_workspace();
+    for rows in [0, 1, 127, 128, 129, 300] {
+        let batch = trades_batch(rows);
+        let bytes = encode_both(&batch, &mut ws);
+        // A single row is smaller plain.
+        let expected = if rows > 1 { 6 } else { 0 };
+        assert!(
+            encodings(&bytes).iter().all(|&e| e == expected),
+            "rows={rows}"
+        );
+
+        assert_decodes_all_paths(&bytes, &batch);
+    }
+
+    // 130 ids: block count | minimum, width 5 and 128 x 5 bits for the first block, then
+    // the last two ids (4 and 7 above the base) in 2 x 2 bits.
+    let bytes = encode_both(&trades_batch(130), &mut ws);
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    let ids = &bytes[info.columns[1].payload1.clone()];
+    assert_eq!(ids.len(), 4 + (4 + 1 + 80) + (4 + 1 + 1));
+    assert_eq!(ids[..4], 2u32.to_le_bytes());
+    assert_eq!(ids[4..8], 2_000_000_000i32.to_le_bytes());
+    assert_eq!(ids[8], 5);
+
+    // Full-range values are not smaller packed, so they stay plain.
+    let wide = single_i64(
+        (0..64)
+            .map(|i| {
+                if i % 2 == 0 {
+                    i64::MIN + i
+                } else {
+                    i64::MAX - i
+                }
+            })
+            .collect(),
+    );
+    let bytes = encode_both(&wide, &mut ws);
+    assert_eq!(encodings(&bytes), [0]);
+    assert_eq!(decode_mathldbt_v1(&bytes).unwrap(), wide);
+
+    // With PgBeFixed also enabled, they get that rather than PlainLe.
+    let mut pg_be = for_workspace();
+    pg_be.set_enable_pg_be_fixed(true);
+    let bytes = encode_both(&wide, &mut pg_be);
+    assert_eq!(encodings(&bytes), [1]);
+    assert_decodes_all_paths(&bytes, &wide);
+}
+
+#[test]
+fn i64_columns_keep_the_smaller_of_delta_and_for() {
+    let mut ws = for_workspace();
+    ws.set_enable_delta_varint_i64(true);
+    let encoding_of = |batch: &ColumnarBatch, ws: &mut MathldbtV1EncodeWorkspace| {
+        let bytes = encode_both(batch, ws);
+        assert_eq!(&decode_mathldbt_v1(&bytes).unwrap(), batch);
+        encodings(&bytes)[0]
+    };
+
+    // Steady increments need one varint byte each, but span too many bits per block.
+    let ticks = single_i64((0..200).map(|i| 1_700_000_000_000 + i * 50).collect());
+    assert_eq!(encoding_of(&ticks, &mut ws), 3);
+    // Bounded, unordered values pack into 4 bits; their deltas still take a byte.
+    let sizes = single_i64((0..200).map(|i| (i * 7) % 16).collect());
+    assert_eq!(encoding_of(&sizes, &mut ws), 6);
+
+    // With a null, the deltas skip the null slot (which decodes as zero).
+    let mut nullable = ticks.clone();
+    let ColumnData::FixedI64 { validity, values } = &mut nullable.columns[0] else {
+        unreachable!()
+    };
+    validity.set(3, false).unwrap();
+    values[3] = 0;
+    assert_eq!(encoding_of(&nullable, &mut ws), 7);
+
+    let mut delta_only = MathldbtV1EncodeWorkspace::default();
+    delta_only.set_enable_delta_varint_i64(true);
+    assert_eq!(encoding_of(&sizes, &mut delta_only), 3);
+}
+
+#[test]
+fn for_bit_packed_adversarial_inputs_are_rejected() {
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field("", ColumnarType::I16)]).unwrap(),
+        15,
+        vec![ColumnData::FixedI16 {
+            validity: ValidityBitmap::new_all_valid(15).unwrap(),
+            values: (0..15).map(|i| 100 + i % 5).collect(),
+        }],
+    )
+    .unwrap();
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut bytes, &mut for_workspace()).unwrap();
+
+    // Header (20) | type id (2) | enc (2) | flags (2) | name_len (2) | validity (4 + 2) |
+    // payload_1 length (4) at 34, then block count (4) at 38, minimum (2) at 42, width at 44
+    // and 15 x 3 bits at 45..51, the last three bits padding.
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(info.columns[0].payload1, 38..51);
+    assert_eq!(bytes[44], 3);
+    let malformed = |offset, reason| Error::Malformed {
+        column: Some(0),
+        offset,
+        reason,
+    };
+
+    let mut blocks = bytes.clone();
+    blocks[38] = 2;
+    assert_eq!(
+        decode_mathldbt_v1(&blocks).unwrap_err(),
+        malformed(38, "FOR block count mismatch")
+    );
+
+    let mut width = bytes.clone();
+    width[44] = 17;
+    assert_eq!(
+        decode_mathldbt_v1(&width).unwrap_err(),
+        malformed(44, "invalid FOR bit width")
+    );
+
+    // A narrower width leaves unread bytes; a wider one runs past the payload.
+    let mut narrower = bytes.clone();
+    narrower[44] = 2;
+    assert_eq!(
+        decode_mathldbt_v1(&narrower).unwrap_err(),
+        malformed(49, "trailing bytes in FOR payload")
+    );
+    let mut wider = bytes.clone();
+    wider[44] = 4;
+    assert_eq!(
+        decode_mathldbt_v1(&wider).unwrap_err(),
+        malformed(45, "FOR payload truncated")
+    );
+
+    let mut padded = bytes.clone();
+    padded[50] |= 0x80;
+    assert_eq!(
+        decode_mathldbt_v1(&padded).unwrap_err(),
+        malformed(50, "FOR padding must be zero")
+    );
+
+    let mut retyped = Vec::new();
+    let mut plain = batch.clone();
+    plain.schema = ColumnarSchema::new(vec![field("", ColumnarType::U16)]).unwrap();
+    plain.columns[0] = ColumnData::FixedU16 {
+        validity: ValidityBitmap::new_all_valid(15).unwrap(),
+        values: vec![0; 15],
+    };
+    encode_mathldbt_v1_into(&plain, &mut retyped).unwrap();
+    retyped[22] = 6;
+    assert_eq!(
+        decode_mathldbt_v1(&retyped).unwrap_err(),
+        Error::UnknownEncoding {
+            column: 0,
+            offset: 22,
+            encoding_id: 6,
+        }
+    );
+}
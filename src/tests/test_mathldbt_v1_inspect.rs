@@ -0,0 +1,145 @@
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1EncodeWorkspace, decode_mathldbt_v1, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+
+fn sample_batch(rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![
+        ColumnarField {
+            name: Some("pair".to_string()),
+            ty: ColumnarType::Utf8,
+        },
+        ColumnarField {
+            name: Some("e_ms".to_string()),
+            ty: ColumnarType::I64,
+        },
+        ColumnarField {
+            name: Some("close".to_string()),
+            ty: ColumnarType::F64,
+        },
+    ])
+    .unwrap();
+
+    let mut pair_validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    if rows > 0 {
+        pair_validity.set(0, false).unwrap();
+    }
+    let mut offsets = vec![0u32; rows + 1];
+    let mut data = Vec::new();
+    for i in 0..rows {
+        if pair_validity.is_valid(i).unwrap() {
+            let v = if i % 2 == 0 { b"BTCUSDT" } else { b"ETHUSDT" };
+            data.extend_from_slice(v);
+            offsets[i + 1] = data.len() as u32;
+        } else {
+            offsets[i + 1] = offsets[i];
+        }
+    }
+
+    let e_validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    let mut e_vals = Vec::with_capacity(rows);
+    for i in 0..rows {
+        e_vals.push(1_700_000_000_000i64 + i as i64 * 60_000);
+    }
+
+    let f_validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    let mut f_bits = Vec::with_capacity(rows);
+    for i in 0..rows {
+        f_bits.push((10_000.0 + i as f64 * 0.25).to_bits());
+    }
+
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::Var {
+                ty: ColumnarType::Utf8,
+                validity: pair_validity,
+                offsets,
+                data,
+            },
+            ColumnData::FixedI64 {
+                validity: e_validity,
+                values: e_vals,
+            },
+            ColumnData::FixedF64Bits {
+                validity: f_validity,
+                values: f_bits,
+            },
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn inspect_reports_header_and_descriptor_ranges() {
+    let batch = sample_batch(10);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(info.version, 1);
+    assert_eq!(info.flags, 0);
+    assert_eq!(info.row_count, 10);
+    assert_eq!(info.schema, batch.schema);
+    assert_eq!(info.encoded_len, bytes.len());
+    assert_eq!(info.columns.len(), 3);
+
+    let (pair, e_ms) = (&info.columns[0], &info.columns[1]);
+    assert_eq!(pair.encoding_id, 0);
+    assert_eq!(pair.validity.len(), 2);
+    assert_eq!(pair.payload1.len(), 11 * 4);
+    assert_eq!(
+        &bytes[pair.payload2.clone()],
+        b"ETHUSDTBTCUSDTETHUSDTBTCUSDTETHUSDTBTCUSDTETHUSDTBTCUSDTETHUSDT"
+    );
+
+    let ColumnData::FixedI64 { validity, values } = &batch.columns[1] else {
+        unreachable!()
+    };
+    assert_eq!(&bytes[e_ms.validity.clone()], validity.as_bytes());
+    let le: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+    assert_eq!(&bytes[e_ms.payload1.clone()], le.as_slice());
+    assert!(e_ms.payload2.is_empty());
+}
+
+#[test]
+fn inspect_reports_opt_encodings() {
+    let batch = sample_batch(64);
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_dict_utf8(true)
+        .set_enable_delta_varint_i64(true);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut bytes, &mut ws).unwrap();
+
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    let ids: Vec<u16> = info.columns.iter().map(|c| c.encoding_id).collect();
+    assert_eq!(ids, vec![2, 3, 0]);
+}
+
+#[test]
+fn inspect_does_not_validate_payloads() {
+    let batch = sample_batch(4);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    // Structural errors are the same as decode.
+    let cut = &bytes[..bytes.len() - 1];
+    assert_eq!(
+        inspect_mathldbt_v1(cut).unwrap_err(),
+        decode_mathldbt_v1(cut).unwrap_err()
+    );
+
+    bytes[10] = 1; // header flags
+
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(info.flags, 1);
+
+    // Corrupt the first varlen offset: decode rejects it, inspect still walks the envelope.
+    let at = info.columns[0].payload1.start;
+    bytes[at] = 1;
+    assert!(decode_mathldbt_v1(&bytes).is_err());
+    assert_eq!(inspect_mathldbt_v1(&bytes).unwrap().columns, info.columns);
+}
@@ -0,0 +1,171 @@
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_into, decode_mathldbt_v1_view_with_workspace,
+    encode_mathldbt_v1_fast_path_into_with_workspace, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+use crate::codec::{StreamReader, StreamWriter};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+
+fn integer_batch(rows: usize) -> ColumnarBatch {
+    let f = |name: &str, ty| ColumnarField {
+        name: Some(name.to_string()),
+        ty,
+    };
+    let schema = ColumnarSchema::new(vec![
+        f("side", ColumnarType::I8),
+        f("flags", ColumnarType::U8),
+        f("venue", ColumnarType::U16),
+        f("seq", ColumnarType::U32),
+        f("order_id", ColumnarType::U64),
+    ])
+    .unwrap();
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    if rows > 1 {
+        validity.set(1, false).unwrap();
+    }
+    // Row 0 holds each type's maximum and row 2 its minimum.
+    let pick = |i: usize, max: u64, min: u64| match i {
+        0 => max,
+        2 => min,
+        _ => i as u64,
+    };
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::FixedI8 {
+                validity: validity.clone(),
+                values: (0..rows)
+                    .map(|i| match i {
+                        0 => i8::MAX,
+                        2 => i8::MIN,
+                        _ => i as i8,
+                    })
+                    .collect(),
+            },
+            ColumnData::FixedU8 {
+                validity: validity.clone(),
+                values: (0..rows)
+                    .map(|i| pick(i, u8::MAX.into(), 0) as u8)
+                    .collect(),
+            },
+            ColumnData::FixedU16 {
+                validity: validity.clone(),
+                values: (0..rows)
+                    .map(|i| pick(i, u16::MAX.into(), 0) as u16)
+                    .collect(),
+            },
+            ColumnData::FixedU32 {
+                validity,
+                values: (0..rows)
+                    .map(|i| pick(i, u32::MAX.into(), 0) as u32)
+                    .collect(),
+            },
+            ColumnData::FixedU64 {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows).map(|i| pick(i, u64::MAX, 0)).collect(),
+            },
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn integer_columns_round_trip_through_all_paths() {
+    for rows in [0, 1, 3, 100] {
+        let batch = integer_batch(rows);
+        let mut owned = Vec::new();
+        encode_mathldbt_v1_into(&batch, &mut owned).unwrap();
+        assert_eq!(decode_mathldbt_v1(&owned).unwrap(), batch);
+        assert_eq!(inspect_mathldbt_v1(&owned).unwrap().schema, batch.schema);
+
+        let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+        let view = ColumnarBatchView {
+            schema: &batch.schema,
+            row_count: batch.row_count,
+            columns: &cols,
+        };
+        let mut fast = Vec::new();
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut ws).unwrap();
+        assert_eq!(fast, owned);
+
+        let mut dws = MathldbtV1DecodeWorkspace::default();
+        let decoded = decode_mathldbt_v1_view_with_workspace(&owned, &mut dws).unwrap();
+        assert_eq!(decoded.to_batch().unwrap(), batch);
+
+        let mut out = integer_batch(7);
+        decode_mathldbt_v1_into(&owned, &mut out).unwrap();
+        assert_eq!(out, batch);
+
+        let mut be = Vec::new();
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        ws.set_enable_pg_be_fixed(true);
+        encode_mathldbt_v1_into_with_workspace(&batch, &mut be, &mut ws).unwrap();
+        assert_eq!(decode_mathldbt_v1(&be).unwrap(), batch);
+        let mut fast_be = Vec::new();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast_be, &mut ws).unwrap();
+        assert_eq!(fast_be, be);
+    }
+
+    let batch = integer_batch(5);
+    let mut w = StreamWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    let stream = w.finish().unwrap();
+    let mut r = StreamReader::new(stream.as_slice(), 1 << 20).unwrap();
+    assert_eq!(r.schema(), &batch.schema);
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out, batch);
+}
+
+#[test]
+fn u64_delta_varint_wraps_across_the_full_range() {
+    let schema = ColumnarSchema::new(vec![ColumnarField {
+        name: None,
+        ty: ColumnarType::U64,
+    }])
+    .unwrap();
+    let values: Vec<u64> = (0..100u64)
+        .map(|i| (u64::MAX - 50).wrapping_add(i))
+        .collect();
+    let batch = ColumnarBatch::new(
+        schema,
+        values.len(),
+        vec![ColumnData::FixedU64 {
+            validity: ValidityBitmap::new_all_valid(values.len()).unwrap(),
+            values,
+        }],
+    )
+    .unwrap();
+
+    let mut plain = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut plain).unwrap();
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_delta_varint_i64(true);
+    ws.set_enable_pg_be_fixed(true);
+    let mut delta = Vec::new();
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut delta, &mut ws).unwrap();
+    assert!(delta.len() < plain.len() / 2);
+    // Header (20) | type id (2), then the encoding id: delta wins over PgBe.
+    assert_eq!(&delta[20..22], &21u16.to_le_bytes());
+    assert_eq!(&delta[22..24], &3u16.to_le_bytes());
+    assert_eq!(decode_mathldbt_v1(&delta).unwrap(), batch);
+
+    let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+    let view = ColumnarBatchView {
+        schema: &batch.schema,
+        row_count: batch.row_count,
+        columns: &cols,
+    };
+    let mut fast = Vec::new();
+    encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut ws).unwrap();
+    assert_eq!(fast, delta);
+
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    let decoded = decode_mathldbt_v1_view_with_workspace(&delta, &mut dws).unwrap();
+    assert_eq!(decoded.to_batch().unwrap(), batch);
+}
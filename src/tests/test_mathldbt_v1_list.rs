@@ -0,0 +1,290 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_into, decode_mathldbt_v1_view_with_workspace,
+    encode_mathldbt_v1_fast_path_into_with_workspace, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+use crate::codec::{StreamReader, StreamWriter};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType, MAX_NESTING_DEPTH};
+
+fn list_of(item: ColumnarType) -> ColumnarType {
+    ColumnarType::List(Box::new(item))
+}
+
+/// Row `i` holds `i % 4` items; every fifth row is null and empty.
+fn list_offsets(rows: usize) -> (ValidityBitmap, Vec<u32>) {
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    let mut offsets = vec![0u32];
+    for i in 0..rows {
+        let len = if i % 5 == 1 {
+            validity.set(i, false).unwrap();
+            0
+        } else {
+            i % 4
+        };
+        offsets.push(offsets[i] + len as u32);
+    }
+    (validity, offsets)
+}
+
+fn list_batch(rows: usize) -> ColumnarBatch {
+    let f = |name: &str, ty| ColumnarField {
+        name: Some(name.to_string()),
+        ty,
+    };
+    let schema = ColumnarSchema::new(vec![
+        f("fills", list_of(ColumnarType::I64)),
+        f("tags", list_of(ColumnarType::Utf8)),
+        f("ladders", list_of(list_of(ColumnarType::F64))),
+    ])
+    .unwrap();
+
+    let (validity, offsets) = list_offsets(rows);
+    let items = offsets[rows] as usize;
+    let fills = ColumnData::List {
+        validity: validity.clone(),
+        offsets: offsets.clone(),
+        child: Box::new(ColumnData::FixedI64 {
+            validity: ValidityBitmap::new_all_valid(items).unwrap(),
+            values: (0..items as i64).map(|i| 1_000 + i * 7).collect(),
+        }),
+    };
+
+    let mut tag_validity = ValidityBitmap::new_all_valid(items).unwrap();
+    let mut tag_offsets = vec![0u32];
+    let mut tag_data = Vec::new();
+    for i in 0..items {
+        if i % 3 == 2 {
+            tag_validity.set(i, false).unwrap();
+        } else {
+            tag_data.extend_from_slice(format!("tag-{i}").as_bytes());
+        }
+        tag_offsets.push(tag_data.len() as u32);
+    }
+    let tags = ColumnData::List {
+        validity: validity.clone(),
+        offsets: offsets.clone(),
+        child: Box::new(ColumnData::Var {
+            ty: ColumnarType::Utf8,
+            validity: tag_validity,
+            offsets: tag_offsets,
+            data: tag_data,
+        }),
+    };
+
+    let (inner_validity, inner_offsets) = list_offsets(items);
+    let prices = inner_offsets[items] as usize;
+    let ladders = ColumnData::List {
+        validity,
+        offsets,
+        child: Box::new(ColumnData::List {
+            validity: inner_validity,
+            offsets: inner_offsets,
+            child: Box::new(ColumnData::FixedF64Bits {
+                validity: ValidityBitmap::new_all_valid(prices).unwrap(),
+                values: (0..prices)
+                    .map(|i| (100.0 + i as f64 / 8.0).to_bits())
+                    .collect(),
+            }),
+        }),
+    };
+
+    ColumnarBatch::new(schema, rows, vec![fills, tags, ladders]).unwrap()
+}
+
+#[test]
+fn list_columns_round_trip_through_all_paths() {
+    for rows in [0, 1, 3, 100] {
+        let batch = list_batch(rows);
+        let mut owned = Vec::new();
+        encode_mathldbt_v1_into(&batch, &mut owned).unwrap();
+        assert_eq!(decode_mathldbt_v1(&owned).unwrap(), batch);
+        assert_eq!(inspect_mathldbt_v1(&owned).unwrap().schema, batch.schema);
+
+        let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+        let view = ColumnarBatchView {
+            schema: &batch.schema,
+            row_count: batch.row_count,
+            columns: &cols,
+        };
+        let mut fast = Vec::new();
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut ws).unwrap();
+        assert_eq!(fast, owned);
+
+        let mut dws = MathldbtV1DecodeWorkspace::default();
+        let decoded = decode_mathldbt_v1_view_with_workspace(&owned, &mut dws).unwrap();
+        assert_eq!(decoded.to_batch().unwrap(), batch);
+
+        let mut out = list_batch(7);
+        decode_mathldbt_v1_into(&owned, &mut out).unwrap();
+        assert_eq!(out, batch);
+
+        // Encoding options apply to the item columns as well.
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        ws.set_enable_pg_be_fixed(true);
+        ws.set_enable_delta_varint_i64(true);
+        let mut tuned = Vec::new();
+        encode_mathldbt_v1_into_with_workspace(&batch, &mut tuned, &mut ws).unwrap();
+        assert_eq!(decode_mathldbt_v1(&tuned).unwrap(), batch);
+        let mut fast_tuned = Vec::new();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast_tuned, &mut ws).unwrap();
+        assert_eq!(fast_tuned, tuned);
+    }
+
+    let batch = list_batch(5);
+    let mut w = StreamWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    let stream = w.finish().unwrap();
+    let mut r = StreamReader::new(stream.as_slice(), 1 << 20).unwrap();
+    assert_eq!(r.schema(), &batch.schema);
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out, batch);
+}
+
+#[test]
+fn list_inspect_reports_nested_descriptors() {
+    let batch = list_batch(10);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    let ladders = &info.columns[2];
+    assert_eq!(ladders.payload1.len(), 11 * 4);
+    assert!(ladders.payload2.is_empty());
+    assert_eq!(ladders.children.len(), 1);
+    assert_eq!(ladders.children[0].children.len(), 1);
+    assert!(ladders.children[0].children[0].children.is_empty());
+}
+
+#[test]
+fn list_validate_checks_offsets_and_items() {
+    let batch = list_batch(8);
+    let rebuild = |col: ColumnData| {
+        let mut columns = batch.columns.clone();
+        columns[0] = col;
+        ColumnarBatch::new(batch.schema.clone(), batch.row_count, columns).unwrap_err()
+    };
+    let ColumnData::List {
+        validity,
+        offsets,
+        child,
+    } = batch.columns[0].clone()
+    else {
+        unreachable!()
+    };
+
+    let mut decreasing = offsets.clone();
+    decreasing[4] = 1;
+    assert_eq!(
+        rebuild(ColumnData::List {
+            validity: validity.clone(),
+            offsets: decreasing,
+            child: child.clone(),
+        }),
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "offsets must be non-decreasing",
+        }
+    );
+
+    // The item column must hold exactly the final offset's worth of rows.
+    let mut short = offsets.clone();
+    *short.last_mut().unwrap() -= 1;
+    assert_eq!(
+        rebuild(ColumnData::List {
+            validity: validity.clone(),
+            offsets: short,
+            child: child.clone(),
+        }),
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "validity length mismatch",
+        }
+    );
+
+    assert_eq!(
+        rebuild(ColumnData::List {
+            validity,
+            offsets,
+            child: Box::new(ColumnData::FixedI32 {
+                validity: ValidityBitmap::new_all_valid(3).unwrap(),
+                values: vec![0; 3],
+            }),
+        }),
+        Error::Schema {
+            column: Some(0),
+            reason: "column type mismatch",
+        }
+    );
+}
+
+#[test]
+fn list_adversarial_inputs_are_rejected() {
+    let mut ty = ColumnarType::I64;
+    for _ in 0..=MAX_NESTING_DEPTH {
+        ty = list_of(ty);
+    }
+    assert_eq!(
+        ColumnarSchema::new(vec![ColumnarField { name: None, ty }]).unwrap_err(),
+        Error::Schema {
+            column: Some(0),
+            reason: "type nesting too deep",
+        }
+    );
+
+    let schema = ColumnarSchema::new(vec![ColumnarField {
+        name: None,
+        ty: list_of(ColumnarType::I64),
+    }])
+    .unwrap();
+    let batch = ColumnarBatch::new(
+        schema,
+        2,
+        vec![ColumnData::List {
+            validity: ValidityBitmap::new_all_valid(2).unwrap(),
+            offsets: vec![0, 1, 3],
+            child: Box::new(ColumnData::FixedI64 {
+                validity: ValidityBitmap::new_all_valid(3).unwrap(),
+                values: vec![1, 2, 3],
+            }),
+        }],
+    )
+    .unwrap();
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    // Header (20) | type ids (4) | enc, flags, name_len (6) | validity (4 + 1) | offsets
+    // (4 + 12) | payload_2 length (4), then the item descriptor at 55.
+    let mut retagged = bytes.clone();
+    retagged[55] = 3;
+    assert_eq!(
+        decode_mathldbt_v1(&retagged).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 55,
+            reason: "list item type mismatch",
+        }
+    );
+
+    let mut decreasing = bytes.clone();
+    decreasing[43] = 4;
+    assert!(matches!(
+        decode_mathldbt_v1(&decreasing).unwrap_err(),
+        Error::InvalidOffsets { column: 0, .. }
+    ));
+
+    let mut deep = bytes.clone();
+    deep.splice(20..20, [22, 0].repeat(MAX_NESTING_DEPTH));
+    assert_eq!(
+        decode_mathldbt_v1(&deep).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 20 + 2 * MAX_NESTING_DEPTH,
+            reason: "type nesting too deep",
+        }
+    );
+}
@@ -0,0 +1,181 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1EncodeWorkspace, decode_mathldbt_v1, inspect_mathldbt_v1,
+};
+use crate::schema::{ColumnarSchema, ColumnarType, TimeUnit};
+use crate::tests::common::{assert_decodes_all_paths, encode_both, encodings, field};
+
+/// Every tenth row is null (and zero) in `e_ms`, `t` and `seq`; `id` has no nulls and
+/// `gap` has no valid rows.
+fn sparse_batch(rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![
+        field(
+            "e_ms",
+            ColumnarType::Timestamp {
+                unit: TimeUnit::Millisecond,
+                tz: None,
+            },
+        ),
+        field("t", ColumnarType::TimestampTzMicros),
+        field("seq", ColumnarType::U64),
+        field("id", ColumnarType::I64),
+        field("gap", ColumnarType::I64),
+    ])
+    .unwrap();
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    for i in (3..rows).step_by(10) {
+        validity.set(i, false).unwrap();
+    }
+    let series = |base: i64, step: i64| -> Vec<i64> {
+        (0..rows as i64)
+            .map(|i| if i % 10 == 3 { 0 } else { base + i * step })
+            .collect()
+    };
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::FixedTimestamp {
+                unit: TimeUnit::Millisecond,
+                tz: None,
+                validity: validity.clone(),
+                values: series(1_700_000_000_000, 10),
+            },
+            ColumnData::FixedTimestampMicros {
+                validity: validity.clone(),
+                values: series(1_700_000_000_000_000, -7),
+            },
+            ColumnData::FixedU64 {
+                validity: validity.clone(),
+                values: series(-5, 1).into_iter().map(|v| v as u64).collect(),
+            },
+            ColumnData::FixedI64 {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows as i64).collect(),
+            },
+            ColumnData::FixedI64 {
+                validity: ValidityBitmap::new_all_invalid(rows).unwrap(),
+                values: vec![0; rows],
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn delta_workspace() -> MathldbtV1EncodeWorkspace {
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_delta_varint_i64(true);
+    ws
+}
+
+#[test]
+fn nullable_delta_round_trips_through_all_paths() {
+    let mut ws = delta_workspace();
+    for rows in [4, 10, 200] {
+        let batch = sparse_batch(rows);
+        let bytes = encode_both(&batch, &mut ws);
+        assert_eq!(encodings(&bytes), [7, 7, 7, 3, 7], "rows={rows}");
+
+        // The base, then one varint byte per following valid row; nothing for `gap`.
+        let valid = rows - (rows + 6) / 10;
+        let info = inspect_mathldbt_v1(&bytes).unwrap();
+        assert_eq!(info.columns[0].payload1.len(), 8 + valid - 1);
+        assert!(info.columns[4].payload1.is_empty());
+
+        assert_decodes_all_paths(&bytes, &batch);
+    }
+
+    // Null slots are not encoded, so whatever they held decodes as zero.
+    let mut batch = sparse_batch(10);
+    let ColumnData::FixedTimestampMicros { values, .. } = &mut batch.columns[1] else {
+        unreachable!()
+    };
+    values[3] = 42;
+    let bytes = encode_both(&batch, &mut ws);
+    assert_eq!(decode_mathldbt_v1(&bytes).unwrap(), sparse_batch(10));
+}
+
+#[test]
+fn nullable_delta_is_opt_in_and_size_checked() {
+    let batch = sparse_batch(50);
+    let bytes = encode_both(&batch, &mut MathldbtV1EncodeWorkspace::default());
+    assert_eq!(encodings(&bytes), [0; 5]);
+
+    // Deltas that need nine or ten varint bytes stay plain.
+    let rows = 8;
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    validity.set(1, false).unwrap();
+    let wide = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field("v", ColumnarType::I64)]).unwrap(),
+        rows,
+        vec![ColumnData::FixedI64 {
+            validity,
+            values: vec![0, 0, 1 << 62, 0, 1 << 62, 0, 1 << 62, 0],
+        }],
+    )
+    .unwrap();
+    let bytes = encode_both(&wide, &mut delta_workspace());
+    assert_eq!(encodings(&bytes), [0]);
+    assert_eq!(decode_mathldbt_v1(&bytes).unwrap(), wide);
+}
+
+#[test]
+fn nullable_delta_adversarial_inputs_are_rejected() {
+    let mut validity = ValidityBitmap::new_all_valid(4).unwrap();
+    validity.set(1, false).unwrap();
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field("", ColumnarType::I64)]).unwrap(),
+        4,
+        vec![ColumnData::FixedI64 {
+            validity,
+            values: vec![10, 0, 12, 9],
+        }],
+    )
+    .unwrap();
+    let bytes = encode_both(&batch, &mut delta_workspace());
+
+    // Header (20) | type id (2) | enc (2) | flags (2) | name_len (2) | validity (4 + 1) |
+    // payload_1 length (4) at 33, then the base at 37 and the deltas +2 and -3 at 45..47.
+    assert_eq!(encodings(&bytes), [7]);
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(info.columns[0].payload1, 37..47);
+    assert_eq!(bytes[32], 0b1101);
+    assert_eq!(bytes[45..47], [4, 5]);
+    let malformed = |offset, reason| Error::Malformed {
+        column: Some(0),
+        offset,
+        reason,
+    };
+
+    // The validity bitmap decides how many values the payload must hold.
+    let mut more_valid = bytes.clone();
+    more_valid[32] = 0b1111;
+    assert_eq!(
+        decode_mathldbt_v1(&more_valid).unwrap_err(),
+        malformed(47, "truncated varint")
+    );
+    let mut fewer_valid = bytes.clone();
+    fewer_valid[32] = 0b1001;
+    assert_eq!(
+        decode_mathldbt_v1(&fewer_valid).unwrap_err(),
+        malformed(46, "trailing bytes in delta payload")
+    );
+    let mut all_null = bytes.clone();
+    all_null[32] = 0;
+    assert_eq!(
+        decode_mathldbt_v1(&all_null).unwrap_err(),
+        malformed(37, "trailing bytes in delta payload")
+    );
+
+    let mut retyped = bytes.clone();
+    retyped[20] = 3;
+    assert_eq!(
+        decode_mathldbt_v1(&retyped).unwrap_err(),
+        Error::UnknownEncoding {
+            column: 0,
+            offset: 22,
+            encoding_id: 7,
+        }
+    );
+}
@@ -0,0 +1,289 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1EncodeWorkspace, decode_mathldbt_v1, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+use crate::schema::{ColumnarSchema, ColumnarType};
+use crate::tests::common::{assert_decodes_all_paths, encode_both, encodings, field};
+
+const PAIRS: [&str; 4] = ["BTC-USD", "ETH-USD", "SOL-USD", "XRP-USD"];
+
+fn utf8(values: impl IntoIterator<Item = &'static str>) -> ColumnData {
+    let mut offsets = vec![0u32];
+    let mut data = Vec::new();
+    for v in values {
+        data.extend_from_slice(v.as_bytes());
+        offsets.push(data.len() as u32);
+    }
+    ColumnData::Var {
+        ty: ColumnarType::Utf8,
+        validity: ValidityBitmap::new_all_valid(offsets.len() - 1).unwrap(),
+        offsets,
+        data,
+    }
+}
+
+/// A constant `tf` (`Utf8`) and `venue` (`I32`), `is_final` flags and `pair` names in runs of
+/// 25 and 40 rows, noisy `close` prices and a list of flags (one per row, in runs of 25).
+fn bars_batch(rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![
+        field("tf", ColumnarType::Utf8),
+        field("is_final", ColumnarType::Bool),
+        field("pair", ColumnarType::Utf8),
+        field("venue", ColumnarType::I32),
+        field("close", ColumnarType::F64),
+        field("flags", ColumnarType::List(Box::new(ColumnarType::Bool))),
+    ])
+    .unwrap();
+    let flags: Vec<u8> = (0..rows).map(|i| (i / 25 % 2) as u8).collect();
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            utf8((0..rows).map(|_| "1m")),
+            ColumnData::FixedBool {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: flags.clone(),
+            },
+            utf8((0..rows).map(|i| PAIRS[i / 40 % PAIRS.len()])),
+            ColumnData::FixedI32 {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: vec![7; rows],
+            },
+            ColumnData::FixedF64Bits {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows as u64)
+                    .map(|i| (42_000.0 + (i.wrapping_mul(0x9e37_79b9) % 977) as f64).to_bits())
+                    .collect(),
+            },
+            ColumnData::List {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                offsets: (0..=rows as u32).collect(),
+                child: Box::new(ColumnData::FixedBool {
+                    validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                    values: flags,
+                }),
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn rle_workspace() -> MathldbtV1EncodeWorkspace {
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_rle(true).set_enable_dict_utf8(true);
+    ws
+}
+
+#[test]
+fn rle_round_trips_through_all_paths() {
+    let mut ws = rle_workspace();
+    for rows in [0, 1, 7, 300] {
+        let batch = bars_batch(rows);
+        let bytes = encode_both(&batch, &mut ws);
+        if rows == 300 {
+            assert_eq!(encodings(&bytes), [11, 10, 10, 11, 0, 0]);
+            let info = inspect_mathldbt_v1(&bytes).unwrap();
+            assert_eq!(info.columns[5].children[0].encoding_id, 10);
+        }
+
+        assert_decodes_all_paths(&bytes, &batch);
+    }
+
+    // `Constant` holds the value once; `Rle` holds (run length, value) pairs, the dictionary
+    // index in place of the value for varlen columns.
+    let bytes = encode_both(&bars_batch(300), &mut ws);
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    let payload = |c: usize| &bytes[info.columns[c].payload1.clone()];
+    assert_eq!(payload(0), b"1m");
+    assert!(info.columns[0].payload2.is_empty());
+    assert_eq!(payload(1)[..4], [25, 0, 25, 1]);
+    assert_eq!(payload(1).len(), 24);
+    assert_eq!(payload(2)[..4], [40, 0, 40, 1]);
+    assert_eq!(payload(3), 7i32.to_le_bytes());
+
+    // Bit-packed flags give the same runs.
+    let mut packed = bars_batch(300);
+    let mut bits = vec![0u8; 300usize.div_ceil(8)];
+    for i in (0..300).filter(|i| i / 25 % 2 == 1) {
+        bits[i / 8] |= 1 << (i % 8);
+    }
+    packed.columns[1] = ColumnData::BitPackedBool {
+        validity: ValidityBitmap::new_all_valid(300).unwrap(),
+        values: bits,
+    };
+    assert_eq!(encode_both(&packed, &mut ws), bytes);
+}
+
+#[test]
+fn rle_is_opt_in_and_size_based() {
+    let batch = bars_batch(300);
+    let bytes = encode_both(&batch, &mut MathldbtV1EncodeWorkspace::default());
+    assert_eq!(encodings(&bytes), [0, 0, 0, 0, 0, 0]);
+
+    // Varlen columns need a dictionary; `DictDelta` columns keep their encoding.
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_rle(true);
+    assert_eq!(
+        encodings(&encode_both(&batch, &mut ws)),
+        [0, 10, 0, 11, 0, 0]
+    );
+    ws.set_enable_dict_utf8(true).set_enable_dict_delta(true);
+    let mut delta = Vec::new();
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut delta, &mut ws).unwrap();
+    assert_eq!(encodings(&delta), [5, 10, 5, 11, 0, 0]);
+
+    // Runs compete with the encoding otherwise chosen: the smaller payload wins.
+    let rows = 300;
+    let mixed = ColumnarBatch::new(
+        ColumnarSchema::new(vec![
+            field("is_final", ColumnarType::Bool),
+            field("alternating", ColumnarType::Bool),
+            field("ts", ColumnarType::I64),
+            field("bucket", ColumnarType::I64),
+        ])
+        .unwrap(),
+        rows,
+        vec![
+            ColumnData::FixedBool {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows).map(|i| (i / 25 % 2) as u8).collect(),
+            },
+            ColumnData::FixedBool {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows).map(|i| (i % 2) as u8).collect(),
+            },
+            ColumnData::FixedI64 {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: (0..rows as i64)
+                    .map(|i| 1_700_000_000_000 + i * 60_000)
+                    .collect(),
+            },
+            ColumnData::FixedI64 {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: vec![1_700_000_000_000; rows],
+            },
+        ],
+    )
+    .unwrap();
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_rle(true);
+    assert_eq!(encodings(&encode_both(&mixed, &mut ws)), [10, 0, 0, 11]);
+    ws.set_enable_bit_packed_bool(true)
+        .set_enable_delta_varint_i64(true)
+        .set_enable_pg_be_fixed(true);
+    let bytes = encode_both(&mixed, &mut ws);
+    assert_eq!(encodings(&bytes), [10, 4, 3, 11]);
+    assert_eq!(decode_mathldbt_v1(&bytes).unwrap(), mixed);
+}
+
+#[test]
+fn rle_adversarial_inputs_are_rejected() {
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field("", ColumnarType::I32)]).unwrap(),
+        6,
+        vec![ColumnData::FixedI32 {
+            validity: ValidityBitmap::new_all_valid(6).unwrap(),
+            values: vec![5, 5, 5, 9, 9, 9],
+        }],
+    )
+    .unwrap();
+    let bytes = encode_both(&batch, &mut rle_workspace());
+
+    // Header (20) | type id (2) | enc (2) | flags (2) | name_len (2) | validity (4 + 1) |
+    // payload_1 length (4) at 33, then the runs (3, 5) at 37 and (3, 9) at 42..47.
+    assert_eq!(encodings(&bytes), [10]);
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(info.columns[0].payload1, 37..47);
+    assert_eq!(bytes[37..43], [3, 5, 0, 0, 0, 3]);
+    let malformed = |offset, reason| Error::Malformed {
+        column: Some(0),
+        offset,
+        reason,
+    };
+    let with_payload = |payload: &[u8]| {
+        let mut b = bytes[..33].to_vec();
+        b.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        b.extend_from_slice(payload);
+        b.extend_from_slice(&0u32.to_le_bytes());
+        b
+    };
+    let payload = &bytes[37..47];
+
+    let mut empty_run = payload.to_vec();
+    empty_run[0] = 0;
+    assert_eq!(
+        decode_mathldbt_v1(&with_payload(&empty_run)).unwrap_err(),
+        malformed(37, "invalid RLE run length")
+    );
+    let mut long_run = payload.to_vec();
+    long_run[0] = 4;
+    assert_eq!(
+        decode_mathldbt_v1(&with_payload(&long_run)).unwrap_err(),
+        malformed(42, "invalid RLE run length")
+    );
+    assert_eq!(
+        decode_mathldbt_v1(&with_payload(&payload[..5])).unwrap_err(),
+        malformed(42, "RLE payload truncated")
+    );
+    assert_eq!(
+        decode_mathldbt_v1(&with_payload(&payload[..9])).unwrap_err(),
+        malformed(43, "RLE payload truncated")
+    );
+    assert_eq!(
+        decode_mathldbt_v1(&with_payload(&[payload, &[0]].concat())).unwrap_err(),
+        malformed(47, "trailing bytes in RLE payload")
+    );
+    let mut constant = bytes.clone();
+    constant[22] = 11;
+    assert_eq!(
+        decode_mathldbt_v1(&constant).unwrap_err(),
+        malformed(37, "values length mismatch")
+    );
+
+    // A run of dictionary indices is checked against the dictionary.
+    let names = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field("", ColumnarType::Utf8)]).unwrap(),
+        6,
+        vec![utf8(["a", "a", "a", "b", "b", "b"])],
+    )
+    .unwrap();
+    let mut bytes = encode_both(&names, &mut rle_workspace());
+    assert_eq!(bytes[37..41], [3, 0, 3, 1]);
+    bytes[40] = 2;
+    assert_eq!(
+        decode_mathldbt_v1(&bytes).unwrap_err(),
+        malformed(40, "dict index out of bounds")
+    );
+
+    let list = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field(
+            "",
+            ColumnarType::List(Box::new(ColumnarType::I32)),
+        )])
+        .unwrap(),
+        1,
+        vec![ColumnData::List {
+            validity: ValidityBitmap::new_all_valid(1).unwrap(),
+            offsets: vec![0, 1],
+            child: Box::new(ColumnData::FixedI32 {
+                validity: ValidityBitmap::new_all_valid(1).unwrap(),
+                values: vec![5],
+            }),
+        }],
+    )
+    .unwrap();
+    // The item type id follows the list type id, so `enc` is at 24.
+    let mut retyped = Vec::new();
+    encode_mathldbt_v1_into(&list, &mut retyped).unwrap();
+    retyped[24] = 10;
+    assert_eq!(
+        decode_mathldbt_v1(&retyped).unwrap_err(),
+        Error::UnknownEncoding {
+            column: 0,
+            offset: 24,
+            encoding_id: 10,
+        }
+    );
+}
@@ -0,0 +1,260 @@
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::codec::{MathldbtV1EncodeWorkspace, StreamReader, StreamWriter};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+use crate::{Error, LimitKind};
+
+fn sample_schema() -> ColumnarSchema {
+    ColumnarSchema::new(vec![
+        ColumnarField {
+            name: Some("pair".to_string()),
+            ty: ColumnarType::Utf8,
+        },
+        ColumnarField {
+            name: Some("e_ms".to_string()),
+            ty: ColumnarType::I64,
+        },
+        ColumnarField {
+            name: Some("close".to_string()),
+            ty: ColumnarType::F64,
+        },
+    ])
+    .unwrap()
+}
+
+fn sample_batch(rows: usize, seed: i64) -> ColumnarBatch {
+    let validity = ValidityBitmap::new_all_valid(rows).unwrap();
+
+    let mut offsets = vec![0u32; rows + 1];
+    let mut data = Vec::new();
+    for i in 0..rows {
+        let v = if i % 2 == 0 { b"BTCUSDT" } else { b"ETHUSDT" };
+        data.extend_from_slice(v);
+        offsets[i + 1] = data.len() as u32;
+    }
+
+    let e_vals = (0..rows)
+        .map(|i| 1_700_000_000_000i64 + seed * 1_000_000 + i as i64 * 60_000)
+        .collect();
+    let f_bits = (0..rows)
+        .map(|i| (10_000.0 + seed as f64 + i as f64 * 0.25).to_bits())
+        .collect();
+
+    ColumnarBatch::new(
+        sample_schema(),
+        rows,
+        vec![
+            ColumnData::Var {
+                ty: ColumnarType::Utf8,
+                validity: validity.clone(),
+                offsets,
+                data,
+            },
+            ColumnData::FixedI64 {
+                validity: validity.clone(),
+                values: e_vals,
+            },
+            ColumnData::FixedF64Bits {
+                validity,
+                values: f_bits,
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn write_stream(batches: &[ColumnarBatch]) -> Vec<u8> {
+    let mut w = StreamWriter::new(Vec::new(), sample_schema()).unwrap();
+    for b in batches {
+        w.write_batch(b).unwrap();
+    }
+    assert_eq!(w.batch_count(), batches.len() as u64);
+    w.finish().unwrap()
+}
+
+fn read_all(bytes: &[u8]) -> Vec<ColumnarBatch> {
+    let mut r = StreamReader::new(bytes, 1 << 20).unwrap();
+    let mut out = r.new_batch().unwrap();
+    let mut got = Vec::new();
+    while r.read_batch_into(&mut out).unwrap() {
+        got.push(out.clone());
+    }
+    got
+}
+
+#[test]
+fn stream_round_trip_multiple_batches() {
+    let batches = vec![
+        sample_batch(16, 0),
+        sample_batch(0, 1),
+        sample_batch(300, 2),
+    ];
+    let bytes = write_stream(&batches);
+
+    let mut r = StreamReader::new(bytes.as_slice(), 1 << 20).unwrap();
+    assert_eq!(r.schema(), &sample_schema());
+    let mut out = r.new_batch().unwrap();
+    for expected in &batches {
+        assert!(r.read_batch_into(&mut out).unwrap());
+        assert_eq!(&out, expected);
+    }
+    assert!(!r.read_batch_into(&mut out).unwrap());
+    assert_eq!(r.batches_read(), 3);
+    // End of stream is sticky.
+    assert!(!r.read_batch_into(&mut out).unwrap());
+}
+
+#[test]
+fn stream_empty_is_valid() {
+    let bytes = write_stream(&[]);
+    assert!(read_all(&bytes).is_empty());
+}
+
+#[test]
+fn stream_is_deterministic_and_supports_opt_encodings() {
+    let batches = vec![sample_batch(64, 0), sample_batch(64, 1)];
+    assert_eq!(write_stream(&batches), write_stream(&batches));
+
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_dict_utf8(true)
+        .set_enable_delta_varint_i64(true);
+    let mut w = StreamWriter::with_workspace(Vec::new(), sample_schema(), ws).unwrap();
+    for b in &batches {
+        w.write_batch(b).unwrap();
+    }
+    let opt = w.finish().unwrap();
+    assert!(opt.len() < write_stream(&batches).len());
+    assert_eq!(read_all(&opt), batches);
+}
+
+#[test]
+fn stream_write_batch_view_matches_owned() {
+    let batches = vec![sample_batch(10, 0), sample_batch(7, 1)];
+    let owned = write_stream(&batches);
+
+    let views: Vec<_> = batches
+        .iter()
+        .map(|b| b.columns.iter().map(|c| c.as_view()).collect::<Vec<_>>())
+        .collect();
+    let mut w = StreamWriter::new(Vec::new(), sample_schema()).unwrap();
+    for (b, cols) in batches.iter().zip(&views) {
+        let view = crate::batch_view::ColumnarBatchView {
+            schema: &b.schema,
+            row_count: b.row_count,
+            columns: cols,
+        };
+        w.write_batch_view(&view).unwrap();
+    }
+    assert_eq!(w.finish().unwrap(), owned);
+}
+
+#[test]
+fn stream_writer_rejects_schema_mismatch() {
+    let other = ColumnarSchema::new(vec![ColumnarField {
+        name: Some("pair".to_string()),
+        ty: ColumnarType::Bytes,
+    }])
+    .unwrap();
+    let mut w = StreamWriter::new(Vec::new(), other).unwrap();
+    let err = w.write_batch(&sample_batch(1, 0)).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Schema {
+            column: None,
+            reason: "batch schema does not match stream schema",
+        }
+    );
+}
+
+#[test]
+fn stream_truncated_is_rejected() {
+    let bytes = write_stream(&[sample_batch(8, 0)]);
+    // Drop the end marker's batch count.
+    let cut = &bytes[..bytes.len() - 8];
+    let mut r = StreamReader::new(cut, 1 << 20).unwrap();
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    let err = r.read_batch_into(&mut out).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Truncated {
+            column: None,
+            offset: cut.len(),
+            needed: 8,
+        }
+    );
+}
+
+#[test]
+fn stream_batch_count_mismatch_is_rejected() {
+    let mut bytes = write_stream(&[sample_batch(8, 0)]);
+    let n = bytes.len();
+    bytes[n - 8..].copy_from_slice(&2u64.to_le_bytes());
+    let mut r = StreamReader::new(bytes.as_slice(), 1 << 20).unwrap();
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    let err = r.read_batch_into(&mut out).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Malformed {
+            column: None,
+            offset: n - 8,
+            reason: "stream batch count mismatch",
+        }
+    );
+}
+
+#[test]
+fn stream_frame_limit_is_enforced() {
+    let bytes = write_stream(&[sample_batch(64, 0)]);
+    let mut r = StreamReader::new(bytes.as_slice(), 64).unwrap();
+    let mut out = r.new_batch().unwrap();
+    let err = r.read_batch_into(&mut out).unwrap_err();
+    assert_eq!(
+        err,
+        Error::LimitExceeded {
+            kind: LimitKind::FrameLength,
+            column: None,
+            offset: Some(39),
+        }
+    );
+}
+
+#[test]
+fn stream_frame_errors_are_offset_into_the_stream() {
+    let bytes = write_stream(&[sample_batch(4, 0)]);
+    let header_len = 8 + 2 + 2 + 2 + (4 + 4) + (4 + 4) + (4 + 5);
+    let frame_start = header_len + 4;
+    assert_eq!(&bytes[frame_start..frame_start + 8], b"MATHLDBT");
+
+    let mut corrupt = bytes.clone();
+    corrupt[frame_start] = b'X';
+    let mut r = StreamReader::new(corrupt.as_slice(), 1 << 20).unwrap();
+    let mut out = r.new_batch().unwrap();
+    let err = r.read_batch_into(&mut out).unwrap_err();
+    assert_eq!(
+        err,
+        Error::BadMagic {
+            offset: frame_start,
+        }
+    );
+}
+
+#[test]
+fn stream_bad_header_is_rejected() {
+    let mut bytes = write_stream(&[]);
+    bytes[0] = b'X';
+    let err = StreamReader::new(bytes.as_slice(), 1 << 20).err().unwrap();
+    assert_eq!(err, Error::BadMagic { offset: 0 });
+
+    let err = StreamReader::new(&b"MATHLDBS\x01\x00"[..], 1 << 20)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        Error::Truncated {
+            column: None,
+            offset: 10,
+            needed: 2,
+        }
+    );
+}
@@ -0,0 +1,339 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_into, decode_mathldbt_v1_view_with_workspace,
+    encode_mathldbt_v1_fast_path_into_with_workspace, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+use crate::codec::{StreamReader, StreamWriter};
+use crate::schema::{ColumnarSchema, ColumnarType};
+use crate::tests::common::field;
+
+fn quote_type() -> ColumnarType {
+    ColumnarType::Struct(vec![
+        field("bid", ColumnarType::F64),
+        field("ask", ColumnarType::F64),
+        field("bid_sz", ColumnarType::I64),
+        field("ask_sz", ColumnarType::I64),
+    ])
+}
+
+fn quote_column(rows: usize) -> ColumnData {
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    for i in (1..rows).step_by(4) {
+        validity.set(i, false).unwrap();
+    }
+    let price = |base: f64| ColumnData::FixedF64Bits {
+        validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+        values: (0..rows)
+            .map(|i| (base + i as f64 / 4.0).to_bits())
+            .collect(),
+    };
+    let size = |base: i64| ColumnData::FixedI64 {
+        validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+        values: (0..rows as i64).map(|i| base + i * 100).collect(),
+    };
+    ColumnData::Struct {
+        names: ["bid", "ask", "bid_sz", "ask_sz"]
+            .map(|n| Some(n.to_string()))
+            .to_vec(),
+        validity,
+        children: vec![price(99.5), price(100.0), size(1_000), size(2_000)],
+    }
+}
+
+/// A quote plus a struct holding an unnamed `Utf8` field and a nested list of quotes.
+fn struct_batch(rows: usize) -> ColumnarBatch {
+    let book_type = ColumnarType::Struct(vec![
+        field("", ColumnarType::Utf8),
+        field("levels", ColumnarType::List(Box::new(quote_type()))),
+    ]);
+    let schema =
+        ColumnarSchema::new(vec![field("quote", quote_type()), field("book", book_type)]).unwrap();
+
+    let mut venue_offsets = vec![0u32];
+    let mut venue_data = Vec::new();
+    let mut level_offsets = vec![0u32];
+    for i in 0..rows {
+        venue_data.extend_from_slice(format!("venue-{i}").as_bytes());
+        venue_offsets.push(venue_data.len() as u32);
+        level_offsets.push(level_offsets[i] + (i % 3) as u32);
+    }
+    let levels = level_offsets[rows] as usize;
+    let book = ColumnData::Struct {
+        names: vec![None, Some("levels".to_string())],
+        validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+        children: vec![
+            ColumnData::Var {
+                ty: ColumnarType::Utf8,
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                offsets: venue_offsets,
+                data: venue_data,
+            },
+            ColumnData::List {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                offsets: level_offsets,
+                child: Box::new(quote_column(levels)),
+            },
+        ],
+    };
+    ColumnarBatch::new(schema, rows, vec![quote_column(rows), book]).unwrap()
+}
+
+#[test]
+fn struct_columns_round_trip_through_all_paths() {
+    for rows in [0, 1, 3, 100] {
+        let batch = struct_batch(rows);
+        let mut owned = Vec::new();
+        encode_mathldbt_v1_into(&batch, &mut owned).unwrap();
+        assert_eq!(decode_mathldbt_v1(&owned).unwrap(), batch);
+        assert_eq!(inspect_mathldbt_v1(&owned).unwrap().schema, batch.schema);
+
+        let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+        let view = ColumnarBatchView {
+            schema: &batch.schema,
+            row_count: batch.row_count,
+            columns: &cols,
+        };
+        let mut fast = Vec::new();
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut ws).unwrap();
+        assert_eq!(fast, owned);
+
+        let mut dws = MathldbtV1DecodeWorkspace::default();
+        let decoded = decode_mathldbt_v1_view_with_workspace(&owned, &mut dws).unwrap();
+        assert_eq!(decoded.to_batch().unwrap(), batch);
+
+        let mut out = struct_batch(7);
+        decode_mathldbt_v1_into(&owned, &mut out).unwrap();
+        assert_eq!(out, batch);
+
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        ws.set_enable_pg_be_fixed(true);
+        ws.set_enable_dict_utf8(true);
+        let mut tuned = Vec::new();
+        encode_mathldbt_v1_into_with_workspace(&batch, &mut tuned, &mut ws).unwrap();
+        assert_eq!(decode_mathldbt_v1(&tuned).unwrap(), batch);
+        let mut fast_tuned = Vec::new();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast_tuned, &mut ws).unwrap();
+        assert_eq!(fast_tuned, tuned);
+    }
+
+    let batch = struct_batch(5);
+    let mut w = StreamWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    let stream = w.finish().unwrap();
+    let mut r = StreamReader::new(stream.as_slice(), 1 << 20).unwrap();
+    assert_eq!(r.schema(), &batch.schema);
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out, batch);
+}
+
+#[test]
+fn struct_view_borrows_field_names_and_children() {
+    // Byte-wide fields are always aligned, so the whole struct is borrowed.
+    let flags = || ColumnData::FixedU8 {
+        validity: ValidityBitmap::new_all_valid(3).unwrap(),
+        values: vec![1, 2, 3],
+    };
+    let batch = ColumnarBatch::new(
+        ColumnarSchema::new(vec![field(
+            "flags",
+            ColumnarType::Struct(vec![
+                field("side", ColumnarType::U8),
+                field("", ColumnarType::U8),
+            ]),
+        )])
+        .unwrap(),
+        3,
+        vec![ColumnData::Struct {
+            names: vec![Some("side".to_string()), None],
+            validity: ValidityBitmap::new_all_valid(3).unwrap(),
+            children: vec![flags(), flags()],
+        }],
+    )
+    .unwrap();
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+    let mut dws = MathldbtV1DecodeWorkspace::default();
+    let decoded = decode_mathldbt_v1_view_with_workspace(&bytes, &mut dws).unwrap();
+    let range = bytes.as_ptr_range();
+    match &decoded.columns()[0] {
+        ColumnDataView::Struct {
+            names, children, ..
+        } => {
+            assert_eq!(names, &[Some("side"), None]);
+            assert!(range.contains(&names[0].unwrap().as_ptr()));
+            match children[1] {
+                ColumnDataView::FixedU8 { values, .. } => {
+                    assert!(range.contains(&values.as_ptr()))
+                }
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(decoded.to_batch().unwrap(), batch);
+
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&struct_batch(10), &mut bytes).unwrap();
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(info.columns[0].children.len(), 4);
+    assert!(info.columns[0].payload1.is_empty());
+    assert_eq!(info.columns[1].children[1].children[0].children.len(), 4);
+}
+
+#[test]
+fn struct_validate_checks_every_field() {
+    let batch = struct_batch(4);
+    let rebuild = |col: ColumnData| {
+        let mut columns = batch.columns.clone();
+        columns[0] = col;
+        ColumnarBatch::new(batch.schema.clone(), batch.row_count, columns).unwrap_err()
+    };
+    let ColumnData::Struct {
+        names,
+        validity,
+        children,
+    } = quote_column(4)
+    else {
+        unreachable!()
+    };
+
+    let mut short = children.clone();
+    short[3] = ColumnData::FixedI64 {
+        validity: ValidityBitmap::new_all_valid(4).unwrap(),
+        values: vec![0; 3],
+    };
+    assert_eq!(
+        rebuild(ColumnData::Struct {
+            names: names.clone(),
+            validity: validity.clone(),
+            children: short,
+        }),
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "values length mismatch",
+        }
+    );
+
+    let mut extra = names.clone();
+    extra.push(Some("mid".to_string()));
+    assert_eq!(
+        rebuild(ColumnData::Struct {
+            names: extra,
+            validity: validity.clone(),
+            children: children.clone(),
+        }),
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "struct field count mismatch",
+        }
+    );
+
+    let mut renamed = names;
+    renamed[0] = Some("bid_px".to_string());
+    assert_eq!(
+        rebuild(ColumnData::Struct {
+            names: renamed,
+            validity,
+            children,
+        }),
+        Error::Schema {
+            column: Some(0),
+            reason: "column type mismatch",
+        }
+    );
+}
+
+#[test]
+fn struct_adversarial_inputs_are_rejected() {
+    assert_eq!(
+        ColumnarSchema::new(vec![field("", ColumnarType::Struct(Vec::new()))]).unwrap_err(),
+        Error::Schema {
+            column: Some(0),
+            reason: "struct must have at least one field",
+        }
+    );
+
+    let schema = ColumnarSchema::new(vec![field(
+        "",
+        ColumnarType::Struct(vec![
+            field("a", ColumnarType::I32),
+            field("b", ColumnarType::I64),
+        ]),
+    )])
+    .unwrap();
+    let batch = ColumnarBatch::new(
+        schema,
+        1,
+        vec![ColumnData::Struct {
+            names: vec![Some("a".to_string()), Some("b".to_string())],
+            validity: ValidityBitmap::new_all_valid(1).unwrap(),
+            children: vec![
+                ColumnData::FixedI32 {
+                    validity: ValidityBitmap::new_all_valid(1).unwrap(),
+                    values: vec![7],
+                },
+                ColumnData::FixedI64 {
+                    validity: ValidityBitmap::new_all_valid(1).unwrap(),
+                    values: vec![8],
+                },
+            ],
+        }],
+    )
+    .unwrap();
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    // Header (20) | struct id, field count (4) | "a" field (5) | "b" field (5).
+    let mut empty = bytes.clone();
+    empty[22] = 0;
+    assert_eq!(
+        decode_mathldbt_v1(&empty).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 22,
+            reason: "struct must have at least one field",
+        }
+    );
+
+    let mut bad_name = bytes.clone();
+    bad_name[26] = 0xff;
+    assert_eq!(
+        decode_mathldbt_v1(&bad_name).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 26,
+            reason: "invalid UTF-8 column name",
+        }
+    );
+
+    // The struct descriptor ends at 53 (enc, flags, name_len, validity and two empty
+    // payloads); the "a" field's own descriptor follows.
+    let mut retagged = bytes.clone();
+    retagged[53] = 4;
+    assert_eq!(
+        decode_mathldbt_v1(&retagged).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 53,
+            reason: "struct field type mismatch",
+        }
+    );
+
+    let mut payload = bytes.clone();
+    payload[45] = 1;
+    payload.insert(49, 0);
+    assert_eq!(
+        decode_mathldbt_v1(&payload).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 49,
+            reason: "struct payloads must be empty",
+        }
+    );
+}
@@ -0,0 +1,250 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_into, decode_mathldbt_v1_view_with_workspace,
+    encode_mathldbt_v1_fast_path_into_with_workspace, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace,
+};
+use crate::codec::{StreamReader, StreamWriter};
+use crate::schema::{
+    ColumnarField, ColumnarSchema, ColumnarType, IntervalMonthDayMicros, TimeUnit,
+};
+
+const LOCAL_TS: ColumnarType = ColumnarType::Timestamp {
+    unit: TimeUnit::Microsecond,
+    tz: None,
+};
+
+fn temporal_batch(rows: usize) -> ColumnarBatch {
+    let f = |name: &str, ty| ColumnarField {
+        name: Some(name.to_string()),
+        ty,
+    };
+    let schema = ColumnarSchema::new(vec![
+        f("trade_date", ColumnarType::Date),
+        f("session_open", ColumnarType::TimeMicros),
+        f("local_ts", LOCAL_TS),
+        f("utc_ts", ColumnarType::TimestampTzMicros),
+        f("tenor", ColumnarType::IntervalMonthDayMicros),
+    ])
+    .unwrap();
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    if rows > 1 {
+        validity.set(1, false).unwrap();
+    }
+    let all_valid = || ValidityBitmap::new_all_valid(rows).unwrap();
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::FixedDate32 {
+                validity: all_valid(),
+                values: (0..rows).map(|i| 19_723 + i as i32).collect(),
+            },
+            ColumnData::FixedTimeMicros {
+                validity: validity.clone(),
+                values: (0..rows).map(|i| 34_200_000_000 + i as i64).collect(),
+            },
+            ColumnData::FixedTimestamp {
+                unit: TimeUnit::Microsecond,
+                tz: None,
+                validity: all_valid(),
+                values: (0..rows)
+                    .map(|i| 1_704_067_200_000_000 + i as i64 * 60_000_000)
+                    .collect(),
+            },
+            ColumnData::FixedTimestampMicros {
+                validity: all_valid(),
+                values: (0..rows)
+                    .map(|i| 1_704_067_200_000_000 + i as i64 * 60_000_000)
+                    .collect(),
+            },
+            ColumnData::FixedInterval {
+                validity,
+                values: (0..rows)
+                    .map(|i| IntervalMonthDayMicros {
+                        months: i as i32,
+                        days: -(i as i32),
+                        micros: i as i64 * 1_000_001,
+                    })
+                    .collect(),
+            },
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn temporal_columns_round_trip_through_all_paths() {
+    for rows in [0, 1, 3, 100] {
+        let batch = temporal_batch(rows);
+        let mut owned = Vec::new();
+        encode_mathldbt_v1_into(&batch, &mut owned).unwrap();
+        assert_eq!(decode_mathldbt_v1(&owned).unwrap(), batch);
+
+        let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+        let view = ColumnarBatchView {
+            schema: &batch.schema,
+            row_count: batch.row_count,
+            columns: &cols,
+        };
+        let mut fast = Vec::new();
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut ws).unwrap();
+        assert_eq!(fast, owned);
+
+        let mut dws = MathldbtV1DecodeWorkspace::default();
+        let decoded = decode_mathldbt_v1_view_with_workspace(&owned, &mut dws).unwrap();
+        assert_eq!(decoded.to_batch().unwrap(), batch);
+
+        let mut out = temporal_batch(7);
+        decode_mathldbt_v1_into(&owned, &mut out).unwrap();
+        assert_eq!(out, batch);
+
+        let mut be = Vec::new();
+        let mut ws = MathldbtV1EncodeWorkspace::default();
+        ws.set_enable_pg_be_fixed(true);
+        encode_mathldbt_v1_into_with_workspace(&batch, &mut be, &mut ws).unwrap();
+        assert_eq!(decode_mathldbt_v1(&be).unwrap(), batch);
+        let mut fast_be = Vec::new();
+        encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast_be, &mut ws).unwrap();
+        assert_eq!(fast_be, be);
+    }
+
+    let batch = temporal_batch(5);
+    let mut w = StreamWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    let stream = w.finish().unwrap();
+    let mut r = StreamReader::new(stream.as_slice(), 1 << 20).unwrap();
+    assert_eq!(r.schema(), &batch.schema);
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out, batch);
+}
+
+#[test]
+fn interval_wire_layouts() {
+    let schema = ColumnarSchema::new(vec![ColumnarField {
+        name: None,
+        ty: ColumnarType::IntervalMonthDayMicros,
+    }])
+    .unwrap();
+    let batch = ColumnarBatch::new(
+        schema,
+        1,
+        vec![ColumnData::FixedInterval {
+            validity: ValidityBitmap::new_all_valid(1).unwrap(),
+            values: vec![IntervalMonthDayMicros {
+                months: 1,
+                days: 2,
+                micros: 3,
+            }],
+        }],
+    )
+    .unwrap();
+
+    // Header (20) | type, enc, flags, name_len (8) | validity (4 + 1) | payload_1 length (4).
+    let payload_at = 37;
+    let mut plain = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut plain).unwrap();
+    let mut expected = Vec::new();
+    expected.extend_from_slice(&1i32.to_le_bytes());
+    expected.extend_from_slice(&2i32.to_le_bytes());
+    expected.extend_from_slice(&3i64.to_le_bytes());
+    assert_eq!(&plain[payload_at..payload_at + 16], expected.as_slice());
+
+    let mut be = Vec::new();
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_pg_be_fixed(true);
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut be, &mut ws).unwrap();
+    let mut expected = Vec::new();
+    expected.extend_from_slice(&3i64.to_be_bytes());
+    expected.extend_from_slice(&2i32.to_be_bytes());
+    expected.extend_from_slice(&1i32.to_be_bytes());
+    assert_eq!(&be[payload_at..payload_at + 16], expected.as_slice());
+}
+
+#[test]
+fn temporal_adversarial_inputs_are_rejected() {
+    let batch = temporal_batch(1);
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    // Column 0 (`Date`): the encoding id follows the type id at 20.
+    let mut delta = bytes.clone();
+    delta[22] = 3;
+    assert_eq!(
+        decode_mathldbt_v1(&delta).unwrap_err(),
+        Error::UnknownEncoding {
+            column: 0,
+            offset: 22,
+            encoding_id: 3,
+        }
+    );
+
+    // Retagging `Date` as `TimeMicros` leaves a 4-byte payload for an 8-byte type; the payload
+    // follows the 10-byte name.
+    let mut retagged = bytes.clone();
+    retagged[20] = 15;
+    assert_eq!(
+        decode_mathldbt_v1(&retagged).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 47,
+            reason: "values length mismatch",
+        }
+    );
+
+    // The naive and tz-aware timestamps share a layout but not a type.
+    let mut out = temporal_batch(1);
+    out.schema = ColumnarSchema::new(
+        out.schema
+            .fields()
+            .iter()
+            .cloned()
+            .map(|mut f| {
+                if f.ty == LOCAL_TS {
+                    f.ty = ColumnarType::TimestampTzMicros;
+                }
+                f
+            })
+            .collect(),
+    )
+    .unwrap();
+    assert!(matches!(
+        decode_mathldbt_v1_into(&bytes, &mut out).unwrap_err(),
+        Error::Schema {
+            column: Some(2),
+            ..
+        }
+    ));
+
+    // An interval column whose payload is one byte short.
+    let interval = ColumnarBatch::new(
+        ColumnarSchema::new(vec![ColumnarField {
+            name: None,
+            ty: ColumnarType::IntervalMonthDayMicros,
+        }])
+        .unwrap(),
+        1,
+        vec![ColumnData::FixedInterval {
+            validity: ValidityBitmap::new_all_valid(1).unwrap(),
+            values: vec![IntervalMonthDayMicros::default()],
+        }],
+    )
+    .unwrap();
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&interval, &mut bytes).unwrap();
+    bytes[33] = 15;
+    bytes.remove(37);
+    assert_eq!(
+        decode_mathldbt_v1(&bytes).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 37,
+            reason: "values length mismatch",
+        }
+    );
+}
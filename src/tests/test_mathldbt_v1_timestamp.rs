@@ -0,0 +1,208 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1DecodeWorkspace, MathldbtV1EncodeWorkspace, decode_mathldbt_v1,
+    decode_mathldbt_v1_into, decode_mathldbt_v1_view_with_workspace,
+    encode_mathldbt_v1_fast_path_into_with_workspace, encode_mathldbt_v1_into,
+    encode_mathldbt_v1_into_with_workspace, inspect_mathldbt_v1,
+};
+use crate::codec::{StreamReader, StreamWriter};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType, TimeUnit};
+
+const UNITS: [(TimeUnit, &str); 4] = [
+    (TimeUnit::Second, "e_s"),
+    (TimeUnit::Millisecond, "e_ms"),
+    (TimeUnit::Microsecond, "e_us"),
+    (TimeUnit::Nanosecond, "e_ns"),
+];
+
+fn timestamp_batch(rows: usize, tz: Option<&str>) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(
+        UNITS
+            .iter()
+            .map(|&(unit, name)| ColumnarField {
+                name: Some(name.to_string()),
+                ty: ColumnarType::Timestamp {
+                    unit,
+                    tz: tz.map(Into::into),
+                },
+            })
+            .collect(),
+    )
+    .unwrap();
+    let columns = UNITS
+        .iter()
+        .zip([1, 1_000, 1_000_000, 1_000_000_000i64])
+        .map(|(&(unit, _), per_second)| ColumnData::FixedTimestamp {
+            unit,
+            tz: tz.map(Into::into),
+            validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+            values: (0..rows)
+                .map(|i| (1_700_000_000 + i as i64 * 60) * per_second + i as i64)
+                .collect(),
+        })
+        .collect();
+    ColumnarBatch::new(schema, rows, columns).unwrap()
+}
+
+#[test]
+fn timestamps_round_trip_with_every_unit_and_zone() {
+    for tz in [
+        None,
+        Some("Etc/UTC"),
+        Some("America/New_York"),
+        Some("+05:30"),
+    ] {
+        for rows in [0, 1, 3, 100] {
+            let batch = timestamp_batch(rows, tz);
+            let mut owned = Vec::new();
+            encode_mathldbt_v1_into(&batch, &mut owned).unwrap();
+            assert_eq!(decode_mathldbt_v1(&owned).unwrap(), batch);
+            assert_eq!(inspect_mathldbt_v1(&owned).unwrap().schema, batch.schema);
+
+            let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+            let view = ColumnarBatchView {
+                schema: &batch.schema,
+                row_count: batch.row_count,
+                columns: &cols,
+            };
+            let mut fast = Vec::new();
+            let mut ws = MathldbtV1EncodeWorkspace::default();
+            encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut ws).unwrap();
+            assert_eq!(fast, owned);
+
+            let mut dws = MathldbtV1DecodeWorkspace::default();
+            let decoded = decode_mathldbt_v1_view_with_workspace(&owned, &mut dws).unwrap();
+            assert_eq!(decoded.to_batch().unwrap(), batch);
+
+            let mut out = timestamp_batch(7, tz);
+            decode_mathldbt_v1_into(&owned, &mut out).unwrap();
+            assert_eq!(out, batch);
+        }
+    }
+
+    let batch = timestamp_batch(5, Some("Europe/Paris"));
+    let mut w = StreamWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    let stream = w.finish().unwrap();
+    let mut r = StreamReader::new(stream.as_slice(), 1 << 20).unwrap();
+    assert_eq!(r.schema(), &batch.schema);
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out, batch);
+}
+
+#[test]
+fn delta_varint_applies_to_every_timestamp_unit() {
+    let batch = timestamp_batch(100, Some("Etc/UTC"));
+    let mut plain = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut plain).unwrap();
+
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_delta_varint_i64(true);
+    let mut delta = Vec::new();
+    encode_mathldbt_v1_into_with_workspace(&batch, &mut delta, &mut ws).unwrap();
+    assert!(delta.len() < plain.len() / 2);
+    assert_eq!(decode_mathldbt_v1(&delta).unwrap(), batch);
+
+    let cols: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+    let view = ColumnarBatchView {
+        schema: &batch.schema,
+        row_count: batch.row_count,
+        columns: &cols,
+    };
+    let mut fast = Vec::new();
+    encode_mathldbt_v1_fast_path_into_with_workspace(&view, &mut fast, &mut ws).unwrap();
+    assert_eq!(fast, delta);
+
+    // Header (20) | type id (2) | unit (1) | tz_len (2) | "Etc/UTC" (7), then the encoding id.
+    assert_eq!(&delta[20..25], &[13, 0, 0, 7, 0]);
+    assert_eq!(&delta[25..32], b"Etc/UTC");
+    assert_eq!(&delta[32..34], &3u16.to_le_bytes());
+}
+
+#[test]
+fn timestamp_descriptor_errors() {
+    assert_eq!(
+        ColumnarSchema::new(vec![ColumnarField {
+            name: None,
+            ty: ColumnarType::Timestamp {
+                unit: TimeUnit::Millisecond,
+                tz: Some("".into()),
+            },
+        }])
+        .unwrap_err(),
+        Error::Schema {
+            column: Some(0),
+            reason: "invalid timestamp time zone",
+        }
+    );
+
+    let batch = timestamp_batch(1, Some("Etc/UTC"));
+    let mut bytes = Vec::new();
+    encode_mathldbt_v1_into(&batch, &mut bytes).unwrap();
+
+    let mut bad_unit = bytes.clone();
+    bad_unit[22] = 4;
+    assert_eq!(
+        decode_mathldbt_v1(&bad_unit).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 22,
+            reason: "invalid timestamp unit",
+        }
+    );
+
+    let mut bad_tz = bytes.clone();
+    bad_tz[26] = 0xff;
+    assert_eq!(
+        decode_mathldbt_v1(&bad_tz).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 25,
+            reason: "invalid timestamp time zone",
+        }
+    );
+
+    // Microseconds in "UTC" are only spelled `TimestampTzMicros`.
+    let utc_ms = ColumnarBatch::new(
+        ColumnarSchema::new(vec![ColumnarField {
+            name: None,
+            ty: ColumnarType::Timestamp {
+                unit: TimeUnit::Millisecond,
+                tz: Some("UTC".into()),
+            },
+        }])
+        .unwrap(),
+        1,
+        vec![ColumnData::FixedTimestamp {
+            unit: TimeUnit::Millisecond,
+            tz: Some("UTC".into()),
+            validity: ValidityBitmap::new_all_valid(1).unwrap(),
+            values: vec![0],
+        }],
+    )
+    .unwrap();
+    let mut utc_us = Vec::new();
+    encode_mathldbt_v1_into(&utc_ms, &mut utc_us).unwrap();
+    utc_us[22] = 2;
+    assert_eq!(
+        decode_mathldbt_v1(&utc_us).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 22,
+            reason: "UTC microsecond timestamps must be TimestampTzMicros",
+        }
+    );
+
+    // Same unit and values layout, different zone.
+    let mut out = timestamp_batch(1, Some("Asia/Tokyo"));
+    assert!(matches!(
+        decode_mathldbt_v1_into(&bytes, &mut out).unwrap_err(),
+        Error::Schema {
+            column: Some(0),
+            ..
+        }
+    ));
+}
@@ -0,0 +1,191 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::codec::mathldbt_v1::{
+    MathldbtV1EncodeWorkspace, decode_mathldbt_v1, inspect_mathldbt_v1,
+};
+use crate::schema::{ColumnarSchema, ColumnarType};
+use crate::tests::common::{assert_decodes_all_paths, encode_both, encodings, field};
+
+/// OHLC-style prices on a 0.25 tick (`F64`), a volume-weighted price (`F32`) with every
+/// seventh row null, and a few special values among the first rows.
+fn bars_batch(rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![
+        field("close", ColumnarType::F64),
+        field("vwap", ColumnarType::F32),
+    ])
+    .unwrap();
+    let mut validity = ValidityBitmap::new_all_valid(rows).unwrap();
+    for i in (6..rows).step_by(7) {
+        validity.set(i, false).unwrap();
+    }
+    let mut close: Vec<u64> = (0..rows)
+        .map(|i| (42_000.0 + ((i * 5) % 12) as f64 * 0.25).to_bits())
+        .collect();
+    let specials = [f64::NAN, -0.0, f64::INFINITY, f64::MIN_POSITIVE / 4.0];
+    for (slot, v) in close.iter_mut().skip(3).zip(specials) {
+        *slot = v.to_bits();
+    }
+    ColumnarBatch::new(
+        schema,
+        rows,
+        vec![
+            ColumnData::FixedF64Bits {
+                validity: ValidityBitmap::new_all_valid(rows).unwrap(),
+                values: close,
+            },
+            ColumnData::FixedF32Bits {
+                validity,
+                values: (0..rows)
+                    .map(|i| (1.5f32 + (i / 4) as f32).to_bits())
+                    .collect(),
+            },
+        ],
+    )
+    .unwrap()
+}
+
+fn xor_workspace() -> MathldbtV1EncodeWorkspace {
+    let mut ws = MathldbtV1EncodeWorkspace::default();
+    ws.set_enable_xor_float(true);
+    ws
+}
+
+fn single_f64(values: &[f64]) -> ColumnarBatch {
+    ColumnarBatch::new(
+        ColumnarSchema::new(vec![field("", ColumnarType::F64)]).unwrap(),
+        values.len(),
+        vec![ColumnData::FixedF64Bits {
+            validity: ValidityBitmap::new_all_valid(values.len()).unwrap(),
+            values: values.iter().map(|v| v.to_bits()).collect(),
+        }],
+    )
+    .unwrap()
+}
+
+#[test]
+fn xor_float_round_trips_through_all_paths() {
+    let mut ws = xor_workspace();
+    for rows in [0, 1, 2, 9, 300] {
+        let batch = bars_batch(rows);
+        let bytes = encode_both(&batch, &mut ws);
+        assert_eq!(encode_both(&batch, &mut ws), bytes);
+        if rows == 300 {
+            assert_eq!(encodings(&bytes)[..2], [8, 8]);
+            let info = inspect_mathldbt_v1(&bytes).unwrap();
+            assert!(info.columns[0].payload1.len() < 300 * 8 / 2);
+        }
+
+        assert_decodes_all_paths(&bytes, &batch);
+    }
+
+    // A constant column costs one bit per value after the first.
+    let bytes = encode_both(&single_f64(&[42_000.5; 17]), &mut ws);
+    assert_eq!(encodings(&bytes), [8]);
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(&bytes[info.columns[0].payload1.clone()][8..], [0, 0]);
+
+    // Unrelated bit patterns are not smaller, so they stay plain.
+    let noise: Vec<f64> = (1..40u64)
+        .map(|i| f64::from_bits(i.wrapping_mul(0x9e37_79b9_7f4a_7c15)))
+        .collect();
+    let bytes = encode_both(&single_f64(&noise), &mut ws);
+    assert_eq!(encodings(&bytes), [0]);
+}
+
+#[test]
+fn xor_float_is_opt_in() {
+    let batch = bars_batch(300);
+    let bytes = encode_both(&batch, &mut MathldbtV1EncodeWorkspace::default());
+    assert_eq!(encodings(&bytes)[..2], [0, 0]);
+
+    // XOR takes precedence over PgBeFixed where it is smaller.
+    let mut ws = xor_workspace();
+    ws.set_enable_pg_be_fixed(true);
+    let bytes = encode_both(&batch, &mut ws);
+    assert_eq!(encodings(&bytes)[0], 8);
+    assert_eq!(decode_mathldbt_v1(&bytes).unwrap(), batch);
+
+    // Where it is not, the column still gets PgBeFixed rather than PlainLe.
+    let noise = single_f64(
+        &(1..40u64)
+            .map(|i| f64::from_bits(i.wrapping_mul(0x9e37_79b9_7f4a_7c15)))
+            .collect::<Vec<_>>(),
+    );
+    let bytes = encode_both(&noise, &mut ws);
+    assert_eq!(encodings(&bytes), [1]);
+    assert_decodes_all_paths(&bytes, &noise);
+}
+
+#[test]
+fn xor_float_adversarial_inputs_are_rejected() {
+    let bytes = encode_both(&single_f64(&[1.0, 1.0, 2.0]), &mut xor_workspace());
+
+    // Header (20) | type id (2) | enc (2) | flags (2) | name_len (2) | validity (4 + 1) |
+    // payload_1 length (4) at 33, then 1.0 at 37 and the stream at 45: `0` for the repeat,
+    // then `1 1`, leading zeros 1, length 11 - 1 and the 11 bits of `1.0 ^ 2.0`.
+    let info = inspect_mathldbt_v1(&bytes).unwrap();
+    assert_eq!(info.columns[0].payload1, 37..49);
+    assert_eq!(bytes[45..49], [0x0e, 0x94, 0xff, 0x03]);
+    let malformed = |offset, reason| Error::Malformed {
+        column: Some(0),
+        offset,
+        reason,
+    };
+    let with_payload = |payload: &[u8]| {
+        let mut b = bytes[..33].to_vec();
+        b.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        b.extend_from_slice(payload);
+        b.extend_from_slice(&0u32.to_le_bytes());
+        b
+    };
+    let payload = &bytes[37..49];
+
+    assert_eq!(
+        decode_mathldbt_v1(&with_payload(&payload[..5])).unwrap_err(),
+        malformed(37, "XOR float payload truncated")
+    );
+    assert_eq!(
+        decode_mathldbt_v1(&with_payload(&payload[..11])).unwrap_err(),
+        malformed(48, "XOR float stream truncated")
+    );
+    assert_eq!(
+        decode_mathldbt_v1(&with_payload(&payload[..8])).unwrap_err(),
+        malformed(45, "XOR float stream truncated")
+    );
+    assert_eq!(
+        decode_mathldbt_v1(&with_payload(&[payload, &[0]].concat())).unwrap_err(),
+        malformed(49, "trailing bytes in XOR float payload")
+    );
+
+    // 60 leading zeros leave no room for 11 bits; a reused window must exist.
+    let mut wide = bytes.clone();
+    wide[45] = 0xe6;
+    wide[46] = 0x95;
+    assert_eq!(
+        decode_mathldbt_v1(&wide).unwrap_err(),
+        malformed(45, "invalid XOR float window")
+    );
+    let mut reuse = bytes.clone();
+    reuse[45] = 0x0a;
+    assert_eq!(
+        decode_mathldbt_v1(&reuse).unwrap_err(),
+        malformed(45, "invalid XOR float window")
+    );
+    let mut padded = bytes.clone();
+    padded[48] |= 0x80;
+    assert_eq!(
+        decode_mathldbt_v1(&padded).unwrap_err(),
+        malformed(48, "XOR float padding must be zero")
+    );
+
+    let mut retyped = bytes.clone();
+    retyped[20] = 4;
+    assert_eq!(
+        decode_mathldbt_v1(&retyped).unwrap_err(),
+        Error::UnknownEncoding {
+            column: 0,
+            offset: 22,
+            encoding_id: 8,
+        }
+    );
+}
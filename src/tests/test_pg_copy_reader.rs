@@ -0,0 +1,330 @@
+use crate::Error;
+use crate::batch::{ColumnData, ValidityBitmap};
+use crate::pg_copy::{PG_EPOCH_OFFSET_MICROS, PgCopyReader};
+use crate::schema::{ColumnarField, ColumnarSchema, ColumnarType};
+
+// `COPY bars TO STDOUT (FORMAT binary)` for
+//
+//   CREATE TABLE bars (id uuid, pair text, t timestamptz, close float8, volume int8,
+//                      flags int2, n int4, ok bool, px float4, raw bytea, meta jsonb);
+//   INSERT INTO bars VALUES
+//     ('a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11', 'BTCUSDT', '2024-01-01 00:00:00+00', 42000.5,
+//      123456789, -2, 70000, true, 1.25, '\x00ff', '{"src": "binance"}'),
+//     ('00000000-0000-0000-0000-000000000001', 'ETH/€', '1999-12-31 23:59:59.999999+00',
+//      NULL, NULL, NULL, NULL, false, NULL, '', NULL),
+//     (NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL);
+const ALL_TYPES: &[u8] = include_bytes!("fixtures/pg_copy_all_types.bin");
+
+fn all_types_schema() -> ColumnarSchema {
+    let f = |name: &str, ty| ColumnarField {
+        name: Some(name.to_string()),
+        ty,
+    };
+    ColumnarSchema::new(vec![
+        f("id", ColumnarType::Uuid),
+        f("pair", ColumnarType::Utf8),
+        f("t", ColumnarType::TimestampTzMicros),
+        f("close", ColumnarType::F64),
+        f("volume", ColumnarType::I64),
+        f("flags", ColumnarType::I16),
+        f("n", ColumnarType::I32),
+        f("ok", ColumnarType::Bool),
+        f("px", ColumnarType::F32),
+        f("raw", ColumnarType::Bytes),
+        f("meta", ColumnarType::JsonbText),
+    ])
+    .unwrap()
+}
+
+fn single_schema(ty: ColumnarType) -> ColumnarSchema {
+    ColumnarSchema::new(vec![ColumnarField { name: None, ty }]).unwrap()
+}
+
+fn copy_stream(flags: u32, ext: &[u8], rows: &[Vec<Option<&[u8]>>]) -> Vec<u8> {
+    let mut out = b"PGCOPY\n\xff\r\n\0".to_vec();
+    out.extend_from_slice(&flags.to_be_bytes());
+    out.extend_from_slice(&(ext.len() as u32).to_be_bytes());
+    out.extend_from_slice(ext);
+    for row in rows {
+        out.extend_from_slice(&(row.len() as i16).to_be_bytes());
+        for field in row {
+            match field {
+                Some(v) => {
+                    out.extend_from_slice(&(v.len() as i32).to_be_bytes());
+                    out.extend_from_slice(v);
+                }
+                None => out.extend_from_slice(&(-1i32).to_be_bytes()),
+            }
+        }
+    }
+    out.extend_from_slice(&(-1i16).to_be_bytes());
+    out
+}
+
+fn validity(bits: &[bool]) -> ValidityBitmap {
+    let mut v = ValidityBitmap::new_all_invalid(bits.len()).unwrap();
+    for (i, &b) in bits.iter().enumerate() {
+        v.set(i, b).unwrap();
+    }
+    v
+}
+
+#[test]
+fn pg_copy_fixture_decodes_all_types() {
+    let mut r = PgCopyReader::new(ALL_TYPES, all_types_schema(), 1024).unwrap();
+    let batch = r.next_batch().unwrap().unwrap();
+    assert!(r.next_batch().unwrap().is_none());
+    assert_eq!(batch.row_count, 3);
+
+    let c = &batch.columns;
+    assert_eq!(
+        c[0],
+        ColumnData::FixedUuid {
+            validity: validity(&[true, true, false]),
+            values: vec![
+                [
+                    0xa0, 0xee, 0xbc, 0x99, 0x9c, 0x0b, 0x4e, 0xf8, 0xbb, 0x6d, 0x6b, 0xb9, 0xbd,
+                    0x38, 0x0a, 0x11,
+                ],
+                [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+                [0; 16],
+            ],
+        }
+    );
+    assert_eq!(
+        c[1],
+        ColumnData::Var {
+            ty: ColumnarType::Utf8,
+            validity: validity(&[true, true, false]),
+            offsets: vec![0, 7, 14, 14],
+            data: "BTCUSDTETH/€".as_bytes().to_vec(),
+        }
+    );
+    assert_eq!(
+        c[2],
+        ColumnData::FixedTimestampMicros {
+            validity: validity(&[true, true, false]),
+            values: vec![1_704_067_200_000_000, PG_EPOCH_OFFSET_MICROS - 1, 0],
+        }
+    );
+    assert_eq!(
+        c[3],
+        ColumnData::FixedF64Bits {
+            validity: validity(&[true, false, false]),
+            values: vec![42000.5f64.to_bits(), 0, 0],
+        }
+    );
+    assert_eq!(
+        c[4],
+        ColumnData::FixedI64 {
+            validity: validity(&[true, false, false]),
+            values: vec![123_456_789, 0, 0],
+        }
+    );
+    assert_eq!(
+        c[5],
+        ColumnData::FixedI16 {
+            validity: validity(&[true, false, false]),
+            values: vec![-2, 0, 0],
+        }
+    );
+    assert_eq!(
+        c[6],
+        ColumnData::FixedI32 {
+            validity: validity(&[true, false, false]),
+            values: vec![70_000, 0, 0],
+        }
+    );
+    assert_eq!(
+        c[7],
+        ColumnData::FixedBool {
+            validity: validity(&[true, true, false]),
+            values: vec![1, 0, 0],
+        }
+    );
+    assert_eq!(
+        c[8],
+        ColumnData::FixedF32Bits {
+            validity: validity(&[true, false, false]),
+            values: vec![1.25f32.to_bits(), 0, 0],
+        }
+    );
+    assert_eq!(
+        c[9],
+        ColumnData::Var {
+            ty: ColumnarType::Bytes,
+            validity: validity(&[true, true, false]),
+            offsets: vec![0, 2, 2, 2],
+            data: vec![0x00, 0xff],
+        }
+    );
+    // The jsonb version byte is stripped.
+    assert_eq!(
+        c[10],
+        ColumnData::Var {
+            ty: ColumnarType::JsonbText,
+            validity: validity(&[true, false, false]),
+            offsets: vec![0, 18, 18, 18],
+            data: br#"{"src": "binance"}"#.to_vec(),
+        }
+    );
+}
+
+#[test]
+fn pg_copy_batches_respect_row_limit_and_reuse() {
+    let mut whole = PgCopyReader::new(ALL_TYPES, all_types_schema(), 1024).unwrap();
+    let whole = whole.next_batch().unwrap().unwrap();
+
+    let mut r = PgCopyReader::new(ALL_TYPES, all_types_schema(), 2).unwrap();
+    let mut out = r.new_batch().unwrap();
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out.row_count, 2);
+    assert_eq!(out.columns[4], {
+        let ColumnData::FixedI64 { values, .. } = &whole.columns[4] else {
+            unreachable!()
+        };
+        ColumnData::FixedI64 {
+            validity: validity(&[true, false]),
+            values: values[..2].to_vec(),
+        }
+    });
+    assert!(r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out.row_count, 1);
+    assert!(!r.read_batch_into(&mut out).unwrap());
+    assert_eq!(out.row_count, 0);
+}
+
+#[test]
+fn pg_copy_header_variants() {
+    let v = 7i32.to_be_bytes();
+    let rows = vec![vec![Some(&v[..])]];
+
+    // Header extension is skipped; unknown non-critical flag bits are ignored.
+    let bytes = copy_stream(1 << 20, b"ext-data", &rows);
+    let mut r = PgCopyReader::new(bytes.as_slice(), single_schema(ColumnarType::I32), 8).unwrap();
+    assert_eq!(r.next_batch().unwrap().unwrap().row_count, 1);
+
+    // With OIDs, each tuple carries an uncounted leading OID field.
+    let mut bytes = copy_stream(1 << 16, b"", &[]);
+    bytes.truncate(bytes.len() - 2);
+    bytes.extend_from_slice(&1i16.to_be_bytes());
+    bytes.extend_from_slice(&4i32.to_be_bytes());
+    bytes.extend_from_slice(&99u32.to_be_bytes());
+    bytes.extend_from_slice(&4i32.to_be_bytes());
+    bytes.extend_from_slice(&v);
+    bytes.extend_from_slice(&(-1i16).to_be_bytes());
+    let mut r = PgCopyReader::new(bytes.as_slice(), single_schema(ColumnarType::I32), 8).unwrap();
+    let b = r.next_batch().unwrap().unwrap();
+    assert_eq!(
+        b.columns[0],
+        ColumnData::FixedI32 {
+            validity: validity(&[true]),
+            values: vec![7],
+        }
+    );
+
+    let mut bad = bytes.clone();
+    bad[0] = b'X';
+    assert_eq!(
+        PgCopyReader::new(bad.as_slice(), single_schema(ColumnarType::I32), 8)
+            .err()
+            .unwrap(),
+        Error::BadMagic { offset: 0 }
+    );
+
+    let bytes = copy_stream(1, b"", &rows);
+    assert_eq!(
+        PgCopyReader::new(bytes.as_slice(), single_schema(ColumnarType::I32), 8)
+            .err()
+            .unwrap(),
+        Error::Malformed {
+            column: None,
+            offset: 11,
+            reason: "unknown critical COPY header flags",
+        }
+    );
+}
+
+#[test]
+fn pg_copy_timestamp_infinity_is_preserved() {
+    let inf = i64::MAX.to_be_bytes();
+    let ninf = i64::MIN.to_be_bytes();
+    let bytes = copy_stream(0, b"", &[vec![Some(&inf[..])], vec![Some(&ninf[..])]]);
+    let mut r = PgCopyReader::new(
+        bytes.as_slice(),
+        single_schema(ColumnarType::TimestampTzMicros),
+        8,
+    )
+    .unwrap();
+    assert_eq!(
+        r.next_batch().unwrap().unwrap().columns[0],
+        ColumnData::FixedTimestampMicros {
+            validity: validity(&[true, true]),
+            values: vec![i64::MAX, i64::MIN],
+        }
+    );
+}
+
+#[test]
+fn pg_copy_malformed_tuples_are_rejected() {
+    let read_all = |bytes: &[u8], ty| {
+        let mut r = PgCopyReader::new(bytes, single_schema(ty), 8)?;
+        r.next_batch()
+    };
+    // Tuples start at 19: signature 11 | flags 4 | extension length 4.
+    let short = [0u8; 3];
+    let bytes = copy_stream(0, b"", &[vec![Some(&short[..])]]);
+    assert_eq!(
+        read_all(&bytes, ColumnarType::I32).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 21,
+            reason: "COPY field length mismatch",
+        }
+    );
+
+    let two = 1i32.to_be_bytes();
+    let bytes = copy_stream(0, b"", &[vec![Some(&two[..]), Some(&two[..])]]);
+    assert_eq!(
+        read_all(&bytes, ColumnarType::I32).unwrap_err(),
+        Error::Malformed {
+            column: None,
+            offset: 19,
+            reason: "COPY tuple field count does not match schema",
+        }
+    );
+
+    let json = b"\x02{}";
+    let bytes = copy_stream(0, b"", &[vec![Some(&json[..])]]);
+    assert_eq!(
+        read_all(&bytes, ColumnarType::JsonbText).unwrap_err(),
+        Error::Malformed {
+            column: Some(0),
+            offset: 21,
+            reason: "unsupported jsonb version",
+        }
+    );
+
+    // Missing trailer.
+    let bytes = copy_stream(0, b"", &[vec![Some(&two[..])]]);
+    let cut = &bytes[..bytes.len() - 2];
+    assert_eq!(
+        read_all(cut, ColumnarType::I32).unwrap_err(),
+        Error::Truncated {
+            column: None,
+            offset: cut.len(),
+            needed: 2,
+        }
+    );
+
+    // Field length past the end of the input.
+    let cut = &bytes[..bytes.len() - 4];
+    assert_eq!(
+        read_all(cut, ColumnarType::I32).unwrap_err(),
+        Error::Truncated {
+            column: Some(0),
+            offset: 27,
+            needed: 2,
+        }
+    );
+}
@@ -0,0 +1,402 @@
+use crate::Error;
+use crate::batch::{ColumnData, ColumnarBatch, ValidityBitmap};
+use crate::batch_view::{ColumnDataView, ColumnarBatchView, VarDataView};
+use crate::pg_copy::{PG_EPOCH_OFFSET_DAYS, PG_EPOCH_OFFSET_MICROS, PgCopyReader, PgCopyWriter};
+use crate::schema::{
+    ColumnarField, ColumnarSchema, ColumnarType, IntervalMonthDayMicros, TimeUnit,
+};
+
+// Same fixture as the reader tests; see `test_pg_copy_reader.rs` for the generating SQL.
+const ALL_TYPES: &[u8] = include_bytes!("fixtures/pg_copy_all_types.bin");
+
+fn fixture_batch() -> ColumnarBatch {
+    let schema = {
+        let f = |name: &str, ty| ColumnarField {
+            name: Some(name.to_string()),
+            ty,
+        };
+        ColumnarSchema::new(vec![
+            f("id", ColumnarType::Uuid),
+            f("pair", ColumnarType::Utf8),
+            f("t", ColumnarType::TimestampTzMicros),
+            f("close", ColumnarType::F64),
+            f("volume", ColumnarType::I64),
+            f("flags", ColumnarType::I16),
+            f("n", ColumnarType::I32),
+            f("ok", ColumnarType::Bool),
+            f("px", ColumnarType::F32),
+            f("raw", ColumnarType::Bytes),
+            f("meta", ColumnarType::JsonbText),
+        ])
+        .unwrap()
+    };
+    let mut r = PgCopyReader::new(ALL_TYPES, schema, 1024).unwrap();
+    r.next_batch().unwrap().unwrap()
+}
+
+fn single_batch(column: ColumnData, rows: usize) -> ColumnarBatch {
+    let schema = ColumnarSchema::new(vec![ColumnarField {
+        name: None,
+        ty: column.ty(),
+    }])
+    .unwrap();
+    ColumnarBatch::new(schema, rows, vec![column]).unwrap()
+}
+
+#[test]
+fn pg_copy_writer_matches_golden_bytes() {
+    let batch = fixture_batch();
+    let mut w = PgCopyWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    assert_eq!(w.row_count(), 3);
+    assert_eq!(w.finish().unwrap(), ALL_TYPES);
+
+    let mut validity = ValidityBitmap::new_all_valid(2).unwrap();
+    validity.set(1, false).unwrap();
+    let batch = single_batch(
+        ColumnData::FixedI32 {
+            validity,
+            values: vec![7, 0],
+        },
+        2,
+    );
+    let mut w = PgCopyWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    let mut expected = b"PGCOPY\n\xff\r\n\0".to_vec();
+    expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]);
+    expected.extend_from_slice(&[0, 1, 0, 0, 0, 4, 0, 0, 0, 7]);
+    expected.extend_from_slice(&[0, 1, 0xff, 0xff, 0xff, 0xff]);
+    expected.extend_from_slice(&[0xff, 0xff]);
+    assert_eq!(w.finish().unwrap(), expected);
+}
+
+#[test]
+fn pg_copy_writer_view_with_chunked_data_matches_owned() {
+    let batch = fixture_batch();
+    let mut views: Vec<ColumnDataView<'_>> = batch.columns.iter().map(|c| c.as_view()).collect();
+    let chunks: [&[u8]; 2] = [b"USDTE", "TH/€".as_bytes()];
+    let ColumnDataView::Var { data, .. } = &mut views[1] else {
+        unreachable!()
+    };
+    *data = VarDataView::Chunks {
+        inline: b"BTC",
+        chunks: &chunks,
+    };
+
+    let mut w = PgCopyWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch_view(&ColumnarBatchView {
+        schema: &batch.schema,
+        row_count: batch.row_count,
+        columns: &views,
+    })
+    .unwrap();
+    assert_eq!(w.finish().unwrap(), ALL_TYPES);
+}
+
+#[test]
+fn pg_copy_writer_round_trips_through_reader() {
+    let batch = fixture_batch();
+    let mut w = PgCopyWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    w.write_batch(&batch).unwrap();
+    assert_eq!(w.row_count(), 6);
+    let bytes = w.finish().unwrap();
+
+    let mut r = PgCopyReader::new(bytes.as_slice(), batch.schema.clone(), 3).unwrap();
+    assert_eq!(r.next_batch().unwrap().unwrap(), batch);
+    assert_eq!(r.next_batch().unwrap().unwrap(), batch);
+    assert!(r.next_batch().unwrap().is_none());
+
+    let ts = single_batch(
+        ColumnData::FixedTimestampMicros {
+            validity: ValidityBitmap::new_all_valid(3).unwrap(),
+            values: vec![i64::MAX, i64::MIN, 0],
+        },
+        3,
+    );
+    let mut w = PgCopyWriter::new(Vec::new(), ts.schema.clone()).unwrap();
+    w.write_batch(&ts).unwrap();
+    let bytes = w.finish().unwrap();
+    let mut r = PgCopyReader::new(bytes.as_slice(), ts.schema.clone(), 8).unwrap();
+    assert_eq!(r.next_batch().unwrap().unwrap(), ts);
+}
+
+#[test]
+fn pg_copy_writer_rejects_invalid_batches() {
+    let batch = fixture_batch();
+    let other = single_batch(
+        ColumnData::FixedI16 {
+            validity: ValidityBitmap::new_all_valid(1).unwrap(),
+            values: vec![1],
+        },
+        1,
+    );
+    let mut w = PgCopyWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+    assert_eq!(
+        w.write_batch(&other).unwrap_err(),
+        Error::Schema {
+            column: None,
+            reason: "batch schema does not match COPY schema",
+        }
+    );
+
+    let ts = single_batch(
+        ColumnData::FixedTimestampMicros {
+            validity: ValidityBitmap::new_all_valid(1).unwrap(),
+            values: vec![i64::MIN + 1],
+        },
+        1,
+    );
+    let mut w = PgCopyWriter::new(Vec::new(), ts.schema.clone()).unwrap();
+    assert_eq!(
+        w.write_batch(&ts).unwrap_err(),
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "timestamp out of Postgres range",
+        }
+    );
+}
+
+#[test]
+fn pg_copy_numeric_matches_postgres_encoding() {
+    // (mantissa, scale, numeric_send words: ndigits, weight, sign, dscale, digits...)
+    let cases: [(i128, i8, &[u16]); 5] = [
+        (4_200_050, 2, &[3, 1, 0, 2, 4, 2000, 5000]), // 42000.50
+        (-1, 4, &[1, 0xffff, 0x4000, 4, 1]),          // -0.0001
+        (0, 2, &[0, 0, 0, 2]),                        // 0.00
+        (12, -5, &[1, 1, 0, 0, 120]),                 // 1200000
+        (
+            10i128.pow(38) - 1,
+            0,
+            &[
+                10, 9, 0, 0, 99, 9999, 9999, 9999, 9999, 9999, 9999, 9999, 9999, 9999,
+            ],
+        ),
+    ];
+    for (mantissa, scale, words) in cases {
+        let batch = single_batch(
+            ColumnData::FixedDecimal128 {
+                precision: 38,
+                scale,
+                validity: ValidityBitmap::new_all_valid(1).unwrap(),
+                values: vec![mantissa],
+            },
+            1,
+        );
+        let mut w = PgCopyWriter::new(Vec::new(), batch.schema.clone()).unwrap();
+        w.write_batch(&batch).unwrap();
+        let bytes = w.finish().unwrap();
+
+        let mut field = ((2 * words.len()) as i32).to_be_bytes().to_vec();
+        for word in words {
+            field.extend_from_slice(&word.to_be_bytes());
+        }
+        assert_eq!(&bytes[19 + 2..bytes.len() - 2], field.as_slice());
+
+        let mut r = PgCopyReader::new(bytes.as_slice(), batch.schema.clone(), 8).unwrap();
+        assert_eq!(
+            r.next_batch().unwrap().unwrap(),
+            batch,
+            "{mantissa}e-{scale}"
+        );
+    }
+}
+
+#[test]
+fn pg_copy_numeric_must_fit_the_decimal_type() {
+    let read = |words: &[u16], precision, scale| {
+        let mut bytes = b"PGCOPY\n\xff\r\n\0".to_vec();
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.extend_from_slice(&1i16.to_be_bytes());
+        bytes.extend_from_slice(&((2 * words.len()) as i32).to_be_bytes());
+        for word in words {
+            bytes.extend_from_slice(&word.to_be_bytes());
+        }
+        bytes.extend_from_slice(&(-1i16).to_be_bytes());
+        let schema = ColumnarSchema::new(vec![ColumnarField {
+            name: None,
+            ty: ColumnarType::Decimal { precision, scale },
+        }])
+        .unwrap();
+        let mut r = PgCopyReader::new(bytes.as_slice(), schema, 8)?;
+        r.next_batch().map(|b| match b.unwrap().columns.remove(0) {
+            ColumnData::FixedDecimal128 { values, .. } => values[0],
+            _ => unreachable!(),
+        })
+    };
+    let err = |reason| Error::Malformed {
+        column: Some(0),
+        offset: 21,
+        reason,
+    };
+    let v42000_50: &[u16] = &[3, 1, 0, 2, 4, 2000, 5000];
+
+    // Widening the scale is exact; narrowing it must not drop digits.
+    assert_eq!(read(v42000_50, 12, 4), Ok(420_005_000));
+    assert_eq!(read(v42000_50, 12, 1), Ok(420_005));
+    assert_eq!(
+        read(v42000_50, 38, 0),
+        Err(err("COPY numeric does not fit decimal type"))
+    );
+    assert_eq!(
+        read(v42000_50, 6, 2),
+        Err(err("COPY numeric does not fit decimal type"))
+    );
+    assert_eq!(
+        read(&[0, 0, 0xc000, 0], 10, 2),
+        Err(err("unsupported COPY numeric value"))
+    );
+    assert_eq!(
+        read(&[1, 0, 0, 0, 10_000], 10, 2),
+        Err(err("invalid COPY numeric"))
+    );
+    assert_eq!(
+        read(&[2, 0, 0, 0, 1], 10, 2),
+        Err(err("invalid COPY numeric"))
+    );
+}
+
+#[test]
+fn pg_copy_date_time_interval_round_trip() {
+    let f = |ty| ColumnarField { name: None, ty };
+    let schema = ColumnarSchema::new(vec![
+        f(ColumnarType::Date),
+        f(ColumnarType::TimeMicros),
+        f(ColumnarType::IntervalMonthDayMicros),
+        f(ColumnarType::Timestamp {
+            unit: TimeUnit::Microsecond,
+            tz: None,
+        }),
+    ])
+    .unwrap();
+    let batch = ColumnarBatch::new(
+        schema.clone(),
+        2,
+        vec![
+            ColumnData::FixedDate32 {
+                validity: ValidityBitmap::new_all_valid(2).unwrap(),
+                values: vec![PG_EPOCH_OFFSET_DAYS + 1, i32::MIN],
+            },
+            ColumnData::FixedTimeMicros {
+                validity: ValidityBitmap::new_all_valid(2).unwrap(),
+                values: vec![3_600_000_000, 0],
+            },
+            ColumnData::FixedInterval {
+                validity: ValidityBitmap::new_all_valid(2).unwrap(),
+                values: vec![
+                    IntervalMonthDayMicros {
+                        months: 14,
+                        days: 3,
+                        micros: -1,
+                    },
+                    IntervalMonthDayMicros::default(),
+                ],
+            },
+            ColumnData::FixedTimestamp {
+                unit: TimeUnit::Microsecond,
+                tz: None,
+                validity: ValidityBitmap::new_all_valid(2).unwrap(),
+                values: vec![PG_EPOCH_OFFSET_MICROS, i64::MAX],
+            },
+        ],
+    )
+    .unwrap();
+    let mut w = PgCopyWriter::new(Vec::new(), schema.clone()).unwrap();
+    w.write_batch(&batch).unwrap();
+    let bytes = w.finish().unwrap();
+
+    // First tuple: date 2000-01-02 is day 1 in Postgres; interval is micros | days | months.
+    let mut tuple = 4i16.to_be_bytes().to_vec();
+    for field in [
+        &1i32.to_be_bytes()[..],
+        &3_600_000_000i64.to_be_bytes(),
+        &[
+            &(-1i64).to_be_bytes()[..],
+            &3i32.to_be_bytes(),
+            &14i32.to_be_bytes(),
+        ]
+        .concat(),
+        &0i64.to_be_bytes(),
+    ] {
+        tuple.extend_from_slice(&(field.len() as i32).to_be_bytes());
+        tuple.extend_from_slice(field);
+    }
+    assert_eq!(&bytes[19..19 + tuple.len()], tuple.as_slice());
+
+    let mut r = PgCopyReader::new(bytes.as_slice(), schema, 8).unwrap();
+    assert_eq!(r.next_batch().unwrap().unwrap(), batch);
+
+    let date = single_batch(
+        ColumnData::FixedDate32 {
+            validity: ValidityBitmap::new_all_valid(1).unwrap(),
+            values: vec![i32::MIN + 1],
+        },
+        1,
+    );
+    let mut w = PgCopyWriter::new(Vec::new(), date.schema.clone()).unwrap();
+    assert_eq!(
+        w.write_batch(&date).unwrap_err(),
+        Error::InvalidBatch {
+            column: Some(0),
+            reason: "date out of Postgres range",
+        }
+    );
+}
+
+#[test]
+fn pg_copy_rejects_types_without_a_postgres_mapping() {
+    for (ty, reason) in [
+        (
+            ColumnarType::Timestamp {
+                unit: TimeUnit::Nanosecond,
+                tz: Some("UTC".into()),
+            },
+            "COPY timestamps must have microsecond unit",
+        ),
+        (
+            ColumnarType::U64,
+            "column type has no Postgres COPY mapping",
+        ),
+        (ColumnarType::I8, "column type has no Postgres COPY mapping"),
+        (
+            ColumnarType::List(Box::new(ColumnarType::I64)),
+            "column type has no Postgres COPY mapping",
+        ),
+        (
+            ColumnarType::Struct(vec![ColumnarField {
+                name: None,
+                ty: ColumnarType::I64,
+            }]),
+            "column type has no Postgres COPY mapping",
+        ),
+        (
+            ColumnarType::FixedSizeBinary(32),
+            "column type has no Postgres COPY mapping",
+        ),
+        (
+            ColumnarType::FixedSizeList(Box::new(ColumnarType::F32), 8),
+            "column type has no Postgres COPY mapping",
+        ),
+    ] {
+        let schema = ColumnarSchema::new(vec![
+            ColumnarField {
+                name: None,
+                ty: ColumnarType::I64,
+            },
+            ColumnarField { name: None, ty },
+        ])
+        .unwrap();
+        let expected = Error::Schema {
+            column: Some(1),
+            reason,
+        };
+        assert_eq!(
+            PgCopyWriter::new(Vec::new(), schema.clone()).err(),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            PgCopyReader::new(ALL_TYPES, schema, 8).err(),
+            Some(expected)
+        );
+    }
+}